@@ -10,7 +10,7 @@ fn main() -> anyhow::Result<()> {
     let cache = fcache::new()?.with_refresh_interval(Duration::from_millis(100));
 
     // Create a file
-    let mut file = cache.get("locked_file.txt", |mut file| {
+    let file = cache.get("locked_file.txt", |mut file| {
         let datetime = Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
         let content = format!("Generated at: {datetime}");
         file.write_all(content.as_bytes())?;
@@ -0,0 +1,398 @@
+use std::fmt::{self, Debug};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+#[cfg(doc)]
+use crate::Cache;
+use crate::InnerDirCache;
+use crate::file::CacheLazyFile;
+use crate::result::{Error, Result};
+
+/// Seconds in a day
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Rotation of an appendable file.
+#[derive(Clone, Copy, Debug)]
+enum Rotation {
+    /// Rotate once the file exceeds the size in bytes, keeping the given number of rotated files
+    Size { max_bytes: u64, keep: usize },
+    /// Rotate once the file was started longer than the interval ago
+    Interval(Duration),
+}
+
+/// An append-only file in the cache.
+///
+/// Unlike [`CacheFile`](crate::CacheFile), the content is never truncated and rewritten; instead bytes are appended to the file.
+/// The header callback runs once whenever a fresh file is started.
+/// Check the [`Cache::get_appendable`] method for more details on how to create appendable files.
+pub struct AppendFile<'a> {
+    /// Underlying lazy file, whose callback writes the header
+    file: CacheLazyFile<'a>,
+    /// Cache the file belongs to
+    cache: &'a InnerDirCache,
+    /// Rotation of the file
+    rotation: Option<Rotation>,
+    /// Time the active file was started
+    started: Mutex<SystemTime>,
+}
+
+impl<'a> AppendFile<'a> {
+    /// Creates an appendable file from a lazy file, writing the header.
+    pub(crate) fn new(file: CacheLazyFile<'a>, cache: &'a InnerDirCache) -> Result<Self> {
+        let _ = file.create()?;
+        let rotation = None;
+        let started = Mutex::new(cache.now());
+        let append_file = Self {
+            file,
+            cache,
+            rotation,
+            started,
+        };
+        Ok(append_file)
+    }
+
+    /// Sets the size-based rotation of the file, replacing any previously set rotation.
+    ///
+    /// Once the file exceeds `max_bytes`, it is rotated to `<path>.1`, shifting older rotated files (`<path>.1` to `<path>.2` and so on) and removing those beyond `keep`.
+    /// A fresh file with the header is then started.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    ///
+    /// // Rotate the log once it exceeds 1 MiB, keeping 3 rotated files
+    /// let append_file = cache
+    ///     .get_appendable("events.log", |mut file| {
+    ///         file.write_all(b"# events\n")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_rotation(1024 * 1024, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_rotation(self, max_bytes: u64, keep: usize) -> Self {
+        let rotation = Some(Rotation::Size { max_bytes, keep });
+        Self { rotation, ..self }
+    }
+
+    /// Sets the time-based rotation of the file, replacing any previously set rotation.
+    ///
+    /// When appending to a file started longer than `rotation_interval` ago, the file is first rotated to `<path>.<start date>`, e.g. `events.log.2024-06-01`, and a fresh file with the header is started.
+    /// For intervals shorter than a day the start time is included as well, e.g. `events.log.2024-06-01T12-00-00`.
+    /// Rotated files are never removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    ///
+    /// // Start a new log every day
+    /// let append_file = cache
+    ///     .get_appendable("events.log", |mut file| {
+    ///         file.write_all(b"# events\n")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_rotation_interval(Duration::from_secs(24 * 60 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_rotation_interval(self, rotation_interval: Duration) -> Self {
+        let rotation = Some(Rotation::Interval(rotation_interval));
+        Self { rotation, ..self }
+    }
+
+    /// Returns the path of the active file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let append_file = cache.get_appendable("events.log", |_| Ok(()))?;
+    ///
+    /// // Get the file path
+    /// let path = append_file.path();
+    /// println!("Events are appended to: {}", path.display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        let Self { file, .. } = self;
+        file.path()
+    }
+
+    /// Returns the path of the rotated file with the given index.
+    ///
+    /// The most recently rotated file has index 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let append_file = cache.get_appendable("events.log", |_| Ok(()))?;
+    ///
+    /// // Get the path of the most recently rotated file
+    /// assert!(append_file.rotated_path(1).ends_with("events.log.1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path().as_os_str().to_owned();
+        path.push(format!(".{index}"));
+        PathBuf::from(path)
+    }
+
+    /// Returns the paths of the rotated files, newest first.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let append_file = cache
+    ///     .get_appendable("events.log", |_| Ok(()))?
+    ///     .with_rotation(4, 3);
+    /// append_file.append(b"started\n")?;
+    ///
+    /// // List the rotated files
+    /// assert_eq!(
+    ///     append_file.rotated_files()?,
+    ///     vec![append_file.rotated_path(1)]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the directory of the file cannot be read.
+    pub fn rotated_files(&self) -> Result<Vec<PathBuf>> {
+        let Self { file, rotation, .. } = self;
+        let Some(dir) = file.path().parent() else {
+            return Ok(Vec::new());
+        };
+        let prefix = format!("{}.", file.name());
+
+        let mut numbered_files = Vec::new();
+        let mut dated_files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(suffix) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+            else {
+                continue;
+            };
+            if let Ok(index) = suffix.parse::<usize>() {
+                numbered_files.push((index, path));
+            } else if suffix.starts_with(|c: char| c.is_ascii_digit())
+                && suffix.chars().all(|c| c.is_ascii_digit() || c == '-' || c == 'T')
+            {
+                dated_files.push(path);
+            }
+        }
+
+        if let Some(Rotation::Interval(_)) = rotation {
+            // Timestamps sort chronologically, so the newest is the greatest
+            dated_files.sort_unstable_by(|a, b| b.cmp(a));
+            Ok(dated_files)
+        } else {
+            numbered_files.sort_unstable_by_key(|(index, _)| *index);
+            Ok(numbered_files.into_iter().map(|(_, path)| path).collect())
+        }
+    }
+
+    /// Appends bytes to the file, rotating it if it exceeds the size limit or the rotation interval.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let append_file = cache.get_appendable("events.log", |_| Ok(()))?;
+    ///
+    /// // Append an event
+    /// append_file.append(b"started\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be written, rotated, or the header callback returns an error.
+    pub fn append(&self, bytes: &[u8]) -> Result<()> {
+        let Self { file, cache, .. } = self;
+        cache.check_frozen(file.path())?;
+        if !file.path().exists() {
+            self.start()?;
+        }
+        self.rotate_by_interval()?;
+        File::options()
+            .append(true)
+            .open(file.path())
+            .and_then(|mut append_file| append_file.write_all(bytes))
+            .map_err(Error::IO)
+            .and_then(|()| self.rotate_by_size())
+    }
+
+    /// Opens the active file for reading, creating it if it doesn't exist.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let append_file = cache.get_appendable("events.log", |_| Ok(()))?;
+    /// append_file.append(b"started\n")?;
+    ///
+    /// // Read the appended events
+    /// let mut content = String::new();
+    /// append_file.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "started\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be created or opened for reading.
+    pub fn open(&self) -> Result<File> {
+        let Self { file, .. } = self;
+        if file.path().exists() {
+            File::open(file.path()).map_err(Error::IO)
+        } else {
+            file.create()
+        }
+    }
+
+    /// Starts a fresh file with the header.
+    fn start(&self) -> Result<()> {
+        let Self {
+            file, cache, started, ..
+        } = self;
+        let _ = file.create()?;
+        *started.lock().unwrap_or_else(PoisonError::into_inner) = cache.now();
+        Ok(())
+    }
+
+    /// Rotates the file if it was started longer than the rotation interval ago.
+    fn rotate_by_interval(&self) -> Result<()> {
+        let Self {
+            file,
+            cache,
+            rotation,
+            started,
+        } = self;
+        let Some(Rotation::Interval(rotation_interval)) = *rotation else {
+            return Ok(());
+        };
+        let started = *started.lock().unwrap_or_else(PoisonError::into_inner);
+        if cache.now().duration_since(started).unwrap_or(Duration::ZERO) < rotation_interval {
+            return Ok(());
+        }
+
+        let with_time = rotation_interval < Duration::from_secs(SECONDS_PER_DAY);
+        let mut rotated_path = file.path().as_os_str().to_owned();
+        rotated_path.push(format!(".{}", format_timestamp(started, with_time)));
+        fs::rename(file.path(), rotated_path)?;
+        self.start()
+    }
+
+    /// Rotates the file if it exceeds the size limit.
+    fn rotate_by_size(&self) -> Result<()> {
+        let Self { file, rotation, .. } = self;
+        let Some(Rotation::Size { max_bytes, keep }) = *rotation else {
+            return Ok(());
+        };
+        let path = file.path();
+        if fs::metadata(path)?.len() <= max_bytes {
+            return Ok(());
+        }
+
+        if keep == 0 {
+            fs::remove_file(path)?;
+        } else {
+            // Shift older rotated files, dropping the oldest one
+            let oldest_path = self.rotated_path(keep);
+            if oldest_path.exists() {
+                fs::remove_file(oldest_path)?;
+            }
+            for index in (1..keep).rev() {
+                let rotated_path = self.rotated_path(index);
+                if rotated_path.exists() {
+                    fs::rename(rotated_path, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(path, self.rotated_path(1))?;
+        }
+        self.start()
+    }
+}
+
+impl Debug for AppendFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { file, rotation, .. } = self;
+        f.debug_struct("AppendFile")
+            .field("path", &file.path())
+            .field("callback", &"...")
+            .field("rotation", &rotation)
+            .finish()
+    }
+}
+
+/// Formats the time as a UTC date, optionally followed by the time of day, usable in file names.
+fn format_timestamp(time: SystemTime, with_time: bool) -> String {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    let (days, seconds) = (seconds / SECONDS_PER_DAY, seconds % SECONDS_PER_DAY);
+
+    // Convert days since the epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    if with_time {
+        let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+        format!("{year:04}-{month:02}-{day:02}T{hours:02}-{minutes:02}-{seconds:02}")
+    } else {
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+}
@@ -0,0 +1,115 @@
+use std::fs::File;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::InnerDirCache;
+use crate::file::CacheLazyFile;
+use crate::result::{Error, Result};
+
+/// Extracts the files of the ZIP archive into the cache, skipping files which already exist.
+///
+/// Returns the number of imported files, not counting sidecar files.
+pub(crate) fn import(cache: &InnerDirCache, zip_path: &Path) -> Result<usize> {
+    cache.check_frozen(cache.path())?;
+    let mut archive = ZipArchive::new(File::open(zip_path)?)?;
+
+    let mut imported = 0;
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        // Directories are created along with the files, and links are never followed
+        if !entry.is_file() {
+            continue;
+        }
+        let Some(key) = entry.enclosed_name() else {
+            let path = PathBuf::from(entry.name());
+            let reason = "archive entry escapes the cache directory".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        };
+        let path = match InnerDirCache::sidecar_owner(&key) {
+            // Sidecar files are validated through the key of the file they belong to, as their prefix is reserved
+            Some((owner, extension)) => {
+                let owner = cache.prepare_path(owner)?;
+                CacheLazyFile::sidecar_path(&owner, &extension.to_string_lossy())
+            },
+            None => cache.prepare_path(&key)?,
+        };
+        if path.exists() {
+            continue;
+        }
+
+        io::copy(&mut entry, &mut File::create_new(&path)?)?;
+        if !InnerDirCache::is_sidecar(&path) {
+            imported += 1;
+        }
+    }
+    Ok(imported)
+}
+
+/// Writes the files of the cache along with their sidecar files into a ZIP archive, optionally skipping invalid files.
+///
+/// Returns the number of exported files, not counting sidecar files.
+pub(crate) fn export(cache: &InnerDirCache, dest: &Path, include_expired: bool) -> Result<usize> {
+    let root = cache.path();
+    let dest_dir = match dest.parent() {
+        Some(parent) if parent != Path::new("") => parent.canonicalize()?,
+        _ => std::env::current_dir()?,
+    };
+    if dest_dir.starts_with(root) {
+        let path = dest.to_path_buf();
+        let reason = "archive destination is within the cache directory".to_string();
+        let error = Error::InvalidPath { path, reason };
+        return Err(error);
+    }
+
+    let mut writer = ZipWriter::new(File::create(dest)?);
+    let options = SimpleFileOptions::default();
+    let mut exported = 0;
+    for path in cache.files()? {
+        if InnerDirCache::is_transient(&path) || (!include_expired && cache.attach(&path)?.is_invalid()?) {
+            continue;
+        }
+
+        let sidecar_paths = [
+            CacheLazyFile::ttl_path(&path),
+            CacheLazyFile::version_path(&path),
+            CacheLazyFile::generation_path(&path),
+            CacheLazyFile::revision_path(&path),
+            CacheLazyFile::rawkey_path(&path),
+        ];
+        let sidecar_paths = sidecar_paths.into_iter().filter(|sidecar_path| sidecar_path.exists());
+        for path in [path.clone()].into_iter().chain(sidecar_paths) {
+            writer.start_file(entry_name(root, &path)?, options)?;
+            io::copy(&mut File::open(&path)?, &mut writer)?;
+        }
+        exported += 1;
+    }
+    writer.finish()?;
+    Ok(exported)
+}
+
+/// Returns the name of the archive entry of the file, which is its path relative to the cache directory separated by slashes.
+fn entry_name(root: &Path, path: &Path) -> Result<String> {
+    let names = path.strip_prefix(root).ok().and_then(|relative_path| {
+        relative_path
+            .components()
+            .map(|component| {
+                match component {
+                    Component::Normal(name) => name.to_str(),
+                    _ => None,
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+    });
+    if let Some(names) = names {
+        Ok(names.join("/"))
+    } else {
+        let path = path.to_path_buf();
+        let reason = "file name cannot be stored in an archive".to_string();
+        let error = Error::InvalidPath { path, reason };
+        Err(error)
+    }
+}
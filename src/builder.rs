@@ -0,0 +1,215 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::Cache;
+use crate::result::{Error, Ok, Result};
+use crate::temp::TempDirOptions;
+
+/// Builder of a [`Cache`], collecting the options of its construction.
+///
+/// The cache is created within a temporary directory, unless a directory is given.
+/// Options which configure a temporary directory, like a prefix, conflict with a given directory.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use fcache::CacheBuilder;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Create a cache within a temporary directory
+/// let cache = CacheBuilder::new()
+///     .temp()
+///     .prefix("my_cache")
+///     .refresh_interval(Duration::from_secs(60))
+///     .build()?;
+/// assert!(
+///     cache
+///         .path()
+///         .file_name()
+///         .is_some_and(|name| name.to_string_lossy().starts_with("my_cache"))
+/// );
+/// assert_eq!(cache.refresh_interval(), Duration::from_secs(60));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheBuilder {
+    /// Directory of the cache, if given
+    dir: Option<PathBuf>,
+    /// Whether a temporary directory was requested
+    temp: bool,
+    /// Prefix of the name of the temporary directory, if given
+    prefix: Option<String>,
+    /// Refresh interval of the files, if given
+    refresh_interval: Option<Duration>,
+}
+
+impl CacheBuilder {
+    /// Creates a new builder of a cache within a temporary directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheBuilder;
+    ///
+    /// let builder = CacheBuilder::new();
+    /// # let _ = builder;
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory of the cache, created if it doesn't exist.
+    ///
+    /// Conflicts with [`temp`](Self::temp) and [`prefix`](Self::prefix).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheBuilder;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let dir = tempfile::tempdir()?;
+    ///
+    /// // Create a cache within the directory
+    /// let cache = CacheBuilder::new().dir(dir.path()).build()?;
+    /// assert_eq!(cache.path(), dir.path());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn dir(self, dir: impl AsRef<Path>) -> Self {
+        let dir = Some(dir.as_ref().to_path_buf());
+        Self { dir, ..self }
+    }
+
+    /// Requests a temporary directory, removed along with the cache.
+    ///
+    /// This is the default when no directory is given, so this only makes the intent explicit.
+    /// Conflicts with [`dir`](Self::dir).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheBuilder;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = CacheBuilder::new().temp().build()?;
+    /// assert!(cache.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn temp(self) -> Self {
+        Self { temp: true, ..self }
+    }
+
+    /// Sets the prefix of the name of the temporary directory.
+    ///
+    /// Conflicts with [`dir`](Self::dir).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheBuilder;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = CacheBuilder::new().prefix("my_cache").build()?;
+    /// assert!(cache.path().to_string_lossy().contains("my_cache"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn prefix(self, prefix: impl Into<String>) -> Self {
+        let prefix = Some(prefix.into());
+        Self { prefix, ..self }
+    }
+
+    /// Sets the refresh interval of the files (see [`Cache::with_refresh_interval`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::CacheBuilder;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = CacheBuilder::new()
+    ///     .refresh_interval(Duration::from_secs(60))
+    ///     .build()?;
+    /// assert_eq!(cache.refresh_interval(), Duration::from_secs(60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval(self, refresh_interval: Duration) -> Self {
+        let refresh_interval = Some(refresh_interval);
+        Self {
+            refresh_interval,
+            ..self
+        }
+    }
+
+    /// Builds the cache, creating its directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::{CacheBuilder, Error};
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let dir = tempfile::tempdir()?;
+    ///
+    /// // A directory and a prefix of a temporary directory conflict
+    /// let result = CacheBuilder::new()
+    ///     .dir(dir.path())
+    ///     .prefix("my_cache")
+    ///     .build();
+    /// assert!(matches!(result, Err(Error::InvalidConfiguration { .. })));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidConfiguration`] if a directory is given along with [`temp`](Self::temp) or [`prefix`](Self::prefix),
+    /// or an error in the same cases as [`Cache::with_dir`] and [`Cache::temp_with`].
+    pub fn build(self) -> Result<Cache> {
+        let Self {
+            dir,
+            temp,
+            prefix,
+            refresh_interval,
+        } = self;
+        let cache = match dir {
+            Some(_) if temp => {
+                let reason = "a directory conflicts with a temporary directory".to_string();
+                let error = Error::InvalidConfiguration { reason };
+                return Err(error);
+            },
+            Some(_) if prefix.is_some() => {
+                let reason = "a directory conflicts with a prefix of a temporary directory".to_string();
+                let error = Error::InvalidConfiguration { reason };
+                return Err(error);
+            },
+            Some(dir) => Cache::with_dir(dir)?,
+            None => {
+                let options = TempDirOptions::default();
+                let options = match prefix {
+                    Some(prefix) => options.with_prefix(prefix),
+                    None => options,
+                };
+                Cache::temp_with(options)?
+            },
+        };
+        let cache = match refresh_interval {
+            Some(refresh_interval) => cache.with_refresh_interval(refresh_interval),
+            None => cache,
+        };
+        Ok(cache)
+    }
+}
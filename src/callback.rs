@@ -1,8 +1,18 @@
+use std::any::Any;
+use std::fmt::{self, Display};
 use std::fs::File;
-use std::{error, result};
+use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::{error, io, result};
 
+use crate::event::CacheEvent;
+#[cfg(feature = "testing")]
+use crate::fault::FaultPoint;
+use crate::result::Result;
 #[cfg(doc)]
-use crate::Cache;
+use crate::{Cache, SingleThreadedCache};
 
 /// Trait alias for callback functions used in cache operations.
 ///
@@ -10,3 +20,119 @@ use crate::Cache;
 pub trait CallbackFn: Fn(File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> + Send + Sync {}
 
 impl<T> CallbackFn for T where T: Fn(File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> + Send + Sync {}
+
+/// Trait alias for callback functions which are not shareable across threads.
+///
+/// Unlike [`CallbackFn`], the function is only required to be [`Send`], so it can capture types like [`Cell`](std::cell::Cell) or [`RefCell`](std::cell::RefCell).
+/// Check the [`SingleThreadedCache`] type for more details on how to use this trait.
+pub trait SendCallbackFn: Fn(File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> + Send {}
+
+impl<T> SendCallbackFn for T where T: Fn(File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> + Send {}
+
+/// Trait alias for path transformer functions used to rewrite the location of cache files.
+///
+/// Check the [`Cache::with_path_transformer`] method for more details on how to use this trait.
+pub trait PathTransformerFn: Fn(PathBuf) -> Result<PathBuf> + Send + Sync {}
+
+impl<T> PathTransformerFn for T where T: Fn(PathBuf) -> Result<PathBuf> + Send + Sync {}
+
+/// Trait alias for observer functions receiving the events of cache operations.
+///
+/// Check the [`Cache::with_observer`] method for more details on how to use this trait.
+pub trait ObserverFn: Fn(CacheEvent) + Send + Sync {}
+
+impl<T> ObserverFn for T where T: Fn(CacheEvent) + Send + Sync {}
+
+/// Trait alias for fault injector functions failing the operations of a cache on demand.
+///
+/// Check the [`Cache::with_fault_injector`] method for more details on how to use this trait.
+#[cfg(feature = "testing")]
+pub trait FaultInjectorFn: Fn(FaultPoint) -> Option<io::Error> + Send + Sync {}
+
+#[cfg(feature = "testing")]
+impl<T> FaultInjectorFn for T where T: Fn(FaultPoint) -> Option<io::Error> + Send + Sync {}
+
+/// Callback function that leaves the file untouched.
+pub(crate) fn noop(_: File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> {
+    Ok(())
+}
+
+/// Callback function failing for files whose content was streamed from a reader, which cannot be read again.
+pub(crate) fn unrepeatable(_: File) -> result::Result<(), Box<dyn error::Error + Send + Sync>> {
+    Err("content streamed from a reader cannot be regenerated".into())
+}
+
+/// Callback function that copies the content of the source file into the file.
+pub(crate) fn copy_from(source: PathBuf) -> impl CallbackFn {
+    move |mut file| {
+        io::copy(&mut File::open(&source)?, &mut file)?;
+        Ok(())
+    }
+}
+
+/// Callback function that writes the given content into the file.
+pub(crate) fn write_all(content: Vec<u8>) -> impl CallbackFn {
+    move |mut file| {
+        file.write_all(&content)?;
+        Ok(())
+    }
+}
+
+/// Callback function that concatenates the content of the source files into the file.
+pub(crate) fn concat_from(sources: Vec<PathBuf>) -> impl CallbackFn {
+    move |mut file| {
+        for source in &sources {
+            io::copy(&mut File::open(source)?, &mut file)?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback function that runs a callback which is not shareable across threads, one call at a time.
+pub(crate) fn synchronized(callback: impl SendCallbackFn + 'static) -> impl CallbackFn {
+    let callback = Mutex::new(callback);
+    move |file| {
+        let callback = callback.lock().unwrap_or_else(PoisonError::into_inner);
+        callback(file)
+    }
+}
+
+/// Panic of a callback function, carried as its error until it is reported as [`Error::CallbackPanicked`](crate::Error::CallbackPanicked).
+#[derive(Debug)]
+pub(crate) struct CallbackPanic {
+    /// Message of the panic
+    pub(crate) message: String,
+}
+
+impl CallbackPanic {
+    /// Creates the panic from the payload of the unwind.
+    fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "callback panicked".to_string()
+        };
+        Self { message }
+    }
+}
+
+impl Display for CallbackPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { message } = self;
+        write!(f, "callback panicked: {message}")
+    }
+}
+
+impl error::Error for CallbackPanic {}
+
+/// Runs a callback function, turning its panic into a [`CallbackPanic`] error.
+///
+/// Callbacks aren't required to be [`UnwindSafe`](std::panic::UnwindSafe): the file they write is discarded after a panic,
+/// and nothing they may leave broken is used by the cache afterwards.
+pub(crate) fn catch_unwind(
+    call: impl FnOnce() -> result::Result<(), Box<dyn error::Error + Send + Sync>>,
+) -> result::Result<(), Box<dyn error::Error + Send + Sync>> {
+    panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or_else(|payload| Err(Box::new(CallbackPanic::new(&*payload))))
+}
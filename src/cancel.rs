@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(doc)]
+use crate::{Cache, Error};
+
+/// Cloneable flag for cooperatively cancelling the creation and refreshing of files.
+///
+/// Long-running callbacks can poll the token obtained from [`Cache::cancellation_token`] and return early once it's cancelled.
+/// The cache then cleans up the partially written file and reports [`Error::Cancelled`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CancelToken;
+///
+/// let token = CancelToken::new();
+/// let shared_token = token.clone();
+///
+/// // Cancel through any clone
+/// token.cancel();
+/// assert!(shared_token.is_cancelled());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Creates a new token which is not cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and all its clones.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// token.cancel();
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn cancel(&self) {
+        let Self(cancelled) = self;
+        cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the token is cancelled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CancelToken;
+    ///
+    /// let token = CancelToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        let Self(cancelled) = self;
+        cancelled.load(Ordering::SeqCst)
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(doc)]
+use crate::Cache;
+
+/// Describes how paths of files are resolved when checking that they are within the cache directory.
+///
+/// Check the [`Cache::with_path_canonicalization`] method for more details on how to use this enum.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CanonicalizationMode;
+///
+/// // Resolve symbolic links without requiring every directory to exist
+/// let mode = CanonicalizationMode::Lenient;
+/// # let _ = mode;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CanonicalizationMode {
+    /// Every directory is created and canonicalized, following symbolic links.
+    #[default]
+    Strict,
+    /// Symbolic links of existing directories are resolved, while missing directories are resolved lexically.
+    ///
+    /// Directories are checked before they are created.
+    Lenient,
+    /// Paths are resolved lexically, without following symbolic links.
+    ///
+    /// This avoids filesystem calls, but symbolic links within the cache directory may point outside of it.
+    None,
+}
@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Source of the current time used by the cache.
+///
+/// Check the [`Cache::with_clock`] method for more details on how to use this trait.
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+}
+
+/// Clock returning the system time.
+///
+/// This is the default clock of the cache.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Clock which only moves when told to, useful for testing time-dependent behavior.
+///
+/// Clones share the same time, so a clone given to the cache can be advanced from the outside.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+///
+/// use fcache::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+/// let shared_clock = clock.clone();
+///
+/// // Step the time forward
+/// clock.advance(Duration::from_secs(60));
+/// assert_eq!(
+///     shared_clock.now(),
+///     SystemTime::UNIX_EPOCH + Duration::from_secs(60)
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock(Arc<Mutex<SystemTime>>);
+
+impl ManualClock {
+    /// Creates a new manual clock starting at the given time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    ///
+    /// use fcache::ManualClock;
+    ///
+    /// let clock = ManualClock::new(SystemTime::now());
+    /// # let _ = clock;
+    /// ```
+    #[must_use]
+    pub fn new(now: SystemTime) -> Self {
+        Self(Arc::new(Mutex::new(now)))
+    }
+
+    /// Sets the current time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    ///
+    /// use fcache::{Clock, ManualClock};
+    ///
+    /// let clock = ManualClock::new(SystemTime::now());
+    ///
+    /// // Jump back to the epoch
+    /// clock.set(SystemTime::UNIX_EPOCH);
+    /// assert_eq!(clock.now(), SystemTime::UNIX_EPOCH);
+    /// ```
+    pub fn set(&self, now: SystemTime) {
+        let Self(time) = self;
+        *time.lock().unwrap_or_else(PoisonError::into_inner) = now;
+    }
+
+    /// Moves the current time forward by the given duration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use fcache::{Clock, ManualClock};
+    ///
+    /// let clock = ManualClock::new(SystemTime::UNIX_EPOCH);
+    ///
+    /// // Step one day forward
+    /// clock.advance(Duration::from_secs(24 * 60 * 60));
+    /// assert_eq!(
+    ///     clock.now(),
+    ///     SystemTime::UNIX_EPOCH + Duration::from_secs(24 * 60 * 60)
+    /// );
+    /// ```
+    pub fn advance(&self, duration: Duration) {
+        let Self(time) = self;
+        *time.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        let Self(time) = self;
+        *time.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(doc)]
+use crate::Cache;
+
+/// Describes how [`Cache::get`] treats files which already exist in the cache.
+///
+/// Check the [`Cache::with_creation_mode`] method for more details on how to use this enum.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CreationMode;
+///
+/// // Always regenerate the file content on `get()`
+/// let mode = CreationMode::CreateOrTruncate;
+/// # let _ = mode;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CreationMode {
+    /// The file must not exist yet, otherwise [`Error::FileAlreadyExists`](crate::Error::FileAlreadyExists) is returned.
+    #[default]
+    CreateNew,
+    /// The file is created if missing, or truncated and regenerated by the callback if it exists.
+    CreateOrTruncate,
+    /// The file is created if missing, or opened as it is without running the callback if it exists.
+    UseExisting,
+}
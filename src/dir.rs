@@ -0,0 +1,197 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{fs, io};
+
+#[cfg(doc)]
+use crate::Cache;
+use crate::result::{Error, Result};
+
+/// Name of the marker file identifying cache directories
+pub(crate) const MARKER_FILE: &str = ".fcache";
+
+/// Describes which directories can be used as a cache directory.
+///
+/// Check the [`Cache::with_dir_checked`] method for more details on how to use this enum.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::DirPolicy;
+///
+/// // Only use directories created by fcache
+/// let policy = DirPolicy::RequireMarker;
+/// # let _ = policy;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DirPolicy {
+    /// The directory must be empty, otherwise [`Error::DirectoryNotEmpty`] is returned.
+    RequireEmpty,
+    /// Any directory is used, like in [`Cache::with_dir`].
+    #[default]
+    AllowExisting,
+    /// The directory must contain a `.fcache` marker file, otherwise [`Error::MissingMarker`] is returned.
+    ///
+    /// The marker file is written on first use, when the directory is empty.
+    RequireMarker,
+}
+
+impl DirPolicy {
+    /// Checks whether the directory can be used as a cache directory, writing the marker file if needed.
+    pub(crate) fn check(self, dir: &Path) -> Result<()> {
+        let is_empty = fs::read_dir(dir)?.next().is_none();
+        match self {
+            Self::RequireEmpty if !is_empty => {
+                let path = dir.to_path_buf();
+                let error = Error::DirectoryNotEmpty { path };
+                Err(error)
+            },
+            Self::RequireMarker if is_empty => {
+                fs::write(dir.join(MARKER_FILE), [])?;
+                Ok(())
+            },
+            Self::RequireMarker if !dir.join(MARKER_FILE).is_file() => {
+                let path = dir.to_path_buf();
+                let error = Error::MissingMarker { path };
+                Err(error)
+            },
+            Self::RequireEmpty | Self::AllowExisting | Self::RequireMarker => Ok(()),
+        }
+    }
+}
+
+/// Guard removing a directory tree, or only its content, when dropped.
+#[derive(Debug)]
+pub(crate) struct RemoveOnDrop {
+    /// Directory to remove
+    dir: PathBuf,
+    /// What is removed from the directory
+    removal: Removal,
+    /// Whether the directory is kept instead
+    kept: AtomicBool,
+}
+
+/// What is removed by a [`RemoveOnDrop`] guard.
+#[derive(Debug)]
+enum Removal {
+    /// The directory itself along with its content
+    Tree,
+    /// The whole content of the directory, keeping the directory itself
+    Content,
+    /// The content added to the directory since the guard was created, keeping the paths which existed before
+    Added {
+        /// Paths of the files and directories which existed before
+        existing: HashSet<PathBuf>,
+    },
+}
+
+impl RemoveOnDrop {
+    /// Creates a guard removing the given directory.
+    pub(crate) fn new(dir: impl AsRef<Path>) -> Self {
+        Self::with_removal(dir, Removal::Tree)
+    }
+
+    /// Creates a guard removing the content of the given directory, keeping the directory itself.
+    pub(crate) fn content(dir: impl AsRef<Path>) -> Self {
+        Self::with_removal(dir, Removal::Content)
+    }
+
+    /// Creates a guard removing the content added to the given directory from now on, keeping its current content.
+    ///
+    /// Nothing is removed if the current content cannot be listed, so no existing file is mistaken for an added one.
+    pub(crate) fn added(dir: impl AsRef<Path>) -> Self {
+        match Self::walk(dir.as_ref()) {
+            Ok(paths) => {
+                let existing = paths.into_iter().collect();
+                Self::with_removal(dir, Removal::Added { existing })
+            },
+            Err(_) => {
+                let guard = Self::with_removal(dir, Removal::Content);
+                guard.keep();
+                guard
+            },
+        }
+    }
+
+    /// Creates a guard removing the given part of the directory.
+    fn with_removal(dir: impl AsRef<Path>, removal: Removal) -> Self {
+        let dir = dir.as_ref().to_path_buf();
+        let kept = AtomicBool::new(false);
+        Self { dir, removal, kept }
+    }
+
+    /// Checks if the guard removes the directory itself.
+    pub(crate) fn removes_dir(&self) -> bool {
+        let Self { removal, .. } = self;
+        matches!(removal, Removal::Tree)
+    }
+
+    /// Keeps the directory when the guard is dropped.
+    pub(crate) fn keep(&self) {
+        let Self { kept, .. } = self;
+        kept.store(true, Ordering::SeqCst);
+    }
+
+    /// Lists the paths of the files and directories within the directory, each directory before its content.
+    fn walk(dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        let mut dirs = vec![dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    dirs.push(path.clone());
+                }
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Removes the file or the empty directory at the given path.
+    fn remove(path: &Path) -> io::Result<()> {
+        if fs::symlink_metadata(path)?.is_dir() {
+            fs::remove_dir(path)
+        } else {
+            fs::remove_file(path)
+        }
+    }
+}
+
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        let Self { dir, removal, kept } = self;
+        if kept.load(Ordering::SeqCst) {
+            return;
+        }
+        // Best-effort cleanup, the directory may have already been removed externally
+        match removal {
+            Removal::Tree => {
+                let _ = fs::remove_dir_all(dir);
+            },
+            Removal::Content => {
+                let Ok(entries) = fs::read_dir(dir) else {
+                    return;
+                };
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    let _ = if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                        fs::remove_dir_all(path)
+                    } else {
+                        fs::remove_file(path)
+                    };
+                }
+            },
+            Removal::Added { existing } => {
+                let Ok(paths) = Self::walk(dir) else {
+                    return;
+                };
+                // Content is removed before its directory, which is kept if it existed before or still contains existing files
+                for path in paths.iter().rev().filter(|path| !existing.contains(*path)) {
+                    let _ = Self::remove(path);
+                }
+            },
+        }
+    }
+}
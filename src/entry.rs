@@ -0,0 +1,337 @@
+use std::fs::File;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::file::{CacheFile, CacheLazyFile};
+use crate::result::{Ok, Result};
+
+/// A file in the cache, either created eagerly or lazily.
+///
+/// This allows keeping [`CacheFile`] and [`CacheLazyFile`] handles in one collection, or returning either of them from one function.
+/// The common operations are delegated to the underlying handle.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CacheEntry;
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+/// let entries: Vec<CacheEntry> = vec![
+///     cache.get("eager.txt", |_| Ok(()))?.into(),
+///     cache.get_lazy("lazy.txt", |_| Ok(()))?.into(),
+/// ];
+///
+/// // Open all the files
+/// for entry in &entries {
+///     entry.open()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum CacheEntry<'a> {
+    /// A file created immediately
+    Eager(CacheFile<'a>),
+    /// A file created when accessed
+    Lazy(CacheLazyFile<'a>),
+}
+
+impl<'a> CacheEntry<'a> {
+    /// Returns the path of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get_lazy("data.txt", |_| Ok(()))?);
+    /// assert!(entry.path().ends_with("data.txt"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Eager(cache_file) => cache_file.path(),
+            Self::Lazy(lazy_file) => lazy_file.path(),
+        }
+    }
+
+    /// Returns the refresh interval of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// assert_eq!(entry.refresh_interval(), Duration::from_secs(60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval(&self) -> Duration {
+        match self {
+            Self::Eager(cache_file) => cache_file.refresh_interval(),
+            Self::Lazy(lazy_file) => lazy_file.refresh_interval(),
+        }
+    }
+
+    /// Checks if the file is valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// assert!(entry.is_valid()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::is_valid`].
+    pub fn is_valid(&self) -> Result<bool> {
+        match self {
+            Self::Eager(cache_file) => cache_file.is_valid(),
+            Self::Lazy(lazy_file) => lazy_file.is_valid(),
+        }
+    }
+
+    /// Opens the file, creating or refreshing it if needed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// use fcache::CacheEntry;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?);
+    ///
+    /// let mut content = String::new();
+    /// entry.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "content");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::open`] and [`CacheLazyFile::open`].
+    pub fn open(&self) -> Result<File> {
+        match self {
+            Self::Eager(cache_file) => cache_file.open(),
+            Self::Lazy(lazy_file) => lazy_file.open(),
+        }
+    }
+
+    /// Refreshes the file if it is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// entry.refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::refresh`].
+    pub fn refresh(&self) -> Result<()> {
+        match self {
+            Self::Eager(cache_file) => cache_file.refresh(),
+            Self::Lazy(lazy_file) => lazy_file.refresh(),
+        }
+    }
+
+    /// Forces a refresh of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// entry.force_refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::force_refresh`].
+    pub fn force_refresh(&self) -> Result<()> {
+        match self {
+            Self::Eager(cache_file) => cache_file.force_refresh(),
+            Self::Lazy(lazy_file) => lazy_file.force_refresh(),
+        }
+    }
+
+    /// Removes the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// entry.remove()?;
+    /// assert!(!entry.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::remove`].
+    pub fn remove(&self) -> Result<()> {
+        match self {
+            Self::Eager(cache_file) => cache_file.remove(),
+            Self::Lazy(lazy_file) => lazy_file.remove(),
+        }
+    }
+
+    /// Checks if the file is locked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// assert!(!entry.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        match self {
+            Self::Eager(cache_file) => cache_file.is_locked(),
+            Self::Lazy(lazy_file) => lazy_file.is_locked(),
+        }
+    }
+
+    /// Locks the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// entry.lock()?;
+    /// assert!(entry.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::lock`].
+    pub fn lock(&self) -> Result<()> {
+        match self {
+            Self::Eager(cache_file) => cache_file.lock(),
+            Self::Lazy(lazy_file) => lazy_file.lock(),
+        }
+    }
+
+    /// Unlocks the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get("data.txt", |_| Ok(()))?);
+    /// entry.lock()?;
+    /// entry.unlock()?;
+    /// assert!(!entry.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::unlock`].
+    pub fn unlock(&self) -> Result<()> {
+        match self {
+            Self::Eager(cache_file) => cache_file.unlock(),
+            Self::Lazy(lazy_file) => lazy_file.unlock(),
+        }
+    }
+
+    /// Converts the entry into an eagerly created file, initializing lazy files (see [`CacheLazyFile::init`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEntry;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let entry = CacheEntry::from(cache.get_lazy("data.txt", |_| Ok(()))?);
+    ///
+    /// // Create the file
+    /// let cache_file = entry.into_eager()?;
+    /// assert!(cache_file.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheLazyFile::init`].
+    pub fn into_eager(self) -> Result<CacheFile<'a>> {
+        match self {
+            Self::Eager(cache_file) => Ok(cache_file),
+            Self::Lazy(lazy_file) => lazy_file.init(),
+        }
+    }
+}
+
+impl<'a> From<CacheFile<'a>> for CacheEntry<'a> {
+    fn from(cache_file: CacheFile<'a>) -> Self {
+        Self::Eager(cache_file)
+    }
+}
+
+impl<'a> From<CacheLazyFile<'a>> for CacheEntry<'a> {
+    fn from(lazy_file: CacheLazyFile<'a>) -> Self {
+        Self::Lazy(lazy_file)
+    }
+}
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Event emitted by the operations of a cache.
+///
+/// Check the [`Cache::with_observer`] method for more details on how to observe the events.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CacheEvent;
+///
+/// // Describe the event in a log line
+/// let event = CacheEvent::Created {
+///     path: "data.txt".into(),
+/// };
+/// if let CacheEvent::Created { path } = &event {
+///     println!("created {}", path.display());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheEvent {
+    /// A file was created by its callback.
+    Created {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file was refreshed by its callback.
+    Refreshed {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file was removed.
+    Removed {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file was evicted by the limit policy or the storage quota.
+    Evicted {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file was locked.
+    LockAcquired {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file was unlocked.
+    LockReleased {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// An operation failed.
+    Error {
+        /// Path of the file the operation failed for, if the operation concerned a single file
+        path: Option<PathBuf>,
+        /// Description of the error
+        error: String,
+    },
+}
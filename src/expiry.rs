@@ -0,0 +1,71 @@
+use std::time::{Duration, SystemTime};
+
+/// Describes when a cached file expires and needs to be refreshed.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+///
+/// use fcache::ExpiryMode;
+///
+/// // Expire five minutes after the last modification of the file
+/// let relative = ExpiryMode::Interval(Duration::from_secs(5 * 60));
+///
+/// // Expire at a fixed point in time, regardless of the file's modification time
+/// let absolute = ExpiryMode::Absolute(SystemTime::now() + Duration::from_secs(60));
+/// # let _ = (relative, absolute);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpiryMode {
+    /// The file expires once the given interval has elapsed since its last modification.
+    Interval(Duration),
+    /// The file expires at the given point in time.
+    ///
+    /// Once this time has passed the file is considered invalid on every check, until a new expiry time is set.
+    Absolute(SystemTime),
+    /// The file never expires, so it is only refreshed manually.
+    Never,
+}
+
+impl ExpiryMode {
+    /// Creates an expiry mode from a refresh interval, where `None` means the file never expires.
+    pub(crate) fn from_refresh_interval(refresh_interval: Option<Duration>) -> Self {
+        refresh_interval.map_or(Self::Never, Self::from)
+    }
+
+    /// Returns the point in time at which a file modified at `modified` expires, or `None` if it never expires.
+    pub(crate) fn valid_until(&self, modified: SystemTime) -> Option<SystemTime> {
+        match self {
+            Self::Interval(refresh_interval) => modified.checked_add(*refresh_interval),
+            Self::Absolute(expires_at) => Some(*expires_at),
+            Self::Never => None,
+        }
+    }
+
+    /// Returns the refresh interval, computing the time remaining until expiry for absolute mode, or `None` if the file never expires.
+    pub(crate) fn refresh_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Interval(refresh_interval) => Some(*refresh_interval),
+            Self::Absolute(expires_at) => Some(expires_at.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO)),
+            Self::Never => None,
+        }
+    }
+}
+
+impl From<Duration> for ExpiryMode {
+    fn from(refresh_interval: Duration) -> Self {
+        // The maximum interval is kept as the way to disable refreshing
+        if refresh_interval == Duration::MAX {
+            Self::Never
+        } else {
+            Self::Interval(refresh_interval)
+        }
+    }
+}
+
+impl From<SystemTime> for ExpiryMode {
+    fn from(expires_at: SystemTime) -> Self {
+        Self::Absolute(expires_at)
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::{Path, PathBuf};
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Operation of a cache at which a fault can be injected.
+///
+/// Check the [`Cache::with_fault_injector`] method for more details on how to inject faults.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::FaultPoint;
+///
+/// // Describe the operation in a log line
+/// let fault_point = FaultPoint::Remove {
+///     path: "data.txt".into(),
+/// };
+/// println!("removing {}", fault_point.path().display());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FaultPoint {
+    /// A file is opened for writing to be created.
+    CreateOpen {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// The callback writes the content of a file.
+    CallbackWrite {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file is opened for writing to be refreshed.
+    RefreshOpen {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// A file is removed.
+    Remove {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// The metadata of a file is read.
+    Metadata {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// The content of a file is read into memory.
+    Read {
+        /// Path of the file
+        path: PathBuf,
+    },
+    /// The cache directory is moved.
+    Rename {
+        /// Path of the cache directory
+        path: PathBuf,
+    },
+}
+
+impl FaultPoint {
+    /// Returns the path of the file or directory the operation concerns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    ///
+    /// use fcache::FaultPoint;
+    ///
+    /// let fault_point = FaultPoint::Metadata {
+    ///     path: "data.txt".into(),
+    /// };
+    /// assert_eq!(fault_point.path(), Path::new("data.txt"));
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::CreateOpen { path }
+            | Self::CallbackWrite { path }
+            | Self::RefreshOpen { path }
+            | Self::Remove { path }
+            | Self::Metadata { path }
+            | Self::Read { path }
+            | Self::Rename { path } => path,
+        }
+    }
+}
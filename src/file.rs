@@ -1,10 +1,28 @@
+use std::ffi::OsString;
 use std::fmt::{self, Debug};
-use std::fs::{self, File};
+use std::fs::{self, File, Metadata};
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+use std::ops::Deref;
 use std::path::{Component, Path, PathBuf};
-use std::time::{Duration, SystemTime};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+use std::{error, result};
 
-use crate::callback::CallbackFn;
+#[cfg(doc)]
+use crate::Cache;
+use crate::callback::{self, CallbackFn, CallbackPanic};
+use crate::creation::CreationMode;
+use crate::event::CacheEvent;
+use crate::expiry::ExpiryMode;
+#[cfg(feature = "testing")]
+use crate::fault::FaultPoint;
+use crate::link::{self, LinkMode};
+use crate::lock::FileLock;
+use crate::recovery::ErrorRecovery;
+use crate::report::{CacheDecision, CacheExplanation, EntryStatus};
 use crate::result::{Error, Result};
+use crate::{InnerDirCache, interval, mime};
 
 /// A file in the cache that is lazily created when accessed.
 ///
@@ -41,18 +59,48 @@ use crate::result::{Error, Result};
 pub struct CacheLazyFile<'a> {
     /// Path to the lazy file
     path: PathBuf,
+    /// Path to the lazy file relative to the cache directory
+    relative_path: PathBuf,
     /// Name of the lazy file
     name: String,
     /// Callback function to initialize the file
     callback: Box<dyn CallbackFn>,
-    /// Refresh interval for the file
-    refresh_interval: Duration,
-    /// Cache root directory
-    cache_root: &'a Path,
-    /// Cache refresh interval
-    cache_refresh_interval: &'a Duration,
-    /// Whether the file is locked
-    locked: bool,
+    /// Expiry mode for the file
+    expiry: ExpiryMode,
+    /// Cache the file belongs to
+    cache: CacheRef<'a>,
+    /// Lock preventing the file from being refreshed, registered with the cache once acquired
+    lock: Arc<FileLock>,
+    /// Whether the callback panicked since the last successful creation or refresh
+    poisoned: AtomicBool,
+    /// Whether an empty file is considered invalid
+    empty_is_invalid: bool,
+    /// Minimum size of a complete file in bytes
+    min_size: Option<u64>,
+    /// Whether the existing content is served when refreshing it fails
+    stale_if_error: bool,
+    /// Maximum time the content is served past its expiry
+    max_stale: Option<Duration>,
+    /// Time after which the file is invalid if it wasn't accessed
+    idle_timeout: Option<Duration>,
+    /// Version token recorded for the file
+    version: Option<String>,
+    /// Revision of the content, incremented whenever the file is created or refreshed
+    revision: AtomicU64,
+    /// Modification time of the file observed after the handle last wrote it, and the precise time of the write
+    written_at: Mutex<Option<(SystemTime, SystemTime)>>,
+    /// Time and duration of the last successful creation or refresh by the handle
+    last_refresh: Mutex<Option<(SystemTime, Duration)>>,
+    /// Description of the error of the last failed creation or refresh by the handle, cleared by a successful one
+    last_refresh_error: Mutex<Option<String>>,
+    /// Whether the file content cannot be regenerated
+    immutable: bool,
+    /// Maximum size of the file in bytes
+    max_file_size: Option<u64>,
+    /// Source file and mode of a linked file
+    link: Option<(PathBuf, LinkMode)>,
+    /// Guard removing the file when the handle is dropped
+    remove_on_drop: Arc<RemoveFileOnDrop<'a>>,
 }
 
 impl<'a> CacheLazyFile<'a> {
@@ -60,11 +108,26 @@ impl<'a> CacheLazyFile<'a> {
     pub(crate) fn new(
         path: impl AsRef<Path>,
         callback: impl CallbackFn + 'static,
-        refresh_interval: Duration,
-        cache_root: &'a Path,
-        cache_refresh_interval: &'a Duration,
+        cache: impl Into<CacheRef<'a>>,
     ) -> Result<Self> {
         let path = path.as_ref();
+        (!path.exists())
+            .then_some(())
+            .ok_or_else(|| {
+                let path = path.to_path_buf();
+                Error::FileAlreadyExists { path }
+            })
+            .and_then(|()| Self::attach(path, callback, cache))
+    }
+
+    /// Creates a lazy file instance regardless of whether the file exists.
+    pub(crate) fn attach(
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+        cache: impl Into<CacheRef<'a>>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let cache = cache.into();
         let name = if let Some(component) = path.components().next_back()
             && let Component::Normal(name) = component
             && let Some(name) = name.to_str()
@@ -73,28 +136,100 @@ impl<'a> CacheLazyFile<'a> {
             name.to_string()
         } else {
             let path = path.to_path_buf();
-            let error = Error::InvalidPath { path };
+            let reason = "invalid file name".to_string();
+            let error = Error::InvalidPath { path, reason };
             return Err(error);
         };
-        (!path.exists())
-            .then(|| {
-                let callback = Box::new(callback);
-                let path = path.to_path_buf();
-                let locked = false;
-                Self {
-                    path,
-                    name,
-                    callback,
-                    refresh_interval,
-                    cache_root,
-                    cache_refresh_interval,
-                    locked,
-                }
-            })
-            .ok_or_else(|| {
-                let path = path.to_path_buf();
-                Error::FileAlreadyExists { path }
-            })
+        let callback = Box::new(callback);
+        let path = path.to_path_buf();
+        let relative_path = path.strip_prefix(cache.path()).unwrap_or(&path).to_path_buf();
+        let refresh_interval = Self::persisted_refresh_interval(&path)?.or(cache.refresh_interval());
+        let expiry = ExpiryMode::from_refresh_interval(refresh_interval);
+        let lock = Arc::new(FileLock::default());
+        let poisoned = AtomicBool::new(false);
+        let empty_is_invalid = cache.empty_is_invalid();
+        let min_size = None;
+        let stale_if_error = false;
+        let max_stale = None;
+        let idle_timeout = None;
+        let version = cache.version().map(str::to_string);
+        let revision = AtomicU64::new(Self::recorded_revision(&path)?);
+        let written_at = Mutex::new(None);
+        let last_refresh = Mutex::new(None);
+        let last_refresh_error = Mutex::new(None);
+        let immutable = false;
+        let max_file_size = cache.max_file_size();
+        let link = None;
+        let remove_on_drop = Arc::new(RemoveFileOnDrop(Mutex::new(None)));
+        let lazy_file = Self {
+            path,
+            relative_path,
+            name,
+            callback,
+            expiry,
+            cache,
+            lock,
+            poisoned,
+            empty_is_invalid,
+            min_size,
+            stale_if_error,
+            max_stale,
+            idle_timeout,
+            version,
+            revision,
+            written_at,
+            last_refresh,
+            last_refresh_error,
+            immutable,
+            max_file_size,
+            link,
+            remove_on_drop,
+        };
+        Ok(lazy_file)
+    }
+
+    /// Reads the refresh interval persisted in the sidecar file of the given path, if any.
+    fn persisted_refresh_interval(path: &Path) -> Result<Option<Duration>> {
+        match fs::read_to_string(Self::ttl_path(path)) {
+            Ok(millis) => {
+                let millis = millis
+                    .trim()
+                    .parse()
+                    .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+                Ok(Some(Duration::from_millis(millis)))
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Sets the maximum size of the lazy file, overriding the cache setting.
+    pub(crate) fn with_max_file_size(self, max_file_size: u64) -> Self {
+        let max_file_size = Some(max_file_size);
+        Self { max_file_size, ..self }
+    }
+
+    /// Links the lazy file to a source file, which it mirrors using the given mode.
+    pub(crate) fn with_link(self, source: PathBuf, link_mode: LinkMode) -> Self {
+        let link = Some((source, link_mode));
+        Self { link, ..self }
+    }
+
+    /// Marks the lazy file as immutable, so it never expires and cannot be refreshed.
+    pub(crate) fn into_immutable(self) -> Self {
+        let expiry = ExpiryMode::Never;
+        let empty_is_invalid = false;
+        let min_size = None;
+        let version = None;
+        let immutable = true;
+        Self {
+            expiry,
+            empty_is_invalid,
+            min_size,
+            version,
+            immutable,
+            ..self
+        }
     }
 
     /// Sets the refresh interval for the lazy file.
@@ -120,24 +255,123 @@ impl<'a> CacheLazyFile<'a> {
     /// ```
     #[must_use]
     pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
-        let Self {
-            path,
-            name,
-            callback,
-            cache_root,
-            cache_refresh_interval,
-            locked,
-            ..
-        } = self;
-        Self {
-            path,
-            name,
-            callback,
-            refresh_interval,
-            cache_root,
-            cache_refresh_interval,
-            locked,
-        }
+        let expiry = ExpiryMode::from(refresh_interval);
+        Self { expiry, ..self }
+    }
+
+    /// Disables automatic refreshing of the lazy file, so it is only refreshed manually.
+    ///
+    /// This is the same as setting the refresh interval to [`Duration::MAX`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Never refresh the file automatically
+    /// let cache_file = cache_file.without_refresh();
+    /// assert_eq!(cache_file.refresh_interval_opt(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn without_refresh(self) -> Self {
+        let expiry = ExpiryMode::Never;
+        Self { expiry, ..self }
+    }
+
+    /// Sets the refresh interval for the lazy file from a human-readable string.
+    ///
+    /// For more details about the format see [`Cache::with_refresh_interval_str`](crate::Cache::with_refresh_interval_str).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set custom refresh interval to 30 minutes
+    /// let cache_file = cache_file.with_refresh_interval_str("30m")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidInterval`] if the string is not a valid interval.
+    pub fn with_refresh_interval_str(self, refresh_interval: &str) -> Result<Self> {
+        let refresh_interval = interval::parse(refresh_interval)?;
+        Ok(self.with_refresh_interval(refresh_interval))
+    }
+
+    /// Sets an absolute expiry time for the lazy file.
+    ///
+    /// Unlike [`with_refresh_interval`](Self::with_refresh_interval), which is relative to the file's modification time,
+    /// the file stays valid until the given point in time and is considered invalid afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("token.txt", |mut file| {
+    ///     file.write_all(b"token")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Expire together with the token lease in one hour
+    /// let cache_file = cache_file.with_expires_at(SystemTime::now() + Duration::from_secs(60 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_expires_at(self, expires_at: SystemTime) -> Self {
+        let expiry = ExpiryMode::Absolute(expires_at);
+        Self { expiry, ..self }
+    }
+
+    /// Sets the expiry mode for the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::ExpiryMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set the expiry mode explicitly
+    /// let cache_file = cache_file.with_expiry(ExpiryMode::Interval(Duration::from_secs(60)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_expiry(self, expiry: ExpiryMode) -> Self {
+        Self { expiry, ..self }
     }
 
     /// Sets the refresh interval to the default value.
@@ -165,28 +399,42 @@ impl<'a> CacheLazyFile<'a> {
     /// ```
     #[must_use]
     pub fn with_default_refresh_interval(self) -> Self {
-        let Self {
-            path,
-            name,
-            callback,
-            cache_root,
-            cache_refresh_interval,
-            locked,
-            ..
-        } = self;
-        let refresh_interval = *cache_refresh_interval;
+        let Self { cache, .. } = &self;
+        let expiry = ExpiryMode::from_refresh_interval(cache.refresh_interval());
+        Self { expiry, ..self }
+    }
+
+    /// Sets whether an empty file is considered invalid.
+    ///
+    /// When enabled, a zero-byte file is refreshed on the next access regardless of its expiry.
+    /// This overrides the cache setting (see [`Cache::with_empty_is_invalid`](crate::Cache::with_empty_is_invalid)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_empty_is_invalid(true);
+    /// let cache_file = cache.get_lazy("marker.txt", |_| Ok(()))?;
+    ///
+    /// // This file is meant to be empty
+    /// let cache_file = cache_file.with_empty_is_invalid(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
         Self {
-            path,
-            name,
-            callback,
-            refresh_interval,
-            cache_root,
-            cache_refresh_interval,
-            locked,
+            empty_is_invalid,
+            ..self
         }
     }
 
-    /// Returns the path of the lazy file.
+    /// Sets the minimum size of the lazy file in bytes.
+    ///
+    /// A file smaller than the minimum is considered incomplete and is refreshed on the next access regardless of its expiry.
+    /// A file at least as large as the minimum is refreshed only once it expires.
     ///
     /// # Example
     ///
@@ -195,24 +443,26 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
-    ///     file.write_all(b"config data")?;
+    /// let cache_file = cache.get_lazy("dataset.bin", |mut file| {
+    ///     file.write_all(&[0; 1024])?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Get the file path
-    /// let path = cache_file.path();
-    /// println!("File will be created at: {}", path.display());
+    /// // Regenerate the file if an earlier download was interrupted
+    /// let cache_file = cache_file.with_min_size(1024);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn path(&self) -> &Path {
-        let Self { path, .. } = self;
-        path
+    pub fn with_min_size(self, min_size: u64) -> Self {
+        let min_size = Some(min_size);
+        Self { min_size, ..self }
     }
 
-    /// Returns the name of the lazy file.
+    /// Sets whether the existing content is served when refreshing the lazy file fails.
+    ///
+    /// When enabled, [`open`](Self::open) serves the expired content instead of returning the error of the refresh,
+    /// unless the content is too stale (see [`with_max_stale`](Self::with_max_stale)).
     ///
     /// # Example
     ///
@@ -221,24 +471,27 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
-    ///     file.write_all(b"config data")?;
+    /// let cache_file = cache.get_lazy("rates.json", |mut file| {
+    ///     file.write_all(b"{}")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Get the file name
-    /// let name = cache_file.name();
-    /// println!("File name: {}", name);
+    /// // Keep serving the last known rates while the upstream is down
+    /// let cache_file = cache_file.with_stale_if_error(true);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn name(&self) -> &str {
-        let Self { name, .. } = self;
-        name
+    pub fn with_stale_if_error(self, stale_if_error: bool) -> Self {
+        Self { stale_if_error, ..self }
     }
 
-    /// Returns the refresh interval of the lazy file.
+    /// Sets the maximum time the content of the lazy file is served past its expiry.
+    ///
+    /// Content which expired longer than `max_stale` ago is never served by [`open`](Self::open): the file is either refreshed,
+    /// or [`Error::TooStale`] is returned, even if stale content is served on errors (see [`with_stale_if_error`](Self::with_stale_if_error))
+    /// or the cache is frozen. Locked files aren't refreshed on access, but the bound applies to them as well.
+    /// Use [`staleness`](Self::staleness) to observe how close the content is to the bound; [`open_stale`](Self::open_stale) ignores it.
     ///
     /// # Example
     ///
@@ -249,26 +502,4170 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache
-    ///     .get_lazy("data.txt", |mut file| {
-    ///         file.write_all(b"content")?;
-    ///         Ok(())
-    ///     })?
-    ///     .with_refresh_interval(Duration::from_secs(300));
+    /// let cache_file = cache.get_lazy("rates.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
     ///
-    /// // Check the current refresh interval
-    /// let interval = cache_file.refresh_interval();
-    /// println!("Refresh interval: {} seconds", interval.as_secs());
+    /// // Never serve rates which expired more than an hour ago
+    /// let cache_file = cache_file
+    ///     .with_stale_if_error(true)
+    ///     .with_max_stale(Duration::from_secs(60 * 60));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn refresh_interval(&self) -> Duration {
-        let Self { refresh_interval, .. } = self;
-        *refresh_interval
+    pub fn with_max_stale(self, max_stale: Duration) -> Self {
+        let max_stale = Some(max_stale);
+        Self { max_stale, ..self }
+    }
+
+    /// Sets the time after which the lazy file is invalid if nobody accessed it.
+    ///
+    /// Accesses are recorded in a `.fcache-meta-<name>.accessed` sidecar file next to the file whenever the file is created, refreshed, or read by a handle with an idle timeout,
+    /// using the time of the cache clock. The idle timeout composes with the expiry: the file is invalid if either of them trips, and is refreshed on the next access.
+    /// Idle files can also be removed with [`Cache::prune_idle`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("session.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Regenerate the session after 15 minutes without use
+    /// let cache_file = cache_file.with_idle_timeout(Duration::from_secs(15 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_idle_timeout(self, idle_timeout: Duration) -> Self {
+        let idle_timeout = Some(idle_timeout);
+        Self { idle_timeout, ..self }
+    }
+
+    /// Sets the version token of the lazy file, overriding the cache setting.
+    ///
+    /// The token is recorded in a `.fcache-meta-<name>.version` sidecar file next to the file whenever the file is created or refreshed.
+    /// A file whose recorded token differs is invalid regardless of its expiry, and is refreshed on the next access.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("settings.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Regenerate the file whenever the config version changes
+    /// let cache_file = cache_file.with_version("config-v2");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_version(self, version: impl Into<String>) -> Self {
+        let version = Some(version.into());
+        Self { version, ..self }
+    }
+
+    /// Sets whether the lazy file is removed when the handle is dropped.
+    ///
+    /// This is useful for ephemeral files, e.g. intermediate results or one-time tokens, whose lifetime is tied to a scope.
+    /// The file is removed as with [`remove`](Self::remove), and errors are ignored, e.g. when the cache is frozen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let path = {
+    ///     let cache_file = cache
+    ///         .get_lazy("token.txt", |mut file| {
+    ///             file.write_all(b"secret")?;
+    ///             Ok(())
+    ///         })?
+    ///         .on_drop_remove(true);
+    ///
+    ///     // Use the file...
+    ///     cache_file.open()?;
+    ///     cache_file.path().to_path_buf()
+    /// };
+    ///
+    /// // The file is removed along with the handle
+    /// assert!(!path.exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_drop_remove(mut self, on_drop_remove: bool) -> Self {
+        self.remove_on_drop(on_drop_remove);
+        self
+    }
+
+    /// Sets whether the lazy file is removed when the handle is dropped, without consuming the handle.
+    ///
+    /// This is the same as [`on_drop_remove`](Self::on_drop_remove), but allows to change the decision later, e.g. to keep a scratch file once it turns out to be needed.
+    /// Nothing is removed on drop once the file has been removed with [`remove`](Self::remove).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let mut cache_file = cache.get_lazy("scratch.bin", |mut file| {
+    ///     file.write_all(&[0; 16])?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Remove the file along with the handle, unless it turns out to be needed
+    /// cache_file.remove_on_drop(true);
+    /// cache_file.open()?;
+    /// cache_file.remove_on_drop(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_on_drop(&mut self, remove_on_drop: bool) {
+        let Self {
+            path,
+            cache,
+            remove_on_drop: guard,
+            ..
+        } = self;
+        guard.set(remove_on_drop.then(|| (path.clone(), cache.clone())));
+    }
+
+    /// Removes the lazy file when the handle is dropped.
+    ///
+    /// This is a shorthand for [`on_drop_remove(true)`](Self::on_drop_remove).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("result.bin", |mut file| {
+    ///     file.write_all(&[0; 16])?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Remove the file once the handle goes out of scope
+    /// let cache_file = cache_file.forget_on_drop();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn forget_on_drop(self) -> Self {
+        self.on_drop_remove(true)
+    }
+
+    /// Converts the handle into one which doesn't borrow the cache, e.g. to keep it alongside the cache in a struct or send it to a thread.
+    ///
+    /// The owned handle shares the state of the cache, e.g. its locks and the generation of files, and keeps the settings of the cache at the time of the conversion.
+    /// It also keeps the cache directory from being removed by [`Cache::with_dir_ephemeral`] until it is dropped, but not a temporary directory created by [`Cache::new`].
+    /// Whether the file is removed when the handle is dropped carries over to the owned handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::OwnedCacheLazyFile;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file: OwnedCacheLazyFile = cache
+    ///     .get_lazy("data.txt", |mut file| {
+    ///         file.write_all(b"content")?;
+    ///         Ok(())
+    ///     })?
+    ///     .into_owned();
+    ///
+    /// // The handle no longer borrows the cache
+    /// let cache_file = std::thread::spawn(move || cache_file.open().map(|_| cache_file))
+    ///     .join()
+    ///     .expect("thread should not panic")?;
+    /// assert!(cache_file.path().exists());
+    /// # drop(cache);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> CacheLazyFile<'static> {
+        let Self {
+            path,
+            relative_path,
+            name,
+            callback,
+            expiry,
+            cache,
+            lock,
+            poisoned,
+            empty_is_invalid,
+            min_size,
+            stale_if_error,
+            max_stale,
+            idle_timeout,
+            version,
+            revision,
+            written_at,
+            last_refresh,
+            last_refresh_error,
+            immutable,
+            max_file_size,
+            link,
+            remove_on_drop,
+        } = self;
+        let cache = cache.into_owned();
+        // The owned handle takes over removing the file on drop
+        let target = remove_on_drop.take().map(|(path, _)| (path, cache.clone()));
+        let remove_on_drop = Arc::new(RemoveFileOnDrop(Mutex::new(target)));
+        CacheLazyFile {
+            path,
+            relative_path,
+            name,
+            callback,
+            expiry,
+            cache,
+            lock,
+            poisoned,
+            empty_is_invalid,
+            min_size,
+            stale_if_error,
+            max_stale,
+            idle_timeout,
+            version,
+            revision,
+            written_at,
+            last_refresh,
+            last_refresh_error,
+            immutable,
+            max_file_size,
+            link,
+            remove_on_drop,
+        }
+    }
+
+    /// Returns the path of the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the file path
+    /// let path = cache_file.path();
+    /// println!("File will be created at: {}", path.display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        let Self { path, .. } = self;
+        path
+    }
+
+    /// Returns the path of the lazy file relative to the cache directory, i.e. its normalized key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("./reports/summary.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the key for logging
+    /// assert_eq!(
+    ///     cache_file.relative_path(),
+    ///     Path::new("reports/summary.json")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn relative_path(&self) -> &Path {
+        let Self { relative_path, .. } = self;
+        relative_path
+    }
+
+    /// Returns the name of the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the file name
+    /// let name = cache_file.name();
+    /// println!("File name: {}", name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &str {
+        let Self { name, .. } = self;
+        name
+    }
+
+    /// Returns the refresh interval of the lazy file.
+    ///
+    /// For files with an absolute expiry time (see [`with_expires_at`](Self::with_expires_at)) this is the time remaining until expiry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get_lazy("data.txt", |mut file| {
+    ///         file.write_all(b"content")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_refresh_interval(Duration::from_secs(300));
+    ///
+    /// // Check the current refresh interval
+    /// let interval = cache_file.refresh_interval();
+    /// println!("Refresh interval: {} seconds", interval.as_secs());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval_opt().unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the refresh interval of the lazy file, or `None` if it is never refreshed automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get_lazy("data.txt", |mut file| {
+    ///         file.write_all(b"content")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_refresh_interval(Duration::from_secs(300));
+    ///
+    /// // Check the current refresh interval
+    /// assert_eq!(
+    ///     cache_file.refresh_interval_opt(),
+    ///     Some(Duration::from_secs(300))
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval_opt(&self) -> Option<Duration> {
+        let Self { expiry, .. } = self;
+        expiry.refresh_interval()
+    }
+
+    /// Persists the refresh interval of the lazy file in a sidecar file.
+    ///
+    /// The interval is written to a `.fcache-meta-<name>.ttl` sidecar file next to the file in milliseconds, and applied instead of the cache refresh interval whenever a handle for this path is created, e.g. after a process restart.
+    /// The refresh interval of this handle is not changed, use [`with_refresh_interval`](Self::with_refresh_interval) for that.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Remember a refresh interval of 30 minutes for this path
+    /// cache_file.set_refresh_interval_persist(Duration::from_secs(30 * 60))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file cannot be written.
+    pub fn set_refresh_interval_persist(&self, refresh_interval: Duration) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        cache.check_frozen(path)?;
+        self.ensure_parent()?;
+        fs::write(Self::ttl_path(path), refresh_interval.as_millis().to_string())?;
+        Ok(())
+    }
+
+    /// Removes the persisted refresh interval of the lazy file, reverting to the cache refresh interval.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.set_refresh_interval_persist(Duration::from_secs(30 * 60))?;
+    ///
+    /// // Forget the persisted refresh interval
+    /// cache_file.clear_refresh_interval_persist()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file exists but cannot be removed.
+    pub fn clear_refresh_interval_persist(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        cache.check_frozen(path)?;
+        let ttl_path = Self::ttl_path(path);
+        if ttl_path.exists() {
+            fs::remove_file(ttl_path)?;
+        }
+        Ok(())
+    }
+
+    /// Explains what accessing the lazy file would do, and why.
+    ///
+    /// All the checks deciding whether the file is created, refreshed, or used as it is are run without modifying anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheDecision;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Locked files are never refreshed
+    /// cache_file.lock()?;
+    /// assert_eq!(cache_file.explain()?.decision, CacheDecision::Locked);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, or its validity cannot be determined.
+    pub fn explain(&self) -> Result<CacheExplanation> {
+        let Self { path, cache, .. } = self;
+        #[cfg(feature = "testing")]
+        cache.inject_fault(|| FaultPoint::Metadata { path: path.clone() })?;
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => Some(metadata),
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
+        let exists = metadata.is_some();
+        let file_size = metadata.as_ref().map(Metadata::len);
+        let mtime = metadata.as_ref().map(Metadata::modified).transpose()?;
+        // Files modified in the future have no age yet
+        let modified = metadata.as_ref().map(|metadata| self.modified(metadata)).transpose()?;
+        let age = modified.map(|modified| cache.now().duration_since(modified).unwrap_or_default());
+        let is_valid = if exists { Some(self.is_valid()?) } else { None };
+        let decision = match is_valid {
+            None => CacheDecision::WouldCreate,
+            Some(_) if self.is_locked() => CacheDecision::Locked,
+            Some(true) => CacheDecision::WouldSkip,
+            Some(false) => CacheDecision::WouldRefresh,
+        };
+        let explanation = CacheExplanation {
+            path: path.clone(),
+            exists,
+            file_size,
+            mtime,
+            age,
+            refresh_interval: self.refresh_interval(),
+            is_locked: self.is_locked(),
+            is_valid,
+            decision,
+        };
+        Ok(explanation)
+    }
+
+    /// Returns the state of the lazy file, read with a single metadata lookup.
+    ///
+    /// The file is never created or refreshed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::EntryStatus;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // The file is not created until accessed
+    /// assert_eq!(cache_file.status()?, EntryStatus::Missing);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, or its validity cannot be determined.
+    pub fn status(&self) -> Result<EntryStatus> {
+        let Self { expiry, .. } = self;
+        let metadata = match self.read_metadata() {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(EntryStatus::Missing),
+            Err(error) => return Err(error.into()),
+        };
+        let modified = self.modified(&metadata)?;
+        let status = EntryStatus::Present {
+            valid: self.is_valid_with(&metadata)?,
+            locked: self.is_locked(),
+            size: metadata.len(),
+            modified,
+            expires: expiry.valid_until(modified),
+        };
+        Ok(status)
+    }
+
+    /// Returns the content type of the lazy file.
+    ///
+    /// The content type is determined from the file extension, or by inspecting the leading bytes when the extension is missing or unknown (see [`sniff`](crate::sniff)).
+    /// The file is never created or refreshed. Returns `None` for unrecognized binary content.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Get the content type to serve the file with
+    /// let content_type = cache_file
+    ///     .content_type()?
+    ///     .unwrap_or("application/octet-stream");
+    /// assert_eq!(content_type, "application/json");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content type is sniffed, but the file cannot be read.
+    pub fn content_type(&self) -> Result<Option<&'static str>> {
+        let Self { path, .. } = self;
+        match mime::from_extension(path) {
+            Some(content_type) => Ok(Some(content_type)),
+            None => mime::sniff(path),
+        }
+    }
+
+    /// Returns the expiry mode of the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::ExpiryMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files use the cache refresh interval by default
+    /// assert_eq!(
+    ///     cache_file.expiry(),
+    ///     ExpiryMode::Interval(cache.refresh_interval())
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn expiry(&self) -> ExpiryMode {
+        let Self { expiry, .. } = self;
+        *expiry
+    }
+
+    /// Returns whether an empty lazy file is considered invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_empty_is_invalid(true);
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files inherit the cache setting
+    /// assert!(cache_file.empty_is_invalid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn empty_is_invalid(&self) -> bool {
+        let Self { empty_is_invalid, .. } = self;
+        *empty_is_invalid
+    }
+
+    /// Returns the minimum size of the lazy file in bytes, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get_lazy("dataset.bin", |mut file| {
+    ///         file.write_all(&[0; 1024])?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_min_size(1024);
+    ///
+    /// // Check the minimum size
+    /// assert_eq!(cache_file.min_size(), Some(1024));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_size(&self) -> Option<u64> {
+        let Self { min_size, .. } = self;
+        *min_size
+    }
+
+    /// Returns whether the existing content is served when refreshing the lazy file fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("rates.json", |_| Ok(()))?;
+    ///
+    /// // Errors are returned by default
+    /// assert!(!cache_file.stale_if_error());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stale_if_error(&self) -> bool {
+        let Self { stale_if_error, .. } = self;
+        *stale_if_error
+    }
+
+    /// Returns the maximum time the content of the lazy file is served past its expiry, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get_lazy("rates.json", |_| Ok(()))?
+    ///     .with_max_stale(Duration::from_secs(60));
+    ///
+    /// // Check the maximum staleness
+    /// assert_eq!(cache_file.max_stale(), Some(Duration::from_secs(60)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_stale(&self) -> Option<Duration> {
+        let Self { max_stale, .. } = self;
+        *max_stale
+    }
+
+    /// Returns the time after which the lazy file is invalid if nobody accessed it, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("session.json", |_| Ok(()))?;
+    ///
+    /// // Files don't expire when idle by default
+    /// assert_eq!(cache_file.idle_timeout(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        let Self { idle_timeout, .. } = self;
+        *idle_timeout
+    }
+
+    /// Returns the version token of the lazy file, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get_lazy("settings.json", |mut file| {
+    ///         file.write_all(b"{}")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_version("config-v2");
+    ///
+    /// // Check the version token
+    /// assert_eq!(cache_file.version(), Some("config-v2"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        let Self { version, .. } = self;
+        version.as_deref()
+    }
+
+    /// Returns the revision of the lazy file content.
+    ///
+    /// The revision starts at zero and is incremented whenever the file is created or refreshed.
+    /// Once the file is refreshed, the revision is recorded in a `.fcache-meta-<name>.revision` sidecar file next to the file, so it survives restarts. It is reset when the file is removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(cache_file.revision(), 0);
+    ///
+    /// // Creating the file increments the revision
+    /// cache_file.open()?;
+    /// assert_eq!(cache_file.revision(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        let Self { revision, .. } = self;
+        revision.load(Ordering::SeqCst)
+    }
+
+    /// Returns the original key the lazy file was requested with, if its name was sanitized.
+    ///
+    /// The original key is recorded in a `.fcache-meta-<name>.rawkey` sidecar file next to the file by [`Cache::get_sanitized`], so it survives restarts. It is removed along with the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files requested with plain keys have no original key
+    /// assert_eq!(cache_file.original_key()?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file exists but cannot be read.
+    pub fn original_key(&self) -> Result<Option<String>> {
+        let Self { path, .. } = self;
+        match fs::read_to_string(Self::rawkey_path(path)) {
+            Ok(original_key) => Ok(Some(original_key)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns when the lazy file was last successfully created or refreshed by this handle, if ever.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// assert!(cache_file.last_refresh_at().is_none());
+    ///
+    /// // Creating the file records the time
+    /// cache_file.open()?;
+    /// assert!(cache_file.last_refresh_at().is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_at(&self) -> Option<SystemTime> {
+        let Self { last_refresh, .. } = self;
+        let last_refresh = last_refresh.lock().unwrap_or_else(PoisonError::into_inner);
+        last_refresh.map(|(refreshed_at, _)| refreshed_at)
+    }
+
+    /// Returns how long the last successful creation or refresh of the lazy file by this handle took, including the callback, if ever.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check how long the callback took
+    /// cache_file.open()?;
+    /// assert!(cache_file.last_refresh_duration() < Some(Duration::from_secs(1)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_duration(&self) -> Option<Duration> {
+        let Self { last_refresh, .. } = self;
+        let last_refresh = last_refresh.lock().unwrap_or_else(PoisonError::into_inner);
+        last_refresh.map(|(_, duration)| duration)
+    }
+
+    /// Returns the description of the error of the last creation or refresh of the lazy file by this handle, if it failed.
+    ///
+    /// The error is described as a string, as errors can't be cloned. It is cleared by the next successful creation or refresh.
+    /// This allows inspecting failures which are otherwise not surfaced, e.g. when stale content is served (see [`with_stale_if_error`](Self::with_stale_if_error)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |_| Err("upstream unavailable".into()))?;
+    ///
+    /// // Check why the creation failed
+    /// assert!(cache_file.open().is_err());
+    /// assert_eq!(
+    ///     cache_file.last_refresh_error().as_deref(),
+    ///     Some("upstream unavailable")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_error(&self) -> Option<String> {
+        let Self { last_refresh_error, .. } = self;
+        last_refresh_error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Records the original key the lazy file was requested with, unless it is already recorded.
+    pub(crate) fn record_original_key(&self, original_key: &str) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        if self.original_key()?.as_deref() != Some(original_key) {
+            cache.check_frozen(path)?;
+            fs::write(Self::rawkey_path(path), original_key)?;
+        }
+        Ok(())
+    }
+
+    /// Returns whether the lazy file is locked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check if the file is locked
+    /// assert!(!cache_file.is_locked());
+    /// cache_file.lock()?;
+    /// assert!(cache_file.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        let Self { lock, cache, .. } = self;
+        lock.is_held(cache.now())
+    }
+
+    /// Returns whether the lazy file is unlocked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check if the file is unlocked
+    /// assert!(cache_file.is_unlocked());
+    /// cache_file.lock()?;
+    /// assert!(!cache_file.is_unlocked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_unlocked(&self) -> bool {
+        !self.is_locked()
+    }
+
+    /// Returns whether the callback of the lazy file panicked since its last successful creation or refresh.
+    ///
+    /// For more details see [`CacheFile::is_poisoned`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |_| panic!("unavailable"))?;
+    ///
+    /// // The panic is reported as an error
+    /// assert!(cache_file.open().is_err());
+    /// assert!(cache_file.is_poisoned());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_poisoned(&self) -> bool {
+        let Self { poisoned, .. } = self;
+        poisoned.load(Ordering::SeqCst)
+    }
+
+    /// Clears the poison of the lazy file, without refreshing it.
+    ///
+    /// For more details see [`CacheFile::clear_poison`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |_| panic!("unavailable"))?;
+    /// assert!(cache_file.open().is_err());
+    ///
+    /// // Acknowledge the panic
+    /// cache_file.clear_poison();
+    /// assert!(!cache_file.is_poisoned());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_poison(&self) {
+        let Self { poisoned, .. } = self;
+        poisoned.store(false, Ordering::SeqCst);
+    }
+
+    /// Checks if the lazy file is valid.
+    ///
+    /// Lazy files which were not created yet are invalid, as they need to be created.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// assert!(!cache_file.is_valid()?);
+    ///
+    /// // Check if the file is still valid once created
+    /// cache_file.open()?;
+    /// if cache_file.is_valid()? {
+    ///     println!("File is still fresh");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
+    pub fn is_valid(&self) -> Result<bool> {
+        let metadata = match self.read_metadata() {
+            Ok(metadata) => metadata,
+            // Files which were not created yet need to be created
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        self.is_valid_with(&metadata)
+    }
+
+    /// Reads the metadata of the lazy file.
+    fn read_metadata(&self) -> io::Result<Metadata> {
+        let Self { path, .. } = self;
+        #[cfg(feature = "testing")]
+        {
+            let Self { cache, .. } = self;
+            cache.inject_fault(|| FaultPoint::Metadata { path: path.clone() })?;
+        }
+        fs::metadata(path)
+    }
+
+    /// Checks if the lazy file is valid, given its metadata.
+    fn is_valid_with(&self, metadata: &Metadata) -> Result<bool> {
+        let Self {
+            path,
+            expiry,
+            empty_is_invalid,
+            min_size,
+            idle_timeout,
+            version,
+            immutable,
+            link,
+            poisoned,
+            cache,
+            ..
+        } = self;
+        // Files whose callback panicked are in an unknown state
+        if poisoned.load(Ordering::SeqCst) {
+            return Ok(false);
+        }
+        if *empty_is_invalid && metadata.len() == 0 {
+            return Ok(false);
+        }
+        // Incomplete files are invalid regardless of their expiry
+        if let Some(min_size) = min_size
+            && metadata.len() < *min_size
+        {
+            return Ok(false);
+        }
+        // Files recorded with another version are invalid regardless of their expiry
+        if let Some(version) = version
+            && Self::recorded_version(path)?.as_deref() != Some(version.as_str())
+        {
+            return Ok(false);
+        }
+        // Files recorded before the generation of the cache was incremented are invalid regardless of their expiry
+        if !*immutable && Self::read_counter(&Self::generation_path(path))? < cache.generation() {
+            return Ok(false);
+        }
+        // Files nobody accessed for too long are invalid regardless of their expiry
+        if !*immutable && let Some(idle_timeout) = idle_timeout {
+            let accessed_at = match Self::recorded_access(path)? {
+                Some(accessed_at) => accessed_at,
+                None => self.modified(metadata)?,
+            };
+            if cache
+                .now()
+                .duration_since(accessed_at)
+                .is_ok_and(|idle| idle >= *idle_timeout)
+            {
+                return Ok(false);
+            }
+        }
+        if let Some((source, _)) = link {
+            // Linked files are valid until the source is modified
+            let modified = fs::symlink_metadata(path)?.modified()?;
+            return Ok(fs::metadata(source)?.modified()? <= modified);
+        }
+        match expiry {
+            ExpiryMode::Interval(refresh_interval) => {
+                let modified = self.modified(metadata)?;
+                let elapsed = cache.now().duration_since(modified)?;
+                Ok(elapsed < *refresh_interval)
+            },
+            ExpiryMode::Absolute(expires_at) => Ok(cache.now() < *expires_at),
+            ExpiryMode::Never => Ok(true),
+        }
+    }
+
+    /// Checks if the lazy file is invalid.
+    ///
+    /// Lazy files which were not created yet are invalid, as they need to be created.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check if the file needs refreshing
+    /// if cache_file.is_invalid()? {
+    ///     println!("File needs to be refreshed");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
+    pub fn is_invalid(&self) -> Result<bool> {
+        self.is_valid().map(|valid| !valid)
+    }
+
+    /// Returns the time until the lazy file is valid, or `None` if it never expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get when the file will expire once created
+    /// assert!(matches!(
+    ///     cache_file.valid_until(),
+    ///     Err(fcache::Error::FileNotFound { .. })
+    /// ));
+    /// cache_file.open()?;
+    /// let valid_until = cache_file.valid_until()?;
+    /// println!("File valid until: {:?}", valid_until);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileNotFound`] if the lazy file was not created yet, or an error if the file metadata cannot be read or the file's modification time cannot be determined.
+    pub fn valid_until(&self) -> Result<Option<SystemTime>> {
+        let Self { path, expiry, .. } = self;
+        let metadata = match fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                let path = path.clone();
+                let error = Error::FileNotFound { path };
+                return Err(error);
+            },
+            Err(error) => return Err(error.into()),
+        };
+        let modified = self.modified(&metadata)?;
+        Ok(expiry.valid_until(modified))
+    }
+
+    /// Returns how long the lazy file is past its expiry, or `None` if it hasn't expired yet or never expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // A freshly created file is not stale
+    /// let _ = cache_file.open()?;
+    /// assert_eq!(cache_file.staleness()?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`valid_until`](Self::valid_until).
+    pub fn staleness(&self) -> Result<Option<Duration>> {
+        let Self { cache, .. } = self;
+        let staleness = self
+            .valid_until()?
+            .and_then(|valid_until| cache.now().duration_since(valid_until).ok());
+        Ok(staleness)
+    }
+
+    /// Refreshes the lazy file if it is invalid, restoring the existing content if the refresh fails.
+    fn refresh_keeping_stale(&self) -> Result<()> {
+        let Self { path, .. } = self;
+        if self.is_valid()? {
+            return Ok(());
+        }
+        // Move the existing content aside, as the refresh discards it before running the callback
+        let stale_path = Self::stale_path(path);
+        fs::rename(path, &stale_path)?;
+        match self.force_refresh() {
+            Ok(()) => fs::remove_file(&stale_path).map_err(Error::IO),
+            Err(error) => {
+                fs::rename(&stale_path, path)?;
+                Err(error)
+            },
+        }
+    }
+
+    /// Returns [`Error::TooStale`] if the content of the lazy file expired longer than the maximum staleness ago.
+    fn check_max_stale(&self) -> Result<()> {
+        let Self { path, max_stale, .. } = self;
+        // Files removed by a failed refresh have no content to serve
+        if let Some(max_stale) = max_stale
+            && path.exists()
+            && let Some(age) = self.staleness()?
+            && age > *max_stale
+        {
+            let path = path.clone();
+            let error = Error::TooStale { path, age };
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Locks this file to prevent other processes from reading or writing to it.
+    ///
+    /// For more details about the locking mechanism see [`CacheFile::lock`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Lock the file to prevent concurrent access
+    /// cache_file.lock()?;
+    /// // ... perform critical operations ...
+    /// cache_file.unlock()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is already locked by another process, system file locking mechanisms fail, or the underlying file cannot be accessed.
+    pub fn lock(&self) -> Result<()> {
+        self.acquire_lock(None)
+    }
+
+    /// Locks the lazy file for the given time, after which it is unlocked automatically.
+    ///
+    /// For more details about the locking mechanism see [`CacheFile::lock_for`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Lock the file for at most a minute
+    /// cache_file.lock_for(Duration::from_secs(60))?;
+    /// assert!(cache_file.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is already locked and the lock hasn't expired.
+    pub fn lock_for(&self, duration: Duration) -> Result<()> {
+        let Self { cache, .. } = self;
+        self.acquire_lock(Some(cache.now() + duration))
+    }
+
+    /// Locks the lazy file, returning a guard unlocking it when dropped.
+    ///
+    /// For more details about the locking mechanism see [`CacheFile::lock_guard`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// {
+    ///     let _guard = cache_file.lock_guard()?;
+    ///     assert!(cache_file.is_locked());
+    /// }
+    /// assert!(!cache_file.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is already locked and the lock hasn't expired.
+    pub fn lock_guard(&self) -> Result<FileLockGuard<'_, 'a>> {
+        self.lock()?;
+        let guard = FileLockGuard(self);
+        Ok(guard)
+    }
+
+    /// Locks the lazy file until the given time, or indefinitely.
+    fn acquire_lock(&self, expires_at: Option<SystemTime>) -> Result<()> {
+        let Self { path, lock, cache, .. } = self;
+        let result = if lock.acquire(cache.now(), expires_at) {
+            // The cache checks the locks of all the handles before clearing its files
+            cache.lock_registry().register(path, lock);
+            Ok(())
+        } else {
+            Err(Error::FileAlreadyLocked)
+        };
+        self.observe(result, |path| CacheEvent::LockAcquired { path })
+    }
+
+    /// Unlocks the lazy file to allow refreshing.
+    ///
+    /// For more details about the locking mechanism see [`CacheFile::unlock`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Lock and then unlock the file
+    /// cache_file.lock()?;
+    /// // ... critical operations complete ...
+    /// cache_file.unlock()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is already unlocked, unless its lock expired.
+    pub fn unlock(&self) -> Result<()> {
+        let Self { lock, cache, .. } = self;
+        let result = if lock.release(cache.now()) {
+            Ok(())
+        } else {
+            Err(Error::FileAlreadyUnlocked)
+        };
+        self.observe(result, |path| CacheEvent::LockReleased { path })
+    }
+
+    /// Creates the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("document.txt", |mut file| {
+    ///     file.write_all(b"Document content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Explicitly create the file if it doesn't exist
+    /// let file = cache_file.create()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, the file already exists, file creation fails due to permissions or disk space, the callback function returns an error, the file exceeds the maximum file size, or the file cannot be reopened for reading.
+    pub fn create(&self) -> Result<File> {
+        let result = self.track(|| self.create_file());
+        let file = self.observe(result, |path| CacheEvent::Created { path })?;
+        self.enforce_storage_quota()?;
+        Ok(file)
+    }
+
+    /// Creates the lazy file without emitting an event.
+    fn create_file(&self) -> Result<File> {
+        // FIXME: Refactor
+        let Self { path, link, cache, .. } = self;
+        cache.check_frozen(path)?;
+        self.ensure_parent()?;
+        self.check_cancelled()?;
+        // Files are kept from being evicted to make room for other files until they are created
+        let _creation_guard = cache.lock_registry().begin_creation(path);
+        cache.make_room(path)?;
+        if let Some((source, LinkMode::Symlink)) = link {
+            link::symlink(source, path)?;
+            self.record_sidecars(true)?;
+            return File::options().read(true).write(false).open(path).map_err(Error::IO);
+        }
+        #[cfg(feature = "testing")]
+        cache.inject_fault(|| FaultPoint::CreateOpen { path: path.clone() })?;
+        cache
+            .file_options()
+            .create_new(true)
+            .read(false)
+            .write(true)
+            .open(path)
+            .map_err(Error::IO)
+            .and_then(|file| {
+                // Creation is all or nothing, so a failed callback leaves no partially written file behind
+                self.run_callback(file).inspect_err(|_| {
+                    let _ = fs::remove_file(path);
+                })
+            })
+            .and_then(|()| self.check_file_size())
+            .and_then(|()| self.record_sidecars(true))
+            .and_then(|()| File::options().read(true).write(false).open(path).map_err(Error::IO))
+    }
+
+    /// Opens the lazy file, creating it if it doesn't exist.
+    ///
+    /// Invalid files are refreshed before being opened, unless the file is locked, in which case the existing content is opened as it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Open and read the file content
+    /// let mut file = cache_file.open()?;
+    /// let mut content = String::new();
+    /// file.read_to_string(&mut content)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if file creation fails (if the file doesn't exist), file refresh fails (if the file exists, unless stale content is served on errors), the file cannot be opened for reading, or the callback function returns an error during creation.
+    /// [`Error::TooStale`] is returned if the content is not refreshed and expired longer than the maximum staleness ago (see [`with_max_stale`](Self::with_max_stale)).
+    pub fn open(&self) -> Result<File> {
+        let Self {
+            path,
+            cache,
+            stale_if_error,
+            ..
+        } = self;
+        let file = if path.exists() {
+            // Frozen caches and locked files serve the existing content as it is
+            let refreshed = if cache.is_frozen() || self.is_locked() {
+                Ok(())
+            } else if *stale_if_error {
+                self.refresh_keeping_stale()
+            } else {
+                self.refresh()
+            };
+            // Content beyond the maximum staleness is never served, regardless of why it wasn't refreshed
+            self.check_max_stale()?;
+            match refreshed {
+                Err(error) if !*stale_if_error => return Err(error),
+                _ => File::options().read(true).write(false).open(path)?,
+            }
+        } else {
+            self.create()?
+        };
+        self.record_access()?;
+        Ok(file)
+    }
+
+    /// Opens the lazy file like [`open`](Self::open), unless its revision is still the last seen one.
+    ///
+    /// This allows consumers to cheaply skip reading content they have already processed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("feed.xml", |mut file| {
+    ///     file.write_all(b"<feed/>")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // The content is new on the first access
+    /// assert!(cache_file.open_if_changed(0)?.is_some());
+    ///
+    /// // The content is unchanged until the file is refreshed
+    /// assert!(cache_file.open_if_changed(cache_file.revision())?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`open`](Self::open).
+    pub fn open_if_changed(&self, last_seen: u64) -> Result<Option<File>> {
+        let file = self.open()?;
+        Ok((self.revision() != last_seen).then_some(file))
+    }
+
+    /// Opens the current content of the lazy file as it is, or returns `None` if it has never been created.
+    ///
+    /// Unlike [`open`](Self::open), the validity of the file is not checked and the callback is never run, so expired content is served as well.
+    /// This is useful to show the last known content while the file is refreshed elsewhere, see [`staleness`](Self::staleness) for how old it is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("quote.txt", |mut file| {
+    ///     file.write_all(b"last known quote")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Nothing to serve before the file is created
+    /// assert!(cache_file.open_stale()?.is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file exists but cannot be opened for reading.
+    pub fn open_stale(&self) -> Result<Option<File>> {
+        let Self { path, .. } = self;
+        match File::options().read(true).write(false).open(path) {
+            Ok(file) => Ok(Some(file)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Opens the lazy file using a pooled file descriptor, creating or refreshing it if needed.
+    ///
+    /// Instead of opening the file on every call, descriptors are kept in a pool of the cache (see [`Cache::with_fd_pool_size`](crate::Cache::with_fd_pool_size)) and duplicated.
+    /// The pool of the file is invalidated when it is refreshed or removed.
+    ///
+    /// Duplicated descriptors share their position, so the file is rewound when handed out, and the returned files shouldn't be read concurrently.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Read the file repeatedly without opening it again
+    /// for _ in 0..3 {
+    ///     let mut content = String::new();
+    ///     cache_file.open_pooled()?.read_to_string(&mut content)?;
+    ///     assert_eq!(content, "config data");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`open`](Self::open), or if the pooled descriptor cannot be duplicated.
+    #[cfg(feature = "fd-pool")]
+    pub fn open_pooled(&self) -> Result<File> {
+        let Self { path, cache, .. } = self;
+        if !path.exists() {
+            // Descriptors of a removed file are stale
+            cache.fd_pool().invalidate(path);
+        } else if !cache.is_frozen() && !self.is_locked() {
+            self.refresh()?;
+        }
+        cache.fd_pool().get(path, || self.open())
+    }
+
+    /// Reads the whole content of the lazy file, creating or refreshing it if needed, like in [`open`](Self::open).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.bin", |mut file| {
+    ///     file.write_all(&[1, 2, 3])?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Read the content in a single call
+    /// assert_eq!(cache_file.read_bytes()?, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`open`](Self::open), or if the file cannot be read.
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
+        let mut content = Vec::new();
+        self.open()?.read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Reads the whole content of the lazy file as a string, creating or refreshing it if needed, like in [`open`](Self::open).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("greeting.txt", |mut file| {
+    ///     file.write_all(b"Hello, Cache!")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Read the text in a single call
+    /// assert_eq!(cache_file.read_string()?, "Hello, Cache!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::Utf8`] if the content is not valid UTF-8, or an error in the same cases as [`read_bytes`](Self::read_bytes).
+    pub fn read_string(&self) -> Result<String> {
+        let Self { path, .. } = self;
+        String::from_utf8(self.read_bytes()?).map_err(|source| {
+            let path = path.clone();
+            Error::Utf8 { path, source }
+        })
+    }
+
+    /// Reads the content of the lazy file, serving it from the in-memory layer of the cache when possible.
+    ///
+    /// The file is created or refreshed if needed, like in [`open`](Self::open). If the in-memory layer is enabled (see [`Cache::with_memory_cache`]),
+    /// the content of small files is kept in memory and served without reading the file again until it is refreshed, removed, or changed on disk.
+    /// Changes on disk are detected by the modification time and size of the file, so a change by another process keeping both of them is not noticed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_memory_cache(1024 * 1024);
+    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Read the file repeatedly without reading it from disk again
+    /// for _ in 0..3 {
+    ///     assert_eq!(&*cache_file.read_cached()?, b"config data");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`open`](Self::open), or if the file metadata or content cannot be read.
+    pub fn read_cached(&self) -> Result<Arc<[u8]>> {
+        let Self { path, cache, .. } = self;
+        let fresh = path.exists() && (cache.is_frozen() || self.is_valid()?);
+        // Content kept in memory is only served for fresh files, others go through the usual creation and refresh
+        let mut file = if fresh { None } else { Some(self.open()?) };
+        let metadata = fs::metadata(path)?;
+        let content = cache.memory_cache().get(path, &metadata, || {
+            #[cfg(feature = "testing")]
+            cache.inject_fault(|| FaultPoint::Read { path: path.clone() })?;
+            let mut file = match file.take() {
+                Some(file) => file,
+                None => File::open(path)?,
+            };
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            Ok(content)
+        })?;
+        self.record_access()?;
+        Ok(content)
+    }
+
+    /// Opens the lazy file and parses its content with the given parser.
+    ///
+    /// The file is created or refreshed if needed, like in [`open`](Self::open), and passed to the parser.
+    /// This allows using streaming parsers working directly on the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("counter.txt", |mut file| {
+    ///     file.write_all(b"42")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Parse the file content
+    /// let counter = cache_file.open_and_parse(|file| {
+    ///     let mut content = String::new();
+    ///     file.read_to_string(&mut content)?;
+    ///     Ok(content.parse::<u32>()?)
+    /// })?;
+    /// assert_eq!(counter, 42);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened (see [`open`](Self::open)), or [`Error::Callback`] if the parser returns an error.
+    pub fn open_and_parse<T>(
+        &self,
+        parser: impl FnOnce(&mut File) -> result::Result<T, Box<dyn error::Error + Send + Sync>>,
+    ) -> Result<T> {
+        let mut file = self.open()?;
+        parser(&mut file).map_err(Error::Callback)
+    }
+
+    /// Refreshes the lazy file if it is invalid.
+    ///
+    /// This method only refreshes the file when it has expired. For unconditional refresh, see [`force_refresh`](Self::force_refresh).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("cache.txt", |mut file| {
+    ///     file.write_all(b"cached data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Refresh only if the file is invalid
+    /// cache_file.refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, file validity cannot be determined, or force refresh fails when the file is invalid, including [`Error::FileAlreadyLocked`] if the file is locked.
+    pub fn refresh(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        cache.check_frozen(path)?;
+        self.is_invalid()
+            .and_then(|invalid| if invalid { self.force_refresh() } else { Ok(()) })
+    }
+
+    /// Forces a refresh of the lazy file.
+    ///
+    /// This method refreshes the file regardless of its validity. For conditional refresh, see [`refresh`](Self::refresh).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"fresh data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Force refresh regardless of validity
+    /// cache_file.force_refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file is immutable, the cache is frozen, the file cannot be opened for writing, the callback function returns an error, the file exceeds the maximum file size, or file truncation fails.
+    pub fn force_refresh(&self) -> Result<()> {
+        let result = self.track(|| self.refresh_file());
+        self.observe(result, |path| CacheEvent::Refreshed { path })?;
+        self.enforce_storage_quota()
+    }
+
+    /// Replaces the content of the lazy file with the content streamed from the reader, returning the number of written bytes.
+    ///
+    /// For more details see [`CacheFile::replace_from_reader`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Replace the content without running the callback
+    /// let written = cache_file.replace_from_reader(&b"new content"[..])?;
+    /// assert_eq!(written, 11);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheFile::replace_from_reader`].
+    pub fn replace_from_reader(&self, reader: impl Read) -> Result<u64> {
+        let result = self.track(|| self.replace_file(reader));
+        let written = self.observe(result, |path| CacheEvent::Refreshed { path })?;
+        self.enforce_storage_quota()?;
+        Ok(written)
+    }
+
+    /// Creates or replaces the lazy file with the content streamed from the reader, initializing it.
+    pub(crate) fn init_from_reader(self, reader: impl Read) -> Result<CacheFile<'a>> {
+        let result = self.track(|| self.replace_file(reader));
+        self.observe(result, |path| CacheEvent::Created { path })?;
+        self.enforce_storage_quota()?;
+        let cache_file = CacheFile(self);
+        Ok(cache_file)
+    }
+
+    /// Evicts other files while the cache exceeds its storage quota after the lazy file was written.
+    fn enforce_storage_quota(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        cache
+            .enforce_storage_quota(Some(path))
+            .map(|_| ())
+            .inspect_err(|error| {
+                cache.emit(|| {
+                    let path = Some(path.clone());
+                    let error = error.to_string();
+                    CacheEvent::Error { path, error }
+                });
+            })
+    }
+
+    /// Refreshes the lazy file without emitting an event.
+    fn refresh_file(&self) -> Result<()> {
+        let Self {
+            path,
+            immutable,
+            link,
+            cache,
+            ..
+        } = self;
+        if *immutable {
+            let path = path.clone();
+            let error = Error::ImmutableFile { path };
+            return Err(error);
+        }
+        if self.is_locked() {
+            let error = Error::FileAlreadyLocked;
+            return Err(error);
+        }
+        cache.check_frozen(path)?;
+        #[cfg(feature = "fd-pool")]
+        cache.fd_pool().invalidate(path);
+        cache.memory_cache().invalidate(path);
+        self.ensure_parent()?;
+        self.check_cancelled()?;
+        if let Some((source, LinkMode::Symlink)) = link {
+            // Recreate the link instead of writing through it into the source
+            if path.symlink_metadata().is_ok() {
+                fs::remove_file(path)?;
+            }
+            link::symlink(source, path)?;
+            return self.record_sidecars(false);
+        }
+        #[cfg(feature = "testing")]
+        cache.inject_fault(|| FaultPoint::RefreshOpen { path: path.clone() })?;
+        cache
+            .file_options()
+            .read(false)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(Error::IO)
+            .and_then(|file| self.run_callback(file))
+            .and_then(|()| self.check_file_size())
+            .and_then(|()| self.record_sidecars(false))
+    }
+
+    /// Replaces the content of the lazy file with the content streamed from the reader without emitting an event.
+    fn replace_file(&self, mut reader: impl Read) -> Result<u64> {
+        let Self {
+            path,
+            max_file_size,
+            immutable,
+            cache,
+            ..
+        } = self;
+        if *immutable {
+            let path = path.clone();
+            let error = Error::ImmutableFile { path };
+            return Err(error);
+        }
+        if self.is_locked() {
+            let error = Error::FileAlreadyLocked;
+            return Err(error);
+        }
+        cache.check_frozen(path)?;
+        self.ensure_parent()?;
+        self.check_cancelled()?;
+        let _creation_guard = cache.lock_registry().begin_creation(path);
+        cache.make_room(path)?;
+        #[cfg(feature = "testing")]
+        cache.inject_fault(|| FaultPoint::RefreshOpen { path: path.clone() })?;
+
+        // Stream the content into a temporary file next to the file, so the file is never seen partially written
+        let mut temp_file = cache.staging_file(path.parent().unwrap_or(path))?;
+        let size = io::copy(&mut reader, temp_file.as_file_mut())?;
+        if let Some(limit) = *max_file_size
+            && size > limit
+        {
+            let path = path.clone();
+            let error = Error::FileTooLarge { path, size, limit };
+            return Err(error);
+        }
+        self.check_cancelled()?;
+
+        let created = !path.exists();
+        #[cfg(feature = "fd-pool")]
+        cache.fd_pool().invalidate(path);
+        cache.memory_cache().invalidate(path);
+        temp_file.persist(path).map_err(|error| error.error)?;
+        self.record_sidecars(created)?;
+        Ok(size)
+    }
+
+    /// Returns when the lazy file was last modified.
+    ///
+    /// The precise time of the last write by this handle is preferred, unless the file was modified since then,
+    /// because file systems may round modification times to whole seconds.
+    fn modified(&self, metadata: &Metadata) -> Result<SystemTime> {
+        let Self { written_at, .. } = self;
+        let mtime = metadata.modified()?;
+        match *written_at.lock().unwrap_or_else(PoisonError::into_inner) {
+            Some((written_mtime, written_at)) if written_mtime == mtime => Ok(written_at),
+            _ => Ok(mtime),
+        }
+    }
+
+    /// Records the precise time of a write by this handle along with the resulting modification time of the file.
+    fn record_written_at(&self, time: SystemTime) -> Result<()> {
+        let Self { path, written_at, .. } = self;
+        let mtime = fs::metadata(path)?.modified()?;
+        *written_at.lock().unwrap_or_else(PoisonError::into_inner) = Some((mtime, time));
+        Ok(())
+    }
+
+    /// Runs the creation or refresh of the lazy file, recording its outcome and timing.
+    fn track<T>(&self, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+        let Self {
+            cache,
+            last_refresh,
+            last_refresh_error,
+            poisoned,
+            ..
+        } = self;
+        let _in_flight_guard = cache.in_flight().begin();
+        let started_at = Instant::now();
+        let result = operation();
+        let mut last_refresh_error = last_refresh_error.lock().unwrap_or_else(PoisonError::into_inner);
+        match &result {
+            Ok(_) => {
+                *last_refresh.lock().unwrap_or_else(PoisonError::into_inner) =
+                    Some((cache.now(), started_at.elapsed()));
+                *last_refresh_error = None;
+                poisoned.store(false, Ordering::SeqCst);
+            },
+            Err(error) => *last_refresh_error = Some(error.to_string()),
+        }
+        result
+    }
+
+    /// Emits the event of the operation on the lazy file if it succeeded, or its error otherwise.
+    fn observe<T>(&self, result: Result<T>, event: impl FnOnce(PathBuf) -> CacheEvent) -> Result<T> {
+        let Self { path, cache, .. } = self;
+        match &result {
+            Ok(_) => cache.emit(|| event(path.clone())),
+            Err(error) => {
+                cache.emit(|| {
+                    let path = Some(path.clone());
+                    let error = error.to_string();
+                    CacheEvent::Error { path, error }
+                })
+            },
+        }
+        result
+    }
+
+    /// Returns an error if the cache has been cancelled.
+    fn check_cancelled(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        if cache.is_cancelled() {
+            let path = path.clone();
+            let error = Error::Cancelled { path };
+            Err(error)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Runs the callback on the file, recovering from its failure according to the error recovery strategy of the cache.
+    ///
+    /// The partially written file is always removed if the callback was cancelled.
+    fn run_callback(&self, file: File) -> Result<()> {
+        let Self {
+            path, callback, cache, ..
+        } = self;
+        #[cfg(feature = "testing")]
+        let mut result = cache
+            .inject_fault(|| FaultPoint::CallbackWrite { path: path.clone() })
+            .map_err(Into::into)
+            .and_then(|()| callback::catch_unwind(|| callback(file)));
+        #[cfg(not(feature = "testing"))]
+        let mut result = callback::catch_unwind(|| callback(file));
+        let mut retries = 0;
+        let mut fallen_back = false;
+        loop {
+            let Err(error) = result else {
+                return Ok(());
+            };
+            if cache.is_cancelled() {
+                let _ = fs::remove_file(path);
+                let path = path.clone();
+                let error = Error::Cancelled { path };
+                return Err(error);
+            }
+
+            // Discard the partially written file before running a callback again
+            result = match cache.error_recovery() {
+                ErrorRecovery::Propagate => return Err(self.callback_error(error)),
+                ErrorRecovery::DeleteAndRetry { max_retries } if retries < *max_retries => {
+                    retries += 1;
+                    let file = self.truncated()?;
+                    callback::catch_unwind(|| callback(file))
+                },
+                ErrorRecovery::Fallback(fallback) if !fallen_back => {
+                    fallen_back = true;
+                    let file = self.truncated()?;
+                    callback::catch_unwind(|| fallback(file))
+                },
+                ErrorRecovery::DeleteAndRetry { .. } | ErrorRecovery::DeleteAndSkip | ErrorRecovery::Fallback(_) => {
+                    let _ = fs::remove_file(path);
+                    return Err(self.callback_error(error));
+                },
+            };
+        }
+    }
+
+    /// Turns the final error of the callback into the error of the creation or refresh.
+    ///
+    /// Panics poison the lazy file and always discard the partially written file, as its state is unknown.
+    fn callback_error(&self, error: Box<dyn error::Error + Send + Sync>) -> Error {
+        let Self { path, poisoned, .. } = self;
+        match error.downcast::<CallbackPanic>() {
+            Ok(panic) => {
+                let _ = fs::remove_file(path);
+                poisoned.store(true, Ordering::SeqCst);
+                let path = path.clone();
+                let CallbackPanic { message } = *panic;
+                Error::CallbackPanicked { path, message }
+            },
+            Err(error) => Error::Callback(error),
+        }
+    }
+
+    /// Opens the lazy file for writing, discarding its content.
+    fn truncated(&self) -> Result<File> {
+        let Self { path, cache, .. } = self;
+        cache
+            .file_options()
+            .read(false)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(Error::IO)
+    }
+
+    /// Shortens the file to the given length and sets its modification time.
+    fn truncate_with_modified(&self, len: u64, modified: SystemTime) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        if self.is_locked() {
+            let error = Error::FileAlreadyLocked;
+            return Err(error);
+        }
+        cache.check_frozen(path)?;
+        let file = File::options().write(true).open(path)?;
+        if file.metadata()?.len() > len {
+            file.set_len(len)?;
+        }
+        file.set_modified(modified)?;
+        Ok(())
+    }
+
+    /// Returns the path of the sidecar file of the given path with the given extension, named with the reserved prefix of sidecar files.
+    pub(crate) fn sidecar_path(path: &Path, extension: &str) -> PathBuf {
+        let mut file_name = OsString::from(InnerDirCache::SIDECAR_PREFIX);
+        file_name.push(path.file_name().unwrap_or_default());
+        file_name.push(".");
+        file_name.push(extension);
+        path.with_file_name(file_name)
+    }
+
+    /// Returns the path of the sidecar file persisting the refresh interval of the given path.
+    pub(crate) fn ttl_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "ttl")
+    }
+
+    /// Returns the path of the archive with the given index rotated from the given path.
+    fn archive_path(path: &Path, index: usize) -> PathBuf {
+        let mut archive_path = path.as_os_str().to_owned();
+        archive_path.push(format!(".{index}"));
+        PathBuf::from(archive_path)
+    }
+
+    /// Returns the path the content of the given path is kept at while it is refreshed.
+    fn stale_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "stale")
+    }
+
+    /// Returns the path of the sidecar file recording the version token of the given path.
+    pub(crate) fn version_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "version")
+    }
+
+    /// Reads the version token recorded in the sidecar file of the given path, if any.
+    fn recorded_version(path: &Path) -> Result<Option<String>> {
+        match fs::read_to_string(Self::version_path(path)) {
+            Ok(version) => Ok(Some(version)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Returns the path of the sidecar file recording the last access of the given path.
+    pub(crate) fn accessed_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "accessed")
+    }
+
+    /// Reads the time of the last access recorded in the sidecar file of the given path, if any.
+    pub(crate) fn recorded_access(path: &Path) -> Result<Option<SystemTime>> {
+        let millis = Self::read_counter(&Self::accessed_path(path))?;
+        Ok((millis > 0).then(|| SystemTime::UNIX_EPOCH + Duration::from_millis(millis)))
+    }
+
+    /// Records the access of the lazy file if it has an idle timeout, unless the cache is frozen.
+    fn record_access(&self) -> Result<()> {
+        let Self {
+            path,
+            idle_timeout,
+            cache,
+            ..
+        } = self;
+        if idle_timeout.is_some() && !cache.is_frozen() {
+            let millis = cache.now().duration_since(SystemTime::UNIX_EPOCH)?.as_millis();
+            fs::write(Self::accessed_path(path), millis.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the sidecar file recording the original key of the given path.
+    pub(crate) fn rawkey_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "rawkey")
+    }
+
+    /// Returns the path of the sidecar file recording the generation of the given path.
+    pub(crate) fn generation_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "generation")
+    }
+
+    /// Returns the path of the sidecar file recording the revision of the given path.
+    pub(crate) fn revision_path(path: &Path) -> PathBuf {
+        Self::sidecar_path(path, "revision")
+    }
+
+    /// Reads the number recorded in the given sidecar file, or zero if none was recorded.
+    fn read_counter(sidecar_path: &Path) -> Result<u64> {
+        match fs::read_to_string(sidecar_path) {
+            Ok(generation) => {
+                generation
+                    .trim()
+                    .parse()
+                    .map_err(|error| io::Error::new(ErrorKind::InvalidData, error).into())
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Reads the revision recorded in the sidecar file of the given path.
+    ///
+    /// Only refreshed files have the sidecar file, so existing files without it are at the first revision.
+    fn recorded_revision(path: &Path) -> Result<u64> {
+        match Self::read_counter(&Self::revision_path(path))? {
+            0 => Ok(u64::from(path.exists())),
+            revision => Ok(revision),
+        }
+    }
+
+    /// Records the version token, the generation, and the incremented revision of the created or refreshed lazy file in its sidecar files.
+    fn record_sidecars(&self, created: bool) -> Result<()> {
+        let Self {
+            path,
+            version,
+            revision,
+            cache,
+            ..
+        } = self;
+        let revision_path = Self::revision_path(path);
+        let next_revision = if created {
+            // Created files are at the first revision, which isn't recorded
+            if revision_path.exists() {
+                fs::remove_file(revision_path)?;
+            }
+            1
+        } else {
+            // Other handles may have refreshed the file in the meantime
+            let next_revision = Self::recorded_revision(path)? + 1;
+            fs::write(revision_path, next_revision.to_string())?;
+            next_revision
+        };
+        revision.store(next_revision, Ordering::SeqCst);
+        self.record_written_at(cache.now())?;
+        if let Some(version) = version {
+            fs::write(Self::version_path(path), version)?;
+        }
+        // Files of caches which were never bumped don't need the sidecar file
+        let generation = cache.generation();
+        if generation > 0 {
+            fs::write(Self::generation_path(path), generation.to_string())?;
+        }
+        self.record_access()
+    }
+
+    /// Returns the path of the chunk with the given index split from the given path.
+    pub(crate) fn chunk_path(path: &Path, index: usize) -> PathBuf {
+        let mut chunk_path = path.as_os_str().to_owned();
+        chunk_path.push(format!(".part{index}"));
+        PathBuf::from(chunk_path)
+    }
+
+    /// Ensures the parent directories of the file exist, recreating them if removed.
+    fn ensure_parent(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        let parent = path.parent().unwrap_or(path);
+        cache.ensure_root(parent)?;
+        cache.ensure_dir(parent)
+    }
+
+    /// Removes the file if it exceeds the maximum file size.
+    fn check_file_size(&self) -> Result<()> {
+        let Self {
+            path, max_file_size, ..
+        } = self;
+        if let Some(limit) = *max_file_size {
+            let size = fs::metadata(path)?.len();
+            if size > limit {
+                fs::remove_file(path)?;
+                let path = path.clone();
+                let error = Error::FileTooLarge { path, size, limit };
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Refreshes the lazy file if its metadata satisfies the given predicate.
+    ///
+    /// The metadata of the file is passed to `predicate`; if it returns `true`, the file is forcefully refreshed.
+    /// Locked files are never refreshed, regardless of the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Refresh the file if it's empty
+    /// let refreshed = cache_file.refresh_if(|metadata| metadata.len() == 0)?;
+    /// assert!(!refreshed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, or force refresh fails when the predicate is satisfied.
+    pub fn refresh_if(&self, predicate: impl FnOnce(&Metadata) -> bool) -> Result<bool> {
+        let Self { path, .. } = self;
+        if self.is_locked() {
+            return Ok(false);
+        }
+        let metadata = fs::metadata(path)?;
+        if predicate(&metadata) {
+            self.force_refresh()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Refreshes the lazy file if it is older than the given age, regardless of its refresh interval.
+    ///
+    /// This allows consumers with different freshness requirements to share the same file, without changing its refresh interval.
+    /// Locked files are never refreshed, regardless of their age.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // The file was just created
+    /// let refreshed = cache_file.refresh_if_older_than(Duration::from_secs(30))?;
+    /// assert!(!refreshed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, or force refresh fails when the file is older than the given age.
+    pub fn refresh_if_older_than(&self, max_age: Duration) -> Result<bool> {
+        let Self { path, cache, .. } = self;
+        if self.is_locked() {
+            return Ok(false);
+        }
+        let metadata = fs::metadata(path)?;
+        // Files modified in the future have no age yet
+        let age = cache
+            .now()
+            .duration_since(self.modified(&metadata)?)
+            .unwrap_or_default();
+        if age > max_age {
+            self.force_refresh()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Opens the lazy file, creating it if it doesn't exist or refreshing it if it is older than the given age.
+    ///
+    /// Unlike [`open`](Self::open), the age of the file is checked against `max_age` instead of its refresh interval (see [`refresh_if_older_than`](Self::refresh_if_older_than)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("prices.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Read content which is at most 30 seconds old
+    /// let mut content = String::new();
+    /// cache_file
+    ///     .open_fresher_than(Duration::from_secs(30))?
+    ///     .read_to_string(&mut content)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if file creation fails (if the file doesn't exist), the refresh fails (see [`refresh_if_older_than`](Self::refresh_if_older_than)), or the file cannot be opened for reading.
+    pub fn open_fresher_than(&self, max_age: Duration) -> Result<File> {
+        let Self { path, .. } = self;
+        if path.exists() {
+            self.refresh_if_older_than(max_age)?;
+            File::options().read(true).write(false).open(path).map_err(Error::IO)
+        } else {
+            self.create()
+        }
+    }
+
+    /// Refreshes the lazy file if its current content satisfies the given condition.
+    ///
+    /// The current content is read and passed to `condition`; if it returns `true`, the file is forcefully refreshed.
+    /// This allows content-based staleness checks which don't fit time-based expiry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"v2 data")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Refresh the file if it's not in the v2 format
+    /// let refreshed = cache_file.conditional_refresh(|content| !content.starts_with(b"v2"))?;
+    /// assert!(!refreshed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be read, or force refresh fails when the condition is satisfied.
+    pub fn conditional_refresh(&self, condition: impl FnOnce(&[u8]) -> bool) -> Result<bool> {
+        let Self { path, .. } = self;
+        let content = fs::read(path)?;
+        if condition(&content) {
+            self.force_refresh()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Truncates the lazy file to the given length, keeping its modification time.
+    ///
+    /// The file is only shortened; if it is not longer than `len`, its content is left untouched.
+    /// Since the modification time is preserved, the validity of the file doesn't change. For resetting it see [`truncate_and_touch`](Self::truncate_and_touch).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content with a corrupt tail")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Drop the corrupt tail
+    /// cache_file.truncate(7)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file cannot be accessed.
+    pub fn truncate(&self, len: u64) -> Result<()> {
+        let modified = fs::metadata(self.path())?.modified()?;
+        self.truncate_with_modified(len, modified)
+    }
+
+    /// Truncates the lazy file to the given length, updating its modification time.
+    ///
+    /// Unlike [`truncate`](Self::truncate), this resets the validity of the file, as if it was refreshed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"content with a corrupt tail")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Drop the corrupt tail and treat the file as fresh
+    /// cache_file.truncate_and_touch(7)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file cannot be accessed.
+    pub fn truncate_and_touch(&self, len: u64) -> Result<()> {
+        let Self { cache, .. } = self;
+        let now = cache.now();
+        self.truncate_with_modified(len, now)
+            .and_then(|()| self.record_written_at(now))
+    }
+
+    /// Reads up to `len` bytes of the lazy file starting at `offset`.
+    ///
+    /// Unlike [`open`](Self::open), the file is never created or refreshed. Fewer bytes are returned if the end of the file is reached.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    ///     file.write_all(b"header:body")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.open()?;
+    ///
+    /// // Read the header only
+    /// assert_eq!(cache_file.read_range(0, 6)?, b"header");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened or read.
+    pub fn read_range(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
+        let Self { path, .. } = self;
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut content = Vec::with_capacity(len);
+        file.take(len as u64).read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Opens the lazy file for appending, creating it if it doesn't exist.
+    ///
+    /// Unlike [`open`](Self::open), the validity of the file is never checked, so the file grows until it is rotated (see [`rotate`](Self::rotate)) or removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("activity.log", |mut file| {
+    ///     file.write_all(b"# activity\n")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Append a record to the log
+    /// cache_file.open_append()?.write_all(b"started\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, file creation fails (if the file doesn't exist), or the file cannot be opened for appending.
+    pub fn open_append(&self) -> Result<File> {
+        let Self { path, cache, .. } = self;
+        cache.check_frozen(path)?;
+        if !path.exists() {
+            let _ = self.create()?;
+        }
+        File::options().append(true).open(path).map_err(Error::IO)
+    }
+
+    /// Rotates the lazy file if it exceeds the given size in bytes.
+    ///
+    /// The file is archived to `<path>.1`, shifting earlier archives (`<path>.1` to `<path>.2` and so on), and a fresh file is written by the given callback.
+    /// Archives are never removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("activity.log", |_| Ok(()))?;
+    /// cache_file.open_append()?.write_all(b"started\n")?;
+    ///
+    /// // Start a fresh log once it exceeds 4 bytes
+    /// cache_file.rotate(4, |mut file| {
+    ///     file.write_all(b"# rotated\n")?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the cache is frozen, the file cannot be archived, or the callback function returns an error.
+    pub fn rotate(&self, max_size: u64, new_callback: impl CallbackFn) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        if self.is_locked() {
+            let error = Error::FileAlreadyLocked;
+            return Err(error);
+        }
+        cache.check_frozen(path)?;
+        if !path.exists() || fs::metadata(path)?.len() <= max_size {
+            return Ok(());
+        }
+
+        #[cfg(feature = "fd-pool")]
+        cache.fd_pool().invalidate(path);
+        cache.memory_cache().invalidate(path);
+        // Shift earlier archives, starting from the oldest one
+        let count = (1..)
+            .take_while(|&index| Self::archive_path(path, index).exists())
+            .count();
+        for index in (1..=count).rev() {
+            fs::rename(Self::archive_path(path, index), Self::archive_path(path, index + 1))?;
+        }
+        fs::rename(path, Self::archive_path(path, 1))?;
+        new_callback(self.truncated()?).map_err(Error::Callback)
+    }
+
+    /// Removes the lazy file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("temp.txt", |mut file| {
+    ///     file.write_all(b"temporary data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Create the file first
+    /// cache_file.open()?;
+    ///
+    /// // Remove the file when no longer needed
+    /// cache_file.remove()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, or the file exists but cannot be removed due to permissions or file system operations fail.
+    pub fn remove(&self) -> Result<()> {
+        let Self {
+            path,
+            cache,
+            remove_on_drop,
+            ..
+        } = self;
+        Self::remove_path(path, cache, |path| CacheEvent::Removed { path })?;
+        // Never remove a file created again at the same path later on
+        remove_on_drop.set(None);
+        Ok(())
+    }
+
+    /// Evicts the lazy file, removing it like [`remove`](Self::remove).
+    pub(crate) fn evict(&self) -> Result<()> {
+        let Self { path, cache, .. } = self;
+        Self::remove_path(path, cache, |path| CacheEvent::Evicted { path })
+    }
+
+    /// Removes the file at the given path along with its empty parent directories, emitting the event if it existed.
+    fn remove_path(path: &Path, cache: &InnerDirCache, event: impl FnOnce(PathBuf) -> CacheEvent) -> Result<()> {
+        cache.check_frozen(path)?;
+        #[cfg(feature = "fd-pool")]
+        cache.fd_pool().invalidate(path);
+        cache.memory_cache().invalidate(path);
+        let cache_root = cache.path();
+        if path.exists() {
+            #[cfg(feature = "testing")]
+            cache.inject_fault(|| {
+                FaultPoint::Remove {
+                    path: path.to_path_buf(),
+                }
+            })?;
+            fs::remove_file(path)?;
+            // Sidecar files describing the content are stale, unlike the persisted refresh interval
+            for sidecar_path in [
+                Self::version_path(path),
+                Self::generation_path(path),
+                Self::revision_path(path),
+                Self::accessed_path(path),
+                Self::rawkey_path(path),
+            ] {
+                if sidecar_path.exists() {
+                    fs::remove_file(sidecar_path)?;
+                }
+            }
+            cache.emit(|| event(path.to_path_buf()));
+
+            // Flat caches have no parent directories to clean up
+            if cache.is_flat() {
+                return Ok(());
+            }
+
+            // Remove empty parent directories up to cache root
+            let mut current_parent = path.parent();
+            while let Some(parent_dir) = current_parent
+                && parent_dir != cache_root
+                && fs::read_dir(parent_dir)?.next().is_none()
+            {
+                // Try to remove the directory if it's empty, it may be repopulated or removed concurrently
+                match fs::remove_dir(parent_dir) {
+                    Ok(()) => current_parent = parent_dir.parent(),
+                    Err(error) if matches!(error.kind(), ErrorKind::NotFound | ErrorKind::DirectoryNotEmpty) => break,
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Initializes the lazy file, converting it to a [`CacheFile`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_lazy("settings.txt", |mut file| {
+    ///     file.write_all(b"default settings")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Initialize and convert to CacheFile
+    /// let cache_file = cache_file.init()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file creation fails, the callback function returns an error, or file system operations fail.
+    pub fn init(self) -> Result<CacheFile<'a>> {
+        self.init_with(CreationMode::UseExisting)
+    }
+
+    /// Initializes the lazy file, treating an existing file according to the creation mode.
+    pub(crate) fn init_with(self, creation_mode: CreationMode) -> Result<CacheFile<'a>> {
+        let Self { path, .. } = &self;
+        if !path.exists() {
+            let _ = self.create()?;
+        } else {
+            match creation_mode {
+                CreationMode::CreateNew => {
+                    let path = path.clone();
+                    let error = Error::FileAlreadyExists { path };
+                    return Err(error);
+                },
+                CreationMode::CreateOrTruncate => {
+                    self.force_refresh()?;
+                },
+                CreationMode::UseExisting => {},
+            }
+        }
+        let cache_file = CacheFile(self);
+        Ok(cache_file)
+    }
+}
+
+impl Debug for CacheLazyFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { path, expiry, lock, .. } = self;
+        f.debug_struct("LazyFile")
+            .field("path", &path)
+            .field("callback", &"...")
+            .field("expiry", &expiry)
+            .field("lock", &lock)
+            .finish()
+    }
+}
+
+/// Guard removing a cache file when dropped, unless it is disarmed.
+///
+/// The guard is shared behind an [`Arc`], so handles sharing it remove the file only once the last of them is dropped.
+struct RemoveFileOnDrop<'a>(Mutex<Option<(PathBuf, CacheRef<'a>)>>);
+
+impl<'a> RemoveFileOnDrop<'a> {
+    /// Arms the guard with the file to remove, or disarms it with `None`.
+    fn set(&self, target: Option<(PathBuf, CacheRef<'a>)>) {
+        let Self(current) = self;
+        *current.lock().unwrap_or_else(PoisonError::into_inner) = target;
+    }
+
+    /// Disarms the guard, returning the file it would have removed.
+    fn take(&self) -> Option<(PathBuf, CacheRef<'a>)> {
+        let Self(current) = self;
+        current.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+}
+
+impl Drop for RemoveFileOnDrop<'_> {
+    fn drop(&mut self) {
+        let Self(target) = self;
+        if let Some((path, cache)) = target.get_mut().unwrap_or_else(PoisonError::into_inner) {
+            // Best-effort cleanup, the file may be in use or the cache frozen
+            let _ = CacheLazyFile::remove_path(path, cache, |path| CacheEvent::Removed { path });
+        }
+    }
+}
+
+/// Cache a file belongs to, either borrowed by the handle or owned by it.
+#[derive(Clone)]
+pub(crate) enum CacheRef<'a> {
+    /// Cache borrowed by the handle
+    Borrowed(&'a InnerDirCache),
+    /// Clone of the cache owned by the handle, sharing the state of the cache
+    Owned(Arc<InnerDirCache>),
+}
+
+impl CacheRef<'_> {
+    /// Converts the reference into one owning a clone of the cache.
+    fn into_owned(self) -> CacheRef<'static> {
+        match self {
+            Self::Borrowed(cache) => CacheRef::Owned(Arc::new(cache.clone())),
+            Self::Owned(cache) => CacheRef::Owned(cache),
+        }
+    }
+}
+
+impl<'a> From<&'a InnerDirCache> for CacheRef<'a> {
+    fn from(cache: &'a InnerDirCache) -> Self {
+        Self::Borrowed(cache)
+    }
+}
+
+impl Deref for CacheRef<'_> {
+    type Target = InnerDirCache;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(cache) => cache,
+            Self::Owned(cache) => cache,
+        }
+    }
+}
+
+/// Guard unlocking a cache file when dropped.
+///
+/// Check the [`CacheFile::lock_guard`] method for more details on how to use this type.
+pub struct FileLockGuard<'h, 'a>(&'h CacheLazyFile<'a>);
+
+impl Debug for FileLockGuard<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(lazy_file) = self;
+        f.debug_tuple("FileLockGuard").field(&lazy_file.path()).finish()
+    }
+}
+
+impl Drop for FileLockGuard<'_, '_> {
+    fn drop(&mut self) {
+        let Self(lazy_file) = self;
+        // The lock may have expired, or been released explicitly in the meantime
+        let _ = lazy_file.unlock();
+    }
+}
+
+/// A file in the cache.
+///
+/// Files are created immediately and can be accessed right away through the cache.
+pub struct CacheFile<'a>(CacheLazyFile<'a>);
+
+/// A file in the cache which doesn't borrow the cache.
+///
+/// Check the [`CacheFile::into_owned`] method for more details on how to create owned handles.
+pub type OwnedCacheFile = CacheFile<'static>;
+
+/// A lazily created file in the cache which doesn't borrow the cache.
+///
+/// Check the [`CacheLazyFile::into_owned`] method for more details on how to create owned handles.
+pub type OwnedCacheLazyFile = CacheLazyFile<'static>;
+
+impl<'a> CacheFile<'a> {
+    /// Sets the refresh interval for the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set custom refresh interval to 10 minutes
+    /// let cache_file = cache_file.with_refresh_interval(Duration::from_secs(10 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_refresh_interval(refresh_interval);
+        Self(inner)
+    }
+
+    /// Disables automatic refreshing of the file, so it is only refreshed manually.
+    ///
+    /// This is the same as setting the refresh interval to [`Duration::MAX`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Never refresh the file automatically
+    /// let cache_file = cache_file.without_refresh();
+    /// assert_eq!(cache_file.valid_until()?, None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn without_refresh(self) -> Self {
+        let Self(inner) = self;
+        let inner = inner.without_refresh();
+        Self(inner)
+    }
+
+    /// Sets the refresh interval for the file from a human-readable string.
+    ///
+    /// For more details about the format see [`Cache::with_refresh_interval_str`](crate::Cache::with_refresh_interval_str).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set custom refresh interval to 10 minutes
+    /// let cache_file = cache_file.with_refresh_interval_str("10m")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidInterval`] if the string is not a valid interval.
+    pub fn with_refresh_interval_str(self, refresh_interval: &str) -> Result<Self> {
+        let Self(inner) = self;
+        let inner = inner.with_refresh_interval_str(refresh_interval)?;
+        Ok(Self(inner))
+    }
+
+    /// Sets an absolute expiry time for the file.
+    ///
+    /// Unlike [`with_refresh_interval`](Self::with_refresh_interval), which is relative to the file's modification time,
+    /// the file stays valid until the given point in time and is considered invalid afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("certificate.pem", |mut file| {
+    ///     file.write_all(b"certificate")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Expire together with the certificate validity period
+    /// let cache_file =
+    ///     cache_file.with_expires_at(SystemTime::now() + Duration::from_secs(24 * 60 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_expires_at(self, expires_at: SystemTime) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_expires_at(expires_at);
+        Self(inner)
+    }
+
+    /// Sets whether an empty file is considered invalid.
+    ///
+    /// When enabled, a zero-byte file is refreshed on the next access regardless of its expiry.
+    /// This overrides the cache setting (see [`Cache::with_empty_is_invalid`](crate::Cache::with_empty_is_invalid)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_empty_is_invalid(true);
+    /// let cache_file = cache.get("marker.txt", |_| Ok(()))?;
+    ///
+    /// // This file is meant to be empty
+    /// let cache_file = cache_file.with_empty_is_invalid(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_empty_is_invalid(empty_is_invalid);
+        Self(inner)
+    }
+
+    /// Sets the minimum size of the file in bytes.
+    ///
+    /// A file smaller than the minimum is considered incomplete and is refreshed on the next access regardless of its expiry.
+    /// A file at least as large as the minimum is refreshed only once it expires.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("dataset.bin", |mut file| {
+    ///     file.write_all(&[0; 1024])?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Regenerate the file if an earlier download was interrupted
+    /// let cache_file = cache_file.with_min_size(1024);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_min_size(self, min_size: u64) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_min_size(min_size);
+        Self(inner)
+    }
+
+    /// Sets whether the existing content is served when refreshing the file fails.
+    ///
+    /// For more details see [`CacheLazyFile::with_stale_if_error`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("rates.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Keep serving the last known rates while the upstream is down
+    /// let cache_file = cache_file.with_stale_if_error(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stale_if_error(self, stale_if_error: bool) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_stale_if_error(stale_if_error);
+        Self(inner)
+    }
+
+    /// Sets the maximum time the content of the file is served past its expiry.
+    ///
+    /// For more details see [`CacheLazyFile::with_max_stale`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("rates.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Never serve rates which expired more than an hour ago
+    /// let cache_file = cache_file
+    ///     .with_stale_if_error(true)
+    ///     .with_max_stale(Duration::from_secs(60 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_stale(self, max_stale: Duration) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_max_stale(max_stale);
+        Self(inner)
+    }
+
+    /// Sets the time after which the file is invalid if nobody accessed it.
+    ///
+    /// For more details see [`CacheLazyFile::with_idle_timeout`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("session.json", |mut file| {
+    ///         file.write_all(b"{}")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_idle_timeout(Duration::from_secs(15 * 60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_idle_timeout(self, idle_timeout: Duration) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_idle_timeout(idle_timeout);
+        Self(inner)
+    }
+
+    /// Sets the version token of the file, overriding the cache setting.
+    ///
+    /// The token is recorded in a `.fcache-meta-<name>.version` sidecar file next to the file whenever the file is created or refreshed.
+    /// A file whose recorded token differs is invalid regardless of its expiry, and is refreshed on the next access.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("settings.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Regenerate the file whenever the config version changes
+    /// let cache_file = cache_file.with_version("config-v2");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_version(self, version: impl Into<String>) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_version(version);
+        Self(inner)
+    }
+
+    /// Sets whether the file is removed when the handle is dropped.
+    ///
+    /// For more details see [`CacheLazyFile::on_drop_remove`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("token.txt", |mut file| {
+    ///         file.write_all(b"secret")?;
+    ///         Ok(())
+    ///     })?
+    ///     .on_drop_remove(true);
+    /// let path = cache_file.path().to_path_buf();
+    ///
+    /// // The file is removed along with the handle
+    /// drop(cache_file);
+    /// assert!(!path.exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_drop_remove(self, on_drop_remove: bool) -> Self {
+        let Self(inner) = self;
+        let inner = inner.on_drop_remove(on_drop_remove);
+        Self(inner)
+    }
+
+    /// Sets whether the file is removed when the handle is dropped, without consuming the handle.
+    ///
+    /// For more details see [`CacheLazyFile::remove_on_drop`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let mut cache_file = cache.get("scratch.bin", |mut file| {
+    ///     file.write_all(&[0; 16])?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.remove_on_drop(true);
+    /// let path = cache_file.path().to_path_buf();
+    ///
+    /// // The file is removed along with the handle
+    /// drop(cache_file);
+    /// assert!(!path.exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_on_drop(&mut self, remove_on_drop: bool) {
+        let Self(inner) = self;
+        inner.remove_on_drop(remove_on_drop);
+    }
+
+    /// Removes the file when the handle is dropped.
+    ///
+    /// This is a shorthand for [`on_drop_remove(true)`](Self::on_drop_remove).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("result.bin", |mut file| {
+    ///     file.write_all(&[0; 16])?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Remove the file once the handle goes out of scope
+    /// let cache_file = cache_file.forget_on_drop();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn forget_on_drop(self) -> Self {
+        self.on_drop_remove(true)
+    }
+
+    /// Converts the handle into one which doesn't borrow the cache.
+    ///
+    /// For more details see [`CacheLazyFile::into_owned`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    /// use fcache::{Cache, OwnedCacheFile};
+    ///
+    /// // Keep the cache along with a file of it
+    /// struct App {
+    ///     cache: Cache,
+    ///     config: OwnedCacheFile,
+    /// }
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    /// let config = cache
+    ///     .get("config.json", |mut file| {
+    ///         file.write_all(b"{}")?;
+    ///         Ok(())
+    ///     })?
+    ///     .into_owned();
+    /// let app = App { cache, config };
+    ///
+    /// assert!(app.config.is_valid()?);
+    /// assert!(app.cache.exists("config.json")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_owned(self) -> OwnedCacheFile {
+        let Self(inner) = self;
+        CacheFile(inner.into_owned())
+    }
+
+    /// Sets the expiry mode for the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::ExpiryMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set the expiry mode explicitly
+    /// let cache_file = cache_file.with_expiry(ExpiryMode::Interval(Duration::from_secs(60)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_expiry(self, expiry: ExpiryMode) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_expiry(expiry);
+        Self(inner)
+    }
+
+    /// Sets the refresh interval to the default value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Set custom interval, then reset to default
+    /// let cache_file = cache_file
+    ///     .with_refresh_interval(Duration::from_secs(120))
+    ///     .with_default_refresh_interval();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_default_refresh_interval(self) -> Self {
+        let Self(inner) = self;
+        let inner = inner.with_default_refresh_interval();
+        Self(inner)
+    }
+
+    /// Returns the path of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("settings.txt", |mut file| {
+    ///     file.write_all(b"settings data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the file path
+    /// let path = cache_file.path();
+    /// println!("Cache file located at: {}", path.display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        let Self(inner) = self;
+        inner.path()
+    }
+
+    /// Returns the path of the file relative to the cache directory, i.e. its normalized key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::Path;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("reports/2024/summary.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the key for logging
+    /// assert_eq!(
+    ///     cache_file.relative_path(),
+    ///     Path::new("reports/2024/summary.json")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn relative_path(&self) -> &Path {
+        let Self(inner) = self;
+        inner.relative_path()
+    }
+
+    /// Returns the name of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the file name
+    /// let name = cache_file.name();
+    /// println!("Cache file name: {}", name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &str {
+        let Self(inner) = self;
+        inner.name()
+    }
+
+    /// Returns the refresh interval of the file.
+    ///
+    /// For files with an absolute expiry time (see [`with_expires_at`](Self::with_expires_at)) this is the time remaining until expiry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("data.txt", |mut file| {
+    ///         file.write_all(b"content")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_refresh_interval(Duration::from_secs(600));
+    ///
+    /// // Check the current refresh interval
+    /// let interval = cache_file.refresh_interval();
+    /// println!("Cache refresh interval: {} seconds", interval.as_secs());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval(&self) -> Duration {
+        let Self(inner) = self;
+        inner.refresh_interval()
+    }
+
+    /// Returns the refresh interval of the file, or `None` if it is never refreshed automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.without_refresh();
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files of the cache are never refreshed automatically
+    /// assert_eq!(cache_file.refresh_interval_opt(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval_opt(&self) -> Option<Duration> {
+        let Self(inner) = self;
+        inner.refresh_interval_opt()
+    }
+
+    /// Persists the refresh interval of the file in a sidecar file.
+    ///
+    /// The interval is written to a `.fcache-meta-<name>.ttl` sidecar file next to the file in milliseconds, and applied instead of the cache refresh interval whenever a handle for this path is created, e.g. after a process restart.
+    /// The refresh interval of this handle is not changed, use [`with_refresh_interval`](Self::with_refresh_interval) for that.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Remember a refresh interval of 30 minutes for this path
+    /// cache_file.set_refresh_interval_persist(Duration::from_secs(30 * 60))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file cannot be written.
+    pub fn set_refresh_interval_persist(&self, refresh_interval: Duration) -> Result<()> {
+        let Self(inner) = self;
+        inner.set_refresh_interval_persist(refresh_interval)
+    }
+
+    /// Removes the persisted refresh interval of the file, reverting to the cache refresh interval.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    /// cache_file.set_refresh_interval_persist(Duration::from_secs(30 * 60))?;
+    ///
+    /// // Forget the persisted refresh interval
+    /// cache_file.clear_refresh_interval_persist()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file exists but cannot be removed.
+    pub fn clear_refresh_interval_persist(&self) -> Result<()> {
+        let Self(inner) = self;
+        inner.clear_refresh_interval_persist()
+    }
+
+    /// Explains what accessing the file would do, and why.
+    ///
+    /// All the checks deciding whether the file is refreshed or used as it is are run without modifying anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheDecision;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Fresh files are used as they are
+    /// assert_eq!(cache_file.explain()?.decision, CacheDecision::WouldSkip);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file metadata cannot be read, or its validity cannot be determined.
+    pub fn explain(&self) -> Result<CacheExplanation> {
+        let Self(inner) = self;
+        inner.explain()
+    }
+
+    /// Returns the state of the file, read with a single metadata lookup.
+    ///
+    /// For more details see [`CacheLazyFile::status`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::EntryStatus;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check the file in one call
+    /// if let EntryStatus::Present { valid, size, .. } = cache_file.status()? {
+    ///     assert!(valid);
+    ///     assert_eq!(size, 7);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheLazyFile::status`].
+    pub fn status(&self) -> Result<EntryStatus> {
+        let Self(inner) = self;
+        inner.status()
+    }
+
+    /// Returns the content type of the file.
+    ///
+    /// The content type is determined from the file extension, or by inspecting the leading bytes when the extension is missing or unknown (see [`sniff`](crate::sniff)).
+    /// The file is never created or refreshed. Returns `None` for unrecognized binary content.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get the content type to serve the file with
+    /// let content_type = cache_file
+    ///     .content_type()?
+    ///     .unwrap_or("application/octet-stream");
+    /// assert_eq!(content_type, "application/json");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content type is sniffed, but the file cannot be read.
+    pub fn content_type(&self) -> Result<Option<&'static str>> {
+        let Self(inner) = self;
+        inner.content_type()
+    }
+
+    /// Returns the expiry mode of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::ExpiryMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files use the cache refresh interval by default
+    /// assert_eq!(
+    ///     cache_file.expiry(),
+    ///     ExpiryMode::Interval(cache.refresh_interval())
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn expiry(&self) -> ExpiryMode {
+        let Self(inner) = self;
+        inner.expiry()
+    }
+
+    /// Returns whether an empty file is considered invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_empty_is_invalid(true);
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files inherit the cache setting
+    /// assert!(cache_file.empty_is_invalid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn empty_is_invalid(&self) -> bool {
+        let Self(inner) = self;
+        inner.empty_is_invalid()
+    }
+
+    /// Returns the minimum size of the file in bytes, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("dataset.bin", |mut file| {
+    ///         file.write_all(&[0; 1024])?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_min_size(1024);
+    ///
+    /// // Check the minimum size
+    /// assert_eq!(cache_file.min_size(), Some(1024));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_size(&self) -> Option<u64> {
+        let Self(inner) = self;
+        inner.min_size()
+    }
+
+    /// Returns whether the existing content is served when refreshing the file fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("rates.json", |_| Ok(()))?
+    ///     .with_stale_if_error(true);
+    ///
+    /// // Check whether stale content is served on errors
+    /// assert!(cache_file.stale_if_error());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stale_if_error(&self) -> bool {
+        let Self(inner) = self;
+        inner.stale_if_error()
+    }
+
+    /// Returns the maximum time the content of the file is served past its expiry, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("rates.json", |_| Ok(()))?;
+    ///
+    /// // There is no bound by default
+    /// assert_eq!(cache_file.max_stale(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_stale(&self) -> Option<Duration> {
+        let Self(inner) = self;
+        inner.max_stale()
+    }
+
+    /// Returns the time after which the file is invalid if nobody accessed it, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("session.json", |_| Ok(()))?
+    ///     .with_idle_timeout(Duration::from_secs(60));
+    ///
+    /// assert_eq!(cache_file.idle_timeout(), Some(Duration::from_secs(60)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        let Self(inner) = self;
+        inner.idle_timeout()
+    }
+
+    /// Returns the version token of the file, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache
+    ///     .get("settings.json", |mut file| {
+    ///         file.write_all(b"{}")?;
+    ///         Ok(())
+    ///     })?
+    ///     .with_version("config-v2");
+    ///
+    /// // Check the version token
+    /// assert_eq!(cache_file.version(), Some("config-v2"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        let Self(inner) = self;
+        inner.version()
+    }
+
+    /// Returns the revision of the file content.
+    ///
+    /// For more details about revisions see [`CacheLazyFile::revision`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Forced refreshes increment the revision
+    /// let revision = cache_file.revision();
+    /// cache_file.force_refresh()?;
+    /// assert_eq!(cache_file.revision(), revision + 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        let Self(inner) = self;
+        inner.revision()
+    }
+
+    /// Returns the original key the file was requested with, if its name was sanitized.
+    ///
+    /// For more details see [`CacheLazyFile::original_key`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get_sanitized("My Report: 2024/Q1", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// assert!(cache_file.path().ends_with("My Report_ 2024_Q1"));
+    /// assert_eq!(
+    ///     cache_file.original_key()?.as_deref(),
+    ///     Some("My Report: 2024/Q1")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the sidecar file exists but cannot be read.
+    pub fn original_key(&self) -> Result<Option<String>> {
+        let Self(inner) = self;
+        inner.original_key()
+    }
+
+    /// Returns when the file was last successfully created or refreshed by this handle, if ever.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Files are created immediately
+    /// assert!(cache_file.last_refresh_at().is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_at(&self) -> Option<SystemTime> {
+        let Self(inner) = self;
+        inner.last_refresh_at()
+    }
+
+    /// Returns how long the last successful creation or refresh of the file by this handle took, including the callback, if ever.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// if let Some(duration) = cache_file.last_refresh_duration() {
+    ///     println!("Created in {duration:?}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_duration(&self) -> Option<Duration> {
+        let Self(inner) = self;
+        inner.last_refresh_duration()
+    }
+
+    /// Returns the description of the error of the last creation or refresh of the file by this handle, if it failed.
+    ///
+    /// For more details see [`CacheLazyFile::last_refresh_error`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Successful refreshes clear the error
+    /// cache_file.force_refresh()?;
+    /// assert_eq!(cache_file.last_refresh_error(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_refresh_error(&self) -> Option<String> {
+        let Self(inner) = self;
+        inner.last_refresh_error()
+    }
+
+    /// Records the original key the file was requested with, unless it is already recorded.
+    pub(crate) fn record_original_key(&self, original_key: &str) -> Result<()> {
+        let Self(inner) = self;
+        inner.record_original_key(original_key)
+    }
+
+    /// Returns whether the file is locked.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check if the file is locked
+    /// assert!(!cache_file.is_locked());
+    /// cache_file.lock()?;
+    /// assert!(cache_file.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_locked(&self) -> bool {
+        let Self(inner) = self;
+        inner.is_locked()
     }
 
-    /// Returns whether the lazy file is locked.
+    /// Returns whether the file is unlocked.
     ///
     /// # Example
     ///
@@ -277,51 +4674,68 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get_lazy("data.txt", |mut file| {
+    /// let cache_file = cache.get("data.txt", |mut file| {
     ///     file.write_all(b"content")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file is locked
-    /// assert!(!cache_file.is_locked());
+    /// // Check if the file is unlocked
+    /// assert!(cache_file.is_unlocked());
     /// cache_file.lock()?;
-    /// assert!(cache_file.is_locked());
+    /// assert!(!cache_file.is_unlocked());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn is_locked(&self) -> bool {
-        let Self { locked, .. } = self;
-        *locked
+    pub fn is_unlocked(&self) -> bool {
+        let Self(inner) = self;
+        inner.is_unlocked()
     }
 
-    /// Returns whether the lazy file is unlocked.
+    /// Returns whether the callback of the file panicked since its last successful creation or refresh.
+    ///
+    /// Panics of the callback are caught and reported as [`Error::CallbackPanicked`], after the partially written file is removed.
+    /// Poisoned files are invalid, so they are refreshed on the next access, and a successful refresh clears the poison.
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
+    /// static FAIL: AtomicBool = AtomicBool::new(false);
+    ///
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get_lazy("data.txt", |mut file| {
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     assert!(!FAIL.load(Ordering::SeqCst), "upstream unavailable");
     ///     file.write_all(b"content")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file is unlocked
-    /// assert!(cache_file.is_unlocked());
-    /// cache_file.lock()?;
-    /// assert!(!cache_file.is_unlocked());
+    /// // The panic is reported as an error
+    /// FAIL.store(true, Ordering::SeqCst);
+    /// assert!(cache_file.force_refresh().is_err());
+    /// assert!(cache_file.is_poisoned());
+    ///
+    /// // A successful refresh clears the poison
+    /// FAIL.store(false, Ordering::SeqCst);
+    /// cache_file.force_refresh()?;
+    /// assert!(!cache_file.is_poisoned());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn is_unlocked(&self) -> bool {
-        !self.is_locked()
+    pub fn is_poisoned(&self) -> bool {
+        let Self(inner) = self;
+        inner.is_poisoned()
     }
 
-    /// Checks if the lazy file is valid.
+    /// Clears the poison of the file, without refreshing it.
+    ///
+    /// This allows using the file as it is after its callback panicked (see [`is_poisoned`](Self::is_poisoned)),
+    /// e.g. when it was successfully written by other means in the meantime.
     ///
     /// # Example
     ///
@@ -330,14 +4744,36 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // Nothing to clear
+    /// cache_file.clear_poison();
+    /// assert!(!cache_file.is_poisoned());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn clear_poison(&self) {
+        let Self(inner) = self;
+        inner.clear_poison();
+    }
+
+    /// Checks if the file is valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file is still valid
+    /// // Check if the cache file is still valid
     /// if cache_file.is_valid()? {
-    ///     println!("File is still fresh");
+    ///     println!("File is valid, using cached content");
     /// }
     /// # Ok(())
     /// # }
@@ -347,16 +4783,11 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
     pub fn is_valid(&self) -> Result<bool> {
-        let Self {
-            path, refresh_interval, ..
-        } = self;
-        let metadata = fs::metadata(path)?;
-        let modified = metadata.modified()?;
-        let elapsed = modified.elapsed()?;
-        Ok(elapsed < *refresh_interval)
+        let Self(inner) = self;
+        inner.is_valid()
     }
 
-    /// Checks if the lazy file is invalid.
+    /// Checks if the file is invalid.
     ///
     /// # Example
     ///
@@ -365,14 +4796,14 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"cached data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file needs refreshing
+    /// // Check if the cache file needs refreshing
     /// if cache_file.is_invalid()? {
-    ///     println!("File needs to be refreshed");
+    ///     println!("File is invalid, needs refresh");
     /// }
     /// # Ok(())
     /// # }
@@ -382,10 +4813,11 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
     pub fn is_invalid(&self) -> Result<bool> {
-        self.is_valid().map(|valid| !valid)
+        let Self(inner) = self;
+        inner.is_invalid()
     }
 
-    /// Returns the time until the lazy file is valid.
+    /// Returns the time until the file is valid, or `None` if it never expires.
     ///
     /// # Example
     ///
@@ -394,7 +4826,7 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
+    /// let cache_file = cache.get("data.txt", |mut file| {
     ///     file.write_all(b"content")?;
     ///     Ok(())
     /// })?;
@@ -409,18 +4841,44 @@ impl<'a> CacheLazyFile<'a> {
     /// # Errors
     ///
     /// This function will return an error if the file metadata cannot be read or the file's modification time cannot be determined.
-    pub fn valid_until(&self) -> Result<SystemTime> {
-        let Self {
-            path, refresh_interval, ..
-        } = self;
-        let metadata = fs::metadata(path)?;
-        let modified = metadata.modified()?;
-        Ok(modified + *refresh_interval)
+    pub fn valid_until(&self) -> Result<Option<SystemTime>> {
+        let Self(inner) = self;
+        inner.valid_until()
     }
 
-    /// Locks this file to prevent other processes from reading or writing to it.
+    /// Returns how long the file is past its expiry, or `None` if it hasn't expired yet or never expires.
     ///
-    /// For more details about the locking mechanism see [`CacheFile::lock`].
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?.with_refresh_interval(Duration::ZERO);
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // The file expires right away
+    /// assert!(cache_file.staleness()?.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`valid_until`](Self::valid_until).
+    pub fn staleness(&self) -> Result<Option<Duration>> {
+        let Self(inner) = self;
+        inner.staleness()
+    }
+
+    /// Locks the file to prevent refreshing.
+    ///
+    /// The lock state is shared by all references to the handle, so a handle shared between threads, e.g. behind an [`Arc`](std::sync::Arc), can be locked by any of them.
     ///
     /// # Example
     ///
@@ -429,7 +4887,7 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get_lazy("shared.txt", |mut file| {
+    /// let cache_file = cache.get("shared.txt", |mut file| {
     ///     file.write_all(b"shared data")?;
     ///     Ok(())
     /// })?;
@@ -444,89 +4902,112 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file is already locked by another process, system file locking mechanisms fail, or the underlying file cannot be accessed.
-    pub fn lock(&mut self) -> Result<()> {
-        self.is_unlocked()
-            .then(|| {
-                self.locked = true;
-            })
-            .ok_or_else(|| Error::FileAlreadyLocked)
+    /// This function will return an error if the file is already locked.
+    pub fn lock(&self) -> Result<()> {
+        let Self(inner) = self;
+        inner.lock()
     }
 
-    /// Unlocks the lazy file to allow refreshing.
+    /// Locks the file for the given time, after which it is unlocked automatically.
     ///
-    /// For more details about the locking mechanism see [`CacheFile::unlock`].
+    /// The expiry is measured with the clock of the cache.
+    /// Once it passes, [`is_locked`](Self::is_locked) reports `false`, the file can be refreshed again, and [`unlock`](Self::unlock) succeeds.
+    /// This keeps a file from staying locked forever when its holder forgets or fails to unlock it.
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::time::Duration;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get_lazy("shared.txt", |mut file| {
+    /// let cache_file = cache.get("shared.txt", |mut file| {
     ///     file.write_all(b"shared data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Lock and then unlock the file
-    /// cache_file.lock()?;
-    /// // ... critical operations complete ...
-    /// cache_file.unlock()?;
+    /// // Lock the file for at most a minute
+    /// cache_file.lock_for(Duration::from_secs(60))?;
+    /// assert!(cache_file.is_locked());
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file is already unlocked.
-    pub fn unlock(&mut self) -> Result<()> {
-        self.is_locked()
-            .then(|| {
-                self.locked = false;
-            })
-            .ok_or_else(|| Error::FileAlreadyUnlocked)
+    /// This function will return an error if the file is already locked and the lock hasn't expired.
+    pub fn lock_for(&self, duration: Duration) -> Result<()> {
+        let Self(inner) = self;
+        inner.lock_for(duration)
     }
 
-    /// Creates the lazy file.
+    /// Locks the file, returning a guard unlocking it when dropped.
+    ///
+    /// This is the preferred way of locking files, as the lock is released even on early returns and panics.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use std::io::Write;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// {
+    ///     let _guard = cache_file.lock_guard()?;
+    ///     // ... perform critical operations ...
+    ///     assert!(cache_file.is_locked());
+    /// }
+    /// assert!(!cache_file.is_locked());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is already locked and the lock hasn't expired.
+    pub fn lock_guard(&self) -> Result<FileLockGuard<'_, 'a>> {
+        let Self(inner) = self;
+        inner.lock_guard()
+    }
+
+    /// Unlocks the file to allow refreshing.
     ///
+    /// # Example
+    ///
+    /// ```rust
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("document.txt", |mut file| {
-    ///     file.write_all(b"Document content")?;
+    /// let cache_file = cache.get("shared.txt", |mut file| {
+    ///     file.write_all(b"shared data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Explicitly create the file if it doesn't exist
-    /// let file = cache_file.create()?;
+    /// // Lock and then unlock the file
+    /// cache_file.lock()?;
+    /// // ... critical operations complete ...
+    /// cache_file.unlock()?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file already exists, file creation fails due to permissions or disk space, the callback function returns an error, or the file cannot be reopened for reading.
-    pub fn create(&self) -> Result<File> {
-        // FIXME: Refactor
-        let Self { path, callback, .. } = self;
-        File::options()
-            .create_new(true)
-            .read(false)
-            .write(true)
-            .open(path)
-            .map_err(Error::IO)
-            .and_then(|file| callback(file).map_err(Error::Callback))
-            .and_then(|()| File::options().read(true).write(false).open(path).map_err(Error::IO))
+    /// This function will return an error if the file is already unlocked, unless its lock expired.
+    pub fn unlock(&self) -> Result<()> {
+        let Self(inner) = self;
+        inner.unlock()
     }
 
-    /// Opens the lazy file, creating it if it doesn't exist.
+    /// Opens the file.
     ///
     /// # Example
     ///
@@ -537,7 +5018,7 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("config.txt", |mut file| {
+    /// let cache_file = cache.get("config.txt", |mut file| {
     ///     file.write_all(b"config data")?;
     ///     Ok(())
     /// })?;
@@ -554,18 +5035,13 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// This function will return an error if file creation fails (if the file doesn't exist), file refresh fails (if the file exists), the file cannot be opened for reading, or the callback function returns an error during creation.
     pub fn open(&self) -> Result<File> {
-        let Self { path, .. } = self;
-        if path.exists() {
-            self.refresh()?;
-            File::options().read(true).write(false).open(path).map_err(Error::IO)
-        } else {
-            self.create()
-        }
+        let Self(inner) = self;
+        inner.open()
     }
 
-    /// Refreshes the lazy file if it is invalid.
+    /// Opens the file like [`open`](Self::open), unless its revision is still the last seen one.
     ///
-    /// This method only refreshes the file when it has expired. For unconditional refresh, see [`force_refresh`](Self::force_refresh).
+    /// For more details see [`CacheLazyFile::open_if_changed`].
     ///
     /// # Example
     ///
@@ -574,107 +5050,133 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("cache.txt", |mut file| {
-    ///     file.write_all(b"cached data")?;
+    /// let cache_file = cache.get("feed.xml", |mut file| {
+    ///     file.write_all(b"<feed/>")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Refresh only if the file is invalid
-    /// cache_file.refresh()?;
+    /// // Process the content only if it changed since the last time
+    /// let last_seen = cache_file.revision();
+    /// if let Some(file) = cache_file.open_if_changed(last_seen)? {
+    ///     println!("Feed changed: {file:?}");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if file validity cannot be determined or force refresh fails when the file is invalid.
-    pub fn refresh(&self) -> Result<()> {
-        self.is_invalid()
-            .and_then(|invalid| if invalid { self.force_refresh() } else { Ok(()) })
+    /// This function will return an error in the same cases as [`open`](Self::open).
+    pub fn open_if_changed(&self, last_seen: u64) -> Result<Option<File>> {
+        let Self(inner) = self;
+        inner.open_if_changed(last_seen)
     }
 
-    /// Forces a refresh of the lazy file.
+    /// Opens the current content of the file as it is, without refreshing it.
     ///
-    /// This method refreshes the file regardless of its validity. For conditional refresh, see [`refresh`](Self::refresh).
+    /// For more details see [`CacheLazyFile::open_stale`].
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::io::Read;
+    /// use std::time::Duration;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("data.txt", |mut file| {
-    ///     file.write_all(b"fresh data")?;
+    /// let cache = fcache::new()?.with_refresh_interval(Duration::ZERO);
+    /// let cache_file = cache.get("quote.txt", |mut file| {
+    ///     file.write_all(b"last known quote")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Force refresh regardless of validity
-    /// cache_file.force_refresh()?;
+    /// // Read the expired content without refreshing it
+    /// let mut content = String::new();
+    /// cache_file.open_stale()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "last known quote");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file cannot be opened for writing, the callback function returns an error, or file truncation fails.
-    pub fn force_refresh(&self) -> Result<()> {
-        let Self { path, callback, .. } = self;
-        File::options()
-            .read(false)
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .map_err(Error::IO)
-            .and_then(|file| callback(file).map_err(Error::Callback))
+    /// This function will return [`Error::FileNotFound`] if the file has been removed, or an error if it cannot be opened for reading.
+    pub fn open_stale(&self) -> Result<File> {
+        let Self(inner) = self;
+        inner.open_stale()?.ok_or_else(|| {
+            let path = inner.path().to_path_buf();
+            Error::FileNotFound { path }
+        })
     }
 
-    /// Removes the lazy file.
+    /// Opens the file using a pooled file descriptor, refreshing it if needed.
+    ///
+    /// For more details about the pooling see [`CacheLazyFile::open_pooled`].
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::io::Read;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("temp.txt", |mut file| {
-    ///     file.write_all(b"temporary data")?;
+    /// let cache = fcache::new()?.with_fd_pool_size(4);
+    /// let cache_file = cache.get("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Create the file first
-    /// cache_file.open()?;
-    ///
-    /// // Remove the file when no longer needed
-    /// cache_file.remove()?;
+    /// // Read the file without opening it again
+    /// let mut content = String::new();
+    /// cache_file.open_pooled()?.read_to_string(&mut content)?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file exists but cannot be removed due to permissions or file system operations fail.
-    pub fn remove(&self) -> Result<()> {
-        let Self { path, cache_root, .. } = self;
-        if path.exists() {
-            fs::remove_file(path)?;
+    /// This function will return an error in the same cases as [`open`](Self::open), or if the pooled descriptor cannot be duplicated.
+    #[cfg(feature = "fd-pool")]
+    pub fn open_pooled(&self) -> Result<File> {
+        let Self(inner) = self;
+        inner.open_pooled()
+    }
 
-            // Remove empty parent directories up to cache root
-            let mut current_parent = path.parent();
-            while let Some(parent_dir) = current_parent
-                && parent_dir != *cache_root
-                && fs::read_dir(parent_dir)?.next().is_none()
-            {
-                // Try to remove the directory if it's empty
-                fs::remove_dir(parent_dir)?;
-                current_parent = parent_dir.parent();
-            }
-        }
-        Ok(())
+    /// Opens the file for appending without checking its validity.
+    ///
+    /// For more details about appending see [`CacheLazyFile::open_append`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = fcache::new()?;
+    /// let cache_file = cache.get("activity.log", |mut file| {
+    ///     file.write_all(b"# activity\n")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Append a record to the log
+    /// cache_file.open_append()?.write_all(b"started\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, or the file cannot be opened for appending.
+    pub fn open_append(&self) -> Result<File> {
+        let Self(inner) = self;
+        inner.open_append()
     }
 
-    /// Initializes the lazy file, converting it to a [`CacheFile`].
+    /// Rotates the file if it exceeds the given size in bytes.
+    ///
+    /// For more details about the rotation see [`CacheLazyFile::rotate`].
     ///
     /// # Example
     ///
@@ -683,112 +5185,119 @@ impl<'a> CacheLazyFile<'a> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get_lazy("settings.txt", |mut file| {
-    ///     file.write_all(b"default settings")?;
-    ///     Ok(())
-    /// })?;
+    /// let cache_file = cache.get("activity.log", |_| Ok(()))?;
+    /// cache_file.open_append()?.write_all(b"started\n")?;
     ///
-    /// // Initialize and convert to CacheFile
-    /// let cache_file = cache_file.init()?;
+    /// // Start a fresh log once it exceeds 4 bytes
+    /// cache_file.rotate(4, |_| Ok(()))?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file creation fails, the callback function returns an error, or file system operations fail.
-    pub fn init(self) -> Result<CacheFile<'a>> {
-        let Self { path, .. } = &self;
-        if !path.exists() {
-            let _ = self.create()?;
-        }
-        let cache_file = CacheFile(self);
-        Ok(cache_file)
-    }
-}
-
-impl Debug for CacheLazyFile<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self {
-            path,
-            refresh_interval,
-            locked,
-            ..
-        } = self;
-        f.debug_struct("LazyFile")
-            .field("path", &path)
-            .field("callback", &"...")
-            .field("refresh_interval", &refresh_interval)
-            .field("locked", &locked)
-            .finish()
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the cache is frozen, the file cannot be archived, or the callback function returns an error.
+    pub fn rotate(&self, max_size: u64, new_callback: impl CallbackFn) -> Result<()> {
+        let Self(inner) = self;
+        inner.rotate(max_size, new_callback)
     }
-}
-
-/// A file in the cache.
-///
-/// Files are created immediately and can be accessed right away through the cache.
-pub struct CacheFile<'a>(CacheLazyFile<'a>);
 
-impl CacheFile<'_> {
-    /// Sets the refresh interval for the file.
+    /// Splits the file into chunks of the given size, stored as cache files next to it.
+    ///
+    /// The chunks are named after the file with a `.partN` suffix, e.g. `data.bin.part0`, `data.bin.part1`, and so on, and the last chunk may be shorter.
+    /// Existing chunks are overwritten, and stale chunks left over from an earlier split into more chunks are removed. The file itself is kept.
+    /// The chunks can be joined back with [`Cache::reassemble_chunks`](crate::Cache::reassemble_chunks).
     ///
     /// # Example
     ///
     /// ```rust
-    /// use std::time::Duration;
-    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("data.bin", |mut file| {
+    ///     file.write_all(&[0; 10])?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Set custom refresh interval to 10 minutes
-    /// let cache_file = cache_file.with_refresh_interval(Duration::from_secs(10 * 60));
+    /// // Split the file into chunks of at most 4 bytes
+    /// let chunks = cache_file.split_into_chunks(4)?;
+    /// assert_eq!(chunks.len(), 3);
+    /// assert_eq!(chunks[2].name(), "data.bin.part2");
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the chunk size is zero, the file cannot be read, or a chunk cannot be written.
+    pub fn split_into_chunks(&self, chunk_size: usize) -> Result<Vec<CacheFile<'a>>> {
         let Self(inner) = self;
-        let inner = inner.with_refresh_interval(refresh_interval);
-        Self(inner)
+        let CacheLazyFile { path, cache, .. } = inner;
+        if chunk_size == 0 {
+            let error = io::Error::new(ErrorKind::InvalidInput, "chunk size must be greater than zero");
+            return Err(error.into());
+        }
+
+        let mut file = self.open()?;
+        let mut chunks = Vec::new();
+        loop {
+            let mut content = Vec::with_capacity(chunk_size);
+            (&mut file).take(chunk_size as u64).read_to_end(&mut content)?;
+            if content.is_empty() {
+                break;
+            }
+            let chunk_path = CacheLazyFile::chunk_path(path, chunks.len());
+            let chunk = CacheLazyFile::attach(chunk_path, callback::write_all(content), cache.clone())?
+                .init_with(CreationMode::CreateOrTruncate)?;
+            chunks.push(chunk);
+        }
+
+        // Remove chunks of an earlier split which would be reassembled otherwise
+        let mut index = chunks.len();
+        loop {
+            let chunk_path = CacheLazyFile::chunk_path(path, index);
+            if !chunk_path.exists() {
+                break;
+            }
+            CacheLazyFile::attach(chunk_path, callback::noop, cache.clone())?.remove()?;
+            index += 1;
+        }
+        Ok(chunks)
     }
 
-    /// Sets the refresh interval to the default value.
+    /// Reads the whole content of the file, refreshing it if needed.
+    ///
+    /// For more details see [`CacheLazyFile::read_bytes`].
     ///
     /// # Example
     ///
     /// ```rust
-    /// use std::time::Duration;
-    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("data.bin", |mut file| {
+    ///     file.write_all(&[1, 2, 3])?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Set custom interval, then reset to default
-    /// let cache_file = cache_file
-    ///     .with_refresh_interval(Duration::from_secs(120))
-    ///     .with_default_refresh_interval();
+    /// assert_eq!(cache_file.read_bytes()?, [1, 2, 3]);
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn with_default_refresh_interval(self) -> Self {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheLazyFile::read_bytes`].
+    pub fn read_bytes(&self) -> Result<Vec<u8>> {
         let Self(inner) = self;
-        let inner = inner.with_default_refresh_interval();
-        Self(inner)
+        inner.read_bytes()
     }
 
-    /// Returns the path of the file.
+    /// Reads the whole content of the file as a string, refreshing it if needed.
+    ///
+    /// For more details see [`CacheLazyFile::read_string`].
     ///
     /// # Example
     ///
@@ -797,24 +5306,27 @@ impl CacheFile<'_> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("settings.txt", |mut file| {
-    ///     file.write_all(b"settings data")?;
+    /// let cache_file = cache.get("greeting.txt", |mut file| {
+    ///     file.write_all(b"Hello, Cache!")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Get the file path
-    /// let path = cache_file.path();
-    /// println!("Cache file located at: {}", path.display());
+    /// assert_eq!(cache_file.read_string()?, "Hello, Cache!");
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn path(&self) -> &Path {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheLazyFile::read_string`].
+    pub fn read_string(&self) -> Result<String> {
         let Self(inner) = self;
-        inner.path()
+        inner.read_string()
     }
 
-    /// Returns the name of the file.
+    /// Reads the content of the file, serving it from the in-memory layer of the cache when possible.
+    ///
+    /// For more details see [`CacheLazyFile::read_cached`].
     ///
     /// # Example
     ///
@@ -822,55 +5334,68 @@ impl CacheFile<'_> {
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache = fcache::new()?.with_memory_cache(1024 * 1024);
+    /// let cache_file = cache.get("config.txt", |mut file| {
+    ///     file.write_all(b"config data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Get the file name
-    /// let name = cache_file.name();
-    /// println!("Cache file name: {}", name);
+    /// let content = cache_file.read_cached()?;
+    /// assert_eq!(&*content, b"config data");
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn name(&self) -> &str {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`CacheLazyFile::read_cached`].
+    pub fn read_cached(&self) -> Result<Arc<[u8]>> {
         let Self(inner) = self;
-        inner.name()
+        inner.read_cached()
     }
 
-    /// Returns the refresh interval of the file.
+    /// Opens the file and parses its content with the given parser.
+    ///
+    /// The file is refreshed if needed, like in [`open`](Self::open), and passed to the parser.
+    /// This allows using streaming parsers working directly on the file.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use std::time::Duration;
-    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache
-    ///     .get("data.txt", |mut file| {
-    ///         file.write_all(b"content")?;
-    ///         Ok(())
-    ///     })?
-    ///     .with_refresh_interval(Duration::from_secs(600));
+    /// let cache_file = cache.get("counter.txt", |mut file| {
+    ///     file.write_all(b"42")?;
+    ///     Ok(())
+    /// })?;
     ///
-    /// // Check the current refresh interval
-    /// let interval = cache_file.refresh_interval();
-    /// println!("Cache refresh interval: {} seconds", interval.as_secs());
+    /// // Parse the file content
+    /// let counter = cache_file.open_and_parse(|file| {
+    ///     let mut content = String::new();
+    ///     file.read_to_string(&mut content)?;
+    ///     Ok(content.parse::<u32>()?)
+    /// })?;
+    /// assert_eq!(counter, 42);
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn refresh_interval(&self) -> Duration {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened (see [`open`](Self::open)), or [`Error::Callback`] if the parser returns an error.
+    pub fn open_and_parse<T>(
+        &self,
+        parser: impl FnOnce(&mut File) -> result::Result<T, Box<dyn error::Error + Send + Sync>>,
+    ) -> Result<T> {
         let Self(inner) = self;
-        inner.refresh_interval()
+        inner.open_and_parse(parser)
     }
 
-    /// Returns whether the file is locked.
+    /// Refreshes the file if it is invalid.
+    ///
+    /// This method only refreshes the file when it has expired. For unconditional refresh, see [`force_refresh`](Self::force_refresh).
     ///
     /// # Example
     ///
@@ -879,25 +5404,28 @@ impl CacheFile<'_> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("cache.txt", |mut file| {
+    ///     file.write_all(b"cached data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file is locked
-    /// assert!(!cache_file.is_locked());
-    /// cache_file.lock()?;
-    /// assert!(cache_file.is_locked());
+    /// // Refresh only if the file is invalid
+    /// cache_file.refresh()?;
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn is_locked(&self) -> bool {
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, file validity cannot be determined, or force refresh fails when the file is invalid, including [`Error::FileAlreadyLocked`] if the file is locked.
+    pub fn refresh(&self) -> Result<()> {
         let Self(inner) = self;
-        inner.is_locked()
+        inner.refresh()
     }
 
-    /// Returns whether the file is unlocked.
+    /// Forces a refresh of the file.
+    ///
+    /// This method refreshes the file regardless of its validity. For conditional refresh, see [`refresh`](Self::refresh).
     ///
     /// # Example
     ///
@@ -906,55 +5434,68 @@ impl CacheFile<'_> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"content")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"fresh data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the file is unlocked
-    /// assert!(cache_file.is_unlocked());
-    /// cache_file.lock()?;
-    /// assert!(!cache_file.is_unlocked());
+    /// // Force refresh regardless of validity
+    /// cache_file.force_refresh()?;
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn is_unlocked(&self) -> bool {
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file is immutable, the cache is frozen, the file cannot be opened for writing, the callback function returns an error, the file exceeds the maximum file size, or file truncation fails.
+    pub fn force_refresh(&self) -> Result<()> {
         let Self(inner) = self;
-        inner.is_unlocked()
+        inner.force_refresh()
     }
 
-    /// Checks if the file is valid.
+    /// Replaces the content of the file with the content streamed from the reader, returning the number of written bytes.
+    ///
+    /// Unlike [`force_refresh`](Self::force_refresh), the callback is not run.
+    /// The content is streamed through a bounded buffer into a temporary file next to the file, which then atomically replaces the file,
+    /// so readers never see partially written content and a failed read leaves the previous content in place.
+    /// Locked files are never replaced.
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::io::Read;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("config.txt", |mut file| {
-    ///     file.write_all(b"config data")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the cache file is still valid
-    /// if cache_file.is_valid()? {
-    ///     println!("File is valid, using cached content");
-    /// }
+    /// // Replace the content with a stream
+    /// cache_file.replace_from_reader(&b"new content"[..])?;
+    /// let mut content = String::new();
+    /// cache_file.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "new content");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
-    pub fn is_valid(&self) -> Result<bool> {
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file is immutable, the cache is frozen or cancelled, reading from the reader or writing the file fails,
+    /// or the content exceeds the maximum file size.
+    pub fn replace_from_reader(&self, reader: impl Read) -> Result<u64> {
         let Self(inner) = self;
-        inner.is_valid()
+        inner.replace_from_reader(reader)
     }
 
-    /// Checks if the file is invalid.
+    /// Refreshes the file if its metadata satisfies the given predicate.
+    ///
+    /// The metadata of the file is passed to `predicate`; if it returns `true`, the file is forcefully refreshed.
+    /// Locked files are never refreshed, regardless of the predicate.
     ///
     /// # Example
     ///
@@ -964,31 +5505,34 @@ impl CacheFile<'_> {
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
     /// let cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"cached data")?;
+    ///     file.write_all(b"content")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Check if the cache file needs refreshing
-    /// if cache_file.is_invalid()? {
-    ///     println!("File is invalid, needs refresh");
-    /// }
+    /// // Refresh the file if it's empty
+    /// let refreshed = cache_file.refresh_if(|metadata| metadata.len() == 0)?;
+    /// assert!(!refreshed);
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file metadata cannot be read, modification time cannot be determined, or system time calculations fail.
-    pub fn is_invalid(&self) -> Result<bool> {
+    /// This function will return an error if the file metadata cannot be read, or force refresh fails when the predicate is satisfied.
+    pub fn refresh_if(&self, predicate: impl FnOnce(&Metadata) -> bool) -> Result<bool> {
         let Self(inner) = self;
-        inner.is_invalid()
+        inner.refresh_if(predicate)
     }
 
-    /// Returns the time until the file is valid.
+    /// Refreshes the file if it is older than the given age, regardless of its refresh interval.
+    ///
+    /// For more details see [`CacheLazyFile::refresh_if_older_than`].
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::time::Duration;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
@@ -998,52 +5542,61 @@ impl CacheFile<'_> {
     ///     Ok(())
     /// })?;
     ///
-    /// // Get when the file will expire
-    /// let valid_until = cache_file.valid_until()?;
-    /// println!("File valid until: {:?}", valid_until);
+    /// // The exporter needs data younger than 30 seconds
+    /// let refreshed = cache_file.refresh_if_older_than(Duration::from_secs(30))?;
+    /// assert!(!refreshed);
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file metadata cannot be read or the file's modification time cannot be determined.
-    pub fn valid_until(&self) -> Result<SystemTime> {
+    /// This function will return an error if the file metadata cannot be read, or force refresh fails when the file is older than the given age.
+    pub fn refresh_if_older_than(&self, max_age: Duration) -> Result<bool> {
         let Self(inner) = self;
-        inner.valid_until()
+        inner.refresh_if_older_than(max_age)
     }
 
-    /// Locks the file to prevent refreshing.
+    /// Opens the file, refreshing it if it is older than the given age.
+    ///
+    /// For more details see [`CacheLazyFile::open_fresher_than`].
     ///
     /// # Example
     ///
     /// ```rust
+    /// use std::io::Read;
+    /// use std::time::Duration;
+    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get("shared.txt", |mut file| {
-    ///     file.write_all(b"shared data")?;
+    /// let cache_file = cache.get("prices.json", |mut file| {
+    ///     file.write_all(b"{}")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Lock the file to prevent concurrent access
-    /// cache_file.lock()?;
-    /// // ... perform critical operations ...
-    /// cache_file.unlock()?;
+    /// // Read content which is at most 30 seconds old
+    /// let mut content = String::new();
+    /// cache_file
+    ///     .open_fresher_than(Duration::from_secs(30))?
+    ///     .read_to_string(&mut content)?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file is already locked.
-    pub fn lock(&mut self) -> Result<()> {
+    /// This function will return an error in the same cases as [`CacheLazyFile::open_fresher_than`].
+    pub fn open_fresher_than(&self, max_age: Duration) -> Result<File> {
         let Self(inner) = self;
-        inner.lock()
+        inner.open_fresher_than(max_age)
     }
 
-    /// Unlocks the file to allow refreshing.
+    /// Refreshes the file if its current content satisfies the given condition.
+    ///
+    /// The current content is read and passed to `condition`; if it returns `true`, the file is forcefully refreshed.
+    /// This allows content-based staleness checks which don't fit time-based expiry.
     ///
     /// # Example
     ///
@@ -1052,62 +5605,60 @@ impl CacheFile<'_> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let mut cache_file = cache.get("shared.txt", |mut file| {
-    ///     file.write_all(b"shared data")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"v2 data")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Lock and then unlock the file
-    /// cache_file.lock()?;
-    /// // ... critical operations complete ...
-    /// cache_file.unlock()?;
+    /// // Refresh the file if it's not in the v2 format
+    /// let refreshed = cache_file.conditional_refresh(|content| !content.starts_with(b"v2"))?;
+    /// assert!(!refreshed);
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file is already unlocked.
-    pub fn unlock(&mut self) -> Result<()> {
+    /// This function will return an error if the file cannot be read, or force refresh fails when the condition is satisfied.
+    pub fn conditional_refresh(&self, condition: impl FnOnce(&[u8]) -> bool) -> Result<bool> {
         let Self(inner) = self;
-        inner.unlock()
+        inner.conditional_refresh(condition)
     }
 
-    /// Opens the file.
+    /// Truncates the file to the given length, keeping its modification time.
+    ///
+    /// The file is only shortened; if it is not longer than `len`, its content is left untouched.
+    /// Since the modification time is preserved, the validity of the file doesn't change. For resetting it see [`truncate_and_touch`](Self::truncate_and_touch).
     ///
     /// # Example
     ///
     /// ```rust
-    /// use std::io::Read;
-    ///
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("config.txt", |mut file| {
-    ///     file.write_all(b"config data")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content with a corrupt tail")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Open and read the file content
-    /// let mut file = cache_file.open()?;
-    /// let mut content = String::new();
-    /// file.read_to_string(&mut content)?;
+    /// // Drop the corrupt tail
+    /// cache_file.truncate(7)?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if file creation fails (if the file doesn't exist), file refresh fails (if the file exists), the file cannot be opened for reading, or the callback function returns an error during creation.
-    pub fn open(&self) -> Result<File> {
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file cannot be accessed.
+    pub fn truncate(&self, len: u64) -> Result<()> {
         let Self(inner) = self;
-        inner.open()
+        inner.truncate(len)
     }
 
-    /// Refreshes the file if it is invalid.
+    /// Truncates the file to the given length, updating its modification time.
     ///
-    /// This method only refreshes the file when it has expired. For unconditional refresh, see [`force_refresh`](Self::force_refresh).
+    /// Unlike [`truncate`](Self::truncate), this resets the validity of the file, as if it was refreshed.
     ///
     /// # Example
     ///
@@ -1116,28 +5667,28 @@ impl CacheFile<'_> {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
-    /// let cache_file = cache.get("cache.txt", |mut file| {
-    ///     file.write_all(b"cached data")?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"content with a corrupt tail")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Refresh only if the file is invalid
-    /// cache_file.refresh()?;
+    /// // Drop the corrupt tail and treat the file as fresh
+    /// cache_file.truncate_and_touch(7)?;
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if file validity cannot be determined or force refresh fails when the file is invalid.
-    pub fn refresh(&self) -> Result<()> {
+    /// This function will return [`Error::FileAlreadyLocked`] if the file is locked, or an error if the file cannot be accessed.
+    pub fn truncate_and_touch(&self, len: u64) -> Result<()> {
         let Self(inner) = self;
-        inner.refresh()
+        inner.truncate_and_touch(len)
     }
 
-    /// Forces a refresh of the file.
+    /// Reads up to `len` bytes of the file starting at `offset`.
     ///
-    /// This method refreshes the file regardless of its validity. For conditional refresh, see [`refresh`](Self::refresh).
+    /// Unlike [`open`](Self::open), the file is never created or refreshed. Fewer bytes are returned if the end of the file is reached.
     ///
     /// # Example
     ///
@@ -1147,22 +5698,22 @@ impl CacheFile<'_> {
     /// # fn wrapper() -> fcache::Result<()> {
     /// let cache = fcache::new()?;
     /// let cache_file = cache.get("data.txt", |mut file| {
-    ///     file.write_all(b"fresh data")?;
+    ///     file.write_all(b"header:body")?;
     ///     Ok(())
     /// })?;
     ///
-    /// // Force refresh regardless of validity
-    /// cache_file.force_refresh()?;
+    /// // Read the header only
+    /// assert_eq!(cache_file.read_range(0, 6)?, b"header");
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file cannot be opened for writing, the callback function returns an error, or file truncation fails.
-    pub fn force_refresh(&self) -> Result<()> {
+    /// This function will return an error if the file cannot be opened or read.
+    pub fn read_range(&self, offset: u64, len: usize) -> Result<Vec<u8>> {
         let Self(inner) = self;
-        inner.force_refresh()
+        inner.read_range(offset, len)
     }
 
     /// Removes the file.
@@ -1187,7 +5738,7 @@ impl CacheFile<'_> {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file exists but cannot be removed due to permissions or file system operations fail.
+    /// This function will return an error if the cache is frozen, or the file exists but cannot be removed due to permissions or file system operations fail.
     pub fn remove(&self) -> Result<()> {
         let Self(inner) = self;
         inner.remove()
@@ -1197,17 +5748,12 @@ impl CacheFile<'_> {
 impl Debug for CacheFile<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self(inner) = self;
-        let CacheLazyFile {
-            path,
-            refresh_interval,
-            locked,
-            ..
-        } = inner;
+        let CacheLazyFile { path, expiry, lock, .. } = inner;
         f.debug_struct("File")
             .field("path", &path)
             .field("callback", &"...")
-            .field("refresh_interval", &refresh_interval)
-            .field("locked", &locked)
+            .field("expiry", &expiry)
+            .field("lock", &lock)
             .finish()
     }
 }
@@ -0,0 +1,60 @@
+use std::sync::{Condvar, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Operations of the file handles of a cache in progress, waited for when the cache is closed.
+#[derive(Debug, Default)]
+pub(crate) struct InFlight {
+    /// Numbers of the operations in progress and of the finished operations
+    counts: Mutex<InFlightCounts>,
+    /// Condition notified whenever an operation finishes
+    finished: Condvar,
+}
+
+/// Numbers of the operations of a cache.
+#[derive(Debug, Default)]
+struct InFlightCounts {
+    /// Number of the operations in progress
+    running: usize,
+    /// Number of the finished operations
+    finished: usize,
+}
+
+/// Guard marking an operation as in progress until it is dropped.
+#[derive(Debug)]
+pub(crate) struct InFlightGuard<'a> {
+    /// Operations the operation is counted in
+    in_flight: &'a InFlight,
+}
+
+impl InFlight {
+    /// Marks an operation as in progress until the returned guard is dropped.
+    pub(crate) fn begin(&self) -> InFlightGuard<'_> {
+        let Self { counts, .. } = self;
+        counts.lock().unwrap_or_else(PoisonError::into_inner).running += 1;
+        InFlightGuard { in_flight: self }
+    }
+
+    /// Waits until no operation is in progress or the timeout elapses.
+    ///
+    /// Returns the number of operations which finished in the meantime, and the number of operations still in progress.
+    pub(crate) fn wait(&self, timeout: Duration) -> (usize, usize) {
+        let Self { counts, finished } = self;
+        let counts = counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let finished_before = counts.finished;
+        let (counts, _) = finished
+            .wait_timeout_while(counts, timeout, |counts| counts.running > 0)
+            .unwrap_or_else(PoisonError::into_inner);
+        (counts.finished - finished_before, counts.running)
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        let Self { in_flight } = self;
+        let InFlight { counts, finished } = in_flight;
+        let mut counts = counts.lock().unwrap_or_else(PoisonError::into_inner);
+        counts.running -= 1;
+        counts.finished += 1;
+        finished.notify_all();
+    }
+}
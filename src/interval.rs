@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use crate::result::{Error, Result};
+
+/// Parses a human-readable interval like `90s`, `15m`, or `1h30m`.
+///
+/// The interval consists of numbers followed by one of the `ms`, `s`, `m`, `h`, and `d` units, optionally separated by whitespace.
+/// The special values `0` and `never` are parsed as [`Duration::ZERO`] and [`Duration::MAX`] respectively.
+pub(crate) fn parse(input: &str) -> Result<Duration> {
+    let invalid = || {
+        let input = input.to_string();
+        Error::InvalidInterval { input }
+    };
+
+    let mut rest = input.trim();
+    match rest {
+        "" => return Err(invalid()),
+        "0" => return Ok(Duration::ZERO),
+        "never" => return Ok(Duration::MAX),
+        _ => {},
+    }
+
+    let mut interval = Duration::ZERO;
+    while !rest.is_empty() {
+        let (number, tail) = rest.split_at(rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len()));
+        let (unit, tail) = tail.split_at(tail.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(tail.len()));
+        let number = number.parse::<u64>().map_err(|_| invalid())?;
+        let part = match unit {
+            "ms" => Some(Duration::from_millis(number)),
+            "s" => Some(Duration::from_secs(number)),
+            "m" => number.checked_mul(60).map(Duration::from_secs),
+            "h" => number.checked_mul(60 * 60).map(Duration::from_secs),
+            "d" => number.checked_mul(24 * 60 * 60).map(Duration::from_secs),
+            _ => return Err(invalid()),
+        };
+        interval = part.and_then(|part| interval.checked_add(part)).ok_or_else(invalid)?;
+        rest = tail.trim_start();
+    }
+    Ok(interval)
+}
@@ -0,0 +1,207 @@
+use std::fmt::{self, Debug};
+use std::path::{Component, Path};
+
+#[cfg(doc)]
+use crate::Cache;
+use crate::result::{Error, Result};
+
+/// Rules validating the paths of files requested from the cache.
+///
+/// The rules are checked before any filesystem work, in addition to the built-in path traversal checks.
+/// The default policy doesn't add any rules. Check the [`Cache::with_key_policy`] method for more details.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::KeyPolicy;
+///
+/// // Allow at most 2 levels of lowercase alphanumeric, non-hidden names
+/// let policy = KeyPolicy::new()
+///     .max_depth(2)
+///     .max_component_len(64)
+///     .allowed_chars(|c| {
+///         c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-' | '_')
+///     })
+///     .allow_hidden(false);
+/// # let _ = policy;
+/// ```
+#[derive(Clone, Copy)]
+pub struct KeyPolicy {
+    /// Maximum number of parent directories
+    max_depth: Option<usize>,
+    /// Maximum number of characters of each component
+    max_component_len: Option<usize>,
+    /// Predicate for characters allowed in components
+    allowed_chars: Option<fn(char) -> bool>,
+    /// Whether dot-prefixed components are allowed
+    allow_hidden: bool,
+}
+
+impl KeyPolicy {
+    /// Creates a new policy without any additional rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    ///
+    /// let policy = KeyPolicy::new();
+    /// # let _ = policy;
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of parent directories of a path, e.g. `a/b/file.txt` has a depth of 2.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    ///
+    /// // Only allow files directly in the cache directory
+    /// let policy = KeyPolicy::new().max_depth(0);
+    /// # let _ = policy;
+    /// ```
+    #[must_use]
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        let max_depth = Some(max_depth);
+        Self { max_depth, ..self }
+    }
+
+    /// Limits the number of characters of each path component.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    ///
+    /// let policy = KeyPolicy::new().max_component_len(255);
+    /// # let _ = policy;
+    /// ```
+    #[must_use]
+    pub fn max_component_len(self, max_component_len: usize) -> Self {
+        let max_component_len = Some(max_component_len);
+        Self {
+            max_component_len,
+            ..self
+        }
+    }
+
+    /// Restricts the characters allowed in path components.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    ///
+    /// let policy = KeyPolicy::new().allowed_chars(|c| c.is_ascii_alphanumeric() || c == '.');
+    /// # let _ = policy;
+    /// ```
+    #[must_use]
+    pub fn allowed_chars(self, allowed_chars: fn(char) -> bool) -> Self {
+        let allowed_chars = Some(allowed_chars);
+        Self { allowed_chars, ..self }
+    }
+
+    /// Sets whether hidden (dot-prefixed) path components are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    ///
+    /// let policy = KeyPolicy::new().allow_hidden(false);
+    /// # let _ = policy;
+    /// ```
+    #[must_use]
+    pub fn allow_hidden(self, allow_hidden: bool) -> Self {
+        Self { allow_hidden, ..self }
+    }
+
+    /// Checks the path against the policy.
+    pub(crate) fn check(&self, path: &Path) -> Result<()> {
+        let Self {
+            max_depth,
+            max_component_len,
+            allowed_chars,
+            allow_hidden,
+        } = self;
+
+        let names = path
+            .components()
+            .filter_map(|component| {
+                match component {
+                    Component::Normal(name) => Some(name.to_string_lossy()),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let depth = names.len().saturating_sub(1);
+        if let Some(max_depth) = *max_depth
+            && depth > max_depth
+        {
+            return Self::violation(path, format!("depth {depth} exceeds {max_depth}"));
+        }
+
+        for name in names {
+            if let Some(max_component_len) = *max_component_len
+                && name.chars().count() > max_component_len
+            {
+                return Self::violation(
+                    path,
+                    format!("component {name:?} exceeds {max_component_len} characters"),
+                );
+            }
+            if let Some(allowed_chars) = allowed_chars
+                && let Some(c) = name.chars().find(|c| !allowed_chars(*c))
+            {
+                return Self::violation(path, format!("character {c:?} is not allowed"));
+            }
+            if !allow_hidden && name.starts_with('.') {
+                return Self::violation(path, format!("hidden component {name:?} is not allowed"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the error for a path violating the policy.
+    fn violation(path: &Path, reason: String) -> Result<()> {
+        let path = path.to_path_buf();
+        let error = Error::InvalidPath { path, reason };
+        Err(error)
+    }
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        let max_depth = None;
+        let max_component_len = None;
+        let allowed_chars = None;
+        let allow_hidden = true;
+        Self {
+            max_depth,
+            max_component_len,
+            allowed_chars,
+            allow_hidden,
+        }
+    }
+}
+
+impl Debug for KeyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            max_depth,
+            max_component_len,
+            allowed_chars,
+            allow_hidden,
+        } = self;
+        f.debug_struct("KeyPolicy")
+            .field("max_depth", &max_depth)
+            .field("max_component_len", &max_component_len)
+            .field("allowed_chars", &allowed_chars.map(|_| "..."))
+            .field("allow_hidden", &allow_hidden)
+            .finish()
+    }
+}
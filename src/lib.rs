@@ -166,7 +166,7 @@
 //! let cache = fcache::new()?;
 //!
 //! // Get or create a cached file
-//! let mut cache_file = cache.get("hello.txt", |mut file| {
+//! let cache_file = cache.get("hello.txt", |mut file| {
 //!     // Write data to the file
 //!     file.write_all(b"Hello, world!")?;
 //!     Ok(())
@@ -188,7 +188,7 @@
 //!
 //! ## Thread Safety
 //!
-//! The cache system is designed to be thread-safe for most operations. Cache instances can be safely shared across multiple threads using [`Arc`](std::sync::Arc) or similar synchronization primitives.
+//! The cache system is designed to be thread-safe for most operations. Cache instances can be safely shared across multiple threads using [`Arc`] or similar synchronization primitives.
 //!
 //! ```rust
 //! use std::sync::Arc;
@@ -233,7 +233,7 @@
 //!
 //! ### Thread Safety Guarantees
 //!
-//! - **Cache instances**: Safe to share across threads using [`Arc`](std::sync::Arc).
+//! - **Cache instances**: Safe to share across threads using [`Arc`].
 //! - **File creation**: Multiple threads can safely create different files simultaneously.
 //! - **File operations**: Reading and writing operations are thread-safe at the filesystem level.
 //!
@@ -258,7 +258,7 @@
 //! let cache = fcache::new()?.with_refresh_interval(Duration::ZERO);
 //!
 //! // Get or create a cached file
-//! let mut cache_file = cache.get("hello.txt", |mut file| {
+//! let cache_file = cache.get("hello.txt", |mut file| {
 //!     // Write data to the file
 //!     file.write_all(b"Hello, world!")?;
 //!     // Inform about the refresh
@@ -277,19 +277,17 @@
 //!
 //! ## Never refresh
 //!
-//! Use [`Duration::MAX`] to disable automatic refresh. Use `force_refresh` for manual control.
+//! Use [`without_refresh`](Cache::without_refresh) to disable automatic refresh, which is also what [`Duration::MAX`] maps to. Use `force_refresh` for manual control.
 //!
 //! ```rust
-//! use std::time::Duration;
-//!
 //! use fcache::prelude::*;
 //!
 //! # fn wrapper() -> fcache::Result<()> {
 //! // Create a new cache instance
-//! let cache = fcache::new()?.with_refresh_interval(Duration::MAX);
+//! let cache = fcache::new()?.without_refresh();
 //!
 //! // Get or create a cached file
-//! let mut cache_file = cache.get("hello.txt", |mut file| {
+//! let cache_file = cache.get("hello.txt", |mut file| {
 //!     // Write data to the file
 //!     file.write_all(b"Hello, world!")?;
 //!     // Inform about the refresh
@@ -315,26 +313,110 @@
 
 #![forbid(unsafe_code)]
 
+mod append;
+#[cfg(feature = "zip")]
+mod archive;
+mod builder;
 mod callback;
+mod cancel;
+mod canonicalization;
+mod clock;
+mod creation;
+mod dir;
+mod entry;
+mod event;
+mod expiry;
+#[cfg(feature = "testing")]
+mod fault;
 mod file;
+mod flight;
+mod interval;
+mod key;
+mod limit;
+mod link;
+mod lock;
+mod memory;
+mod mime;
+mod object;
+#[cfg(feature = "fd-pool")]
+mod pool;
 pub mod prelude;
+mod quota;
+mod recovery;
+mod report;
 mod result;
+mod sanitize;
+mod single;
+mod temp;
+mod typed;
 
-use std::fmt::Debug;
-use std::fs;
-use std::path::{Component, Path, PathBuf};
-use std::time::Duration;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ffi::OsStr;
+use std::fmt::{self, Debug};
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::ops::ControlFlow;
+use std::path::{Component, Components, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+use std::{error, vec};
 
-use tempfile::TempDir;
+use tempfile::{NamedTempFile, TempDir};
 
-pub use crate::callback::CallbackFn;
-pub use crate::file::{CacheFile, CacheLazyFile};
+pub use crate::append::AppendFile;
+pub use crate::builder::CacheBuilder;
+#[cfg(feature = "testing")]
+pub use crate::callback::FaultInjectorFn;
+pub use crate::callback::{CallbackFn, ObserverFn, PathTransformerFn, SendCallbackFn};
+pub use crate::cancel::CancelToken;
+pub use crate::canonicalization::CanonicalizationMode;
+pub use crate::clock::{Clock, ManualClock, SystemClock};
+pub use crate::creation::CreationMode;
+pub use crate::dir::DirPolicy;
+use crate::dir::RemoveOnDrop;
+pub use crate::entry::CacheEntry;
+pub use crate::event::CacheEvent;
+pub use crate::expiry::ExpiryMode;
+#[cfg(feature = "testing")]
+pub use crate::fault::FaultPoint;
+pub use crate::file::{CacheFile, CacheLazyFile, FileLockGuard, OwnedCacheFile, OwnedCacheLazyFile};
+use crate::flight::InFlight;
+pub use crate::key::KeyPolicy;
+pub use crate::limit::LimitPolicy;
+pub use crate::link::LinkMode;
+use crate::lock::LockRegistry;
+use crate::memory::{DEFAULT_MAX_ENTRY_SIZE, MemoryCache};
+pub use crate::mime::sniff;
+use crate::object::DigestWriter;
+#[cfg(feature = "fd-pool")]
+use crate::pool::{DEFAULT_FD_POOL_SIZE, FileDescriptorPool};
+pub use crate::quota::{EvictionPolicy, StorageQuota};
+pub use crate::recovery::ErrorRecovery;
+pub use crate::report::{
+    BackupReport,
+    CacheDecision,
+    CacheExplanation,
+    CacheStats,
+    CloseReport,
+    DryRunReport,
+    EntryInfo,
+    EntryStatus,
+};
 use crate::result::Ok;
-pub use crate::result::{Error, Result};
+pub use crate::result::{Error, PersistError, Result};
+pub use crate::sanitize::{SanitizeOptions, sanitize_key};
+pub use crate::single::SingleThreadedCache;
+pub use crate::temp::TempDirOptions;
+pub use crate::typed::TypedCacheFile;
 
 /// Default refresh interval for the cache.
 pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Default time [`Cache::close`] waits for the operations of file handles in progress.
+pub const DEFAULT_CLOSE_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Creates a new cache instance within a temporary directory.
 ///
 /// For more information on how to use the cache, refer to the [`Cache`] documentation.
@@ -382,6 +464,35 @@ pub fn with_prefix(prefix: &str) -> Result<Cache> {
     Cache::with_prefix(prefix)
 }
 
+/// Creates a new cache instance within a temporary directory created with the specified options.
+///
+/// For more information on how to use the cache, refer to the [`Cache`] documentation.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::TempDirOptions;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Create a new cache instance with a short directory name
+/// let cache = fcache::temp_with(
+///     TempDirOptions::default()
+///         .with_prefix("c")
+///         .with_random_len(4),
+/// )?;
+///
+/// // Use the cache...
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the temporary directory cannot be created or there are issues with the underlying filesystem operations.
+pub fn temp_with(options: TempDirOptions) -> Result<Cache> {
+    Cache::temp_with(options)
+}
+
 /// Creates a new cache instance within a specified directory.
 ///
 /// For more information on how to use the cache, refer to the [`Cache`] documentation.
@@ -400,11 +511,82 @@ pub fn with_prefix(prefix: &str) -> Result<Cache> {
 ///
 /// # Errors
 ///
-/// This function will return an error if the specified path exists but is not a directory, the specified path does not exist and directory creation fails, or there are other underlying filesystem operation issues.
+/// This function will return an error if the specified path exists but is not a directory, the specified path does not exist and directory creation fails, [`Error::CacheNotWritable`] if the directory is not writable, or there are other underlying filesystem operation issues.
 pub fn with_dir(dir: impl AsRef<Path>) -> Result<Cache> {
     Cache::with_dir(dir)
 }
 
+/// Creates a new cache instance within a specified directory, checking the directory against a policy.
+///
+/// For more information on how to use the cache, refer to the [`Cache`] documentation.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use fcache::DirPolicy;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Create a new cache instance in a directory created by fcache
+/// let cache = fcache::with_dir_checked("/path/to/cache", DirPolicy::RequireMarker)?;
+///
+/// // Use the cache...
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error in the same cases as [`with_dir`], or if the directory violates the policy.
+pub fn with_dir_checked(dir: impl AsRef<Path>, dir_policy: DirPolicy) -> Result<Cache> {
+    Cache::with_dir_checked(dir, dir_policy)
+}
+
+/// Opens a cache instance within an existing directory, without creating it.
+///
+/// For more information on how to use the cache, refer to the [`Cache`] documentation.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Open a cache instance in a provisioned directory
+/// let cache = fcache::open_dir("/path/to/cache")?;
+///
+/// // Use the cache...
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error in the same cases as [`with_dir`], or if the specified directory does not exist.
+pub fn open_dir(dir: impl AsRef<Path>) -> Result<Cache> {
+    Cache::open_dir(dir)
+}
+
+/// Creates a new cache instance within a specified directory, which is removed when the cache is dropped.
+///
+/// For more information on how to use the cache, refer to the [`Cache`] documentation.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Create a new cache instance in a scratch directory
+/// let cache = fcache::with_dir_ephemeral("/path/to/workspace/run-1")?;
+///
+/// // Use the cache...
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error in the same cases as [`with_dir`], or if the directory already exists and is not empty.
+pub fn with_dir_ephemeral(dir: impl AsRef<Path>) -> Result<Cache> {
+    Cache::with_dir_ephemeral(dir)
+}
+
 /// Represents a cache instance.
 ///
 /// # Example
@@ -457,7 +639,7 @@ impl Cache {
     ///
     /// This function will return an error if the temporary directory cannot be created or if there are issues with the underlying filesystem operations.
     pub fn new() -> Result<Self> {
-        InnerCache::temp().map(Self)
+        InnerCache::temp_with(TempDirOptions::default()).map(Self)
     }
 
     /// Creates a new cache instance within a temporary directory with a specified prefix.
@@ -481,7 +663,65 @@ impl Cache {
     ///
     /// This function will return an error if the temporary directory cannot be created or there are issues with the underlying filesystem operations.
     pub fn with_prefix(prefix: &str) -> Result<Self> {
-        InnerCache::temp_with_prefix(prefix).map(Self)
+        InnerCache::temp_with(TempDirOptions::default().with_prefix(prefix)).map(Self)
+    }
+
+    /// Creates a new cache instance within a temporary directory created with the specified options.
+    ///
+    /// The options control the name of the directory, the directory it is created within, and (on Unix) its permissions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance with a short directory name
+    /// let options = TempDirOptions::default()
+    ///     .with_prefix("c")
+    ///     .with_random_len(4);
+    /// let cache = Cache::temp_with(options)?;
+    /// assert_eq!(cache.path().file_name().map(|name| name.len()), Some(5));
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the temporary directory cannot be created or there are issues with the underlying filesystem operations.
+    pub fn temp_with(options: TempDirOptions) -> Result<Self> {
+        InnerCache::temp_with(options).map(Self)
+    }
+
+    /// Creates a new cache instance within a temporary directory, using restrictive permissions.
+    ///
+    /// The directory is created restricted to the owner, and files and directories in the cache are created
+    /// restricted to the owner as well (see [`with_secure_permissions`](Self::with_secure_permissions)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance for tokens
+    /// let cache = Cache::secure()?;
+    /// assert!(cache.secure_permissions());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the temporary directory cannot be created or there are issues with the underlying filesystem operations.
+    pub fn secure() -> Result<Self> {
+        let options = TempDirOptions::default();
+        #[cfg(unix)]
+        let options = options.with_mode(InnerDirCache::SECURE_DIR_MODE);
+        Self::temp_with(options)?.with_secure_permissions(true)
     }
 
     /// Creates a new cache instance within a specified directory.
@@ -502,58 +742,93 @@ impl Cache {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the specified path exists but is not a directory, the specified path does not exist and directory creation fails, or there are other underlying filesystem operation issues.
+    /// This function will return an error if the specified path exists but is not a directory, the specified path does not exist and directory creation fails, [`Error::CacheNotWritable`] if the directory is not writable, or there are other underlying filesystem operation issues.
     pub fn with_dir(dir: impl AsRef<Path>) -> Result<Self> {
         InnerCache::dir(dir).map(Self)
     }
 
-    /// Sets the refresh interval for the cache.
+    /// Creates a new cache instance within a specified directory, checking the directory against a policy.
+    ///
+    /// The policy protects against accidentally using an arbitrary directory, such as the home directory, as a cache directory.
+    /// With [`DirPolicy::AllowExisting`] this is the same as [`with_dir`](Self::with_dir).
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
+    /// use fcache::DirPolicy;
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// // Create a new cache instance with a custom refresh interval
-    /// let cache = Cache::new()?.with_refresh_interval(Duration::from_secs(10));
+    /// // Create a new cache instance in a directory created by fcache
+    /// let cache = Cache::with_dir_checked("/path/to/cache", DirPolicy::RequireMarker)?;
     ///
     /// // Use the cache...
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
-        let Self(inner) = self;
-        inner.with_refresh_interval(refresh_interval).into()
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`with_dir`](Self::with_dir), [`Error::DirectoryNotEmpty`] if the directory must be empty but isn't, or [`Error::MissingMarker`] if the directory must contain the marker file but doesn't.
+    pub fn with_dir_checked(dir: impl AsRef<Path>, dir_policy: DirPolicy) -> Result<Self> {
+        InnerCache::dir_checked(dir, dir_policy).map(Self)
     }
 
-    /// Sets the refresh interval to the default value.
+    /// Opens a cache instance within an existing directory, without creating it.
+    ///
+    /// This is useful when the cache directory is provisioned beforehand, e.g. with a specific ownership, and must not be created by the application.
+    /// Otherwise it behaves exactly like [`with_dir`](Self::with_dir).
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,no_run
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// // Create a new cache instance
-    /// let cache = Cache::new()?.with_refresh_interval(Duration::from_secs(60));
+    /// // Open a cache instance in a provisioned directory
+    /// let cache = Cache::open_dir("/path/to/cache")?;
     ///
     /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
     ///
-    /// // Reset to default refresh interval
-    /// let cache = cache.with_default_refresh_interval();
+    /// # Errors
     ///
-    /// // Use the cache...
+    /// This function will return [`Error::CacheDirMissing`] if the specified directory does not exist, or an error in the same cases as [`with_dir`](Self::with_dir).
+    pub fn open_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        InnerCache::open_dir(dir).map(Self)
+    }
+
+    /// Opens a read-only cache instance within an existing directory.
+    ///
+    /// The directory is not probed for writability (see [`with_write_check`](Self::with_write_check)), and the cache is frozen (see [`freeze`](Self::freeze)),
+    /// so existing files can be opened while writes are rejected with [`Error::Frozen`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Open a cache instance in a directory shipped with the application
+    /// let cache = Cache::open_dir_read_only("/usr/share/app/cache")?;
+    /// assert!(cache.is_frozen());
     /// # Ok(())
     /// # }
-    #[must_use]
-    pub fn with_default_refresh_interval(self) -> Self {
-        let Self(inner) = self;
-        inner.with_default_refresh_interval().into()
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::CacheDirMissing`] if the specified directory does not exist, or an error in the same cases as [`with_dir`](Self::with_dir), except for the writability probe.
+    pub fn open_dir_read_only(dir: impl AsRef<Path>) -> Result<Self> {
+        InnerCache::open_dir_read_only(dir).map(Self)
     }
 
-    /// Returns the path of the cache directory.
+    /// Creates a new cache instance within a specified directory, which is removed when the cache is dropped.
+    ///
+    /// This combines the stable, caller-chosen path of [`with_dir`](Self::with_dir) with the cleanup of [`new`](Self::new), e.g. for scratch directories.
+    /// The directory is removed on a best-effort basis; errors are ignored, including the directory having been removed already.
     ///
     /// # Example
     ///
@@ -561,21 +836,31 @@ impl Cache {
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// // Create a new cache instance
-    /// let cache = Cache::new()?;
+    /// let workspace = tempfile::tempdir()?;
+    /// let dir = workspace.path().join("run-1");
     ///
-    /// // Print the cache path
-    /// println!("Cache path: {}", cache.path().display());
+    /// // Create a new cache instance in a scratch directory
+    /// let cache = Cache::with_dir_ephemeral(&dir)?;
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // The directory is removed along with the cache
+    /// drop(cache);
+    /// assert!(!dir.exists());
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn path(&self) -> &Path {
-        let Self(inner) = self;
-        inner.path()
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`with_dir`](Self::with_dir), or [`Error::DirectoryNotEmpty`] if the directory already exists and is not empty.
+    pub fn with_dir_ephemeral(dir: impl AsRef<Path>) -> Result<Self> {
+        InnerCache::dir_ephemeral(dir).map(Self)
     }
 
-    /// Returns the refresh interval of the cache.
+    /// Opens a cache instance within a backup created with [`backup`](Self::backup).
+    ///
+    /// The backup directory is used as the cache directory, so the files keep their validity from the time of the backup.
+    /// Back up the backup first if it has to stay untouched.
     ///
     /// # Example
     ///
@@ -583,21 +868,25 @@ impl Cache {
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// // Create a new cache instance
+    /// // Back up a cache
     /// let cache = Cache::new()?;
+    /// let backup_dir = tempfile::tempdir()?;
+    /// let _ = cache.backup(backup_dir.path())?;
     ///
-    /// // Print the refresh interval
-    /// println!("Refresh interval: {:?}", cache.refresh_interval());
+    /// // Restore the cache from the backup
+    /// let cache = Cache::restore_from_backup(backup_dir.path())?;
     /// # Ok(())
     /// # }
     /// ```
-    #[must_use]
-    pub fn refresh_interval(&self) -> Duration {
-        let Self(inner) = self;
-        inner.refresh_interval()
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`open_dir`](Self::open_dir).
+    pub fn restore_from_backup(backup_dir: impl AsRef<Path>) -> Result<Self> {
+        Self::open_dir(backup_dir)
     }
 
-    /// Creates a file in the cache using a callback for initialization.
+    /// Sets the refresh interval for the cache.
     ///
     /// # Example
     ///
@@ -605,36 +894,46 @@ impl Cache {
     /// use fcache::prelude::*;
     ///
     /// # fn wrapper() -> fcache::Result<()> {
-    /// // Create a new cache instance
-    /// let cache = Cache::new()?;
+    /// // Create a new cache instance with a custom refresh interval
+    /// let cache = Cache::new()?.with_refresh_interval(Duration::from_secs(10));
     ///
-    /// // Get or create a cached file
-    /// let cache_file = cache.get("example.txt", |mut file| {
-    ///     // Write data to the file
-    ///     file.write_all(b"Hello, Cache!")?;
-    ///     Ok(())
-    /// })?;
-    /// // File is created and can be used...
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        let Self(inner) = self;
+        inner.with_refresh_interval(refresh_interval).into()
+    }
+
+    /// Sets the refresh interval for the cache from a human-readable string.
     ///
-    /// // Open the cached file
-    /// let mut file = cache_file.open()?;
-    /// // Read data from the file
-    /// let mut contents = String::new();
-    /// file.read_to_string(&mut contents)?;
-    /// println!("Cached file contents: {}", contents);
+    /// The interval consists of numbers followed by one of the `ms`, `s`, `m`, `h`, and `d` units, e.g. `90s`, `6h`, or `1h30m`.
+    /// The special value `0` sets the refresh interval to [`Duration::ZERO`], and `never` disables refreshing like [`without_refresh`](Self::without_refresh).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance refreshing files every 90 minutes
+    /// let cache = Cache::new()?.with_refresh_interval_str("1h30m")?;
+    /// assert_eq!(cache.refresh_interval(), Duration::from_secs(90 * 60));
     /// # Ok(())
     /// # }
     /// ```
     ///
     /// # Errors
     ///
-    /// This function will return an error if the file already exists, file creation fails due to permissions or disk space, the callback function returns an error, path traversal is detected outside the cache directory, or parent directory creation fails.
-    pub fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
-        let Self(inner) = self;
-        inner.get(path, callback)
+    /// This function will return [`Error::InvalidInterval`] if the string is not a valid interval.
+    pub fn with_refresh_interval_str(self, refresh_interval: &str) -> Result<Self> {
+        let refresh_interval = interval::parse(refresh_interval)?;
+        Ok(self.with_refresh_interval(refresh_interval))
     }
 
-    /// Creates a file in the cache that is lazily created when accessed.
+    /// Sets the refresh interval to the default value.
     ///
     /// # Example
     ///
@@ -643,110 +942,6376 @@ impl Cache {
     ///
     /// # fn wrapper() -> fcache::Result<()> {
     /// // Create a new cache instance
-    /// let cache = Cache::new()?;
+    /// let cache = Cache::new()?.with_refresh_interval(Duration::from_secs(60));
     ///
-    /// // Get or create a lazy cached file
-    /// let cache_file = cache.get_lazy("lazy_file.txt", |mut file| {
-    ///     // Write data to the file
-    ///     file.write_all(b"Hello, Lazy Cache!")?;
-    ///     Ok(())
-    /// })?;
+    /// // Use the cache...
     ///
-    /// // File isn't created until opened...
-    /// assert!(!cache_file.path().exists());
+    /// // Reset to default refresh interval
+    /// let cache = cache.with_default_refresh_interval();
     ///
-    /// // Open the lazy cached file
-    /// let mut file = cache_file.open()?;
-    /// // Read data from the file
-    /// let mut contents = String::new();
-    /// file.read_to_string(&mut contents)?;
-    /// println!("Lazy cached file contents: {}", contents);
+    /// // Use the cache...
     /// # Ok(())
     /// # }
-    /// ```
+    #[must_use]
+    pub fn with_default_refresh_interval(self) -> Self {
+        let Self(inner) = self;
+        inner.with_default_refresh_interval().into()
+    }
+
+    /// Disables automatic refreshing of files in the cache, so they are only refreshed manually.
     ///
-    /// # Errors
+    /// This is the same as setting the refresh interval to [`Duration::MAX`].
     ///
-    /// This function will return an error if the file already exists, path traversal is detected outside the cache directory, parent directory creation fails, or there are issues with path resolution or filesystem operations.
-    pub fn get_lazy<'a>(
-        &'a self,
-        path: impl AsRef<Path>,
-        callback: impl CallbackFn + 'static,
-    ) -> Result<CacheLazyFile<'a>> {
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance which never refreshes files automatically
+    /// let cache = Cache::new()?.without_refresh();
+    /// assert_eq!(cache.refresh_interval_opt(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn without_refresh(self) -> Self {
         let Self(inner) = self;
-        inner.get_lazy(path, callback)
+        inner.without_refresh().into()
+    }
+
+    /// Sets whether empty files in the cache are considered invalid.
+    ///
+    /// When enabled, zero-byte files (e.g. left behind by crashes or failed callbacks) are refreshed on the next access regardless of their modification time.
+    /// Individual files can opt out with [`CacheFile::with_empty_is_invalid`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance that regenerates empty files
+    /// let cache = Cache::new()?.with_empty_is_invalid(true);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
+        let Self(inner) = self;
+        inner.with_empty_is_invalid(empty_is_invalid).into()
+    }
+
+    /// Sets the maximum size of files in the cache.
+    ///
+    /// After a callback runs during creation or refresh, files larger than the limit are removed and [`Error::FileTooLarge`] is returned.
+    /// This acts as a guardrail against runaway callbacks; see [`get_with_max_size`](Self::get_with_max_size) for per-file overrides.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance limited to 1 MiB files
+    /// let cache = Cache::new()?.with_max_file_size(1024 * 1024);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_file_size(self, max_file_size: u64) -> Self {
+        let Self(inner) = self;
+        inner.with_max_file_size(max_file_size).into()
+    }
+
+    /// Sets a transformer rewriting the location of files in the cache.
+    ///
+    /// The transformer receives the absolute path of a file after the path traversal checks and returns the path where the file is physically stored.
+    /// This allows e.g. partitioning files into subdirectories, while the files are still requested using their original paths.
+    /// Transformed paths escaping the cache directory are rejected with [`Error::PathTraversal`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance storing files in a partition directory
+    /// let cache = Cache::new()?;
+    /// let root = cache.path().to_path_buf();
+    /// let cache = cache.with_path_transformer(move |path| {
+    ///     let relative_path = path.strip_prefix(&root).unwrap_or(&path);
+    ///     Ok(root.join("partition").join(relative_path))
+    /// });
+    ///
+    /// // Files are stored in the partition directory
+    /// let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    /// assert!(cache_file.path().ends_with("partition/file.txt"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_path_transformer(self, path_transformer: impl PathTransformerFn + 'static) -> Self {
+        let Self(inner) = self;
+        inner.with_path_transformer(path_transformer).into()
+    }
+
+    /// Sets an observer receiving the events of cache operations.
+    ///
+    /// The observer is called synchronously whenever a file is created, refreshed, removed, evicted, locked, or unlocked, and whenever creating or refreshing a file fails (see [`CacheEvent`]).
+    /// This allows integrating the cache with logging, tracing, or metrics systems. Setting another observer replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CacheEvent;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance logging the created files
+    /// let cache = Cache::new()?.with_observer(|event| {
+    ///     if let CacheEvent::Created { path } = event {
+    ///         println!("Created {}", path.display());
+    ///     }
+    /// });
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_observer(self, observer: impl ObserverFn + 'static) -> Self {
+        let Self(inner) = self;
+        inner.with_observer(observer).into()
+    }
+
+    /// Sets a fault injector failing the operations of the cache on demand.
+    ///
+    /// The injector is called before each operation identified by a [`FaultPoint`]. If it returns an error, the operation fails with that error instead of touching the filesystem.
+    /// This allows testing how applications handle a misbehaving cache, e.g. a full disk or denied permissions. Setting another injector replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, ErrorKind};
+    ///
+    /// use fcache::FaultPoint;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance failing to remove files
+    /// let cache = Cache::new()?.with_fault_injector(|fault_point| {
+    ///     match fault_point {
+    ///         FaultPoint::Remove { .. } => Some(io::Error::from(ErrorKind::PermissionDenied)),
+    ///         _ => None,
+    ///     }
+    /// });
+    ///
+    /// let cache_file = cache.get("data.txt", |_| Ok(()))?;
+    /// assert!(cache_file.remove().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "testing")]
+    #[must_use]
+    pub fn with_fault_injector(self, fault_injector: impl FaultInjectorFn + 'static) -> Self {
+        let Self(inner) = self;
+        inner.with_fault_injector(fault_injector).into()
+    }
+
+    /// Sets the clock used by the cache to determine the current time.
+    ///
+    /// By default the system time is used (see [`SystemClock`]).
+    /// Injecting a [`ManualClock`] allows stepping time forward in tests instead of sleeping.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    ///
+    /// use fcache::ManualClock;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance with a manually driven clock
+    /// let clock = ManualClock::new(SystemTime::now());
+    /// let cache = Cache::new()?.with_clock(clock.clone());
+    ///
+    /// // Step the time forward
+    /// clock.advance(Duration::from_secs(60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        let Self(inner) = self;
+        inner.with_clock(clock).into()
+    }
+
+    /// Sets whether the cache directory is recreated when it disappears.
+    ///
+    /// If the cache directory is removed while the cache is in use (e.g. by an operator or a cleanup job), file operations return [`Error::CacheRootMissing`].
+    /// When enabled, the cache directory and the needed parent directories are recreated instead, reusing the original path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance recovering from a removed directory
+    /// let cache = Cache::new()?.with_auto_recreate_root(true);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_auto_recreate_root(self, auto_recreate_root: bool) -> Self {
+        let Self(inner) = self;
+        inner.with_auto_recreate_root(auto_recreate_root).into()
+    }
+
+    /// Sets whether the cache directory is probed for writability.
+    ///
+    /// The probe creates and deletes a small file in the cache directory, failing fast with [`Error::CacheNotWritable`]
+    /// instead of a permission error inside a callback later. It runs by default when the cache is created, and when
+    /// enabled with this method. Caches derived with [`subdirectory`](Self::subdirectory) and [`new_sibling`](Self::new_sibling)
+    /// inherit the setting, so disabling it is useful for read-only caches (see [`open_dir_read_only`](Self::open_dir_read_only)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance, probing the directory once more
+    /// let cache = Cache::new()?.with_write_check(true)?;
+    /// assert!(cache.write_check());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::CacheNotWritable`] if the check is enabled and the cache directory is not writable.
+    pub fn with_write_check(self, write_check: bool) -> Result<Self> {
+        let Self(inner) = self;
+        inner.with_write_check(write_check).map(Self)
+    }
+
+    /// Sets whether the cache uses restrictive permissions, e.g. for caching secrets.
+    ///
+    /// When enabled, the cache directory is restricted to the owner (`0o700`), and files and directories are created with
+    /// `0o600` and `0o700` permissions respectively. The cache directory must not be writable by the group or others,
+    /// otherwise [`Error::InsecurePermissions`] is returned, as its content could have been tampered with.
+    /// Caches derived with [`subdirectory`](Self::subdirectory) and [`new_sibling`](Self::new_sibling) inherit the setting.
+    ///
+    /// This is a no-op on non-Unix platforms, where permissions are not expressed as mode bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?.with_secure_permissions(true)?;
+    /// assert!(cache.secure_permissions());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InsecurePermissions`] if the setting is enabled and the cache directory is writable by the group or others,
+    /// or an error if the permissions of the cache directory cannot be read or changed.
+    pub fn with_secure_permissions(self, secure_permissions: bool) -> Result<Self> {
+        let Self(inner) = self;
+        inner.with_secure_permissions(secure_permissions).map(Self)
+    }
+
+    /// Sets whether the cache is flat, storing all files directly in the cache directory.
+    ///
+    /// In a flat cache keys containing a path separator are rejected with [`Error::InvalidPath`] before touching the filesystem,
+    /// and no subdirectories are ever created or removed. Content-addressed objects (see [`put_content_addressed`](Self::put_content_addressed)) are not affected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new flat cache instance
+    /// let cache = Cache::new()?.with_flat(true);
+    ///
+    /// // Nested keys are rejected
+    /// assert!(cache.get("a/b.txt", |_| Ok(())).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_flat(self, flat: bool) -> Self {
+        let Self(inner) = self;
+        inner.with_flat(flat).into()
+    }
+
+    /// Sets the policy limiting the files in the cache.
+    ///
+    /// After each [`get`](Self::get) the policy is enforced by evicting files, in order:
+    /// files older than the maximum age first, then the least recently modified files until the total size fits, and finally until the number of files fits.
+    /// The file just created by [`get`](Self::get) is never evicted. The policy can also be enforced manually with [`enforce_limit_policy`](Self::enforce_limit_policy).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping at most 100 files of at most 500 MiB in total
+    /// let policy = LimitPolicy::new()
+    ///     .max_entries(100)
+    ///     .max_size_bytes(500 * 1024 * 1024);
+    /// let cache = Cache::new()?.with_entry_limit_policy(policy);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_entry_limit_policy(self, limit_policy: LimitPolicy) -> Self {
+        let Self(inner) = self;
+        inner.with_entry_limit_policy(limit_policy).into()
+    }
+
+    /// Sets the storage quota of the cache.
+    ///
+    /// Whenever a file is created or refreshed the total size and the number of files in the cache are checked against the quota,
+    /// and if it is exceeded files are evicted in the order of its [`EvictionPolicy`] until the cache fits again.
+    /// The file just written and locked files (see [`CacheFile::lock`]) are never evicted, so a file larger than the quota evicts all other files.
+    /// Evicted files are reported to the observer as [`CacheEvent::Evicted`] (see [`with_observer`](Self::with_observer)).
+    ///
+    /// Combined with [`subdirectory`](Self::subdirectory), independent quotas can be given to subsystems sharing a cache directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    /// use fcache::{EvictionPolicy, StorageQuota};
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance with a budget of 10 MiB in at most 100 files
+    /// let quota = StorageQuota {
+    ///     max_bytes: 10 * 1024 * 1024,
+    ///     max_files: 100,
+    ///     eviction: EvictionPolicy::LeastRecentlyModified,
+    /// };
+    /// let cache = Cache::new()?.with_storage_quota(quota);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_storage_quota(self, storage_quota: StorageQuota) -> Self {
+        let Self(inner) = self;
+        inner.with_storage_quota(storage_quota).into()
+    }
+
+    /// Sets the maximum total size of files in the cache in bytes.
+    ///
+    /// This is a shorthand for a [`StorageQuota`] without a limit on the number of files, evicting the least recently modified files first.
+    /// For more details see [`with_storage_quota`](Self::with_storage_quota).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping at most 8 bytes
+    /// let cache = Cache::new()?.with_max_size(8);
+    /// let old_file = cache.get("old.txt", |mut file| {
+    ///     file.write_all(b"old")?;
+    ///     Ok(())
+    /// })?;
+    /// # std::thread::sleep(std::time::Duration::from_millis(10));
+    ///
+    /// // Writing past the limit evicts the older file
+    /// let new_file = cache.get("new.txt", |mut file| {
+    ///     file.write_all(b"newer")?;
+    ///     Ok(())
+    /// })?;
+    /// assert!(!old_file.path().exists());
+    /// assert!(new_file.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_size(self, max_size: u64) -> Self {
+        let storage_quota = StorageQuota {
+            max_bytes: max_size,
+            max_files: usize::MAX,
+            eviction: EvictionPolicy::LeastRecentlyModified,
+        };
+        self.with_storage_quota(storage_quota)
+    }
+
+    /// Sets the maximum number of files in the cache.
+    ///
+    /// Before a file is created, the least recently modified files are removed until the new file fits within the limit.
+    /// Locked files (see [`CacheFile::lock`]) are never removed, so if all the files are locked [`Error::CacheFull`] is returned instead.
+    /// Files are counted when a file is created, so files created concurrently by several threads can exceed the limit by at most the number of threads.
+    /// Files still being created by other handles are neither counted nor removed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping at most 2 files
+    /// let cache = Cache::new()?.with_max_entries(2);
+    /// let first_file = cache.get("first.txt", |_| Ok(()))?;
+    /// # std::thread::sleep(std::time::Duration::from_millis(10));
+    /// let _ = cache.get("second.txt", |_| Ok(()))?;
+    ///
+    /// // Creating a third file removes the oldest one
+    /// let _ = cache.get("third.txt", |_| Ok(()))?;
+    /// assert!(!first_file.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_max_entries(self, max_entries: usize) -> Self {
+        let Self(inner) = self;
+        inner.with_max_entries(max_entries).into()
+    }
+
+    /// Sets the policy validating the paths of files requested from the cache.
+    ///
+    /// Paths violating the policy are rejected with [`Error::InvalidPath`] before any filesystem work.
+    /// The default policy doesn't add any rules to the built-in checks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::KeyPolicy;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance rejecting hidden files
+    /// let cache = Cache::new()?.with_key_policy(KeyPolicy::new().allow_hidden(false));
+    ///
+    /// // Hidden files are rejected
+    /// assert!(cache.get(".env", |_| Ok(())).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_key_policy(self, key_policy: KeyPolicy) -> Self {
+        let Self(inner) = self;
+        inner.with_key_policy(key_policy).into()
+    }
+
+    /// Sets how [`get`](Self::get) treats files which already exist in the cache.
+    ///
+    /// By default existing files are rejected with [`Error::FileAlreadyExists`] ([`CreationMode::CreateNew`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CreationMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance regenerating files on every `get()`
+    /// let cache = Cache::new()?.with_creation_mode(CreationMode::CreateOrTruncate);
+    ///
+    /// // The callback runs every time
+    /// let _ = cache.get("file.txt", |mut file| {
+    ///     file.write_all(b"first")?;
+    ///     Ok(())
+    /// })?;
+    /// let _ = cache.get("file.txt", |mut file| {
+    ///     file.write_all(b"second")?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_creation_mode(self, creation_mode: CreationMode) -> Self {
+        let Self(inner) = self;
+        inner.with_creation_mode(creation_mode).into()
+    }
+
+    /// Sets how failures of callbacks are handled when files are created or refreshed.
+    ///
+    /// By default errors are returned as they are ([`ErrorRecovery::Propagate`]), leaving the partially written file in place when refreshing.
+    /// A file which fails to be created is always removed, so it is created again on the next access.
+    /// Other strategies discard the partial file and retry the callback, run an alternate callback, or remove the file so it is created again on the next access.
+    /// Cancellation (see [`cancel_all`](Self::cancel_all)) is never recovered from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::ErrorRecovery;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance retrying failed callbacks up to 3 times
+    /// let cache = Cache::new()?.with_error_recovery(ErrorRecovery::DeleteAndRetry { max_retries: 3 });
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_error_recovery(self, error_recovery: ErrorRecovery) -> Self {
+        let Self(inner) = self;
+        inner.with_error_recovery(error_recovery).into()
+    }
+
+    /// Sets whether the files of the cache are removed when the cache is dropped.
+    ///
+    /// When enabled, the files and directories added to the cache directory from now on, like the files of the cache along with their sidecar files
+    /// and the marker file, are removed once the cache and its owned file handles (see [`CacheFile::into_owned`]) are dropped.
+    /// Files which were in the directory before are kept, so are the directory itself and the directories containing them;
+    /// use [`with_cleanup_all_on_drop`](Self::with_cleanup_all_on_drop) to remove them as well. Errors are ignored while cleaning up.
+    ///
+    /// Disabling the cleanup keeps the directory of an ephemeral cache as well, while a temporary cache is always removed (see [`into_path`](Self::into_path)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// # let temp_dir = tempfile::tempdir()?;
+    /// # let dir = temp_dir.path().join("cache");
+    /// // Create a new cache instance removing its files when dropped
+    /// let cache = Cache::with_dir(&dir)?.with_cleanup_on_drop(true);
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // Verify the directory is empty once the cache is dropped
+    /// drop(cache);
+    /// assert!(std::fs::read_dir(&dir)?.next().is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_cleanup_on_drop(self, cleanup_on_drop: bool) -> Self {
+        let Self(inner) = self;
+        inner.with_cleanup_on_drop(cleanup_on_drop).into()
+    }
+
+    /// Sets whether the whole content of the cache directory is removed when the cache is dropped.
+    ///
+    /// Unlike [`with_cleanup_on_drop`](Self::with_cleanup_on_drop), files which were in the directory before the cache are removed as well.
+    /// The directory itself is kept, as it may have existed before the cache; use [`with_dir_ephemeral`](Self::with_dir_ephemeral) to remove it as well.
+    /// Errors are ignored while cleaning up.
+    ///
+    /// Disabling the cleanup is the same as [`with_cleanup_on_drop(false)`](Self::with_cleanup_on_drop).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// # let temp_dir = tempfile::tempdir()?;
+    /// # let dir = temp_dir.path().join("cache");
+    /// # std::fs::create_dir(&dir)?;
+    /// std::fs::write(dir.join("notes.txt"), b"notes")?;
+    ///
+    /// // Create a new cache instance removing the whole content of its directory when dropped
+    /// let cache = Cache::with_dir(&dir)?.with_cleanup_all_on_drop(true);
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // Verify the directory is empty once the cache is dropped
+    /// drop(cache);
+    /// assert!(std::fs::read_dir(&dir)?.next().is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_cleanup_all_on_drop(self, cleanup_all_on_drop: bool) -> Self {
+        let Self(inner) = self;
+        inner.with_cleanup_all_on_drop(cleanup_all_on_drop).into()
+    }
+
+    /// Sets the version token of files in the cache.
+    ///
+    /// The token is recorded in a `.fcache-meta-<name>.version` sidecar file next to the file whenever a file is created or refreshed, and files whose recorded token differs are invalid regardless of their expiry.
+    /// This allows invalidating files derived from e.g. application config as soon as the config version changes.
+    /// The token can be overridden for a single file with [`CacheFile::with_version`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance whose files are invalid once the config version changes
+    /// let cache = Cache::new()?.with_version("config-v1");
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_version(self, version: impl Into<String>) -> Self {
+        let Self(inner) = self;
+        inner.with_version(version.into()).into()
+    }
+
+    /// Sets how paths of files are resolved when checking that they are within the cache directory.
+    ///
+    /// By default every directory is canonicalized ([`CanonicalizationMode::Strict`]), which is the most secure option.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CanonicalizationMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance resolving paths lexically
+    /// let cache = Cache::new()?.with_path_canonicalization(CanonicalizationMode::None);
+    ///
+    /// // Path traversal is still detected
+    /// assert!(cache.get("../file.txt", |_| Ok(())).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_path_canonicalization(self, canonicalization_mode: CanonicalizationMode) -> Self {
+        let Self(inner) = self;
+        inner.with_path_canonicalization(canonicalization_mode).into()
+    }
+
+    /// Sets the maximum number of open file descriptors pooled per file for [`CacheFile::open_pooled`].
+    ///
+    /// Pooled descriptors are reused instead of opening the file again, which avoids the cost of `open()` calls in read-heavy workloads.
+    /// By default a single descriptor is pooled per file, and `0` disables pooling. Changing the size drops all pooled descriptors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance pooling up to 4 descriptors per file
+    /// let cache = Cache::new()?.with_fd_pool_size(4);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fd-pool")]
+    #[must_use]
+    pub fn with_fd_pool_size(self, fd_pool_size: usize) -> Self {
+        let Self(inner) = self;
+        inner.with_fd_pool_size(fd_pool_size).into()
+    }
+
+    /// Enables an in-memory layer keeping up to `max_bytes` bytes of small files for [`CacheFile::read_cached`].
+    ///
+    /// Kept content is served without opening and reading the file again, which avoids the cost of system calls for small, frequently read files.
+    /// It is evicted in least recently used order, and dropped whenever the file is refreshed or removed through the cache, or its modification time or size changes on disk.
+    /// Files larger than the maximum entry size bypass the layer (see [`with_memory_cache_max_entry_size`](Self::with_memory_cache_max_entry_size)).
+    /// By default the layer is disabled, which is the same as `0`. Changing the size drops all kept content.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping up to 1 MiB of files in memory
+    /// let cache = Cache::new()?.with_memory_cache(1024 * 1024);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_memory_cache(self, max_bytes: usize) -> Self {
+        let Self(inner) = self;
+        inner.with_memory_cache(max_bytes).into()
+    }
+
+    /// Sets the maximum size of a single file kept in memory by the in-memory layer, in bytes.
+    ///
+    /// Larger files are read from disk every time. By default files of up to 64 KiB are kept. Changing the size drops all kept content.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping files of up to 4 KiB in memory
+    /// let cache = Cache::new()?
+    ///     .with_memory_cache(1024 * 1024)
+    ///     .with_memory_cache_max_entry_size(4 * 1024);
+    ///
+    /// // Use the cache...
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_memory_cache_max_entry_size(self, max_entry_size: usize) -> Self {
+        let Self(inner) = self;
+        inner.with_memory_cache_max_entry_size(max_entry_size).into()
+    }
+
+    /// Converts the cache into a single-threaded cache accepting callbacks which are only [`Send`].
+    ///
+    /// The returned cache is not [`Sync`], which allows callbacks capturing types like [`Cell`](std::cell::Cell) or [`RefCell`](std::cell::RefCell).
+    /// The options of the cache are preserved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cell::Cell;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new single-threaded cache instance
+    /// let cache = Cache::new()?.with_single_threaded_mode();
+    ///
+    /// // Use a callback with state which is not thread-safe
+    /// let calls = Cell::new(0);
+    /// let cache_file = cache.get("example.txt", move |mut file| {
+    ///     calls.set(calls.get() + 1);
+    ///     writeln!(file, "Generated {} time(s)", calls.get())?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_single_threaded_mode(self) -> SingleThreadedCache {
+        SingleThreadedCache::new(self)
+    }
+
+    /// Returns the path of the cache directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Print the cache path
+    /// println!("Cache path: {}", cache.path().display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        let Self(inner) = self;
+        inner.path()
+    }
+
+    /// Returns the absolute path of the file with the given key, without creating it.
+    ///
+    /// The key is validated and normalized the same way as when getting a file, so the relative path of a file (see [`CacheFile::relative_path`])
+    /// resolves back to the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    ///
+    /// // Resolve the location of a file
+    /// let path = cache.path_for("reports/summary.json")?;
+    /// assert_eq!(path, cache.path().join("reports/summary.json"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the key is invalid or rejected by the key policy, or [`Error::PathTraversal`] if it points outside the cache directory.
+    pub fn path_for(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let Self(inner) = self;
+        inner.path_for(path)
+    }
+
+    /// Returns the refresh interval of the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Print the refresh interval
+    /// println!("Refresh interval: {:?}", cache.refresh_interval());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval(&self) -> Duration {
+        self.refresh_interval_opt().unwrap_or(Duration::MAX)
+    }
+
+    /// Returns the refresh interval of the cache, or `None` if files are never refreshed automatically.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Files are refreshed automatically by default
+    /// assert_eq!(
+    ///     cache.refresh_interval_opt(),
+    ///     Some(fcache::DEFAULT_REFRESH_INTERVAL)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn refresh_interval_opt(&self) -> Option<Duration> {
+        let Self(inner) = self;
+        inner.refresh_interval()
+    }
+
+    /// Returns whether empty files in the cache are considered invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Empty files are valid by default
+    /// assert!(!cache.empty_is_invalid());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn empty_is_invalid(&self) -> bool {
+        let Self(inner) = self;
+        inner.empty_is_invalid()
+    }
+
+    /// Returns the maximum size of files in the cache, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Files are not limited by default
+    /// assert_eq!(cache.max_file_size(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_file_size(&self) -> Option<u64> {
+        let Self(inner) = self;
+        inner.max_file_size()
+    }
+
+    /// Returns whether the cache directory is recreated when it disappears.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // The cache directory is not recreated by default
+    /// assert!(!cache.auto_recreate_root());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn auto_recreate_root(&self) -> bool {
+        let Self(inner) = self;
+        inner.auto_recreate_root()
+    }
+
+    /// Returns whether the cache directory is probed for writability.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    /// assert!(cache.write_check());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_check(&self) -> bool {
+        let Self(inner) = self;
+        inner.write_check()
+    }
+
+    /// Returns whether the cache uses restrictive permissions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    /// assert!(!cache.secure_permissions());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn secure_permissions(&self) -> bool {
+        let Self(inner) = self;
+        inner.secure_permissions()
+    }
+
+    /// Returns whether the cache is flat (see [`with_flat`](Self::with_flat)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Caches allow nested keys by default
+    /// assert!(!cache.is_flat());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_flat(&self) -> bool {
+        let Self(inner) = self;
+        inner.is_flat()
+    }
+
+    /// Returns the policy limiting the files in the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // The files are not limited by default
+    /// assert_eq!(cache.entry_limit_policy(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn entry_limit_policy(&self) -> Option<LimitPolicy> {
+        let Self(inner) = self;
+        inner.entry_limit_policy()
+    }
+
+    /// Returns the storage quota of the cache, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // The storage is not limited by default
+    /// assert_eq!(cache.storage_quota(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn storage_quota(&self) -> Option<StorageQuota> {
+        let Self(inner) = self;
+        inner.storage_quota()
+    }
+
+    /// Returns the maximum number of files in the cache, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?.with_max_entries(100);
+    /// assert_eq!(cache.max_entries(), Some(100));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_entries(&self) -> Option<usize> {
+        let Self(inner) = self;
+        inner.max_entries()
+    }
+
+    /// Returns the policy validating the paths of files requested from the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Print the key policy
+    /// println!("Key policy: {:?}", cache.key_policy());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn key_policy(&self) -> KeyPolicy {
+        let Self(inner) = self;
+        inner.key_policy()
+    }
+
+    /// Returns how files which already exist in the cache are treated.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CreationMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Existing files are rejected by default
+    /// assert_eq!(cache.creation_mode(), CreationMode::CreateNew);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn creation_mode(&self) -> CreationMode {
+        let Self(inner) = self;
+        inner.creation_mode()
+    }
+
+    /// Returns how failures of callbacks are handled when files are created or refreshed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::ErrorRecovery;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Errors are propagated by default
+    /// assert!(matches!(cache.error_recovery(), ErrorRecovery::Propagate));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn error_recovery(&self) -> &ErrorRecovery {
+        let Self(inner) = self;
+        inner.error_recovery()
+    }
+
+    /// Returns the version token of files in the cache, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance with a version token
+    /// let cache = Cache::new()?.with_version("config-v1");
+    ///
+    /// // Get the version token
+    /// assert_eq!(cache.version(), Some("config-v1"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        let Self(inner) = self;
+        inner.version()
+    }
+
+    /// Returns the generation of files in the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Files start at generation zero
+    /// assert_eq!(cache.generation(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        let Self(inner) = self;
+        inner.generation()
+    }
+
+    /// Increments the generation of files in the cache, invalidating every file created or refreshed before.
+    ///
+    /// The generation is recorded in a `.fcache-meta-<name>.generation` sidecar file next to the file whenever a file is created or refreshed, and files recorded with an older generation are invalid regardless of their expiry.
+    /// Unlike refreshing every file, the files are regenerated lazily on their next access, e.g. after deploying a new version of an application.
+    /// The generation is persisted in the cache directory, so it applies to caches opened later in the same directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Invalidate every file at once
+    /// cache.bump_generation()?;
+    /// assert!(cache_file.is_invalid()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, or the generation cannot be persisted.
+    pub fn bump_generation(&self) -> Result<()> {
+        let Self(inner) = self;
+        inner.bump_generation()
+    }
+
+    /// Returns how paths of files are resolved when checking that they are within the cache directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::CanonicalizationMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Paths are canonicalized strictly by default
+    /// assert_eq!(cache.path_canonicalization(), CanonicalizationMode::Strict);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn path_canonicalization(&self) -> CanonicalizationMode {
+        let Self(inner) = self;
+        inner.path_canonicalization()
+    }
+
+    /// Returns the maximum number of open file descriptors pooled per file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // A single descriptor is pooled by default
+    /// assert_eq!(cache.fd_pool_size(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fd-pool")]
+    #[must_use]
+    pub fn fd_pool_size(&self) -> usize {
+        let Self(inner) = self;
+        inner.fd_pool_size()
+    }
+
+    /// Returns the maximum total size of files kept in memory by the in-memory layer, in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // The layer is disabled by default
+    /// assert_eq!(cache.memory_cache_max_bytes(), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn memory_cache_max_bytes(&self) -> usize {
+        let Self(inner) = self;
+        inner.memory_cache_max_bytes()
+    }
+
+    /// Returns the maximum size of a single file kept in memory by the in-memory layer, in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Files of up to 64 KiB are kept by default
+    /// assert_eq!(cache.memory_cache_max_entry_size(), 64 * 1024);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn memory_cache_max_entry_size(&self) -> usize {
+        let Self(inner) = self;
+        inner.memory_cache_max_entry_size()
+    }
+
+    /// Returns the token cancelling the creation and refreshing of files in the cache.
+    ///
+    /// Long-running callbacks can capture the token and poll [`CancelToken::is_cancelled`] to stop early.
+    /// Once the token is cancelled (see [`cancel_all`](Self::cancel_all)), files are no longer created or refreshed, callbacks failing afterwards have their partially written files removed, and [`Error::Cancelled`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Stop the download when the cache is cancelled
+    /// let token = cache.cancellation_token();
+    /// let cache_file = cache.get("download.bin", move |mut file| {
+    ///     for chunk in [b"chunk 1", b"chunk 2"] {
+    ///         if token.is_cancelled() {
+    ///             return Err("cancelled".into());
+    ///         }
+    ///         file.write_all(chunk)?;
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cancellation_token(&self) -> CancelToken {
+        let Self(inner) = self;
+        inner.cancellation_token()
+    }
+
+    /// Cancels the creation and refreshing of files in the cache (see [`cancellation_token`](Self::cancellation_token)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Cancel on shutdown
+    /// cache.cancel_all();
+    /// assert!(matches!(
+    ///     cache.get("file.txt", |_| Ok(())),
+    ///     Err(fcache::Error::Cancelled { .. })
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cancel_all(&self) {
+        self.cancellation_token().cancel();
+    }
+
+    /// Freezes the cache, forbidding all writes until it is thawed (see [`thaw`](Self::thaw)).
+    ///
+    /// While frozen, creating, refreshing, truncating, appending to, and removing files return [`Error::Frozen`].
+    /// Existing files can still be opened and read; [`CacheLazyFile::open`] skips its implicit refresh, and limit policies aren't enforced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let cache_file = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Forbid writes while taking a backup
+    /// cache.freeze();
+    /// assert!(matches!(
+    ///     cache_file.force_refresh(),
+    ///     Err(fcache::Error::Frozen { .. })
+    /// ));
+    /// let _ = cache_file.open()?;
+    /// cache.thaw();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn freeze(&self) {
+        let Self(inner) = self;
+        inner.freeze();
+    }
+
+    /// Thaws the cache, allowing writes again after it was frozen (see [`freeze`](Self::freeze)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Freeze and thaw the cache
+    /// cache.freeze();
+    /// cache.thaw();
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn thaw(&self) {
+        let Self(inner) = self;
+        inner.thaw();
+    }
+
+    /// Returns whether the cache is frozen (see [`freeze`](Self::freeze)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Caches aren't frozen by default
+    /// assert!(!cache.is_frozen());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        let Self(inner) = self;
+        inner.is_frozen()
+    }
+
+    /// Creates a file in the cache using a callback for initialization.
+    ///
+    /// File names starting with `.fcache` are reserved for the files of the cache itself, e.g. the `.fcache-meta-` prefix of sidecar files,
+    /// so keys with such file names are rejected with [`Error::InvalidPath`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Get or create a cached file
+    /// let cache_file = cache.get("example.txt", |mut file| {
+    ///     // Write data to the file
+    ///     file.write_all(b"Hello, Cache!")?;
+    ///     Ok(())
+    /// })?;
+    /// // File is created and can be used...
+    ///
+    /// // Open the cached file
+    /// let mut file = cache_file.open()?;
+    /// // Read data from the file
+    /// let mut contents = String::new();
+    /// file.read_to_string(&mut contents)?;
+    /// println!("Cached file contents: {}", contents);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file already exists, file creation fails due to permissions or disk space, the callback function returns an error, path traversal is detected outside the cache directory, the file name of the key starts with the reserved `.fcache` prefix, or parent directory creation fails.
+    pub fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
+        let Self(inner) = self;
+        inner.get(path, callback)
+    }
+
+    /// Creates a file in the cache with the given content.
+    ///
+    /// This is a shorthand for [`get`](Self::get) with a callback writing the content, which is also used when the file is refreshed.
+    /// Existing files are handled the same way too, according to the creation mode (see [`with_creation_mode`](Self::with_creation_mode)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Store the bytes without writing a callback
+    /// let cache_file = cache.put("data.bin", &[1, 2, 3])?;
+    /// assert_eq!(std::fs::read(cache_file.path())?, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`get`](Self::get).
+    pub fn put(&self, path: impl AsRef<Path>, bytes: &[u8]) -> Result<CacheFile<'_>> {
+        self.get(path, callback::write_all(bytes.to_vec()))
+    }
+
+    /// Creates a file in the cache with the given text content.
+    ///
+    /// For more details see [`put`](Self::put).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Store the text without writing a callback
+    /// let cache_file = cache.put_string("greeting.txt", "Hello, Cache!")?;
+    /// assert_eq!(std::fs::read_to_string(cache_file.path())?, "Hello, Cache!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`get`](Self::get).
+    pub fn put_string(&self, path: impl AsRef<Path>, content: impl AsRef<str>) -> Result<CacheFile<'_>> {
+        self.put(path, content.as_ref().as_bytes())
+    }
+
+    /// Creates a file in the cache using a callback returning errors of a specific type.
+    ///
+    /// Unlike [`get`](Self::get), the callback errors are not boxed: the creation and the refresh operations of the returned file
+    /// return [`Error<E>`](enum@Error), so the callback errors can be matched without downcasting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use fcache::{Cache, Error};
+    ///
+    /// #[derive(Debug, thiserror::Error)]
+    /// enum FetchError {
+    ///     #[error("upstream unavailable")]
+    ///     Unavailable,
+    /// }
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    ///
+    /// let result = cache.get_typed("data.txt", |_| Err(FetchError::Unavailable));
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(Error::Callback(FetchError::Unavailable))
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return the same errors as [`get`](Self::get), with the callback errors returned as [`Error::Callback`].
+    pub fn get_typed<'a, E: error::Error + Send + Sync + 'static>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl Fn(File) -> Result<(), E> + Send + Sync + 'static,
+    ) -> Result<TypedCacheFile<'a, E>, Error<E>> {
+        let callback = move |file| callback(file).map_err(Into::into);
+        self.get(path, callback)
+            .map(TypedCacheFile::new)
+            .map_err(Error::downcast_callback)
+    }
+
+    /// Creates a file in the cache using an arbitrary string as the key, e.g. a title or a URL.
+    ///
+    /// The key is turned into a safe file name with [`sanitize_key`] using the default [`SanitizeOptions`],
+    /// and the original key is recorded along with the file (see [`CacheFile::original_key`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    /// let cache_file = cache.get_sanitized("https://example.com/feed?page=2", |mut file| {
+    ///     file.write_all(b"feed")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// assert!(
+    ///     cache_file
+    ///         .path()
+    ///         .ends_with("https___example.com_feed_page=2")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`get`](Self::get), or if the original key cannot be recorded.
+    pub fn get_sanitized<'a>(&'a self, raw_key: &str, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
+        let key = sanitize_key(raw_key, &SanitizeOptions::default());
+        let cache_file = self.get(key, callback)?;
+        cache_file.record_original_key(raw_key)?;
+        Ok(cache_file)
+    }
+
+    /// Creates a file in the cache using a callback for initialization, with a custom maximum file size.
+    ///
+    /// The limit overrides the cache setting (see [`with_max_file_size`](Self::with_max_file_size)) for this file only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance limited to 1 KiB files
+    /// let cache = Cache::new()?.with_max_file_size(1024);
+    ///
+    /// // Allow a larger file for this entry only
+    /// let cache_file = cache.get_with_max_size(
+    ///     "large.txt",
+    ///     |mut file| {
+    ///         file.write_all(&[0; 4096])?;
+    ///         Ok(())
+    ///     },
+    ///     8 * 1024,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`get`](Self::get), or if the file exceeds the given size limit.
+    pub fn get_with_max_size<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+        max_file_size: u64,
+    ) -> Result<CacheFile<'a>> {
+        let Self(inner) = self;
+        inner.get_with_max_size(path, callback, max_file_size)
+    }
+
+    /// Creates a file in the cache that is lazily created when accessed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Get or create a lazy cached file
+    /// let cache_file = cache.get_lazy("lazy_file.txt", |mut file| {
+    ///     // Write data to the file
+    ///     file.write_all(b"Hello, Lazy Cache!")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // File isn't created until opened...
+    /// assert!(!cache_file.path().exists());
+    ///
+    /// // Open the lazy cached file
+    /// let mut file = cache_file.open()?;
+    /// // Read data from the file
+    /// let mut contents = String::new();
+    /// file.read_to_string(&mut contents)?;
+    /// println!("Lazy cached file contents: {}", contents);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file already exists, path traversal is detected outside the cache directory, the file name of the key starts with the reserved `.fcache` prefix, parent directory creation fails, or there are issues with path resolution or filesystem operations.
+    pub fn get_lazy<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+    ) -> Result<CacheLazyFile<'a>> {
+        let Self(inner) = self;
+        inner.get_lazy(path, callback)
+    }
+
+    /// Creates an append-only file in the cache.
+    ///
+    /// The header callback is invoked once when the file is created, and again whenever a fresh file is started after a rotation (see [`AppendFile::with_rotation`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Create an event log with a header
+    /// let append_file = cache.get_appendable("events.log", |mut file| {
+    ///     file.write_all(b"# events\n")?;
+    ///     Ok(())
+    /// })?;
+    /// append_file.append(b"started\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file already exists, the path is invalid, or the header callback returns an error.
+    pub fn get_appendable(
+        &self,
+        path: impl AsRef<Path>,
+        header_callback: impl CallbackFn + 'static,
+    ) -> Result<AppendFile<'_>> {
+        let Self(inner) = self;
+        inner.get_appendable(path, header_callback)
+    }
+
+    /// Creates a file in the cache mirroring an external source file.
+    ///
+    /// Depending on the [`LinkMode`], the source is either copied into the cache or linked to with a symbolic link.
+    /// Linked files are valid until the source is modified; copies are then refreshed with the new content, while links are recreated.
+    /// The path traversal checks apply to the location of the file in the cache, not to the source.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LinkMode;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// # let temp_dir = tempfile::TempDir::new()?;
+    /// # let source = temp_dir.path().join("settings.conf");
+    /// # std::fs::write(&source, b"key = value")?;
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Mirror the external file in the cache
+    /// let cache_file = cache.get_linked("settings.conf", &source, LinkMode::Copy)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the source cannot be resolved, the path is invalid, or the file cannot be created in the cache.
+    pub fn get_linked(
+        &self,
+        path: impl AsRef<Path>,
+        source: impl AsRef<Path>,
+        link_mode: LinkMode,
+    ) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.get_linked(path, source, link_mode)
+    }
+
+    /// Predicts what [`get`](Self::get) would do without touching the filesystem.
+    ///
+    /// The path goes through the same validation as in [`get`](Self::get), but no directories are created and the callback is never invoked.
+    /// Errors that [`get`](Self::get) would return are reported in [`DryRunReport::would_error`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Check what would happen before running an expensive callback
+    /// let report = cache.dry_run_get("report.txt", |mut file| {
+    ///     file.write_all(b"Expensive report")?;
+    ///     Ok(())
+    /// })?;
+    /// if report.would_create {
+    ///     println!("Would create: {}", report.path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the existing file or the cache directory cannot be inspected.
+    pub fn dry_run_get(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<DryRunReport> {
+        let Self(inner) = self;
+        inner.dry_run_get(path, callback)
+    }
+
+    /// Explains what accessing a file in the cache would do, and why.
+    ///
+    /// All the checks deciding whether the file is created, refreshed, or used as it is are run without modifying anything, and reported in a [`CacheExplanation`].
+    /// The file is explained with the settings of the cache; files are locked through their handles, so use [`CacheLazyFile::explain`] for explaining a specific handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Find out why the file is (not) refreshed
+    /// let explanation = cache.explain("data.txt")?;
+    /// println!("{:?} (age: {:?})", explanation.decision, explanation.age);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory, or the metadata of the file cannot be read.
+    pub fn explain(&self, path: impl AsRef<Path>) -> Result<CacheExplanation> {
+        let Self(inner) = self;
+        inner.explain(path)
+    }
+
+    /// Returns the state of a file in the cache, read with a single metadata lookup.
+    ///
+    /// The file is never created or refreshed, and its path is validated like in [`get`](Self::get).
+    /// The file is checked with the settings of the cache; files are locked through their handles, so it is always reported as unlocked.
+    /// Use [`CacheFile::status`] for checking a specific handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::EntryStatus;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// assert_eq!(cache.status("data.txt")?, EntryStatus::Missing);
+    ///
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    /// assert!(matches!(
+    ///     cache.status("data.txt")?,
+    ///     EntryStatus::Present { size: 4, .. }
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory, or the metadata of the file cannot be read.
+    pub fn status(&self, path: impl AsRef<Path>) -> Result<EntryStatus> {
+        let Self(inner) = self;
+        inner.status(path)
+    }
+
+    /// Checks if a file exists in the cache, without creating it.
+    ///
+    /// The path is validated like in [`get_lazy`](Self::get_lazy), but no directories are created, and the file is neither created nor refreshed.
+    /// The validity of the file is not checked (see [`status`](Self::status)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// assert!(!cache.exists("data.txt")?);
+    ///
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// assert!(cache.exists("data.txt")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory.
+    pub fn exists(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let Self(inner) = self;
+        inner.exists(path)
+    }
+
+    /// Lists the files in the cache, sorted by their paths.
+    ///
+    /// Paths are relative to the cache directory, so they are the keys to pass to e.g. [`get`](Self::get) or [`remove`](Self::remove),
+    /// unless the cache rewrites the location of files (see [`with_path_transformer`](Self::with_path_transformer)).
+    /// Housekeeping files such as `.ttl` and `.version` sidecar files, the marker file of the cache, and temporary files of interrupted writes are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::path::PathBuf;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// let _ = cache.get("reports/report.json", |_| Ok(()))?;
+    ///
+    /// // Enumerate the cached files
+    /// assert_eq!(
+    ///     cache.list()?,
+    ///     [
+    ///         PathBuf::from("data.txt"),
+    ///         PathBuf::from("reports/report.json")
+    ///     ]
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be read.
+    pub fn list(&self) -> Result<Vec<PathBuf>> {
+        let Self(inner) = self;
+        inner.list()
+    }
+
+    /// Returns an iterator over the files in the cache, sorted by their paths.
+    ///
+    /// Files are listed like in [`list`](Self::list), with their absolute paths, sizes, and modification times, described by [`EntryInfo`].
+    /// Symbolic links are described themselves rather than followed, so nothing outside the cache directory is read,
+    /// and files removed concurrently, e.g. by another process, are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("reports/report.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Find the largest file
+    /// let largest = cache.entries()?.max_by_key(|entry| entry.size);
+    /// assert!(largest.is_some_and(|entry| entry.key.ends_with("report.json")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn entries(&self) -> Result<impl Iterator<Item = EntryInfo>> {
+        let Self(inner) = self;
+        inner.entries()
+    }
+
+    /// Removes a file from the cache, without needing a handle for it.
+    ///
+    /// The path is validated like in [`get_lazy`](Self::get_lazy), and the file is removed like in [`CacheFile::remove`],
+    /// along with its empty parent directories. Removing a missing file succeeds without doing anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("reports/report.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Evict the report
+    /// cache.remove("reports/report.json")?;
+    /// assert!(!cache.exists("reports/report.json")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory, or an error in the same cases as [`CacheFile::remove`].
+    pub fn remove(&self, path: impl AsRef<Path>) -> Result<()> {
+        let Self(inner) = self;
+        inner.remove(path)
+    }
+
+    /// Removes every file from the cache, leaving the cache directory itself in place, and returns the number of removed files.
+    ///
+    /// Files are removed like in [`CacheFile::remove`], along with their sidecar files and the subdirectories holding them,
+    /// and counted like in [`file_count`](Self::file_count). The cache stays usable, and the directory of a temporary cache is kept.
+    /// Nothing is removed while any handle of a file in the cache is locked (see [`CacheFile::lock`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// let _ = cache.get("reports/report.json", |_| Ok(()))?;
+    ///
+    /// // Start over with an empty cache
+    /// assert_eq!(cache.clear()?, 2);
+    /// assert!(!cache.exists("reports/report.json")?);
+    /// assert!(cache.path().exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileAlreadyLocked`] if a file in the cache is locked, [`Error::Frozen`] if the cache is frozen,
+    /// [`Error::RemoveFailed`] if a file cannot be removed, e.g. because it is open by another process on Windows, or an error if the cache directory cannot be read.
+    pub fn clear(&self) -> Result<usize> {
+        let Self(inner) = self;
+        inner.clear()
+    }
+
+    /// Creates a file in the cache, or creates a handle for it if it already exists.
+    ///
+    /// Missing files are created like in [`get`](Self::get), and existing files are attached like in [`attach`](Self::attach),
+    /// regardless of the creation mode of the cache, so the callback isn't run until the file expires.
+    /// When the file is created concurrently, e.g. by another thread, only one of the callers runs the callback, and the others attach to the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // The file is created by the first call, and reused by the second one
+    /// for _ in 0..2 {
+    ///     let cache_file = cache.get_or_attach("data.json", |mut file| {
+    ///         file.write_all(b"{}")?;
+    ///         Ok(())
+    ///     })?;
+    ///     assert!(cache_file.is_valid()?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory, or an error in the same cases as [`get`](Self::get) when the file is created.
+    pub fn get_or_attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.get_or_attach(path, callback)
+    }
+
+    /// Reads the content of a file in the cache, creating or refreshing it if needed.
+    ///
+    /// This is a shorthand for [`get_or_attach`](Self::get_or_attach) followed by [`CacheFile::read_bytes`],
+    /// for callers which don't need the handle, like [`fs::read`] for plain files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Read the content in a single call
+    /// let content = cache.read_bytes("data.bin", |mut file| {
+    ///     file.write_all(&[1, 2, 3])?;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(content, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`get_or_attach`](Self::get_or_attach) and [`CacheFile::open`], or if the file cannot be read.
+    pub fn read_bytes(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<Vec<u8>> {
+        self.get_or_attach(path, callback)?.read_bytes()
+    }
+
+    /// Reads the content of a file in the cache as a string, creating or refreshing it if needed.
+    ///
+    /// For more details see [`read_bytes`](Self::read_bytes).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Read the text in a single call
+    /// let content = cache.read_string("greeting.txt", |mut file| {
+    ///     file.write_all(b"Hello, Cache!")?;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(content, "Hello, Cache!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::Utf8`] if the content is not valid UTF-8, or an error in the same cases as [`read_bytes`](Self::read_bytes).
+    pub fn read_string(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<String> {
+        self.get_or_attach(path, callback)?.read_string()
+    }
+
+    /// Creates a handle for a file which already exists in the cache, e.g. created by a previous run of the program.
+    ///
+    /// Unlike [`get`](Self::get), the file must exist, and the callback is only used for future refreshes.
+    /// The path is validated like in [`get_lazy`](Self::get_lazy), and the refresh interval of the cache applies to the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Get another handle for the file
+    /// let cache_file = cache.attach("data.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    /// assert!(cache_file.is_valid()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::FileNotFound`] if the file doesn't exist, or an error if the path is invalid or outside the cache directory.
+    pub fn attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.attach(path, callback)
+    }
+
+    /// Checks if a file exists in the cache, without creating it.
+    ///
+    /// This is an alias for [`exists`](Self::exists).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// assert!(!cache.contains("data.txt")?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`exists`](Self::exists).
+    pub fn contains(&self, path: impl AsRef<Path>) -> Result<bool> {
+        self.exists(path)
+    }
+
+    /// Backs up the whole cache directory to the destination directory.
+    ///
+    /// The directory structure, sidecar files, permissions, and modification times are preserved, so the validity of the files is retained.
+    /// Staging files (`.fcache-tmp`), temporary files (`.tmp`), and journals (`.journal`) are excluded, and symbolic links are backed up as copies of their targets.
+    /// The backup can be used as a cache again with [`restore_from_backup`](Self::restore_from_backup).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Back up the cache
+    /// let backup_dir = tempfile::tempdir()?;
+    /// let report = cache.backup(backup_dir.path())?;
+    /// println!("Backed up {} files", report.files_copied);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidPath`] if the destination is within the cache directory, or an error if the files cannot be copied.
+    pub fn backup(&self, dest: impl AsRef<Path>) -> Result<BackupReport> {
+        let Self(inner) = self;
+        inner.backup(dest)
+    }
+
+    /// Closes the cache, waiting for work in progress and cleaning up after interrupted work.
+    ///
+    /// This is the same as [`close_with_timeout`](Self::close_with_timeout) with the [`DEFAULT_CLOSE_TIMEOUT`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // Close the cache once done
+    /// let report = cache.close()?;
+    /// assert_eq!(report.temp_files_removed, 0);
+    /// assert_eq!(report.operations_abandoned, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`close_with_timeout`](Self::close_with_timeout).
+    pub fn close(self) -> Result<CloseReport> {
+        self.close_with_timeout(DEFAULT_CLOSE_TIMEOUT)
+    }
+
+    /// Closes the cache, waiting at most the timeout for work in progress and cleaning up after interrupted work.
+    ///
+    /// As the cache is consumed, only owned handles (see [`CacheFile::into_owned`]) can still be creating, refreshing, or replacing files, e.g. on other threads.
+    /// Their operations are waited for until the timeout elapses, after which the remaining ones are abandoned:
+    /// the cache is cancelled (see [`cancellation_token`](Self::cancellation_token)), so their callbacks can stop early.
+    /// The numbers of completed and abandoned operations are reported in a [`CloseReport`].
+    ///
+    /// Streamed writes (see [`put_from_reader`](Self::put_from_reader) and [`put_content_addressed`](Self::put_content_addressed)) are staged in temporary files prefixed with `.fcache-tmp`,
+    /// which are left behind when a process is interrupted mid-write. Staging files which weren't modified for an hour are removed, and their number reported as well,
+    /// while newer ones are kept, as they may be in use by another process sharing the cache directory.
+    /// The cache has no background threads or pending index writes, so there is nothing else to stop or flush.
+    /// Dropping the cache instead never waits and never touches the cache directory, except for removing temporary caches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let cache_file = cache.get_lazy("data.txt", |_| Ok(()))?.into_owned();
+    ///
+    /// // Create the file on another thread
+    /// let handle = std::thread::spawn(move || cache_file.create().map(|_| ()));
+    ///
+    /// // Close the cache, waiting for the file at most a second
+    /// let report = cache.close_with_timeout(Duration::from_secs(1))?;
+    /// assert_eq!(report.operations_abandoned, 0);
+    /// # handle.join().expect("thread should not panic")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be read, or a staging file cannot be removed.
+    pub fn close_with_timeout(self, timeout: Duration) -> Result<CloseReport> {
+        let Self(inner) = self;
+        inner.close(timeout)
+    }
+
+    /// Imports the files of a ZIP archive into the cache, returning the number of imported files.
+    ///
+    /// The relative paths of the entries are preserved and validated like keys of [`get`](Self::get), and files which already exist in the cache are skipped.
+    /// Imported files are fresh, as if they were just created, and their sidecar files (e.g. `.ttl`) are imported along with them but not counted.
+    /// This allows seeding a cache with an archive created by [`export_zip`](Self::export_zip), e.g. in CI or offline environments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Export the files of a cache
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    /// let archive_dir = tempfile::tempdir()?;
+    /// let archive_path = archive_dir.path().join("cache.zip");
+    /// cache.export_zip(&archive_path, true)?;
+    ///
+    /// // Seed another cache with the archive
+    /// let seeded_cache = Cache::new()?;
+    /// assert_eq!(seeded_cache.import_zip(&archive_path)?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidPath`] if an entry is not a valid key, [`Error::Zip`] if the archive is malformed, or an error if the cache is frozen or the files cannot be written.
+    #[cfg(feature = "zip")]
+    pub fn import_zip(&self, zip_path: impl AsRef<Path>) -> Result<usize> {
+        let Self(inner) = self;
+        inner.import_zip(zip_path)
+    }
+
+    /// Exports the files of the cache into a ZIP archive, returning the number of exported files.
+    ///
+    /// Paths relative to the cache directory are used as the names of the entries, and sidecar files are exported along with their files but not counted.
+    /// Unless `include_expired` is set, only valid files are exported. Staging files (`.fcache-tmp`), temporary files (`.tmp`), and journals (`.journal`) are always excluded.
+    /// The archive can be imported with [`import_zip`](Self::import_zip).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Export the valid files
+    /// let archive_dir = tempfile::tempdir()?;
+    /// let exported = cache.export_zip(archive_dir.path().join("cache.zip"), false)?;
+    /// println!("Exported {exported} files");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidPath`] if the destination is within the cache directory or a file name cannot be stored in the archive, or an error if the files cannot be read or the archive cannot be written.
+    #[cfg(feature = "zip")]
+    pub fn export_zip(&self, dest: impl AsRef<Path>, include_expired: bool) -> Result<usize> {
+        let Self(inner) = self;
+        inner.export_zip(dest, include_expired)
+    }
+
+    /// Stores content in the cache under the SHA-256 digest of the content.
+    ///
+    /// The data is streamed into the cache while its digest is computed, and stored as `objects/<first 2 digits>/<remaining digits>`.
+    /// If an object with the same content already exists, it is reused without being rewritten, so identical content is stored only once.
+    ///
+    /// Objects are immutable, never expire, and cannot be refreshed (see [`Error::ImmutableFile`]).
+    /// There is no reference counting: removing an object through any handle removes it for every holder of the digest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Store the content under its digest
+    /// let (digest, cache_file) = cache.put_content_addressed(&b"Hello, world!"[..])?;
+    /// println!("Stored {} at {}", digest, cache_file.path().display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if reading the data fails, or the object cannot be written to the cache directory.
+    pub fn put_content_addressed(&self, data: impl Read) -> Result<(String, CacheFile<'_>)> {
+        let Self(inner) = self;
+        inner.put_content_addressed(data)
+    }
+
+    /// Creates a file in the cache with the content streamed from the reader.
+    ///
+    /// The content is streamed through a bounded buffer, so it is never loaded into memory as a whole,
+    /// into a temporary file which then atomically becomes the file (see [`CacheFile::replace_from_reader`]).
+    /// A failed read leaves no file behind, and the number of written bytes is the size of the file.
+    ///
+    /// An existing file is replaced, unless the creation mode is [`CreationMode::CreateNew`].
+    /// As the reader cannot be read again, the file never expires, and its callback fails with [`Error::Callback`],
+    /// so explicitly refreshing it fails too; use [`CacheFile::replace_from_reader`] to replace its content instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Stream the content into the cache
+    /// let reader = std::io::repeat(b'a').take(1024);
+    /// let cache_file = cache.put_from_reader("data.bin", reader)?;
+    /// assert_eq!(std::fs::metadata(cache_file.path())?.len(), 1024);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid or outside the cache directory, the file already exists and the creation mode is [`CreationMode::CreateNew`],
+    /// or an error in the same cases as [`CacheFile::replace_from_reader`].
+    pub fn put_from_reader(&self, path: impl AsRef<Path>, reader: impl Read) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.put_from_reader(path, reader)
+    }
+
+    /// Opens a content-addressed object previously stored with [`put_content_addressed`](Self::put_content_addressed).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Store the content and retrieve it by its digest
+    /// let (digest, _) = cache.put_content_addressed(&b"Hello, world!"[..])?;
+    /// let cache_file = cache.open_object(&digest)?;
+    ///
+    /// let mut content = String::new();
+    /// cache_file.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the digest is not a valid SHA-256 hex digest, or the object does not exist.
+    pub fn open_object(&self, digest: &str) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.open_object(digest)
+    }
+
+    /// Reassembles a file from the chunks created by [`CacheFile::split_into_chunks`].
+    ///
+    /// The chunks `<prefix>.part0`, `<prefix>.part1`, and so on, up to the first missing one, are concatenated into the file `prefix`, replacing its content if it exists.
+    /// The chunks are kept, and the file is reassembled from them again whenever it is refreshed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let cache_file = cache.get("data.bin", |mut file| {
+    ///     file.write_all(b"Hello, world!")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Split the file and join the chunks back
+    /// let _ = cache_file.split_into_chunks(4)?;
+    /// let cache_file = cache.reassemble_chunks("data.bin")?;
+    ///
+    /// let mut content = String::new();
+    /// cache_file.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid, the first chunk does not exist, or the chunks cannot be read or the file written.
+    pub fn reassemble_chunks(&self, prefix: &str) -> Result<CacheFile<'_>> {
+        let Self(inner) = self;
+        inner.reassemble_chunks(prefix)
+    }
+
+    /// Returns the content of a file in the cache, inserting the content generated by `f` if the file is missing or invalid.
+    ///
+    /// This is the cache counterpart of [`Entry::or_insert_with`](std::collections::hash_map::Entry::or_insert_with), for content generated in memory.
+    /// If another process creates the file concurrently, its content is returned instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    ///
+    /// // Generate the content only when needed
+    /// let content = cache.open_or_insert_with("greeting.txt", || b"Hello, world!".to_vec())?;
+    /// assert_eq!(content, b"Hello, world!");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is invalid, or the file cannot be read or written.
+    pub fn open_or_insert_with(&self, path: impl AsRef<Path>, f: impl FnOnce() -> Vec<u8>) -> Result<Vec<u8>> {
+        let Self(inner) = self;
+        inner.open_or_insert_with(path, f)
+    }
+
+    /// Groups the files in the cache by their validity, without modifying anything.
+    ///
+    /// Returns the paths of valid files first, followed by the paths of invalid files.
+    /// Validity is determined the same way as by [`CacheFile::is_valid`], using the cache settings and persisted refresh intervals.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Split the files by validity
+    /// let (valid_paths, invalid_paths) = cache.group_by_validity()?;
+    /// println!(
+    ///     "{} valid, {} invalid",
+    ///     valid_paths.len(),
+    ///     invalid_paths.len()
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn group_by_validity(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        let Self(inner) = self;
+        inner.group_by_validity()
+    }
+
+    /// Returns the `n` most recently modified files in the cache, newest first.
+    ///
+    /// Paths are relative to the cache directory, and housekeeping files such as `.ttl` and `.version` sidecar files are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Print the most recently modified files
+    /// for (path, modified) in cache.most_recent(5)? {
+    ///     println!("{}: {modified:?}", path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn most_recent(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        let Self(inner) = self;
+        inner.most_recent(n)
+    }
+
+    /// Returns the `n` least recently modified files in the cache, oldest first.
+    ///
+    /// Paths are relative to the cache directory, and housekeeping files such as `.ttl` and `.version` sidecar files are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Print the stalest file
+    /// if let Some((path, modified)) = cache.oldest(1)?.first() {
+    ///     println!("{}: {modified:?}", path.display());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn oldest(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        let Self(inner) = self;
+        inner.oldest(n)
+    }
+
+    /// Enforces the policy limiting the files in the cache (see [`with_entry_limit_policy`](Self::with_entry_limit_policy)).
+    ///
+    /// Returns the number of evicted files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance keeping at most 10 files
+    /// let cache = Cache::new()?.with_entry_limit_policy(LimitPolicy::new().max_entries(10));
+    ///
+    /// // Evict the files exceeding the limits
+    /// let evicted = cache.enforce_limit_policy()?;
+    /// assert_eq!(evicted, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be read, or a file cannot be removed.
+    pub fn enforce_limit_policy(&self) -> Result<usize> {
+        let Self(inner) = self;
+        inner.enforce_limit_policy()
+    }
+
+    /// Removes the files in the cache which nobody accessed for at least the given time, returning the number of removed files.
+    ///
+    /// The last access of a file is recorded by handles with an idle timeout (see [`CacheFile::with_idle_timeout`]).
+    /// Files without a recorded access are considered last accessed when they were last modified.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// let cache = Cache::new()?;
+    ///
+    /// // Remove the files unused for a day
+    /// let pruned = cache.prune_idle(Duration::from_secs(24 * 60 * 60))?;
+    /// assert_eq!(pruned, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, the cache directory or the metadata of its files cannot be read, or a file cannot be removed.
+    pub fn prune_idle(&self, idle_timeout: Duration) -> Result<usize> {
+        let Self(inner) = self;
+        inner.prune_idle(idle_timeout)
+    }
+
+    /// Removes the files in the cache which expired, returning the number of removed files.
+    ///
+    /// Files are checked like in [`CacheFile::is_valid`] with the refresh interval of the cache, or the interval persisted for the file (see [`CacheFile::set_refresh_interval_persist`]),
+    /// and removed like in [`CacheFile::remove`], along with their empty parent directories.
+    /// Files locked by a handle (see [`CacheFile::lock`]) are skipped, as are temporary files of writes in progress.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance refreshing files every hour
+    /// let cache = Cache::new()?.with_refresh_interval(Duration::from_secs(60 * 60));
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    ///
+    /// // Remove the expired files, of which there are none yet
+    /// let purged = cache.purge_expired()?;
+    /// assert_eq!(purged, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache is frozen, the cache directory or the metadata of its files cannot be read, or a file cannot be removed.
+    pub fn purge_expired(&self) -> Result<usize> {
+        let Self(inner) = self;
+        inner.purge_expired()
+    }
+
+    /// Returns the current usage of the cache as the total size of files in bytes and the number of files.
+    ///
+    /// This is the usage checked against the storage quota (see [`with_storage_quota`](Self::with_storage_quota)).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check the usage of the cache
+    /// let (bytes, count) = cache.quota_usage()?;
+    /// assert_eq!((bytes, count), (4, 1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn quota_usage(&self) -> Result<(u64, usize)> {
+        let Self(inner) = self;
+        inner.quota_usage()
+    }
+
+    /// Returns the number of files in the cache.
+    ///
+    /// Files are counted like in [`list`](Self::list), so housekeeping files such as `.ttl` sidecar files are skipped.
+    /// Files and directories removed concurrently, e.g. by another process, are skipped instead of failing the count.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// let _ = cache.get("reports/report.json", |_| Ok(()))?;
+    ///
+    /// // Count the cached files
+    /// assert_eq!(cache.file_count()?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be read.
+    pub fn file_count(&self) -> Result<usize> {
+        let Self(inner) = self;
+        inner.file_count()
+    }
+
+    /// Checks if the cache holds no files.
+    ///
+    /// Files are considered like in [`list`](Self::list), so a cache with only empty subdirectories or housekeeping files is empty.
+    /// The cache directory is walked only until the first file is found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// assert!(cache.is_empty()?);
+    ///
+    /// let _ = cache.get("data.txt", |_| Ok(()))?;
+    /// assert!(!cache.is_empty()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be read.
+    pub fn is_empty(&self) -> Result<bool> {
+        let Self(inner) = self;
+        inner.is_empty()
+    }
+
+    /// Returns the total size of the files in the cache in bytes.
+    ///
+    /// Files are counted like in [`file_count`](Self::file_count), and files removed concurrently don't add to the size.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("data.txt", |mut file| {
+    ///     file.write_all(b"data")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Check how much space the cache takes
+    /// assert_eq!(cache.total_size()?, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn total_size(&self) -> Result<u64> {
+        let Self(inner) = self;
+        inner.total_size()
+    }
+
+    /// Returns statistics of the files in the cache, such as their total size and number.
+    ///
+    /// Files are considered like in [`entries`](Self::entries), and files and directories removed concurrently, e.g. by another process, are skipped.
+    /// The statistics are gathered by walking the cache directory on every call.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("reports/report.json", |mut file| {
+    ///     file.write_all(b"{}")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Plan the capacity of the cache
+    /// let stats = cache.stats()?;
+    /// assert_eq!(
+    ///     (stats.total_bytes, stats.file_count, stats.dir_count),
+    ///     (2, 1, 1)
+    /// );
+    /// assert_eq!(stats.oldest_modified, stats.newest_modified);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory or the metadata of its files cannot be read.
+    pub fn stats(&self) -> Result<CacheStats> {
+        let Self(inner) = self;
+        inner.stats()
+    }
+
+    /// Creates a cache within a subdirectory of the cache directory.
+    ///
+    /// The new cache inherits the refresh interval, the size limit and version token of files, the observer, and the policies validating and creating files,
+    /// but not the limit policy or the storage quota, so each subdirectory can be given its own budget.
+    /// The files of the subdirectory are still files of this cache, e.g. they are counted by its [`quota_usage`](Self::quota_usage).
+    /// Subdirectories can be nested, and the paths of their files are validated relative to the subdirectory,
+    /// so e.g. [`clear`](Self::clear) on a subdirectory never touches the rest of the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    /// use fcache::{EvictionPolicy, StorageQuota};
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance shared by subsystems
+    /// let cache = Cache::new()?;
+    ///
+    /// // Give the thumbnails a budget of 1 MiB
+    /// let quota = StorageQuota {
+    ///     max_bytes: 1024 * 1024,
+    ///     max_files: 100,
+    ///     eviction: EvictionPolicy::LargestFirst,
+    /// };
+    /// let thumbnails = cache.subdirectory("thumbnails")?.with_storage_quota(quota);
+    /// assert_eq!(thumbnails.path(), cache.path().join("thumbnails"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the path is not a relative path of plain directory names, or the subdirectory cannot be created.
+    pub fn subdirectory(&self, path: impl AsRef<Path>) -> Result<Self> {
+        let Self(inner) = self;
+        inner.subdirectory(path).map(Into::into)
+    }
+
+    /// Creates a cache scoped to the given prefix within the cache directory.
+    ///
+    /// This is equivalent to [`subdirectory`](Self::subdirectory): the scoped cache is rooted at the prefix, so keys need no prefix of their own,
+    /// scopes can be nested, and path traversal is validated relative to the scope, rejecting keys which escape it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance shared by projects
+    /// let cache = Cache::new()?;
+    ///
+    /// // Cache the artifacts of a project without prefixing every key
+    /// let project = cache.scope("project")?;
+    /// let artifact = project.get("artifact.bin", |_| Ok(()))?;
+    /// assert_eq!(artifact.path(), cache.path().join("project/artifact.bin"));
+    ///
+    /// // Keys escaping the scope are rejected
+    /// assert!(project.get("../other/artifact.bin", |_| Ok(())).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`subdirectory`](Self::subdirectory).
+    pub fn scope(&self, prefix: impl AsRef<Path>) -> Result<Self> {
+        self.subdirectory(prefix)
+    }
+
+    /// Creates a cache in a sibling directory with the given name, next to the cache directory.
+    ///
+    /// The new cache inherits the refresh interval, and is always a directory cache, so a sibling of a temporary cache is not removed when dropped.
+    /// This allows managing a family of caches, e.g. one per subsystem, under the same parent directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new cache instance for the images
+    /// let parent_dir = tempfile::tempdir()?;
+    /// let images = Cache::with_dir(parent_dir.path().join("images"))?;
+    ///
+    /// // Create a cache for the thumbnails next to it
+    /// let thumbnails = images.new_sibling("thumbnails")?;
+    /// assert_eq!(
+    ///     thumbnails.path(),
+    ///     images.path().with_file_name("thumbnails")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`Error::InvalidPath`] if the name is not a plain directory name, [`Error::NoParentDirectory`] if the cache directory has no parent, or [`Error::IO`] if the sibling directory cannot be created.
+    pub fn new_sibling(&self, name: &str) -> Result<Self> {
+        let Self(inner) = self;
+        inner.new_sibling(name).map(Into::into)
+    }
+
+    /// Moves the cache directory to the target directory, returning a cache there which is never removed when dropped.
+    ///
+    /// This allows keeping the files of a temporary cache (see [`new`](Self::new)) once they turn out to be worth it.
+    /// The directory is renamed when possible, and otherwise, e.g. when the target is on another file system,
+    /// copied along with the modification times of the files, after which the original directory is removed.
+    /// The target must not exist or be an empty directory. The new cache keeps the settings of this one,
+    /// while owned file handles (see [`CacheFile::into_owned`]) keep pointing at the previous location.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// # let parent_dir = tempfile::tempdir()?;
+    /// # let target = parent_dir.path().join("results");
+    /// // Create a new temporary cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("result.txt", |mut file| {
+    ///     file.write_all(b"worth keeping")?;
+    ///     Ok(())
+    /// })?;
+    ///
+    /// // Keep the results in the target directory
+    /// let cache = cache.persist(&target)?;
+    /// assert_eq!(std::fs::read(target.join("result.txt"))?, b"worth keeping");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return a [`PersistError`] if the target is not empty, or the directory can be neither renamed nor copied.
+    /// The error gives the cache back with its files left in place, so a temporary cache isn't removed until it is dropped.
+    pub fn persist(self, target: impl AsRef<Path>) -> Result<Self, PersistError> {
+        let Self(inner) = self;
+        inner
+            .persist(target.as_ref())
+            .map(Into::into)
+            .map_err(|(error, inner)| {
+                let cache = Box::new((*inner).into());
+                PersistError { error, cache }
+            })
+    }
+
+    /// Consumes the cache, returning the path of the cache directory, which is kept instead of being removed.
+    ///
+    /// This allows keeping the files of a temporary cache (see [`new`](Self::new)) or an ephemeral one (see [`with_dir_ephemeral`](Self::with_dir_ephemeral)) in place.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new temporary cache instance
+    /// let cache = Cache::new()?;
+    /// let _ = cache.get("result.txt", |_| Ok(()))?;
+    ///
+    /// // Keep the temporary directory
+    /// let path = cache.into_path();
+    /// assert!(path.join("result.txt").exists());
+    /// # std::fs::remove_dir_all(path)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_path(self) -> PathBuf {
+        let Self(inner) = self;
+        inner.into_path()
+    }
+}
+
+impl From<InnerCache> for Cache {
+    fn from(inner: InnerCache) -> Self {
+        Self(inner)
+    }
+}
+
+/// Represents the inner cache implementation, either directory-based or temporary.
+#[derive(Debug)]
+enum InnerCache {
+    /// Directory cache implementation
+    Dir(InnerDirCache),
+    /// Temporary cache implementation
+    Temp(InnerTempCache),
+}
+
+impl InnerCache {
+    /// Creates a new cache instance within a specified directory.
+    fn dir(dir: impl AsRef<Path>) -> Result<Self> {
+        InnerDirCache::new(dir).map(Self::Dir)
+    }
+
+    /// Creates a new cache instance within a specified directory, checking the directory against a policy.
+    fn dir_checked(dir: impl AsRef<Path>, dir_policy: DirPolicy) -> Result<Self> {
+        let dir_cache = InnerDirCache::new(dir)?;
+        dir_policy.check(dir_cache.path())?;
+        Ok(Self::Dir(dir_cache))
+    }
+
+    /// Creates a new cache instance within a specified directory, which is removed when the cache is dropped.
+    fn dir_ephemeral(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir_cache = InnerDirCache::new(dir)?;
+        // Never take over a directory with content which isn't ours to remove
+        DirPolicy::RequireEmpty.check(dir_cache.path())?;
+        Ok(Self::Dir(dir_cache.with_remove_on_drop()))
+    }
+
+    /// Opens a cache instance within an existing directory.
+    fn open_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            let path = dir.to_path_buf();
+            let error = Error::CacheDirMissing { path };
+            return Err(error);
+        }
+        InnerDirCache::new(dir).map(Self::Dir)
+    }
+
+    /// Opens a frozen cache instance within an existing directory, without probing it for writability.
+    fn open_dir_read_only(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        if !dir.exists() {
+            let path = dir.to_path_buf();
+            let error = Error::CacheDirMissing { path };
+            return Err(error);
+        }
+        let dir_cache = InnerDirCache::new_unchecked(dir)?.with_write_check(false)?;
+        dir_cache.freeze();
+        Ok(Self::Dir(dir_cache))
+    }
+
+    /// Creates a new cache instance within a temporary directory created with the specified options.
+    fn temp_with(options: TempDirOptions) -> Result<Self> {
+        InnerTempCache::with_options(&options).map(Self::Temp)
+    }
+
+    /// Sets the refresh interval for the cache.
+    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_refresh_interval(refresh_interval).into(),
+            Self::Temp(temp_cache) => temp_cache.with_refresh_interval(refresh_interval).into(),
+        }
+    }
+
+    /// Sets the refresh interval to the default value.
+    fn with_default_refresh_interval(self) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_default_refresh_interval().into(),
+            Self::Temp(temp_cache) => temp_cache.with_default_refresh_interval().into(),
+        }
+    }
+
+    /// Disables automatic refreshing of files in the cache.
+    fn without_refresh(self) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.without_refresh().into(),
+            Self::Temp(temp_cache) => temp_cache.without_refresh().into(),
+        }
+    }
+
+    /// Sets whether empty files in the cache are considered invalid.
+    fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_empty_is_invalid(empty_is_invalid).into(),
+            Self::Temp(temp_cache) => temp_cache.with_empty_is_invalid(empty_is_invalid).into(),
+        }
+    }
+
+    /// Sets the maximum size of files in the cache.
+    fn with_max_file_size(self, max_file_size: u64) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_max_file_size(max_file_size).into(),
+            Self::Temp(temp_cache) => temp_cache.with_max_file_size(max_file_size).into(),
+        }
+    }
+
+    /// Sets a transformer rewriting the location of files in the cache.
+    fn with_path_transformer(self, path_transformer: impl PathTransformerFn + 'static) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_path_transformer(path_transformer).into(),
+            Self::Temp(temp_cache) => temp_cache.with_path_transformer(path_transformer).into(),
+        }
+    }
+
+    /// Sets an observer receiving the events of cache operations.
+    fn with_observer(self, observer: impl ObserverFn + 'static) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_observer(observer).into(),
+            Self::Temp(temp_cache) => temp_cache.with_observer(observer).into(),
+        }
+    }
+
+    /// Sets a fault injector failing the operations of the cache on demand.
+    #[cfg(feature = "testing")]
+    fn with_fault_injector(self, fault_injector: impl FaultInjectorFn + 'static) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_fault_injector(fault_injector).into(),
+            Self::Temp(temp_cache) => temp_cache.with_fault_injector(fault_injector).into(),
+        }
+    }
+
+    /// Sets the clock used by the cache to determine the current time.
+    fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_clock(clock).into(),
+            Self::Temp(temp_cache) => temp_cache.with_clock(clock).into(),
+        }
+    }
+
+    /// Sets whether the cache directory is recreated when it disappears.
+    fn with_auto_recreate_root(self, auto_recreate_root: bool) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_auto_recreate_root(auto_recreate_root).into(),
+            Self::Temp(temp_cache) => temp_cache.with_auto_recreate_root(auto_recreate_root).into(),
+        }
+    }
+
+    /// Sets whether the cache directory is probed for writability.
+    fn with_write_check(self, write_check: bool) -> Result<Self> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_write_check(write_check).map(Self::Dir),
+            Self::Temp(temp_cache) => temp_cache.with_write_check(write_check).map(Self::Temp),
+        }
+    }
+
+    /// Sets whether the cache uses restrictive permissions.
+    fn with_secure_permissions(self, secure_permissions: bool) -> Result<Self> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_secure_permissions(secure_permissions).map(Self::Dir),
+            Self::Temp(temp_cache) => temp_cache.with_secure_permissions(secure_permissions).map(Self::Temp),
+        }
+    }
+
+    /// Sets whether the cache is flat.
+    fn with_flat(self, flat: bool) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_flat(flat).into(),
+            Self::Temp(temp_cache) => temp_cache.with_flat(flat).into(),
+        }
+    }
+
+    /// Sets the policy limiting the files in the cache.
+    fn with_entry_limit_policy(self, limit_policy: LimitPolicy) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_entry_limit_policy(limit_policy).into(),
+            Self::Temp(temp_cache) => temp_cache.with_entry_limit_policy(limit_policy).into(),
+        }
+    }
+
+    /// Sets the storage quota of the cache.
+    fn with_storage_quota(self, storage_quota: StorageQuota) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_storage_quota(storage_quota).into(),
+            Self::Temp(temp_cache) => temp_cache.with_storage_quota(storage_quota).into(),
+        }
+    }
+
+    /// Sets the maximum number of files in the cache.
+    fn with_max_entries(self, max_entries: usize) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_max_entries(max_entries).into(),
+            Self::Temp(temp_cache) => temp_cache.with_max_entries(max_entries).into(),
+        }
+    }
+
+    /// Sets the policy validating the paths of files requested from the cache.
+    fn with_key_policy(self, key_policy: KeyPolicy) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_key_policy(key_policy).into(),
+            Self::Temp(temp_cache) => temp_cache.with_key_policy(key_policy).into(),
+        }
+    }
+
+    /// Sets how files which already exist in the cache are treated.
+    fn with_creation_mode(self, creation_mode: CreationMode) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_creation_mode(creation_mode).into(),
+            Self::Temp(temp_cache) => temp_cache.with_creation_mode(creation_mode).into(),
+        }
+    }
+
+    /// Sets how failures of callbacks are handled.
+    fn with_error_recovery(self, error_recovery: ErrorRecovery) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_error_recovery(error_recovery).into(),
+            Self::Temp(temp_cache) => temp_cache.with_error_recovery(error_recovery).into(),
+        }
+    }
+
+    /// Sets whether the files of the cache are removed when the cache is dropped.
+    fn with_cleanup_on_drop(self, cleanup_on_drop: bool) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_cleanup_on_drop(cleanup_on_drop).into(),
+            Self::Temp(temp_cache) => temp_cache.with_cleanup_on_drop(cleanup_on_drop).into(),
+        }
+    }
+
+    /// Sets whether the whole content of the cache directory is removed when the cache is dropped.
+    fn with_cleanup_all_on_drop(self, cleanup_all_on_drop: bool) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_cleanup_all_on_drop(cleanup_all_on_drop).into(),
+            Self::Temp(temp_cache) => temp_cache.with_cleanup_all_on_drop(cleanup_all_on_drop).into(),
+        }
+    }
+
+    /// Sets the version token of files.
+    fn with_version(self, version: String) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_version(version).into(),
+            Self::Temp(temp_cache) => temp_cache.with_version(version).into(),
+        }
+    }
+
+    /// Sets how paths of files are resolved when checking that they are within the cache directory.
+    fn with_path_canonicalization(self, canonicalization_mode: CanonicalizationMode) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_path_canonicalization(canonicalization_mode).into(),
+            Self::Temp(temp_cache) => temp_cache.with_path_canonicalization(canonicalization_mode).into(),
+        }
+    }
+
+    /// Sets the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn with_fd_pool_size(self, fd_pool_size: usize) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_fd_pool_size(fd_pool_size).into(),
+            Self::Temp(temp_cache) => temp_cache.with_fd_pool_size(fd_pool_size).into(),
+        }
+    }
+
+    /// Enables an in-memory layer keeping up to `max_bytes` bytes of small files.
+    fn with_memory_cache(self, max_bytes: usize) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_memory_cache(max_bytes).into(),
+            Self::Temp(temp_cache) => temp_cache.with_memory_cache(max_bytes).into(),
+        }
+    }
+
+    /// Sets the maximum size of a single file kept in memory by the in-memory layer.
+    fn with_memory_cache_max_entry_size(self, max_entry_size: usize) -> Self {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.with_memory_cache_max_entry_size(max_entry_size).into(),
+            Self::Temp(temp_cache) => temp_cache.with_memory_cache_max_entry_size(max_entry_size).into(),
+        }
+    }
+
+    /// Returns the path of the cache directory.
+    fn path(&self) -> &Path {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.path(),
+            Self::Temp(temp_cache) => temp_cache.path(),
+        }
+    }
+
+    /// Returns the absolute path of the file with the given key.
+    fn path_for(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.path_for(path),
+            Self::Temp(temp_cache) => temp_cache.path_for(path),
+        }
+    }
+
+    /// Returns the refresh interval of the cache.
+    fn refresh_interval(&self) -> Option<Duration> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.refresh_interval(),
+            Self::Temp(temp_cache) => temp_cache.refresh_interval(),
+        }
+    }
+
+    /// Returns whether empty files in the cache are considered invalid.
+    fn empty_is_invalid(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.empty_is_invalid(),
+            Self::Temp(temp_cache) => temp_cache.empty_is_invalid(),
+        }
+    }
+
+    /// Returns the maximum size of files in the cache.
+    fn max_file_size(&self) -> Option<u64> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.max_file_size(),
+            Self::Temp(temp_cache) => temp_cache.max_file_size(),
+        }
+    }
+
+    /// Returns whether the cache directory is recreated when it disappears.
+    fn auto_recreate_root(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.auto_recreate_root(),
+            Self::Temp(temp_cache) => temp_cache.auto_recreate_root(),
+        }
+    }
+
+    /// Returns whether the cache directory is probed for writability.
+    fn write_check(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.write_check(),
+            Self::Temp(temp_cache) => temp_cache.write_check(),
+        }
+    }
+
+    /// Returns whether the cache uses restrictive permissions.
+    fn secure_permissions(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.secure_permissions(),
+            Self::Temp(temp_cache) => temp_cache.secure_permissions(),
+        }
+    }
+
+    /// Returns whether the cache is flat.
+    fn is_flat(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.is_flat(),
+            Self::Temp(temp_cache) => temp_cache.is_flat(),
+        }
+    }
+
+    /// Returns the policy limiting the files in the cache.
+    fn entry_limit_policy(&self) -> Option<LimitPolicy> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.entry_limit_policy(),
+            Self::Temp(temp_cache) => temp_cache.entry_limit_policy(),
+        }
+    }
+
+    /// Returns the storage quota of the cache, if any.
+    fn storage_quota(&self) -> Option<StorageQuota> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.storage_quota(),
+            Self::Temp(temp_cache) => temp_cache.storage_quota(),
+        }
+    }
+
+    /// Returns the maximum number of files in the cache, if any.
+    fn max_entries(&self) -> Option<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.max_entries(),
+            Self::Temp(temp_cache) => temp_cache.max_entries(),
+        }
+    }
+
+    /// Returns the policy validating the paths of files requested from the cache.
+    fn key_policy(&self) -> KeyPolicy {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.key_policy(),
+            Self::Temp(temp_cache) => temp_cache.key_policy(),
+        }
+    }
+
+    /// Returns how files which already exist in the cache are treated.
+    fn creation_mode(&self) -> CreationMode {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.creation_mode(),
+            Self::Temp(temp_cache) => temp_cache.creation_mode(),
+        }
+    }
+
+    /// Returns how failures of callbacks are handled.
+    fn error_recovery(&self) -> &ErrorRecovery {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.error_recovery(),
+            Self::Temp(temp_cache) => temp_cache.error_recovery(),
+        }
+    }
+
+    /// Returns the version token of files.
+    fn version(&self) -> Option<&str> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.version(),
+            Self::Temp(temp_cache) => temp_cache.version(),
+        }
+    }
+
+    /// Returns the generation of files.
+    fn generation(&self) -> u64 {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.generation(),
+            Self::Temp(temp_cache) => temp_cache.generation(),
+        }
+    }
+
+    /// Increments the generation of files.
+    fn bump_generation(&self) -> Result<()> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.bump_generation(),
+            Self::Temp(temp_cache) => temp_cache.bump_generation(),
+        }
+    }
+
+    /// Returns how paths of files are resolved when checking that they are within the cache directory.
+    fn path_canonicalization(&self) -> CanonicalizationMode {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.path_canonicalization(),
+            Self::Temp(temp_cache) => temp_cache.path_canonicalization(),
+        }
+    }
+
+    /// Returns the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn fd_pool_size(&self) -> usize {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.fd_pool_size(),
+            Self::Temp(temp_cache) => temp_cache.fd_pool_size(),
+        }
+    }
+
+    /// Returns the maximum total size of files kept in memory by the in-memory layer.
+    fn memory_cache_max_bytes(&self) -> usize {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.memory_cache_max_bytes(),
+            Self::Temp(temp_cache) => temp_cache.memory_cache_max_bytes(),
+        }
+    }
+
+    /// Returns the maximum size of a single file kept in memory by the in-memory layer.
+    fn memory_cache_max_entry_size(&self) -> usize {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.memory_cache_max_entry_size(),
+            Self::Temp(temp_cache) => temp_cache.memory_cache_max_entry_size(),
+        }
+    }
+
+    /// Returns the token cancelling the creation and refreshing of files in the cache.
+    fn cancellation_token(&self) -> CancelToken {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.cancellation_token(),
+            Self::Temp(temp_cache) => temp_cache.cancellation_token(),
+        }
+    }
+
+    /// Freezes the cache, forbidding all writes.
+    fn freeze(&self) {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.freeze(),
+            Self::Temp(temp_cache) => temp_cache.freeze(),
+        }
+    }
+
+    /// Thaws the cache, allowing writes again.
+    fn thaw(&self) {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.thaw(),
+            Self::Temp(temp_cache) => temp_cache.thaw(),
+        }
+    }
+
+    /// Returns whether the cache is frozen.
+    fn is_frozen(&self) -> bool {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.is_frozen(),
+            Self::Temp(temp_cache) => temp_cache.is_frozen(),
+        }
+    }
+
+    /// Creates a file in the cache using a callback for initialization.
+    fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get(path, callback),
+            Self::Temp(temp_cache) => temp_cache.get(path, callback),
+        }
+    }
+
+    /// Creates a file in the cache with a size limit overriding the cache-wide one.
+    fn get_with_max_size<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+        max_file_size: u64,
+    ) -> Result<CacheFile<'a>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get_with_max_size(path, callback, max_file_size),
+            Self::Temp(temp_cache) => temp_cache.get_with_max_size(path, callback, max_file_size),
+        }
+    }
+
+    /// Creates a file in the cache that is lazily created when accessed.
+    fn get_lazy<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+    ) -> Result<CacheLazyFile<'a>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get_lazy(path, callback),
+            Self::Temp(temp_cache) => temp_cache.get_lazy(path, callback),
+        }
+    }
+
+    /// Creates an append-only file in the cache.
+    fn get_appendable(
+        &self,
+        path: impl AsRef<Path>,
+        header_callback: impl CallbackFn + 'static,
+    ) -> Result<AppendFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get_appendable(path, header_callback),
+            Self::Temp(temp_cache) => temp_cache.get_appendable(path, header_callback),
+        }
+    }
+
+    /// Creates a file in the cache mirroring an external source file.
+    fn get_linked(
+        &self,
+        path: impl AsRef<Path>,
+        source: impl AsRef<Path>,
+        link_mode: LinkMode,
+    ) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get_linked(path, source, link_mode),
+            Self::Temp(temp_cache) => temp_cache.get_linked(path, source, link_mode),
+        }
+    }
+
+    /// Predicts what [`get`](Self::get) would do without touching the filesystem.
+    fn dry_run_get(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<DryRunReport> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.dry_run_get(path, callback),
+            Self::Temp(temp_cache) => temp_cache.dry_run_get(path, callback),
+        }
+    }
+
+    /// Explains what accessing a file in the cache would do.
+    fn explain(&self, path: impl AsRef<Path>) -> Result<CacheExplanation> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.explain(path),
+            Self::Temp(temp_cache) => temp_cache.explain(path),
+        }
+    }
+
+    /// Returns the state of a file in the cache.
+    fn status(&self, path: impl AsRef<Path>) -> Result<EntryStatus> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.status(path),
+            Self::Temp(temp_cache) => temp_cache.status(path),
+        }
+    }
+
+    /// Checks if a file exists in the cache.
+    fn exists(&self, path: impl AsRef<Path>) -> Result<bool> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.exists(path),
+            Self::Temp(temp_cache) => temp_cache.exists(path),
+        }
+    }
+
+    /// Lists the files in the cache.
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.list(),
+            Self::Temp(temp_cache) => temp_cache.list(),
+        }
+    }
+
+    /// Returns the files in the cache along with their metadata.
+    fn entries(&self) -> Result<vec::IntoIter<EntryInfo>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.entries(),
+            Self::Temp(temp_cache) => temp_cache.entries(),
+        }
+    }
+
+    /// Removes a file from the cache.
+    fn remove(&self, path: impl AsRef<Path>) -> Result<()> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.remove(path),
+            Self::Temp(temp_cache) => temp_cache.remove(path),
+        }
+    }
+
+    /// Removes every file from the cache.
+    fn clear(&self) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.clear(),
+            Self::Temp(temp_cache) => temp_cache.clear(),
+        }
+    }
+
+    /// Creates a file in the cache, or creates a handle for it if it already exists.
+    fn get_or_attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.get_or_attach(path, callback),
+            Self::Temp(temp_cache) => temp_cache.get_or_attach(path, callback),
+        }
+    }
+
+    /// Creates a handle for a file which already exists in the cache.
+    fn attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.attach_existing(path, callback),
+            Self::Temp(temp_cache) => temp_cache.attach(path, callback),
+        }
+    }
+
+    /// Backs up the whole cache directory to the destination directory.
+    fn backup(&self, dest: impl AsRef<Path>) -> Result<BackupReport> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.backup(dest),
+            Self::Temp(temp_cache) => temp_cache.backup(dest),
+        }
+    }
+
+    /// Closes the cache, waiting for the operations of file handles in progress and removing the staging files left behind by interrupted writes.
+    fn close(&self, timeout: Duration) -> Result<CloseReport> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.close(timeout),
+            Self::Temp(temp_cache) => temp_cache.close(timeout),
+        }
+    }
+
+    /// Imports the files of a ZIP archive into the cache.
+    #[cfg(feature = "zip")]
+    fn import_zip(&self, zip_path: impl AsRef<Path>) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.import_zip(zip_path),
+            Self::Temp(temp_cache) => temp_cache.import_zip(zip_path),
+        }
+    }
+
+    /// Exports the files of the cache into a ZIP archive.
+    #[cfg(feature = "zip")]
+    fn export_zip(&self, dest: impl AsRef<Path>, include_expired: bool) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.export_zip(dest, include_expired),
+            Self::Temp(temp_cache) => temp_cache.export_zip(dest, include_expired),
+        }
+    }
+
+    /// Stores content in the cache under the SHA-256 digest of the content.
+    fn put_content_addressed(&self, data: impl Read) -> Result<(String, CacheFile<'_>)> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.put_content_addressed(data),
+            Self::Temp(temp_cache) => temp_cache.put_content_addressed(data),
+        }
+    }
+
+    /// Creates a file in the cache with the content streamed from the reader.
+    fn put_from_reader(&self, path: impl AsRef<Path>, reader: impl Read) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.put_from_reader(path, reader),
+            Self::Temp(temp_cache) => temp_cache.put_from_reader(path, reader),
+        }
+    }
+
+    /// Opens a content-addressed object.
+    fn open_object(&self, digest: &str) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.open_object(digest),
+            Self::Temp(temp_cache) => temp_cache.open_object(digest),
+        }
+    }
+
+    /// Reassembles a file from its chunks.
+    fn reassemble_chunks(&self, prefix: &str) -> Result<CacheFile<'_>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.reassemble_chunks(prefix),
+            Self::Temp(temp_cache) => temp_cache.reassemble_chunks(prefix),
+        }
+    }
+
+    /// Returns the content of a file, inserting the generated content if the file is missing or invalid.
+    fn open_or_insert_with(&self, path: impl AsRef<Path>, f: impl FnOnce() -> Vec<u8>) -> Result<Vec<u8>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.open_or_insert_with(path, f),
+            Self::Temp(temp_cache) => temp_cache.open_or_insert_with(path, f),
+        }
+    }
+
+    /// Groups the files in the cache by their validity.
+    fn group_by_validity(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.group_by_validity(),
+            Self::Temp(temp_cache) => temp_cache.group_by_validity(),
+        }
+    }
+
+    /// Returns the most recently modified files in the cache.
+    fn most_recent(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.most_recent(n),
+            Self::Temp(temp_cache) => temp_cache.most_recent(n),
+        }
+    }
+
+    /// Returns the least recently modified files in the cache.
+    fn oldest(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.oldest(n),
+            Self::Temp(temp_cache) => temp_cache.oldest(n),
+        }
+    }
+
+    /// Enforces the policy limiting the files in the cache.
+    fn enforce_limit_policy(&self) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.enforce_limit_policy(None),
+            Self::Temp(temp_cache) => temp_cache.enforce_limit_policy(),
+        }
+    }
+
+    /// Removes the files in the cache which nobody accessed for at least the given time.
+    fn prune_idle(&self, idle_timeout: Duration) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.prune_idle(idle_timeout),
+            Self::Temp(temp_cache) => temp_cache.prune_idle(idle_timeout),
+        }
+    }
+
+    /// Removes the files in the cache which expired.
+    fn purge_expired(&self) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.purge_expired(),
+            Self::Temp(temp_cache) => temp_cache.purge_expired(),
+        }
+    }
+
+    /// Returns the current usage of the cache as the total size of files in bytes and the number of files.
+    fn quota_usage(&self) -> Result<(u64, usize)> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.quota_usage(),
+            Self::Temp(temp_cache) => temp_cache.quota_usage(),
+        }
+    }
+
+    /// Returns the number of files in the cache.
+    fn file_count(&self) -> Result<usize> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.file_count(),
+            Self::Temp(temp_cache) => temp_cache.file_count(),
+        }
+    }
+
+    /// Checks if the cache holds no files.
+    fn is_empty(&self) -> Result<bool> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.is_empty(),
+            Self::Temp(temp_cache) => temp_cache.is_empty(),
+        }
+    }
+
+    /// Returns the total size of the files in the cache in bytes.
+    fn total_size(&self) -> Result<u64> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.total_size(),
+            Self::Temp(temp_cache) => temp_cache.total_size(),
+        }
+    }
+
+    /// Returns statistics of the files in the cache.
+    fn stats(&self) -> Result<CacheStats> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.stats(),
+            Self::Temp(temp_cache) => temp_cache.stats(),
+        }
+    }
+
+    /// Creates a cache within a subdirectory of the cache directory.
+    fn subdirectory(&self, path: impl AsRef<Path>) -> Result<Self> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.subdirectory(path).map(Self::Dir),
+            Self::Temp(temp_cache) => temp_cache.subdirectory(path).map(Self::Dir),
+        }
+    }
+
+    /// Creates a cache in a sibling directory of the cache directory.
+    fn new_sibling(&self, name: &str) -> Result<Self> {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.new_sibling(name).map(Self::Dir),
+            Self::Temp(temp_cache) => temp_cache.new_sibling(name).map(Self::Dir),
+        }
+    }
+
+    /// Moves the cache directory to the target directory, returning a cache there which is never removed when dropped.
+    fn persist(self, target: &Path) -> Result<Self, (Error, Box<Self>)> {
+        match self {
+            Self::Dir(dir_cache) => {
+                dir_cache
+                    .persist(target)
+                    .map(Self::Dir)
+                    .map_err(|(error, dir_cache)| (error, Box::new(Self::Dir(*dir_cache))))
+            },
+            Self::Temp(temp_cache) => {
+                temp_cache
+                    .persist(target)
+                    .map(Self::Dir)
+                    .map_err(|(error, temp_cache)| (error, Box::new(Self::Temp(*temp_cache))))
+            },
+        }
+    }
+
+    /// Consumes the cache, returning the path of the cache directory, which is kept instead of being removed.
+    fn into_path(self) -> PathBuf {
+        match self {
+            Self::Dir(dir_cache) => dir_cache.into_path(),
+            Self::Temp(temp_cache) => temp_cache.into_path(),
+        }
+    }
+}
+
+impl From<InnerDirCache> for InnerCache {
+    fn from(dir_cache: InnerDirCache) -> Self {
+        Self::Dir(dir_cache)
+    }
+}
+
+impl From<InnerTempCache> for InnerCache {
+    fn from(temp_cache: InnerTempCache) -> Self {
+        Self::Temp(temp_cache)
+    }
+}
+
+/// Inner cache implementation for a specified directory.
+///
+/// Clones share the state of the cache, e.g. the generation of files and the locks, and outlive the borrows of file handles.
+#[derive(Clone)]
+struct InnerDirCache {
+    /// Directory where the cache is stored
+    root: PathBuf,
+    /// Refresh interval for the cache, or `None` if files are never refreshed automatically
+    refresh_interval: Option<Duration>,
+    /// Whether empty files are considered invalid
+    empty_is_invalid: bool,
+    /// Maximum size of files in bytes
+    max_file_size: Option<u64>,
+    /// Whether the cache directory is recreated when it disappears
+    auto_recreate_root: bool,
+    /// Whether the cache directory is probed for writability
+    write_check: bool,
+    /// Whether files and directories are created with restrictive permissions
+    secure_permissions: bool,
+    /// Whether nested keys are rejected
+    flat: bool,
+    /// Transformer rewriting the location of files
+    path_transformer: Option<Arc<dyn PathTransformerFn>>,
+    /// Observer receiving the events of cache operations
+    observer: Option<Arc<dyn ObserverFn>>,
+    /// Clock determining the current time
+    clock: Arc<dyn Clock>,
+    /// Policy limiting the files
+    limit_policy: Option<LimitPolicy>,
+    /// Storage budget of the cache
+    storage_quota: Option<StorageQuota>,
+    /// Maximum number of files
+    max_entries: Option<usize>,
+    /// Policy validating the paths of files
+    key_policy: KeyPolicy,
+    /// Token cancelling the creation and refreshing of files
+    cancel_token: CancelToken,
+    /// How files which already exist are treated
+    creation_mode: CreationMode,
+    /// How failures of callbacks are handled
+    error_recovery: Arc<ErrorRecovery>,
+    /// Version token recorded for files
+    version: Option<String>,
+    /// Generation of files, incremented to invalidate all of them
+    generation: Arc<AtomicU64>,
+    /// Whether writes are forbidden
+    frozen: Arc<AtomicBool>,
+    /// Guard removing the cache directory when the cache and its clones are dropped
+    remove_on_drop: Option<Arc<RemoveOnDrop>>,
+    /// How paths are resolved when checking that they are within the cache directory
+    canonicalization_mode: CanonicalizationMode,
+    /// Pool of open file descriptors
+    #[cfg(feature = "fd-pool")]
+    fd_pool: Arc<FileDescriptorPool>,
+    /// In-memory layer keeping small files
+    memory_cache: Arc<MemoryCache>,
+    /// Locks of the file handles, shared with the caches of subdirectories
+    lock_registry: Arc<LockRegistry>,
+    /// Operations of the file handles in progress, waited for when the cache is closed
+    in_flight: Arc<InFlight>,
+    /// Injector failing the operations of the cache on demand
+    #[cfg(feature = "testing")]
+    fault_injector: Option<Arc<dyn FaultInjectorFn>>,
+}
+
+impl InnerDirCache {
+    /// File persisting the generation of files
+    const GENERATION_FILE: &str = ".fcache-generation";
+    /// Directory of content-addressed objects
+    const OBJECTS_DIR: &str = "objects";
+    /// Prefix of the files of the cache itself, which keys can not start with
+    const RESERVED_PREFIX: &str = dir::MARKER_FILE;
+    /// Permissions of directories in secure mode
+    #[cfg(unix)]
+    const SECURE_DIR_MODE: u32 = 0o700;
+    /// Permissions of files in secure mode
+    #[cfg(unix)]
+    const SECURE_FILE_MODE: u32 = 0o600;
+    /// Prefix of the sidecar files recording the metadata of other files
+    pub(crate) const SIDECAR_PREFIX: &str = ".fcache-meta-";
+    /// Prefix of the temporary files staging streamed writes
+    const STAGING_PREFIX: &str = ".fcache-tmp";
+    /// Time after which staging files which are no longer modified are left behind by interrupted writes, rather than in use by another process
+    const STALE_STAGING_AGE: Duration = Duration::from_secs(60 * 60);
+    /// Prefix of the file probing the writability of the cache directory
+    const WRITE_PROBE_PREFIX: &str = ".fcache-probe";
+
+    /// Creates a new cache instance within a specified directory, probing it for writability.
+    fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir_cache = Self::new_unchecked(dir)?;
+        dir_cache.check_writable()?;
+        Ok(dir_cache)
+    }
+
+    /// Creates a new cache instance within a specified directory, without probing it for writability.
+    fn new_unchecked(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+
+        if dir.exists() && !dir.is_dir() {
+            return Err(Error::NotADirectory { path: dir });
+        } else if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+
+        // Canonicalize after ensuring the directory exists
+        let root = dir.canonicalize()?;
+        let refresh_interval = Some(DEFAULT_REFRESH_INTERVAL);
+        let empty_is_invalid = false;
+        let max_file_size = None;
+        let auto_recreate_root = false;
+        let write_check = true;
+        let secure_permissions = false;
+        let flat = false;
+        let path_transformer = None;
+        let observer = None;
+        let clock = Arc::new(SystemClock);
+        let limit_policy = None;
+        let storage_quota = None;
+        let max_entries = None;
+        let key_policy = KeyPolicy::default();
+        let cancel_token = CancelToken::new();
+        let creation_mode = CreationMode::default();
+        let error_recovery = Arc::new(ErrorRecovery::default());
+        let version = None;
+        let generation = Arc::new(AtomicU64::new(Self::persisted_generation(&root)?));
+        let frozen = Arc::new(AtomicBool::new(false));
+        let remove_on_drop = None;
+        let canonicalization_mode = CanonicalizationMode::default();
+        #[cfg(feature = "fd-pool")]
+        let fd_pool = Arc::new(FileDescriptorPool::new(DEFAULT_FD_POOL_SIZE));
+        let memory_cache = Arc::new(MemoryCache::new(0, DEFAULT_MAX_ENTRY_SIZE));
+        let lock_registry = Arc::new(LockRegistry::default());
+        let in_flight = Arc::new(InFlight::default());
+        #[cfg(feature = "testing")]
+        let fault_injector = None;
+        let inner_dir_cache = Self {
+            root,
+            refresh_interval,
+            empty_is_invalid,
+            max_file_size,
+            auto_recreate_root,
+            write_check,
+            secure_permissions,
+            flat,
+            path_transformer,
+            observer,
+            clock,
+            limit_policy,
+            storage_quota,
+            max_entries,
+            key_policy,
+            cancel_token,
+            creation_mode,
+            error_recovery,
+            version,
+            generation,
+            frozen,
+            remove_on_drop,
+            canonicalization_mode,
+            #[cfg(feature = "fd-pool")]
+            fd_pool,
+            memory_cache,
+            lock_registry,
+            in_flight,
+            #[cfg(feature = "testing")]
+            fault_injector,
+        };
+        Ok(inner_dir_cache)
+    }
+
+    /// Sets the refresh interval for the cache.
+    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        // The maximum interval is kept as the way to disable refreshing
+        let refresh_interval = (refresh_interval != Duration::MAX).then_some(refresh_interval);
+        Self {
+            refresh_interval,
+            ..self
+        }
+    }
+
+    /// Sets the refresh interval to the default value.
+    fn with_default_refresh_interval(self) -> Self {
+        self.with_refresh_interval(DEFAULT_REFRESH_INTERVAL)
+    }
+
+    /// Disables automatic refreshing of files in the cache.
+    fn without_refresh(self) -> Self {
+        let refresh_interval = None;
+        Self {
+            refresh_interval,
+            ..self
+        }
+    }
+
+    /// Sets whether empty files in the cache are considered invalid.
+    fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
+        Self {
+            empty_is_invalid,
+            ..self
+        }
+    }
+
+    /// Sets the maximum size of files in the cache.
+    fn with_max_file_size(self, max_file_size: u64) -> Self {
+        let max_file_size = Some(max_file_size);
+        Self { max_file_size, ..self }
+    }
+
+    /// Sets a transformer rewriting the location of files in the cache.
+    fn with_path_transformer(self, path_transformer: impl PathTransformerFn + 'static) -> Self {
+        let path_transformer = Some(Arc::new(path_transformer) as Arc<dyn PathTransformerFn>);
+        Self {
+            path_transformer,
+            ..self
+        }
+    }
+
+    /// Sets an observer receiving the events of cache operations.
+    fn with_observer(self, observer: impl ObserverFn + 'static) -> Self {
+        let observer = Some(Arc::new(observer) as Arc<dyn ObserverFn>);
+        Self { observer, ..self }
+    }
+
+    /// Sets a fault injector failing the operations of the cache on demand.
+    #[cfg(feature = "testing")]
+    fn with_fault_injector(self, fault_injector: impl FaultInjectorFn + 'static) -> Self {
+        let fault_injector = Some(Arc::new(fault_injector) as Arc<dyn FaultInjectorFn>);
+        Self { fault_injector, ..self }
+    }
+
+    /// Sets the clock used by the cache to determine the current time.
+    fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        let clock = Arc::new(clock);
+        Self { clock, ..self }
+    }
+
+    /// Sets whether the cache directory is recreated when it disappears.
+    fn with_auto_recreate_root(self, auto_recreate_root: bool) -> Self {
+        Self {
+            auto_recreate_root,
+            ..self
+        }
+    }
+
+    /// Sets whether the cache directory is probed for writability, probing it if enabled.
+    fn with_write_check(self, write_check: bool) -> Result<Self> {
+        if write_check {
+            self.check_writable()?;
+        }
+        Ok(Self { write_check, ..self })
+    }
+
+    /// Sets whether the cache uses restrictive permissions, restricting the cache directory if enabled.
+    fn with_secure_permissions(self, secure_permissions: bool) -> Result<Self> {
+        #[cfg(unix)]
+        if secure_permissions {
+            use std::fs::Permissions;
+            use std::os::unix::fs::PermissionsExt;
+
+            let Self { root, .. } = &self;
+            let mode = fs::metadata(root)?.permissions().mode() & 0o7777;
+            if mode & 0o022 != 0 {
+                let path = root.clone();
+                let error = Error::InsecurePermissions { path, mode };
+                return Err(error);
+            }
+            fs::set_permissions(root, Permissions::from_mode(Self::SECURE_DIR_MODE))?;
+        }
+        Ok(Self {
+            secure_permissions,
+            ..self
+        })
+    }
+
+    /// Probes the cache directory for writability by creating and deleting a small file.
+    fn check_writable(&self) -> Result<()> {
+        let Self { root, .. } = self;
+        let probe = || {
+            let mut probe_file = tempfile::Builder::new()
+                .prefix(Self::WRITE_PROBE_PREFIX)
+                .tempfile_in(root)?;
+            probe_file.write_all(b"probe")?;
+            // Close explicitly, so a failed removal is reported instead of ignored on drop
+            probe_file.close()
+        };
+        probe().map_err(|source| {
+            let path = root.clone();
+            Error::CacheNotWritable { path, source }
+        })
+    }
+
+    /// Returns the options for opening files, which are created with restrictive permissions in secure mode.
+    pub(crate) fn file_options(&self) -> fs::OpenOptions {
+        let mut options = File::options();
+        #[cfg(unix)]
+        if self.secure_permissions() {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            options.mode(Self::SECURE_FILE_MODE);
+        }
+        options
+    }
+
+    /// Creates a directory, optionally with its missing parents, with restrictive permissions in secure mode.
+    fn create_dir(&self, path: &Path, recursive: bool) -> io::Result<()> {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(recursive);
+        #[cfg(unix)]
+        if self.secure_permissions() {
+            use std::os::unix::fs::DirBuilderExt;
+
+            builder.mode(Self::SECURE_DIR_MODE);
+        }
+        builder.create(path)
+    }
+
+    /// Sets whether the cache is flat.
+    fn with_flat(self, flat: bool) -> Self {
+        Self { flat, ..self }
+    }
+
+    /// Sets the policy limiting the files in the cache.
+    fn with_entry_limit_policy(self, limit_policy: LimitPolicy) -> Self {
+        let limit_policy = Some(limit_policy);
+        Self { limit_policy, ..self }
+    }
+
+    /// Sets the storage quota of the cache.
+    fn with_storage_quota(self, storage_quota: StorageQuota) -> Self {
+        let storage_quota = Some(storage_quota);
+        Self { storage_quota, ..self }
+    }
+
+    /// Sets the maximum number of files in the cache.
+    fn with_max_entries(self, max_entries: usize) -> Self {
+        let max_entries = Some(max_entries);
+        Self { max_entries, ..self }
+    }
+
+    /// Sets the policy validating the paths of files requested from the cache.
+    fn with_key_policy(self, key_policy: KeyPolicy) -> Self {
+        Self { key_policy, ..self }
+    }
+
+    /// Removes the cache directory when the cache is dropped.
+    fn with_remove_on_drop(self) -> Self {
+        let Self { root, .. } = &self;
+        let remove_on_drop = Some(Arc::new(RemoveOnDrop::new(root)));
+        Self { remove_on_drop, ..self }
+    }
+
+    /// Sets how files which already exist in the cache are treated.
+    fn with_creation_mode(self, creation_mode: CreationMode) -> Self {
+        Self { creation_mode, ..self }
+    }
+
+    /// Sets how failures of callbacks are handled.
+    fn with_error_recovery(self, error_recovery: ErrorRecovery) -> Self {
+        let error_recovery = Arc::new(error_recovery);
+        Self { error_recovery, ..self }
+    }
+
+    /// Sets whether the files added to the cache directory are removed when the cache is dropped.
+    fn with_cleanup_on_drop(self, cleanup_on_drop: bool) -> Self {
+        self.with_cleanup(cleanup_on_drop.then_some(|root: &Path| RemoveOnDrop::added(root)))
+    }
+
+    /// Sets whether the whole content of the cache directory is removed when the cache is dropped.
+    fn with_cleanup_all_on_drop(self, cleanup_all_on_drop: bool) -> Self {
+        self.with_cleanup(cleanup_all_on_drop.then_some(|root: &Path| RemoveOnDrop::content(root)))
+    }
+
+    /// Sets the guard cleaning up the cache directory when the cache is dropped, created from the cache directory, or disables the cleanup.
+    fn with_cleanup(self, cleanup: Option<impl FnOnce(&Path) -> RemoveOnDrop>) -> Self {
+        let Self {
+            root, remove_on_drop, ..
+        } = &self;
+        let remove_on_drop = match (remove_on_drop, cleanup) {
+            // Removing the whole directory removes its files as well
+            (Some(remove_on_drop), Some(_)) if remove_on_drop.removes_dir() => Some(remove_on_drop.clone()),
+            (Some(remove_on_drop), cleanup) => {
+                remove_on_drop.keep();
+                cleanup.map(|cleanup| Arc::new(cleanup(root)))
+            },
+            (None, cleanup) => cleanup.map(|cleanup| Arc::new(cleanup(root))),
+        };
+        Self { remove_on_drop, ..self }
+    }
+
+    /// Sets the version token of files.
+    fn with_version(self, version: String) -> Self {
+        let version = Some(version);
+        Self { version, ..self }
+    }
+
+    /// Sets how paths of files are resolved when checking that they are within the cache directory.
+    fn with_path_canonicalization(self, canonicalization_mode: CanonicalizationMode) -> Self {
+        Self {
+            canonicalization_mode,
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn with_fd_pool_size(self, fd_pool_size: usize) -> Self {
+        let fd_pool = Arc::new(FileDescriptorPool::new(fd_pool_size));
+        Self { fd_pool, ..self }
+    }
+
+    /// Enables an in-memory layer keeping up to `max_bytes` bytes of small files.
+    fn with_memory_cache(self, max_bytes: usize) -> Self {
+        let Self { memory_cache, .. } = &self;
+        let memory_cache = Arc::new(MemoryCache::new(max_bytes, memory_cache.max_entry_size()));
+        Self { memory_cache, ..self }
+    }
+
+    /// Sets the maximum size of a single file kept in memory by the in-memory layer.
+    fn with_memory_cache_max_entry_size(self, max_entry_size: usize) -> Self {
+        let Self { memory_cache, .. } = &self;
+        let memory_cache = Arc::new(MemoryCache::new(memory_cache.max_bytes(), max_entry_size));
+        Self { memory_cache, ..self }
+    }
+
+    /// Returns the path of the cache directory.
+    fn path(&self) -> &Path {
+        let Self { root, .. } = self;
+        root
+    }
+
+    /// Returns the absolute path of the file with the given key.
+    fn path_for(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        self.resolve(path)
+    }
+
+    /// Returns the refresh interval of the cache.
+    fn refresh_interval(&self) -> Option<Duration> {
+        let Self { refresh_interval, .. } = self;
+        *refresh_interval
+    }
+
+    /// Returns whether empty files in the cache are considered invalid.
+    fn empty_is_invalid(&self) -> bool {
+        let Self { empty_is_invalid, .. } = self;
+        *empty_is_invalid
+    }
+
+    /// Returns the maximum size of files in the cache.
+    fn max_file_size(&self) -> Option<u64> {
+        let Self { max_file_size, .. } = self;
+        *max_file_size
+    }
+
+    /// Returns whether the cache directory is recreated when it disappears.
+    fn auto_recreate_root(&self) -> bool {
+        let Self { auto_recreate_root, .. } = self;
+        *auto_recreate_root
+    }
+
+    /// Returns whether the cache directory is probed for writability.
+    fn write_check(&self) -> bool {
+        let Self { write_check, .. } = self;
+        *write_check
+    }
+
+    /// Returns whether the cache uses restrictive permissions.
+    fn secure_permissions(&self) -> bool {
+        let Self { secure_permissions, .. } = self;
+        *secure_permissions
+    }
+
+    /// Returns whether the cache is flat.
+    fn is_flat(&self) -> bool {
+        let Self { flat, .. } = self;
+        *flat
+    }
+
+    /// Checks the key has no parent directories if the cache is flat.
+    fn check_flat(&self, path: &Path) -> Result<()> {
+        let Self { flat, .. } = self;
+        if *flat && path.components().nth(1).is_some() {
+            let path = path.to_path_buf();
+            let reason = "nested keys are not allowed in a flat cache".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Returns the policy limiting the files in the cache.
+    fn entry_limit_policy(&self) -> Option<LimitPolicy> {
+        let Self { limit_policy, .. } = self;
+        *limit_policy
+    }
+
+    /// Returns the storage quota of the cache, if any.
+    fn storage_quota(&self) -> Option<StorageQuota> {
+        let Self { storage_quota, .. } = self;
+        *storage_quota
+    }
+
+    /// Returns the maximum number of files in the cache, if any.
+    fn max_entries(&self) -> Option<usize> {
+        let Self { max_entries, .. } = self;
+        *max_entries
+    }
+
+    /// Returns the policy validating the paths of files requested from the cache.
+    fn key_policy(&self) -> KeyPolicy {
+        let Self { key_policy, .. } = self;
+        *key_policy
+    }
+
+    /// Returns how files which already exist in the cache are treated.
+    fn creation_mode(&self) -> CreationMode {
+        let Self { creation_mode, .. } = self;
+        *creation_mode
+    }
+
+    /// Returns how failures of callbacks are handled.
+    fn error_recovery(&self) -> &ErrorRecovery {
+        let Self { error_recovery, .. } = self;
+        error_recovery
+    }
+
+    /// Returns the version token of files.
+    fn version(&self) -> Option<&str> {
+        let Self { version, .. } = self;
+        version.as_deref()
+    }
+
+    /// Returns the generation of files.
+    fn generation(&self) -> u64 {
+        let Self { generation, .. } = self;
+        generation.load(Ordering::SeqCst)
+    }
+
+    /// Increments the generation of files, persisting it in the cache directory.
+    fn bump_generation(&self) -> Result<()> {
+        let Self { root, generation, .. } = self;
+        let generation_path = root.join(Self::GENERATION_FILE);
+        self.check_frozen(&generation_path)?;
+        let generation = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        fs::write(generation_path, generation.to_string())?;
+        Ok(())
+    }
+
+    /// Returns how paths of files are resolved when checking that they are within the cache directory.
+    fn path_canonicalization(&self) -> CanonicalizationMode {
+        let Self {
+            canonicalization_mode, ..
+        } = self;
+        *canonicalization_mode
+    }
+
+    /// Returns the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn fd_pool_size(&self) -> usize {
+        let Self { fd_pool, .. } = self;
+        fd_pool.size()
+    }
+
+    /// Returns the maximum total size of files kept in memory by the in-memory layer.
+    fn memory_cache_max_bytes(&self) -> usize {
+        let Self { memory_cache, .. } = self;
+        memory_cache.max_bytes()
+    }
+
+    /// Returns the maximum size of a single file kept in memory by the in-memory layer.
+    fn memory_cache_max_entry_size(&self) -> usize {
+        let Self { memory_cache, .. } = self;
+        memory_cache.max_entry_size()
+    }
+
+    /// Returns the in-memory layer keeping small files.
+    pub(crate) fn memory_cache(&self) -> &MemoryCache {
+        let Self { memory_cache, .. } = self;
+        memory_cache
+    }
+
+    /// Returns the registry of the locks of the file handles.
+    pub(crate) fn lock_registry(&self) -> &LockRegistry {
+        let Self { lock_registry, .. } = self;
+        lock_registry
+    }
+
+    /// Returns the operations of the file handles in progress.
+    pub(crate) fn in_flight(&self) -> &InFlight {
+        let Self { in_flight, .. } = self;
+        in_flight
+    }
+
+    /// Creates a temporary file staging a streamed write within the directory.
+    pub(crate) fn staging_file(&self, dir: &Path) -> Result<NamedTempFile> {
+        let staging_file = tempfile::Builder::new().prefix(Self::STAGING_PREFIX).tempfile_in(dir)?;
+        Ok(staging_file)
+    }
+
+    /// Returns the pool of open file descriptors.
+    #[cfg(feature = "fd-pool")]
+    fn fd_pool(&self) -> &FileDescriptorPool {
+        let Self { fd_pool, .. } = self;
+        fd_pool
+    }
+
+    /// Returns the token cancelling the creation and refreshing of files in the cache.
+    fn cancellation_token(&self) -> CancelToken {
+        let Self { cancel_token, .. } = self;
+        cancel_token.clone()
+    }
+
+    /// Freezes the cache, forbidding all writes.
+    fn freeze(&self) {
+        let Self { frozen, .. } = self;
+        frozen.store(true, Ordering::SeqCst);
+    }
+
+    /// Thaws the cache, allowing writes again.
+    fn thaw(&self) {
+        let Self { frozen, .. } = self;
+        frozen.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns whether the cache is frozen.
+    fn is_frozen(&self) -> bool {
+        let Self { frozen, .. } = self;
+        frozen.load(Ordering::SeqCst)
+    }
+
+    /// Returns an error if the cache is frozen, reporting the given path.
+    fn check_frozen(&self, path: &Path) -> Result<()> {
+        if self.is_frozen() {
+            let path = path.to_path_buf();
+            let error = Error::Frozen { path };
+            Err(error)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Passes the event to the observer, if any.
+    fn emit(&self, event: impl FnOnce() -> CacheEvent) {
+        let Self { observer, .. } = self;
+        if let Some(observer) = observer {
+            observer(event());
+        }
+    }
+
+    /// Returns the error injected at the given operation, if any.
+    #[cfg(feature = "testing")]
+    pub(crate) fn inject_fault(&self, fault_point: impl FnOnce() -> FaultPoint) -> io::Result<()> {
+        let Self { fault_injector, .. } = self;
+        match fault_injector
+            .as_ref()
+            .and_then(|fault_injector| fault_injector(fault_point()))
+        {
+            Some(error) => Err(error),
+            None => io::Result::Ok(()),
+        }
+    }
+
+    /// Returns whether the cache has been cancelled.
+    fn is_cancelled(&self) -> bool {
+        let Self { cancel_token, .. } = self;
+        cancel_token.is_cancelled()
+    }
+
+    /// Returns the current time according to the clock of the cache.
+    fn now(&self) -> SystemTime {
+        let Self { clock, .. } = self;
+        clock.now()
+    }
+
+    /// Ensures the cache directory exists, recreating it up to the given directory within the cache if enabled.
+    fn ensure_root(&self, dir: &Path) -> Result<()> {
+        let Self {
+            root,
+            auto_recreate_root,
+            ..
+        } = self;
+        if root.exists() {
+            Ok(())
+        } else if *auto_recreate_root {
+            let dir = if dir.starts_with(root) { dir } else { root };
+            self.create_dir(dir, true)?;
+            Ok(())
+        } else {
+            let path = root.clone();
+            let error = Error::CacheRootMissing { path };
+            Err(error)
+        }
+    }
+
+    /// Ensures the given directory within the cache directory exists, recreating missing components.
+    fn ensure_dir(&self, dir: &Path) -> Result<()> {
+        let Self { root, .. } = self;
+        let Some(components) = dir.strip_prefix(root).ok() else {
+            let path = dir.to_path_buf();
+            let cache_dir = root.clone();
+            let error = Error::PathTraversal { path, cache_dir };
+            return Err(error);
+        };
+
+        // Ensure the absolute path is within the cache directory to prevent path traversal attacks
+        let mut path = root.clone();
+        for component in components.components() {
+            path.push(component);
+            if let Err(error) = self.create_dir(&path, false)
+                && error.kind() != ErrorKind::AlreadyExists
+            {
+                return Err(error.into());
+            }
+            if !self.is_within_root(&path)? {
+                let cache_dir = root.clone();
+                let error = Error::PathTraversal { path, cache_dir };
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a file in the cache using a callback for initialization.
+    fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
+        let path = self.prepare_path(path)?;
+        let lazy_file = CacheLazyFile::attach(path, callback, self)?;
+        self.init(lazy_file)
+    }
+
+    /// Creates a file in the cache with a size limit overriding the cache-wide one.
+    fn get_with_max_size<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+        max_file_size: u64,
+    ) -> Result<CacheFile<'a>> {
+        let path = self.prepare_path(path)?;
+        let lazy_file = CacheLazyFile::attach(path, callback, self)?.with_max_file_size(max_file_size);
+        self.init(lazy_file)
+    }
+
+    /// Initializes a lazy file according to the creation mode and enforces the limit policy and the storage quota.
+    fn init<'a>(&'a self, lazy_file: CacheLazyFile<'a>) -> Result<CacheFile<'a>> {
+        let Self { creation_mode, .. } = self;
+        let cache_file = lazy_file.init_with(*creation_mode)?;
+        self.enforce_limits(cache_file)
+    }
+
+    /// Enforces the limit policy and the storage quota after a file was initialized.
+    fn enforce_limits<'a>(&'a self, cache_file: CacheFile<'a>) -> Result<CacheFile<'a>> {
+        // The storage quota is enforced whenever a file is written
+        self.enforce_limit_policy(Some(cache_file.path()))
+            .inspect_err(|error| {
+                self.emit(|| {
+                    let path = None;
+                    let error = error.to_string();
+                    CacheEvent::Error { path, error }
+                });
+            })?;
+        Ok(cache_file)
+    }
+
+    /// Creates a file in the cache that is lazily created when accessed.
+    fn get_lazy<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+    ) -> Result<CacheLazyFile<'a>> {
+        let path = self.prepare_path(path)?;
+        CacheLazyFile::new(path, callback, self)
+    }
+
+    /// Validates the path of a file, creating its parent directories within the cache directory.
+    fn prepare_path(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let Self {
+            root,
+            flat,
+            key_policy,
+            canonicalization_mode,
+            ..
+        } = self;
+        let path = self.relative_key(path.as_ref())?;
+        self.check_flat(path)?;
+        key_policy.check(path)?;
+        let (components, file_name) = Self::split(path)?;
+        self.ensure_root(root)?;
+        if *flat {
+            // Flat keys have no directories to create
+            return self.transform(root.join(file_name));
+        }
+
+        // Ensure the absolute path is within the cache directory to prevent path traversal attacks
+        let mut path = root.clone();
+        for component in components {
+            path.push(component);
+            // Strict canonicalization requires the directory to exist before it can be checked
+            if *canonicalization_mode == CanonicalizationMode::Strict && !path.exists() {
+                self.create_dir(&path, false)?;
+            }
+            if !self.is_within_root(&path)? {
+                let cache_dir = root.clone();
+                let error = Error::PathTraversal { path, cache_dir };
+                return Err(error);
+            }
+            if !path.exists() {
+                self.create_dir(&path, false)?;
+            }
+        }
+
+        let path = self.transform(path.join(file_name))?;
+        if let Some(parent) = path.parent() {
+            self.ensure_dir(parent)?;
+        }
+        Ok(path)
+    }
+
+    /// Creates an append-only file in the cache.
+    fn get_appendable(
+        &self,
+        path: impl AsRef<Path>,
+        header_callback: impl CallbackFn + 'static,
+    ) -> Result<AppendFile<'_>> {
+        AppendFile::new(self.get_lazy(path, header_callback)?, self)
+    }
+
+    /// Creates a file in the cache mirroring an external source file.
+    fn get_linked(
+        &self,
+        path: impl AsRef<Path>,
+        source: impl AsRef<Path>,
+        link_mode: LinkMode,
+    ) -> Result<CacheFile<'_>> {
+        let source = source.as_ref().canonicalize()?;
+        let callback = callback::copy_from(source.clone());
+        self.get_lazy(path, callback)?.with_link(source, link_mode).init()
+    }
+
+    /// Predicts the outcome of [`get`](Self::get) without touching the filesystem.
+    fn dry_run_get(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<DryRunReport> {
+        let path = path.as_ref();
+        match self.resolve(path) {
+            Result::Ok(path) if path.exists() => {
+                let Self { creation_mode, .. } = self;
+                // The callback is only stored to check validity, never invoked
+                let is_invalid = CacheLazyFile::attach(&path, callback, self)?.is_invalid()?;
+                let would_refresh = is_invalid || *creation_mode == CreationMode::CreateOrTruncate;
+                let would_error = (*creation_mode == CreationMode::CreateNew).then(|| {
+                    let path = path.clone();
+                    Error::FileAlreadyExists { path }
+                });
+                Ok(DryRunReport {
+                    would_create: false,
+                    would_refresh,
+                    would_error,
+                    path,
+                    estimated_duration: None,
+                })
+            },
+            Result::Ok(path) => {
+                Ok(DryRunReport {
+                    would_create: true,
+                    would_refresh: false,
+                    would_error: None,
+                    path,
+                    estimated_duration: None,
+                })
+            },
+            Err(error @ (Error::InvalidPath { .. } | Error::PathTraversal { .. })) => {
+                Ok(DryRunReport {
+                    would_create: false,
+                    would_refresh: false,
+                    would_error: Some(error),
+                    path: path.to_path_buf(),
+                    estimated_duration: None,
+                })
+            },
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Explains what accessing a file in the cache would do.
+    fn explain(&self, path: impl AsRef<Path>) -> Result<CacheExplanation> {
+        let path = self.resolve(path)?;
+        self.attach(&path)?.explain()
+    }
+
+    /// Returns the state of a file in the cache.
+    fn status(&self, path: impl AsRef<Path>) -> Result<EntryStatus> {
+        let path = self.resolve(path)?;
+        self.attach(&path)?.status()
+    }
+
+    /// Checks if a file exists in the cache.
+    fn exists(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = self.resolve(path)?;
+        Ok(path.exists())
+    }
+
+    /// Lists the files in the cache.
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        let Self { root, .. } = self;
+        let files = self
+            .files()?
+            .into_iter()
+            .filter(|path| !Self::is_transient(path))
+            .filter_map(|path| path.strip_prefix(root).map(Path::to_path_buf).ok())
+            .collect();
+        Ok(files)
+    }
+
+    /// Returns the files in the cache along with their metadata.
+    fn entries(&self) -> Result<vec::IntoIter<EntryInfo>> {
+        let Self { root, .. } = self;
+        let mut entries = Vec::new();
+        for key in self.list()? {
+            let path = root.join(&key);
+            let metadata = match fs::symlink_metadata(&path) {
+                io::Result::Ok(metadata) => metadata,
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            };
+            let size = metadata.len();
+            let modified = metadata.modified()?;
+            let entry = EntryInfo {
+                key,
+                path,
+                size,
+                modified,
+            };
+            entries.push(entry);
+        }
+        Ok(entries.into_iter())
+    }
+
+    /// Removes a file from the cache.
+    fn remove(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = self.resolve(path)?;
+        self.attach(&path)?.remove()
+    }
+
+    /// Removes every file from the cache.
+    fn clear(&self) -> Result<usize> {
+        let Self {
+            root, lock_registry, ..
+        } = self;
+        self.check_frozen(root)?;
+        if lock_registry.is_held_within(root, self.now()) {
+            let error = Error::FileAlreadyLocked;
+            return Err(error);
+        }
+
+        let mut files = Vec::new();
+        self.visit_files(|path| {
+            files.push(path);
+            Ok(())
+        })?;
+        let mut removed = 0;
+        for path in files {
+            self.attach(&path)?
+                .remove()
+                .map_err(|error| Self::remove_failed(&path, error))?;
+            if !Self::is_transient(&path) {
+                removed += 1;
+            }
+        }
+
+        // Persisted refresh intervals, temporary files, and directories left behind are removed as well
+        for entry in fs::read_dir(root)? {
+            let entry = entry?;
+            let path = entry.path();
+            let result = if entry.file_type()?.is_dir() {
+                fs::remove_dir_all(&path)
+            } else if path != root.join(dir::MARKER_FILE)
+                && path != root.join(Self::GENERATION_FILE)
+                && !entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(Self::WRITE_PROBE_PREFIX)
+            {
+                fs::remove_file(&path)
+            } else {
+                continue;
+            };
+            result.map_err(|error| Self::remove_failed(&path, error.into()))?;
+        }
+        Ok(removed)
+    }
+
+    /// Attaches the path to an I/O error of removing it.
+    fn remove_failed(path: &Path, error: Error) -> Error {
+        match error {
+            Error::IO(source) => {
+                let path = path.to_path_buf();
+                Error::RemoveFailed { path, source }
+            },
+            error => error,
+        }
+    }
+
+    /// Creates a file in the cache, or creates a handle for it if it already exists.
+    fn get_or_attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let path = self.prepare_path(path)?;
+        let lazy_file = self.attach_with(&path, callback)?;
+        if !path.exists()
+            && let Err(error) = lazy_file.create()
+        {
+            // The file was created concurrently in the meantime
+            let created_concurrently = matches!(&error, Error::IO(error) if error.kind() == ErrorKind::AlreadyExists);
+            if !created_concurrently {
+                return Err(error);
+            }
+        }
+        let cache_file = lazy_file.init()?;
+        self.enforce_limits(cache_file)
+    }
+
+    /// Creates a handle for a file which already exists in the cache.
+    fn attach_existing(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let path = self.resolve(path)?;
+        if !path.exists() {
+            let error = Error::FileNotFound { path };
+            return Err(error);
+        }
+        self.attach_with(&path, callback)?.init()
+    }
+
+    /// Backs up the whole cache directory to the destination directory.
+    fn backup(&self, dest: impl AsRef<Path>) -> Result<BackupReport> {
+        let Self { root, .. } = self;
+        let dest = dest.as_ref();
+        fs::create_dir_all(dest)?;
+        let dest = dest.canonicalize()?;
+        if dest.starts_with(root) {
+            let path = dest;
+            let reason = "backup destination is within the cache directory".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+
+        let mut files_copied = 0;
+        let mut bytes_written = 0;
+        let mut dirs = vec![root.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                let Some(relative_path) = path.strip_prefix(root).ok() else {
+                    continue;
+                };
+                let dest_path = dest.join(relative_path);
+                if entry.file_type()?.is_dir() {
+                    fs::create_dir_all(&dest_path)?;
+                    dirs.push(path);
+                } else if !Self::is_transient(&path) {
+                    // Copying preserves the permissions, but not the modification time
+                    bytes_written += fs::copy(&path, &dest_path)?;
+                    let modified = fs::metadata(&path)?.modified()?;
+                    File::options().write(true).open(&dest_path)?.set_modified(modified)?;
+                    files_copied += 1;
+                }
+            }
+        }
+
+        let report = BackupReport {
+            files_copied,
+            bytes_written,
+            dest,
+        };
+        Ok(report)
+    }
+
+    /// Closes the cache, waiting for the operations of file handles in progress and removing the staging files left behind by interrupted writes.
+    fn close(&self, timeout: Duration) -> Result<CloseReport> {
+        let Self {
+            cancel_token,
+            in_flight,
+            ..
+        } = self;
+        let (operations_completed, operations_abandoned) = in_flight.wait(timeout);
+        // Abandoned operations are stopped at the next cancellation point
+        if operations_abandoned > 0 {
+            cancel_token.cancel();
+        }
+        let temp_files_removed = self.remove_staging_files()?;
+        let report = CloseReport {
+            temp_files_removed,
+            operations_completed,
+            operations_abandoned,
+        };
+        Ok(report)
+    }
+
+    /// Removes the staging files left behind by interrupted streamed writes.
+    ///
+    /// Staging files modified recently may be in use by another process sharing the cache directory, so they are kept.
+    fn remove_staging_files(&self) -> Result<usize> {
+        let Self { root, .. } = self;
+        // Frozen caches are never written, so they have no interrupted writes either
+        if self.is_frozen() {
+            return Ok(0);
+        }
+        let stale_before = self
+            .now()
+            .checked_sub(Self::STALE_STAGING_AGE)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut removed = 0;
+        let mut dirs = vec![root.clone()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+                if !entry.file_name().to_string_lossy().starts_with(Self::STAGING_PREFIX) {
+                    continue;
+                }
+                // Staging files may be persisted or removed concurrently
+                let modified = match entry.metadata().and_then(|metadata| metadata.modified()) {
+                    io::Result::Ok(modified) => modified,
+                    Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                    Err(error) => return Err(error.into()),
+                };
+                if modified >= stale_before {
+                    continue;
+                }
+                match fs::remove_file(path) {
+                    io::Result::Ok(()) => removed += 1,
+                    Err(error) if error.kind() == ErrorKind::NotFound => {},
+                    Err(error) => return Err(error.into()),
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Imports the files of a ZIP archive into the cache.
+    #[cfg(feature = "zip")]
+    fn import_zip(&self, zip_path: impl AsRef<Path>) -> Result<usize> {
+        archive::import(self, zip_path.as_ref())
+    }
+
+    /// Exports the files of the cache into a ZIP archive.
+    #[cfg(feature = "zip")]
+    fn export_zip(&self, dest: impl AsRef<Path>, include_expired: bool) -> Result<usize> {
+        archive::export(self, dest.as_ref(), include_expired)
+    }
+
+    /// Returns whether the path is a temporary file or a journal, which are excluded from backups.
+    fn is_transient(path: &Path) -> bool {
+        let is_temp_file = path
+            .file_name()
+            .and_then(OsStr::to_str)
+            .is_some_and(|file_name| file_name.starts_with(Self::STAGING_PREFIX));
+        let is_excluded_extension = path
+            .extension()
+            .is_some_and(|extension| extension == "tmp" || extension == "journal");
+        is_temp_file || is_excluded_extension
+    }
+
+    /// Stores content in the cache under the SHA-256 digest of the content.
+    fn put_content_addressed(&self, mut data: impl Read) -> Result<(String, CacheFile<'_>)> {
+        let Self { root, .. } = self;
+        let objects_dir = root.join(Self::OBJECTS_DIR);
+        self.check_frozen(&objects_dir)?;
+        self.create_dir(&objects_dir, true)?;
+
+        // Stream the data into a temporary file while computing its digest
+        let mut temp_file = self.staging_file(&objects_dir)?;
+        let mut writer = DigestWriter::new(temp_file.as_file_mut());
+        io::copy(&mut data, &mut writer)?;
+        let digest = writer.hex_digest();
+
+        // Reuse the existing object, or move the temporary file into place
+        let path = self.object_path(&digest)?;
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                self.create_dir(parent, true)?;
+            }
+            // The same object may have been stored concurrently in the meantime
+            if let Err(error) = temp_file.persist_noclobber(&path)
+                && error.error.kind() != ErrorKind::AlreadyExists
+            {
+                return Err(error.error.into());
+            }
+        }
+
+        let cache_file = CacheLazyFile::attach(path, callback::noop, self)?
+            .into_immutable()
+            .init()?;
+        Ok((digest, cache_file))
+    }
+
+    /// Creates a file in the cache with the content streamed from the reader.
+    fn put_from_reader(&self, path: impl AsRef<Path>, reader: impl Read) -> Result<CacheFile<'_>> {
+        let Self { creation_mode, .. } = self;
+        let path = self.prepare_path(path)?;
+        if *creation_mode == CreationMode::CreateNew && path.exists() {
+            let error = Error::FileAlreadyExists { path };
+            return Err(error);
+        }
+        let cache_file = CacheLazyFile::attach(path, callback::unrepeatable, self)?
+            .with_expiry(ExpiryMode::Never)
+            .init_from_reader(reader)?;
+        self.enforce_limits(cache_file)
+    }
+
+    /// Opens a content-addressed object.
+    fn open_object(&self, digest: &str) -> Result<CacheFile<'_>> {
+        let path = self.object_path(digest)?;
+        if !path.exists() {
+            let error = Error::FileNotFound { path };
+            return Err(error);
+        }
+        CacheLazyFile::attach(path, callback::noop, self)?
+            .into_immutable()
+            .init()
+    }
+
+    /// Reassembles a file from its chunks.
+    fn reassemble_chunks(&self, prefix: &str) -> Result<CacheFile<'_>> {
+        let path = self.prepare_path(prefix)?;
+        let chunks = (0..)
+            .map(|index| CacheLazyFile::chunk_path(&path, index))
+            .take_while(|chunk_path| chunk_path.exists())
+            .collect::<Vec<_>>();
+        if chunks.is_empty() {
+            let path = CacheLazyFile::chunk_path(&path, 0);
+            let error = Error::FileNotFound { path };
+            return Err(error);
+        }
+        CacheLazyFile::attach(path, callback::concat_from(chunks), self)?.init_with(CreationMode::CreateOrTruncate)
+    }
+
+    /// Returns the content of a file, inserting the generated content if the file is missing or invalid.
+    fn open_or_insert_with(&self, path: impl AsRef<Path>, f: impl FnOnce() -> Vec<u8>) -> Result<Vec<u8>> {
+        let path = path.as_ref();
+        let resolved_path = self.resolve(path)?;
+        if resolved_path.exists() {
+            let lazy_file = self.attach(&resolved_path)?;
+            if lazy_file.is_valid()? {
+                return Ok(fs::read(resolved_path)?);
+            }
+            let content = f();
+            CacheLazyFile::attach(&resolved_path, callback::write_all(content.clone()), self)?.force_refresh()?;
+            return Ok(content);
+        }
+
+        let content = f();
+        match self
+            .get_lazy(path, callback::write_all(content.clone()))
+            .and_then(CacheLazyFile::init)
+        {
+            Result::Ok(_) => Ok(content),
+            // Another process created the file in the meantime
+            Err(Error::FileAlreadyExists { path }) => Ok(fs::read(path)?),
+            Err(Error::IO(error)) if error.kind() == ErrorKind::AlreadyExists => Ok(fs::read(resolved_path)?),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Groups the files in the cache by their validity.
+    fn group_by_validity(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+        let mut valid_paths = Vec::new();
+        let mut invalid_paths = Vec::new();
+        for path in self.files()? {
+            if self.attach(&path)?.is_valid()? {
+                valid_paths.push(path);
+            } else {
+                invalid_paths.push(path);
+            }
+        }
+        Ok((valid_paths, invalid_paths))
+    }
+
+    /// Returns the most recently modified files in the cache.
+    fn most_recent(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        self.select_by_modified(n, Reverse)
+    }
+
+    /// Returns the least recently modified files in the cache.
+    fn oldest(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
+        self.select_by_modified(n, |modified| modified)
+    }
+
+    /// Selects the `n` files ranked first by the key of their modification time, in a single pass.
+    fn select_by_modified<K: Ord>(
+        &self,
+        n: usize,
+        key: impl Fn(SystemTime) -> K,
+    ) -> Result<Vec<(PathBuf, SystemTime)>> {
+        let Self { root, .. } = self;
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        // The heap holds at most `n` files, with the worst ranked one on top
+        let mut heap = BinaryHeap::with_capacity(n + 1);
+        self.visit_files(|path| {
+            let modified = fs::metadata(&path)?.modified()?;
+            heap.push((key(modified), path, modified));
+            if heap.len() > n {
+                let _ = heap.pop();
+            }
+            Ok(())
+        })?;
+
+        let files = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(_, path, modified)| {
+                let path = path.strip_prefix(root).map(Path::to_path_buf).unwrap_or(path);
+                (path, modified)
+            })
+            .collect();
+        Ok(files)
+    }
+
+    /// Enforces the policy limiting the files in the cache, never evicting the given file.
+    fn enforce_limit_policy(&self, keep: Option<&Path>) -> Result<usize> {
+        let Self { limit_policy, .. } = self;
+        let Some(limit_policy) = limit_policy else {
+            return Ok(0);
+        };
+        // Eviction is deferred until the cache is thawed
+        if self.is_frozen() {
+            return Ok(0);
+        }
+
+        // Collect the files from the least recently modified
+        let mut files = Vec::new();
+        for path in self.files()? {
+            if keep.is_some_and(|keep| keep == path) {
+                continue;
+            }
+            let metadata = fs::symlink_metadata(&path)?;
+            files.push((metadata.modified()?, metadata.len(), path));
+        }
+        files.sort_unstable();
+        let mut count = files.len() + usize::from(keep.is_some_and(Path::exists));
+        let mut size = files.iter().map(|(_, len, _)| len).sum::<u64>() + keep.map_or(Ok(0), Self::file_size)?;
+
+        let now = self.now();
+        let mut evicted = 0;
+        for (modified, len, path) in files {
+            let expired = limit_policy
+                .get_max_age()
+                .is_some_and(|max_age| now.duration_since(modified).is_ok_and(|age| age > max_age));
+            let oversized = limit_policy
+                .get_max_size_bytes()
+                .is_some_and(|max_size| size > max_size);
+            let overcrowded = limit_policy
+                .get_max_entries()
+                .is_some_and(|max_entries| count > max_entries);
+            if expired || oversized || overcrowded {
+                self.attach(&path)?.evict()?;
+                count -= 1;
+                size -= len;
+                evicted += 1;
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Removes the files in the cache which nobody accessed for at least the given time.
+    fn prune_idle(&self, idle_timeout: Duration) -> Result<usize> {
+        let now = self.now();
+        let mut pruned = 0;
+        for path in self.files()? {
+            let accessed_at = match CacheLazyFile::recorded_access(&path)? {
+                Some(accessed_at) => accessed_at,
+                None => fs::symlink_metadata(&path)?.modified()?,
+            };
+            if now.duration_since(accessed_at).is_ok_and(|idle| idle >= idle_timeout) {
+                self.attach(&path)?.evict()?;
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Removes the files in the cache which expired.
+    fn purge_expired(&self) -> Result<usize> {
+        let Self { lock_registry, .. } = self;
+        let mut purged = 0;
+        for path in self.files()? {
+            if Self::is_transient(&path) || lock_registry.is_held_within(&path, self.now()) {
+                continue;
+            }
+            let lazy_file = self.attach(&path)?;
+            if lazy_file.is_invalid()? {
+                lazy_file.evict()?;
+                purged += 1;
+            }
+        }
+        Ok(purged)
+    }
+
+    /// Removes the least recently modified unlocked files until the file about to be created fits within the maximum number of files.
+    pub(crate) fn make_room(&self, path: &Path) -> Result<()> {
+        let Self {
+            max_entries,
+            lock_registry,
+            ..
+        } = self;
+        let Some(max_entries) = *max_entries else {
+            return Ok(());
+        };
+        // Replacing an existing file doesn't change the number of files
+        if path.exists() {
+            return Ok(());
+        }
+
+        // Collect the files from the least recently modified, leaving out the files other handles are still creating
+        let mut files = Vec::new();
+        for path in self.files()? {
+            if Self::is_transient(&path) || lock_registry.is_creating(&path) {
+                continue;
+            }
+            // Files may be removed concurrently
+            let metadata = match fs::symlink_metadata(&path) {
+                io::Result::Ok(metadata) => metadata,
+                Err(error) if error.kind() == ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            };
+            files.push((metadata.modified()?, path));
+        }
+        files.sort_unstable();
+        let mut count = files.len();
+
+        let now = self.now();
+        let mut unlocked = files
+            .into_iter()
+            .map(|(_, path)| path)
+            .filter(|path| !lock_registry.is_held_within(path, now));
+        while count >= max_entries {
+            let Some(path) = unlocked.next() else {
+                let error = Error::CacheFull { max_entries };
+                return Err(error);
+            };
+            // Files removed concurrently make room as well
+            match self.attach(&path)?.evict() {
+                Err(Error::IO(error)) if error.kind() == ErrorKind::NotFound => {},
+                result => result?,
+            }
+            count -= 1;
+        }
+        Ok(())
+    }
+
+    /// Evicts files exceeding the storage quota, skipping the given file.
+    fn enforce_storage_quota(&self, keep: Option<&Path>) -> Result<usize> {
+        let Self {
+            storage_quota,
+            lock_registry,
+            ..
+        } = self;
+        let Some(storage_quota) = storage_quota else {
+            return Ok(0);
+        };
+        // Eviction is deferred until the cache is thawed
+        if self.is_frozen() {
+            return Ok(0);
+        }
+
+        let mut files = Vec::new();
+        for path in self.files()? {
+            if keep.is_some_and(|keep| keep == path) {
+                continue;
+            }
+            let metadata = fs::symlink_metadata(&path)?;
+            files.push((metadata.modified()?, metadata.len(), path));
+        }
+        let mut count = files.len() + usize::from(keep.is_some_and(Path::exists));
+        let mut size = files.iter().map(|(_, len, _)| len).sum::<u64>() + keep.map_or(Ok(0), Self::file_size)?;
+        if size <= storage_quota.max_bytes && count <= storage_quota.max_files {
+            return Ok(0);
+        }
+
+        match storage_quota.eviction {
+            EvictionPolicy::LeastRecentlyModified => files.sort_unstable(),
+            EvictionPolicy::LargestFirst => files.sort_unstable_by(|(_, a, _), (_, b, _)| b.cmp(a)),
+        }
+        let now = self.now();
+        let mut evicted = 0;
+        for (_, len, path) in files {
+            if size <= storage_quota.max_bytes && count <= storage_quota.max_files {
+                break;
+            }
+            // Locked files are kept, but still count towards the quota
+            if lock_registry.is_held_within(&path, now) {
+                continue;
+            }
+            self.attach(&path)?.evict()?;
+            count -= 1;
+            size -= len;
+            evicted += 1;
+        }
+        Ok(evicted)
+    }
+
+    /// Returns the current usage of the cache as the total size of files in bytes and the number of files.
+    fn quota_usage(&self) -> Result<(u64, usize)> {
+        let mut size = 0;
+        let mut count = 0;
+        self.visit_files(|path| {
+            size += fs::symlink_metadata(path)?.len();
+            count += 1;
+            Ok(())
+        })?;
+        Ok((size, count))
+    }
+
+    /// Returns the number of files in the cache.
+    fn file_count(&self) -> Result<usize> {
+        let mut count = 0;
+        self.visit_files(|path| {
+            if !Self::is_transient(&path) {
+                count += 1;
+            }
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Checks if the cache holds no files.
+    fn is_empty(&self) -> Result<bool> {
+        let mut is_empty = true;
+        self.try_visit_files(|path| {
+            if Self::is_transient(&path) {
+                return Ok(ControlFlow::Continue(()));
+            }
+            is_empty = false;
+            Ok(ControlFlow::Break(()))
+        })?;
+        Ok(is_empty)
+    }
+
+    /// Returns the total size of the files in the cache in bytes.
+    fn total_size(&self) -> Result<u64> {
+        let mut size = 0;
+        self.visit_files(|path| {
+            if !Self::is_transient(&path) {
+                size += Self::file_size(&path)?;
+            }
+            Ok(())
+        })?;
+        Ok(size)
+    }
+
+    /// Returns statistics of the files in the cache.
+    fn stats(&self) -> Result<CacheStats> {
+        let Self { root, .. } = self;
+        let mut stats = CacheStats::default();
+        for entry in self.entries()? {
+            let EntryInfo { size, modified, .. } = entry;
+            stats.total_bytes += size;
+            stats.file_count += 1;
+            stats.oldest_modified = Some(stats.oldest_modified.map_or(modified, |oldest| oldest.min(modified)));
+            stats.newest_modified = Some(stats.newest_modified.map_or(modified, |newest| newest.max(modified)));
+        }
+
+        let mut dirs = vec![root.clone()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(&dir) {
+                io::Result::Ok(entries) => entries,
+                // Subdirectories removed concurrently are no longer counted
+                Err(error) if error.kind() == ErrorKind::NotFound && dir != *root => continue,
+                Err(error) => return Err(error.into()),
+            };
+            for entry in entries {
+                let entry = entry?;
+                if entry.file_type()?.is_dir() {
+                    dirs.push(entry.path());
+                    stats.dir_count += 1;
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Creates a cache within a subdirectory of the cache directory.
+    fn subdirectory(&self, path: impl AsRef<Path>) -> Result<Self> {
+        let Self {
+            root,
+            refresh_interval,
+            empty_is_invalid,
+            max_file_size,
+            auto_recreate_root,
+            write_check,
+            secure_permissions,
+            flat,
+            observer,
+            key_policy,
+            cancel_token,
+            creation_mode,
+            version,
+            canonicalization_mode,
+            lock_registry,
+            #[cfg(feature = "testing")]
+            fault_injector,
+            ..
+        } = self;
+        let path = self.relative_key(path.as_ref())?;
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            let path = path.to_path_buf();
+            let reason = "subdirectory must be a relative path of directory names".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+
+        let dir_cache = Self::new_unchecked(root.join(path))?
+            .with_write_check(*write_check)?
+            .with_secure_permissions(*secure_permissions)?;
+        Ok(Self {
+            refresh_interval: *refresh_interval,
+            empty_is_invalid: *empty_is_invalid,
+            max_file_size: *max_file_size,
+            auto_recreate_root: *auto_recreate_root,
+            flat: *flat,
+            observer: observer.clone(),
+            key_policy: *key_policy,
+            cancel_token: cancel_token.clone(),
+            creation_mode: *creation_mode,
+            version: version.clone(),
+            canonicalization_mode: *canonicalization_mode,
+            lock_registry: lock_registry.clone(),
+            #[cfg(feature = "testing")]
+            fault_injector: fault_injector.clone(),
+            ..dir_cache
+        })
+    }
+
+    /// Creates a cache in a sibling directory of the cache directory.
+    fn new_sibling(&self, name: &str) -> Result<Self> {
+        let Self {
+            root,
+            refresh_interval,
+            write_check,
+            secure_permissions,
+            ..
+        } = self;
+        let mut components = Path::new(name).components();
+        if !matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        ) {
+            let path = PathBuf::from(name);
+            let reason = "sibling must be a plain directory name".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+        let Some(parent) = root.parent() else {
+            let path = root.clone();
+            let error = Error::NoParentDirectory { path };
+            return Err(error);
+        };
+
+        let dir_cache = Self::new_unchecked(parent.join(name))?
+            .with_write_check(*write_check)?
+            .with_secure_permissions(*secure_permissions)?;
+        Ok(Self {
+            refresh_interval: *refresh_interval,
+            ..dir_cache
+        })
+    }
+
+    /// Moves the cache directory to the target directory, returning a cache there which is never removed when dropped.
+    ///
+    /// The cache is given back along with the error if the directory cannot be moved, with its files left in place.
+    fn persist(self, target: &Path) -> Result<Self, (Error, Box<Self>)> {
+        let target = match Self::prepare_persist_target(target) {
+            Result::Ok(target) => target,
+            Err(error) => return Err((error, Box::new(self))),
+        };
+        let Self {
+            root, remove_on_drop, ..
+        } = &self;
+
+        #[cfg(feature = "testing")]
+        let renamed = self
+            .inject_fault(|| FaultPoint::Rename { path: root.clone() })
+            .and_then(|()| fs::rename(root, &target));
+        #[cfg(not(feature = "testing"))]
+        let renamed = fs::rename(root, &target);
+        match renamed {
+            io::Result::Ok(()) => {},
+            // Directories cannot be renamed across file systems, so they are copied instead
+            Err(error) if error.kind() == ErrorKind::CrossesDevices => {
+                if let Err(error) = self.copy_dir(&target) {
+                    let _ = fs::remove_dir_all(&target);
+                    return Err((error, Box::new(self)));
+                }
+                // The files are kept at the target already, so leftovers of the original directory don't fail the move
+                let _ = fs::remove_dir_all(root);
+            },
+            Err(error) => return Err((error.into(), Box::new(self))),
+        }
+        if let Some(remove_on_drop) = remove_on_drop {
+            remove_on_drop.keep();
+        }
+
+        let root = target;
+        let remove_on_drop = None;
+        Result::Ok(Self {
+            root,
+            remove_on_drop,
+            ..self
+        })
+    }
+
+    /// Checks the target directory of a move of the cache directory, creating its parent directories.
+    ///
+    /// Returns the canonical path of the target, which must not exist or be an empty directory.
+    fn prepare_persist_target(target: &Path) -> Result<PathBuf> {
+        if target.exists() {
+            DirPolicy::RequireEmpty.check(target)?;
+            return Ok(target.canonicalize()?);
+        }
+        let (Some(parent), Some(name)) = (target.parent(), target.file_name()) else {
+            let path = target.to_path_buf();
+            let reason = "target does not name a directory".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        };
+        // Relative targets without a parent directory are within the current directory
+        let parent = if parent == Path::new("") {
+            Path::new(".")
+        } else {
+            parent
+        };
+        fs::create_dir_all(parent)?;
+        Ok(parent.canonicalize()?.join(name))
+    }
+
+    /// Copies the content of the cache directory into the target directory, keeping the modification times of files.
+    fn copy_dir(&self, target: &Path) -> Result<()> {
+        let Self { root, .. } = self;
+        let mut dirs = vec![(root.clone(), target.to_path_buf())];
+        while let Some((dir, dest_dir)) = dirs.pop() {
+            fs::create_dir_all(&dest_dir)?;
+            for entry in fs::read_dir(dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                let dest_path = dest_dir.join(entry.file_name());
+                let file_type = entry.file_type()?;
+                if file_type.is_dir() {
+                    dirs.push((path, dest_path));
+                } else if file_type.is_symlink() {
+                    link::symlink(&fs::read_link(&path)?, &dest_path)?;
+                } else {
+                    // Copying preserves the permissions, but not the modification time, which determines the validity
+                    fs::copy(&path, &dest_path)?;
+                    let modified = entry.metadata()?.modified()?;
+                    File::options().write(true).open(&dest_path)?.set_modified(modified)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the cache, returning the path of the cache directory, which is kept instead of being removed.
+    fn into_path(self) -> PathBuf {
+        let Self {
+            root, remove_on_drop, ..
+        } = self;
+        if let Some(remove_on_drop) = remove_on_drop {
+            remove_on_drop.keep();
+        }
+        root
+    }
+
+    /// Returns the size of the file, or zero if it doesn't exist.
+    fn file_size(path: &Path) -> Result<u64> {
+        match fs::symlink_metadata(path) {
+            io::Result::Ok(metadata) => Ok(metadata.len()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Lists the files in the cache, skipping sidecar files.
+    fn files(&self) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        self.visit_files(|path| {
+            files.push(path);
+            Ok(())
+        })?;
+        files.sort_unstable();
+        Ok(files)
+    }
+
+    /// Calls the visitor for every file in the cache, skipping the files of the cache itself, e.g. sidecar files, the marker file, the generation file, and write probes.
+    fn visit_files(&self, mut visitor: impl FnMut(PathBuf) -> Result<()>) -> Result<()> {
+        self.try_visit_files(|path| visitor(path).map(|()| ControlFlow::Continue(())))
+    }
+
+    /// Calls the visitor for files in the cache like [`visit_files`](Self::visit_files), until the visitor breaks.
+    fn try_visit_files(&self, mut visitor: impl FnMut(PathBuf) -> Result<ControlFlow<()>>) -> Result<()> {
+        let Self { root, .. } = self;
+        let mut dirs = vec![root.clone()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(&dir) {
+                io::Result::Ok(entries) => entries,
+                // Subdirectories removed concurrently, e.g. by another process, have no files left to visit
+                Err(error) if error.kind() == ErrorKind::NotFound && dir != *root => continue,
+                Err(error) => return Err(error.into()),
+            };
+            for entry in entries {
+                let entry = entry?;
+                let path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    dirs.push(path);
+                } else if !Self::is_reserved(&entry.file_name()) && visitor(path)?.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if the file name is of a file of the cache itself, e.g. the marker file, a sidecar file, a write probe, or a staging file.
+    fn is_reserved(file_name: &OsStr) -> bool {
+        file_name.to_string_lossy().starts_with(Self::RESERVED_PREFIX)
+    }
+
+    /// Checks if the path is a sidecar file of another file.
+    fn is_sidecar(path: &Path) -> bool {
+        path.file_name()
+            .is_some_and(|file_name| file_name.to_string_lossy().starts_with(Self::SIDECAR_PREFIX))
+    }
+
+    /// Splits the path of a sidecar file into the path of the file it belongs to and the extension of the sidecar file.
+    fn sidecar_owner(path: &Path) -> Option<(PathBuf, &OsStr)> {
+        let file_name = path.file_name()?.to_str()?.strip_prefix(Self::SIDECAR_PREFIX)?;
+        let extension = Path::new(path.file_name()?).extension()?;
+        let owner = path.with_file_name(Path::new(file_name).file_stem()?);
+        Some((owner, extension))
+    }
+
+    /// Reads the generation persisted in the cache directory, or zero if it was never incremented.
+    fn persisted_generation(root: &Path) -> Result<u64> {
+        match fs::read_to_string(root.join(Self::GENERATION_FILE)) {
+            io::Result::Ok(generation) => {
+                generation
+                    .trim()
+                    .parse()
+                    .map_err(|error| io::Error::new(ErrorKind::InvalidData, error).into())
+            },
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Creates a handle for an existing file in the cache.
+    fn attach(&self, path: &Path) -> Result<CacheLazyFile<'_>> {
+        self.attach_with(path, callback::noop)
+    }
+
+    /// Creates a handle for an existing file in the cache, refreshed with the given callback.
+    fn attach_with(&self, path: &Path, callback: impl CallbackFn + 'static) -> Result<CacheLazyFile<'_>> {
+        let Self { root, .. } = self;
+        let lazy_file = CacheLazyFile::attach(path, callback, self)?;
+        if path.starts_with(root.join(Self::OBJECTS_DIR)) {
+            Ok(lazy_file.into_immutable())
+        } else {
+            Ok(lazy_file)
+        }
+    }
+
+    /// Returns the path of a content-addressed object.
+    fn object_path(&self, digest: &str) -> Result<PathBuf> {
+        let Self { root, .. } = self;
+        if digest.len() != 64 || !digest.bytes().all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')) {
+            let path = PathBuf::from(digest);
+            let reason = "invalid object digest".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+        let (prefix, rest) = digest.split_at(2);
+        let path = root.join(Self::OBJECTS_DIR).join(prefix).join(rest);
+        Ok(path)
+    }
+
+    /// Resolves the path of a file within the cache without creating any directories.
+    fn resolve(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let Self { root, key_policy, .. } = self;
+        let path = self.relative_key(path.as_ref())?;
+        self.check_flat(path)?;
+        key_policy.check(path)?;
+        let (components, file_name) = Self::split(path)?;
+
+        // Ensure the absolute path is within the cache directory to prevent path traversal attacks
+        let mut path = root.clone();
+        for component in components {
+            path.push(component);
+            if !Self::normalize(&path)?.starts_with(root) {
+                let cache_dir = root.clone();
+                let error = Error::PathTraversal { path, cache_dir };
+                return Err(error);
+            }
+        }
+
+        self.transform(path.join(file_name))
+    }
+
+    /// Applies the path transformer, ensuring the transformed path is within the cache directory.
+    fn transform(&self, path: PathBuf) -> Result<PathBuf> {
+        let Self {
+            root, path_transformer, ..
+        } = self;
+        let Some(path_transformer) = path_transformer else {
+            return Ok(path);
+        };
+
+        let path = path_transformer(path)?;
+        if Self::normalize(&path)?.starts_with(root) && path.file_name().is_some() {
+            Ok(path)
+        } else {
+            let cache_dir = root.clone();
+            let error = Error::PathTraversal { path, cache_dir };
+            Err(error)
+        }
+    }
+
+    /// Splits a path into its parent components and a validated file name.
+    fn split(path: &Path) -> Result<(Components<'_>, &OsStr)> {
+        // Ensure the path does not end with a slash
+        if path.to_str().is_some_and(|path| path.ends_with('/')) {
+            let path = path.to_path_buf();
+            let reason = "path ends with a slash".to_string();
+            let error = Error::InvalidPath { path, reason };
+            return Err(error);
+        }
+
+        let mut components = path.components();
+        if let Some(component) = components.next_back()
+            && let Component::Normal(file_name) = component
+            && file_name.to_str().is_some_and(|file_name| file_name.trim() != "")
+        {
+            // Files with the prefix of the files of the cache itself would be mistaken for sidecar, staging, or marker files
+            if Self::is_reserved(file_name) {
+                let path = path.to_path_buf();
+                let reason = "file name has the reserved prefix of the files of the cache".to_string();
+                let error = Error::InvalidPath { path, reason };
+                return Err(error);
+            }
+            Ok((components, file_name))
+        } else {
+            let path = path.to_path_buf();
+            let reason = "invalid file name".to_string();
+            let error = Error::InvalidPath { path, reason };
+            Err(error)
+        }
+    }
+
+    /// Converts an absolute key within the cache directory to a key relative to the cache directory.
+    ///
+    /// Leading `.` components are stripped from relative keys, while absolute keys outside of the cache directory are rejected.
+    fn relative_key<'p>(&self, path: &'p Path) -> Result<&'p Path> {
+        let Self { root, .. } = self;
+        if !path.has_root() {
+            let mut components = path.components();
+            if components.next() != Some(Component::CurDir) {
+                return Ok(path);
+            }
+
+            // Stripping the leading component also drops a trailing slash, so the key is checked beforehand
+            let key = components.as_path();
+            if key.as_os_str().is_empty() || path.to_str().is_some_and(|path| path.ends_with('/')) {
+                let path = path.to_path_buf();
+                let reason = "key does not name a file".to_string();
+                let error = Error::InvalidPath { path, reason };
+                return Err(error);
+            }
+            return Ok(key);
+        }
+        path.strip_prefix(root).map_err(|_| {
+            let path = path.to_path_buf();
+            let cache_dir = root.clone();
+            Error::PathTraversal { path, cache_dir }
+        })
+    }
+
+    /// Checks whether the path is within the cache directory, resolving it according to the canonicalization mode.
+    fn is_within_root(&self, path: &Path) -> Result<bool> {
+        let Self {
+            root,
+            canonicalization_mode,
+            ..
+        } = self;
+        let resolved_path = match canonicalization_mode {
+            CanonicalizationMode::Strict => path.canonicalize()?,
+            CanonicalizationMode::Lenient => Self::normalize(path)?,
+            CanonicalizationMode::None => Self::normalize_lexically(path),
+        };
+        Ok(resolved_path.starts_with(root))
+    }
+
+    /// Normalizes a path lexically, without accessing the filesystem.
+    fn normalize_lexically(path: &Path) -> PathBuf {
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {},
+                Component::ParentDir => {
+                    normalized.pop();
+                },
+                component => normalized.push(component),
+            }
+        }
+        normalized
+    }
+
+    /// Normalizes a path which may not exist yet, resolving symbolic links of its existing ancestors.
+    fn normalize(path: &Path) -> Result<PathBuf> {
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {},
+                Component::ParentDir => {
+                    normalized.pop();
+                },
+                component => {
+                    normalized.push(component);
+                    if normalized.exists() {
+                        normalized = normalized.canonicalize()?;
+                    }
+                },
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+impl Debug for InnerDirCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            root,
+            refresh_interval,
+            empty_is_invalid,
+            max_file_size,
+            auto_recreate_root,
+            write_check,
+            secure_permissions,
+            flat,
+            path_transformer,
+            observer,
+            clock,
+            limit_policy,
+            storage_quota,
+            max_entries,
+            key_policy,
+            cancel_token,
+            creation_mode,
+            error_recovery,
+            version,
+            generation,
+            frozen,
+            remove_on_drop,
+            canonicalization_mode,
+            #[cfg(feature = "fd-pool")]
+            fd_pool,
+            memory_cache,
+            lock_registry,
+            in_flight,
+            #[cfg(feature = "testing")]
+            fault_injector,
+        } = self;
+        let mut debug_struct = f.debug_struct("InnerDirCache");
+        debug_struct
+            .field("root", &root)
+            .field("refresh_interval", &refresh_interval)
+            .field("empty_is_invalid", &empty_is_invalid)
+            .field("max_file_size", &max_file_size)
+            .field("auto_recreate_root", &auto_recreate_root)
+            .field("write_check", &write_check)
+            .field("secure_permissions", &secure_permissions)
+            .field("flat", &flat)
+            .field("path_transformer", &path_transformer.as_ref().map(|_| "..."))
+            .field("observer", &observer.as_ref().map(|_| "..."))
+            .field("clock", &clock)
+            .field("limit_policy", &limit_policy)
+            .field("storage_quota", &storage_quota)
+            .field("max_entries", &max_entries)
+            .field("key_policy", &key_policy)
+            .field("cancel_token", &cancel_token)
+            .field("creation_mode", &creation_mode)
+            .field("error_recovery", &error_recovery)
+            .field("version", &version)
+            .field("generation", &generation)
+            .field("frozen", &frozen)
+            .field("remove_on_drop", &remove_on_drop)
+            .field("canonicalization_mode", &canonicalization_mode);
+        #[cfg(feature = "fd-pool")]
+        debug_struct.field("fd_pool", &fd_pool);
+        debug_struct
+            .field("memory_cache", &memory_cache)
+            .field("lock_registry", &lock_registry)
+            .field("in_flight", &in_flight);
+        #[cfg(feature = "testing")]
+        debug_struct.field("fault_injector", &fault_injector.as_ref().map(|_| "..."));
+        debug_struct.finish()
+    }
+}
+
+/// Inner cache implementation for a temporary directory.
+#[derive(Debug)]
+struct InnerTempCache {
+    /// Temporary directory for the cache
+    temp_dir: TempDir, // Keep the temporary directory alive for the lifetime of the cache
+    /// Directory cache implementation
+    dir_cache: InnerDirCache,
+}
+
+impl InnerTempCache {
+    /// Creates a new cache instance within a temporary directory created with the specified options.
+    fn with_options(options: &TempDirOptions) -> Result<Self> {
+        let temp_dir = options.tempdir()?;
+        InnerDirCache::new(temp_dir.path()).map(|dir_cache| Self { temp_dir, dir_cache })
+    }
+
+    /// Sets the refresh interval for the cache.
+    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_refresh_interval(refresh_interval);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the refresh interval to the default value.
+    fn with_default_refresh_interval(self) -> Self {
+        self.with_refresh_interval(DEFAULT_REFRESH_INTERVAL)
+    }
+
+    /// Disables automatic refreshing of files in the cache.
+    fn without_refresh(self) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.without_refresh();
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets whether empty files in the cache are considered invalid.
+    fn with_empty_is_invalid(self, empty_is_invalid: bool) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_empty_is_invalid(empty_is_invalid);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the maximum size of files in the cache.
+    fn with_max_file_size(self, max_file_size: u64) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_max_file_size(max_file_size);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets a transformer rewriting the location of files in the cache.
+    fn with_path_transformer(self, path_transformer: impl PathTransformerFn + 'static) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_path_transformer(path_transformer);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets an observer receiving the events of cache operations.
+    fn with_observer(self, observer: impl ObserverFn + 'static) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_observer(observer);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets a fault injector failing the operations of the cache on demand.
+    #[cfg(feature = "testing")]
+    fn with_fault_injector(self, fault_injector: impl FaultInjectorFn + 'static) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_fault_injector(fault_injector);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the clock used by the cache to determine the current time.
+    fn with_clock(self, clock: impl Clock + 'static) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_clock(clock);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets whether the cache directory is recreated when it disappears.
+    fn with_auto_recreate_root(self, auto_recreate_root: bool) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_auto_recreate_root(auto_recreate_root);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets whether the cache directory is probed for writability.
+    fn with_write_check(self, write_check: bool) -> Result<Self> {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_write_check(write_check)?;
+        Ok(Self { temp_dir, dir_cache })
+    }
+
+    /// Sets whether the cache uses restrictive permissions.
+    fn with_secure_permissions(self, secure_permissions: bool) -> Result<Self> {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_secure_permissions(secure_permissions)?;
+        Ok(Self { temp_dir, dir_cache })
+    }
+
+    /// Sets whether the cache is flat.
+    fn with_flat(self, flat: bool) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_flat(flat);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the policy limiting the files in the cache.
+    fn with_entry_limit_policy(self, limit_policy: LimitPolicy) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_entry_limit_policy(limit_policy);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the storage quota of the cache.
+    fn with_storage_quota(self, storage_quota: StorageQuota) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_storage_quota(storage_quota);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the maximum number of files in the cache.
+    fn with_max_entries(self, max_entries: usize) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_max_entries(max_entries);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the policy validating the paths of files requested from the cache.
+    fn with_key_policy(self, key_policy: KeyPolicy) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_key_policy(key_policy);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets how files which already exist in the cache are treated.
+    fn with_creation_mode(self, creation_mode: CreationMode) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_creation_mode(creation_mode);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets how failures of callbacks are handled.
+    fn with_error_recovery(self, error_recovery: ErrorRecovery) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_error_recovery(error_recovery);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets whether the files of the cache are removed when the cache is dropped.
+    fn with_cleanup_on_drop(self, cleanup_on_drop: bool) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_cleanup_on_drop(cleanup_on_drop);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets whether the whole content of the cache directory is removed when the cache is dropped.
+    fn with_cleanup_all_on_drop(self, cleanup_all_on_drop: bool) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_cleanup_all_on_drop(cleanup_all_on_drop);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the version token of files.
+    fn with_version(self, version: String) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_version(version);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets how paths of files are resolved when checking that they are within the cache directory.
+    fn with_path_canonicalization(self, canonicalization_mode: CanonicalizationMode) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_path_canonicalization(canonicalization_mode);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn with_fd_pool_size(self, fd_pool_size: usize) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_fd_pool_size(fd_pool_size);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Enables an in-memory layer keeping up to `max_bytes` bytes of small files.
+    fn with_memory_cache(self, max_bytes: usize) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_memory_cache(max_bytes);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Sets the maximum size of a single file kept in memory by the in-memory layer.
+    fn with_memory_cache_max_entry_size(self, max_entry_size: usize) -> Self {
+        let Self { temp_dir, dir_cache } = self;
+        let dir_cache = dir_cache.with_memory_cache_max_entry_size(max_entry_size);
+        Self { temp_dir, dir_cache }
+    }
+
+    /// Returns the path of the cache directory.
+    fn path(&self) -> &Path {
+        let Self { dir_cache, .. } = self;
+        dir_cache.path()
+    }
+
+    /// Returns the absolute path of the file with the given key.
+    fn path_for(&self, path: impl AsRef<Path>) -> Result<PathBuf> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.path_for(path)
+    }
+
+    /// Returns the refresh interval of the cache.
+    fn refresh_interval(&self) -> Option<Duration> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.refresh_interval()
+    }
+
+    /// Returns whether empty files in the cache are considered invalid.
+    fn empty_is_invalid(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.empty_is_invalid()
+    }
+
+    /// Returns the maximum size of files in the cache.
+    fn max_file_size(&self) -> Option<u64> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.max_file_size()
+    }
+
+    /// Returns whether the cache directory is recreated when it disappears.
+    fn auto_recreate_root(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.auto_recreate_root()
+    }
+
+    /// Returns whether the cache directory is probed for writability.
+    fn write_check(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.write_check()
+    }
+
+    /// Returns whether the cache uses restrictive permissions.
+    fn secure_permissions(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.secure_permissions()
+    }
+
+    /// Returns whether the cache is flat.
+    fn is_flat(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.is_flat()
+    }
+
+    /// Returns the policy limiting the files in the cache.
+    fn entry_limit_policy(&self) -> Option<LimitPolicy> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.entry_limit_policy()
+    }
+
+    /// Returns the storage quota of the cache, if any.
+    fn storage_quota(&self) -> Option<StorageQuota> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.storage_quota()
+    }
+
+    /// Returns the maximum number of files in the cache, if any.
+    fn max_entries(&self) -> Option<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.max_entries()
+    }
+
+    /// Returns the policy validating the paths of files requested from the cache.
+    fn key_policy(&self) -> KeyPolicy {
+        let Self { dir_cache, .. } = self;
+        dir_cache.key_policy()
+    }
+
+    /// Returns how files which already exist in the cache are treated.
+    fn creation_mode(&self) -> CreationMode {
+        let Self { dir_cache, .. } = self;
+        dir_cache.creation_mode()
     }
-}
 
-impl From<InnerCache> for Cache {
-    fn from(inner: InnerCache) -> Self {
-        Self(inner)
+    /// Returns how failures of callbacks are handled.
+    fn error_recovery(&self) -> &ErrorRecovery {
+        let Self { dir_cache, .. } = self;
+        dir_cache.error_recovery()
     }
-}
 
-/// Represents the inner cache implementation, either directory-based or temporary.
-#[derive(Debug)]
-enum InnerCache {
-    /// Directory cache implementation
-    Dir(InnerDirCache),
-    /// Temporary cache implementation
-    Temp(InnerTempCache),
-}
+    /// Returns the version token of files.
+    fn version(&self) -> Option<&str> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.version()
+    }
 
-impl InnerCache {
-    /// Creates a new cache instance within a specified directory.
-    fn dir(dir: impl AsRef<Path>) -> Result<Self> {
-        InnerDirCache::new(dir).map(Self::Dir)
+    /// Returns the generation of files.
+    fn generation(&self) -> u64 {
+        let Self { dir_cache, .. } = self;
+        dir_cache.generation()
     }
 
-    /// Creates a new cache instance within a temporary directory.
-    fn temp() -> Result<Self> {
-        InnerTempCache::new().map(Self::Temp)
+    /// Increments the generation of files.
+    fn bump_generation(&self) -> Result<()> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.bump_generation()
     }
 
-    /// Creates a new cache instance within a temporary directory with a specified prefix.
-    fn temp_with_prefix(prefix: &str) -> Result<Self> {
-        InnerTempCache::with_prefix(prefix).map(Self::Temp)
+    /// Returns how paths of files are resolved when checking that they are within the cache directory.
+    fn path_canonicalization(&self) -> CanonicalizationMode {
+        let Self { dir_cache, .. } = self;
+        dir_cache.path_canonicalization()
     }
 
-    /// Sets the refresh interval for the cache.
-    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.with_refresh_interval(refresh_interval).into(),
-            Self::Temp(temp_cache) => temp_cache.with_refresh_interval(refresh_interval).into(),
-        }
+    /// Returns the maximum number of open file descriptors pooled per file.
+    #[cfg(feature = "fd-pool")]
+    fn fd_pool_size(&self) -> usize {
+        let Self { dir_cache, .. } = self;
+        dir_cache.fd_pool_size()
     }
 
-    /// Sets the refresh interval to the default value.
-    fn with_default_refresh_interval(self) -> Self {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.with_default_refresh_interval().into(),
-            Self::Temp(temp_cache) => temp_cache.with_default_refresh_interval().into(),
-        }
+    /// Returns the maximum total size of files kept in memory by the in-memory layer.
+    fn memory_cache_max_bytes(&self) -> usize {
+        let Self { dir_cache, .. } = self;
+        dir_cache.memory_cache_max_bytes()
     }
 
-    /// Returns the path of the cache directory.
-    fn path(&self) -> &Path {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.path(),
-            Self::Temp(temp_cache) => temp_cache.path(),
-        }
+    /// Returns the maximum size of a single file kept in memory by the in-memory layer.
+    fn memory_cache_max_entry_size(&self) -> usize {
+        let Self { dir_cache, .. } = self;
+        dir_cache.memory_cache_max_entry_size()
     }
 
-    /// Returns the refresh interval of the cache.
-    fn refresh_interval(&self) -> Duration {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.refresh_interval(),
-            Self::Temp(temp_cache) => temp_cache.refresh_interval(),
-        }
+    /// Returns the token cancelling the creation and refreshing of files in the cache.
+    fn cancellation_token(&self) -> CancelToken {
+        let Self { dir_cache, .. } = self;
+        dir_cache.cancellation_token()
+    }
+
+    /// Freezes the cache, forbidding all writes.
+    fn freeze(&self) {
+        let Self { dir_cache, .. } = self;
+        dir_cache.freeze();
+    }
+
+    /// Thaws the cache, allowing writes again.
+    fn thaw(&self) {
+        let Self { dir_cache, .. } = self;
+        dir_cache.thaw();
+    }
+
+    /// Returns whether the cache is frozen.
+    fn is_frozen(&self) -> bool {
+        let Self { dir_cache, .. } = self;
+        dir_cache.is_frozen()
     }
 
     /// Creates a file in the cache using a callback for initialization.
     fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.get(path, callback),
-            Self::Temp(temp_cache) => temp_cache.get(path, callback),
-        }
+        let Self { dir_cache, .. } = self;
+        dir_cache.get(path, callback)
+    }
+
+    /// Creates a file in the cache with a size limit overriding the cache-wide one.
+    fn get_with_max_size<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl CallbackFn + 'static,
+        max_file_size: u64,
+    ) -> Result<CacheFile<'a>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.get_with_max_size(path, callback, max_file_size)
     }
 
     /// Creates a file in the cache that is lazily created when accessed.
@@ -755,187 +7320,247 @@ impl InnerCache {
         path: impl AsRef<Path>,
         callback: impl CallbackFn + 'static,
     ) -> Result<CacheLazyFile<'a>> {
-        match self {
-            Self::Dir(dir_cache) => dir_cache.get_lazy(path, callback),
-            Self::Temp(temp_cache) => temp_cache.get_lazy(path, callback),
-        }
+        let Self { dir_cache, .. } = self;
+        dir_cache.get_lazy(path, callback)
     }
-}
 
-impl From<InnerDirCache> for InnerCache {
-    fn from(dir_cache: InnerDirCache) -> Self {
-        Self::Dir(dir_cache)
+    /// Creates an append-only file in the cache.
+    fn get_appendable(
+        &self,
+        path: impl AsRef<Path>,
+        header_callback: impl CallbackFn + 'static,
+    ) -> Result<AppendFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.get_appendable(path, header_callback)
     }
-}
 
-impl From<InnerTempCache> for InnerCache {
-    fn from(temp_cache: InnerTempCache) -> Self {
-        Self::Temp(temp_cache)
+    /// Creates a file in the cache mirroring an external source file.
+    fn get_linked(
+        &self,
+        path: impl AsRef<Path>,
+        source: impl AsRef<Path>,
+        link_mode: LinkMode,
+    ) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.get_linked(path, source, link_mode)
     }
-}
 
-/// Inner cache implementation for a specified directory.
-#[derive(Debug)]
-struct InnerDirCache {
-    /// Directory where the cache is stored
-    root: PathBuf,
-    /// Refresh interval for the cache
-    refresh_interval: Duration,
-}
+    /// Predicts what [`get`](Self::get) would do without touching the filesystem.
+    fn dry_run_get(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<DryRunReport> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.dry_run_get(path, callback)
+    }
 
-impl InnerDirCache {
-    /// Creates a new cache instance within a specified directory.
-    fn new(dir: impl AsRef<Path>) -> Result<Self> {
-        let dir = dir.as_ref().to_path_buf();
+    /// Explains what accessing a file in the cache would do.
+    fn explain(&self, path: impl AsRef<Path>) -> Result<CacheExplanation> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.explain(path)
+    }
 
-        if dir.exists() && !dir.is_dir() {
-            return Err(Error::NotADirectory { path: dir });
-        } else if !dir.exists() {
-            fs::create_dir_all(&dir)?;
-        }
+    /// Returns the state of a file in the cache.
+    fn status(&self, path: impl AsRef<Path>) -> Result<EntryStatus> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.status(path)
+    }
 
-        // Canonicalize after ensuring the directory exists
-        let root = dir.canonicalize()?;
-        let refresh_interval = DEFAULT_REFRESH_INTERVAL;
-        let inner_dir_cache = Self { root, refresh_interval };
-        Ok(inner_dir_cache)
+    /// Checks if a file exists in the cache.
+    fn exists(&self, path: impl AsRef<Path>) -> Result<bool> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.exists(path)
     }
 
-    /// Sets the refresh interval for the cache.
-    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
-        let Self { root, .. } = self;
-        Self { root, refresh_interval }
+    /// Lists the files in the cache.
+    fn list(&self) -> Result<Vec<PathBuf>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.list()
     }
 
-    /// Sets the refresh interval to the default value.
-    fn with_default_refresh_interval(self) -> Self {
-        self.with_refresh_interval(DEFAULT_REFRESH_INTERVAL)
+    /// Returns the files in the cache along with their metadata.
+    fn entries(&self) -> Result<vec::IntoIter<EntryInfo>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.entries()
     }
 
-    /// Returns the path of the cache directory.
-    fn path(&self) -> &Path {
-        let Self { root, .. } = self;
-        root
+    /// Removes a file from the cache.
+    fn remove(&self, path: impl AsRef<Path>) -> Result<()> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.remove(path)
     }
 
-    /// Returns the refresh interval of the cache.
-    fn refresh_interval(&self) -> Duration {
-        let Self { refresh_interval, .. } = self;
-        *refresh_interval
+    /// Removes every file from the cache.
+    fn clear(&self) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.clear()
     }
 
-    /// Creates a file in the cache using a callback for initialization.
-    fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
-        self.get_lazy(path, callback)?.init()
+    /// Creates a file in the cache, or creates a handle for it if it already exists.
+    fn get_or_attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.get_or_attach(path, callback)
     }
 
-    /// Creates a file in the cache that is lazily created when accessed.
-    fn get_lazy<'a>(
-        &'a self,
-        path: impl AsRef<Path>,
-        callback: impl CallbackFn + 'static,
-    ) -> Result<CacheLazyFile<'a>> {
-        let Self { root, refresh_interval } = self;
-        let path = path.as_ref();
+    /// Creates a handle for a file which already exists in the cache.
+    fn attach(&self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.attach_existing(path, callback)
+    }
 
-        // Ensure the path does not end with a slash
-        if path.to_str().is_some_and(|path| path.ends_with('/')) {
-            let path = path.to_path_buf();
-            let error = Error::InvalidPath { path };
-            return Err(error);
-        }
+    /// Backs up the whole cache directory to the destination directory.
+    fn backup(&self, dest: impl AsRef<Path>) -> Result<BackupReport> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.backup(dest)
+    }
 
-        // Ensure the absolute path is within the cache directory to prevent path traversal attacks
-        let mut components = path.components();
-        let file_name = if let Some(component) = components.next_back()
-            && let Component::Normal(file_name) = component
-            && file_name.to_str().is_some_and(|file_name| file_name.trim() != "")
-        {
-            file_name
-        } else {
-            let path = path.to_path_buf();
-            let error = Error::InvalidPath { path };
-            return Err(error);
-        };
-        let mut path = root.clone();
-        for component in components {
-            path.push(component);
-            if !path.exists() {
-                fs::create_dir(&path)?;
-            }
-            let canonicalized_path = path.canonicalize()?;
-            if !canonicalized_path.starts_with(root) {
-                let cache_dir = root.clone();
-                let error = Error::PathTraversal { path, cache_dir };
-                return Err(error);
-            }
-        }
+    /// Closes the cache, waiting for the operations of file handles in progress and removing the staging files left behind by interrupted writes.
+    fn close(&self, timeout: Duration) -> Result<CloseReport> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.close(timeout)
+    }
 
-        let path = path.join(file_name);
-        CacheLazyFile::new(path, callback, *refresh_interval, root, refresh_interval)
+    /// Imports the files of a ZIP archive into the cache.
+    #[cfg(feature = "zip")]
+    fn import_zip(&self, zip_path: impl AsRef<Path>) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.import_zip(zip_path)
     }
-}
 
-/// Inner cache implementation for a temporary directory.
-#[derive(Debug)]
-struct InnerTempCache {
-    /// Temporary directory for the cache
-    temp_dir: TempDir, // Keep the temporary directory alive for the lifetime of the cache
-    /// Directory cache implementation
-    dir_cache: InnerDirCache,
-}
+    /// Exports the files of the cache into a ZIP archive.
+    #[cfg(feature = "zip")]
+    fn export_zip(&self, dest: impl AsRef<Path>, include_expired: bool) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.export_zip(dest, include_expired)
+    }
 
-impl InnerTempCache {
-    const DEFAULT_PREFIX: &str = "fcache";
+    /// Stores content in the cache under the SHA-256 digest of the content.
+    fn put_content_addressed(&self, data: impl Read) -> Result<(String, CacheFile<'_>)> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.put_content_addressed(data)
+    }
 
-    /// Creates a new cache instance within a temporary directory.
-    fn new() -> Result<Self> {
-        Self::with_prefix(Self::DEFAULT_PREFIX)
+    /// Creates a file in the cache with the content streamed from the reader.
+    fn put_from_reader(&self, path: impl AsRef<Path>, reader: impl Read) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.put_from_reader(path, reader)
     }
 
-    /// Creates a new cache instance within a temporary directory with a specified prefix.
-    fn with_prefix(prefix: &str) -> Result<Self> {
-        let temp_dir = tempfile::Builder::new().prefix(prefix).tempdir()?;
-        InnerDirCache::new(temp_dir.path()).map(|dir_cache| Self { temp_dir, dir_cache })
+    /// Opens a content-addressed object.
+    fn open_object(&self, digest: &str) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.open_object(digest)
     }
 
-    /// Sets the refresh interval for the cache.
-    fn with_refresh_interval(self, refresh_interval: Duration) -> Self {
-        let Self { temp_dir, dir_cache } = self;
-        let dir_cache = dir_cache.with_refresh_interval(refresh_interval);
-        Self { temp_dir, dir_cache }
+    /// Reassembles a file from its chunks.
+    fn reassemble_chunks(&self, prefix: &str) -> Result<CacheFile<'_>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.reassemble_chunks(prefix)
     }
 
-    /// Sets the refresh interval to the default value.
-    fn with_default_refresh_interval(self) -> Self {
-        self.with_refresh_interval(DEFAULT_REFRESH_INTERVAL)
+    /// Returns the content of a file, inserting the generated content if the file is missing or invalid.
+    fn open_or_insert_with(&self, path: impl AsRef<Path>, f: impl FnOnce() -> Vec<u8>) -> Result<Vec<u8>> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.open_or_insert_with(path, f)
     }
 
-    /// Returns the path of the cache directory.
-    fn path(&self) -> &Path {
+    /// Groups the files in the cache by their validity.
+    fn group_by_validity(&self) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
         let Self { dir_cache, .. } = self;
-        dir_cache.path()
+        dir_cache.group_by_validity()
     }
 
-    /// Returns the refresh interval of the cache.
-    fn refresh_interval(&self) -> Duration {
+    /// Returns the most recently modified files in the cache.
+    fn most_recent(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
         let Self { dir_cache, .. } = self;
-        dir_cache.refresh_interval()
+        dir_cache.most_recent(n)
     }
 
-    /// Creates a file in the cache using a callback for initialization.
-    fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl CallbackFn + 'static) -> Result<CacheFile<'a>> {
+    /// Returns the least recently modified files in the cache.
+    fn oldest(&self, n: usize) -> Result<Vec<(PathBuf, SystemTime)>> {
         let Self { dir_cache, .. } = self;
-        dir_cache.get(path, callback)
+        dir_cache.oldest(n)
     }
 
-    /// Creates a file in the cache that is lazily created when accessed.
-    fn get_lazy<'a>(
-        &'a self,
-        path: impl AsRef<Path>,
-        callback: impl CallbackFn + 'static,
-    ) -> Result<CacheLazyFile<'a>> {
+    /// Enforces the policy limiting the files in the cache.
+    fn enforce_limit_policy(&self) -> Result<usize> {
         let Self { dir_cache, .. } = self;
-        dir_cache.get_lazy(path, callback)
+        dir_cache.enforce_limit_policy(None)
+    }
+
+    /// Removes the files in the cache which nobody accessed for at least the given time.
+    fn prune_idle(&self, idle_timeout: Duration) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.prune_idle(idle_timeout)
+    }
+
+    /// Removes the files in the cache which expired.
+    fn purge_expired(&self) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.purge_expired()
+    }
+
+    /// Returns the current usage of the cache as the total size of files in bytes and the number of files.
+    fn quota_usage(&self) -> Result<(u64, usize)> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.quota_usage()
+    }
+
+    /// Returns the number of files in the cache.
+    fn file_count(&self) -> Result<usize> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.file_count()
+    }
+
+    /// Checks if the cache holds no files.
+    fn is_empty(&self) -> Result<bool> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.is_empty()
+    }
+
+    /// Returns the total size of the files in the cache in bytes.
+    fn total_size(&self) -> Result<u64> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.total_size()
+    }
+
+    /// Returns statistics of the files in the cache.
+    fn stats(&self) -> Result<CacheStats> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.stats()
+    }
+
+    /// Creates a cache within a subdirectory of the cache directory.
+    fn subdirectory(&self, path: impl AsRef<Path>) -> Result<InnerDirCache> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.subdirectory(path)
+    }
+
+    /// Creates a cache in a sibling directory of the cache directory.
+    fn new_sibling(&self, name: &str) -> Result<InnerDirCache> {
+        let Self { dir_cache, .. } = self;
+        dir_cache.new_sibling(name)
+    }
+
+    /// Moves the temporary directory to the target directory, returning a cache there which is never removed when dropped.
+    ///
+    /// The cache is given back along with the error if the directory cannot be moved, so the temporary directory is kept until it is dropped.
+    fn persist(self, target: &Path) -> Result<InnerDirCache, (Error, Box<Self>)> {
+        let Self { temp_dir, dir_cache } = self;
+        match dir_cache.persist(target) {
+            Result::Ok(dir_cache) => {
+                // The temporary directory is gone already
+                let _ = temp_dir.keep();
+                Result::Ok(dir_cache)
+            },
+            Err((error, dir_cache)) => {
+                let dir_cache = *dir_cache;
+                Err((error, Box::new(Self { temp_dir, dir_cache })))
+            },
+        }
+    }
+
+    /// Consumes the cache, returning the path of the temporary directory, which is kept instead of being removed.
+    fn into_path(self) -> PathBuf {
+        let Self { temp_dir, dir_cache } = self;
+        let _ = temp_dir.keep();
+        dir_cache.into_path()
     }
 }
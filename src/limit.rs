@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Combined limits on the files in the cache, enforced by evicting files.
+///
+/// Check the [`Cache::with_entry_limit_policy`] method for more details on how the limits are enforced.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use fcache::LimitPolicy;
+///
+/// // Keep at most 100 files, at most 500 MiB, and no file older than 7 days
+/// let policy = LimitPolicy::new()
+///     .max_entries(100)
+///     .max_size_bytes(500 * 1024 * 1024)
+///     .max_age(Duration::from_secs(7 * 24 * 60 * 60));
+/// # let _ = policy;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LimitPolicy {
+    /// Maximum number of files
+    max_entries: Option<usize>,
+    /// Maximum total size of files in bytes
+    max_size_bytes: Option<u64>,
+    /// Maximum age of files, based on their modification time
+    max_age: Option<Duration>,
+}
+
+impl LimitPolicy {
+    /// Creates a new policy without any limits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new();
+    /// assert_eq!(policy.get_max_entries(), None);
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of files in the cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new().max_entries(100);
+    /// assert_eq!(policy.get_max_entries(), Some(100));
+    /// ```
+    #[must_use]
+    pub fn max_entries(self, max_entries: usize) -> Self {
+        let max_entries = Some(max_entries);
+        Self { max_entries, ..self }
+    }
+
+    /// Limits the total size of files in the cache in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new().max_size_bytes(1024 * 1024);
+    /// assert_eq!(policy.get_max_size_bytes(), Some(1024 * 1024));
+    /// ```
+    #[must_use]
+    pub fn max_size_bytes(self, max_size_bytes: u64) -> Self {
+        let max_size_bytes = Some(max_size_bytes);
+        Self { max_size_bytes, ..self }
+    }
+
+    /// Limits the age of files in the cache, based on their modification time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new().max_age(Duration::from_secs(60 * 60));
+    /// assert_eq!(policy.get_max_age(), Some(Duration::from_secs(60 * 60)));
+    /// ```
+    #[must_use]
+    pub fn max_age(self, max_age: Duration) -> Self {
+        let max_age = Some(max_age);
+        Self { max_age, ..self }
+    }
+
+    /// Returns the maximum number of files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new();
+    /// assert_eq!(policy.get_max_entries(), None);
+    /// ```
+    #[must_use]
+    pub fn get_max_entries(&self) -> Option<usize> {
+        let Self { max_entries, .. } = self;
+        *max_entries
+    }
+
+    /// Returns the maximum total size of files in bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new();
+    /// assert_eq!(policy.get_max_size_bytes(), None);
+    /// ```
+    #[must_use]
+    pub fn get_max_size_bytes(&self) -> Option<u64> {
+        let Self { max_size_bytes, .. } = self;
+        *max_size_bytes
+    }
+
+    /// Returns the maximum age of files.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::LimitPolicy;
+    ///
+    /// let policy = LimitPolicy::new();
+    /// assert_eq!(policy.get_max_age(), None);
+    /// ```
+    #[must_use]
+    pub fn get_max_age(&self) -> Option<Duration> {
+        let Self { max_age, .. } = self;
+        *max_age
+    }
+}
@@ -0,0 +1,40 @@
+use std::io;
+use std::path::Path;
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Describes how a linked cache file mirrors its source file.
+///
+/// Check the [`Cache::get_linked`] method for more details on how to use this enum.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::LinkMode;
+///
+/// // Copy the source into the cache whenever the source is newer
+/// let mode = LinkMode::Copy;
+/// # let _ = mode;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkMode {
+    /// The source is copied into the cache, and copied again once the source is modified.
+    Copy,
+    /// A symbolic link pointing at the source is created in the cache.
+    ///
+    /// Opening the file follows the link, so the content always reflects the source.
+    Symlink,
+}
+
+/// Creates a symbolic link at `path` pointing at `source`.
+#[cfg(unix)]
+pub(crate) fn symlink(source: &Path, path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(source, path)
+}
+
+/// Creates a symbolic link at `path` pointing at `source`.
+#[cfg(windows)]
+pub(crate) fn symlink(source: &Path, path: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(source, path)
+}
@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::SystemTime;
+
+/// Lock of a file handle, optionally expiring at a given time.
+#[derive(Debug, Default)]
+pub(crate) struct FileLock {
+    /// Whether the lock is held
+    locked: AtomicBool,
+    /// When the lock expires, if it was acquired for a limited time
+    expires_at: Mutex<Option<SystemTime>>,
+}
+
+impl FileLock {
+    /// Acquires the lock until the given time, or indefinitely, returning whether it wasn't held yet.
+    pub(crate) fn acquire(&self, now: SystemTime, expires_at: Option<SystemTime>) -> bool {
+        let Self {
+            locked,
+            expires_at: lock_expires_at,
+        } = self;
+        self.release_expired(now);
+        let mut lock_expires_at = lock_expires_at.lock().unwrap_or_else(PoisonError::into_inner);
+        locked
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|_| *lock_expires_at = expires_at)
+            .is_ok()
+    }
+
+    /// Releases the lock, returning whether it was held, including until it expired.
+    pub(crate) fn release(&self, now: SystemTime) -> bool {
+        let Self { locked, expires_at } = self;
+        // Expired locks were held until now, so releasing them succeeds
+        if self.release_expired(now) {
+            return true;
+        }
+        let mut expires_at = expires_at.lock().unwrap_or_else(PoisonError::into_inner);
+        locked
+            .compare_exchange(true, false, Ordering::SeqCst, Ordering::SeqCst)
+            .map(|_| *expires_at = None)
+            .is_ok()
+    }
+
+    /// Returns whether the lock is held and hasn't expired.
+    pub(crate) fn is_held(&self, now: SystemTime) -> bool {
+        let Self { locked, .. } = self;
+        self.release_expired(now);
+        locked.load(Ordering::SeqCst)
+    }
+
+    /// Releases the lock if it expired, returning whether it did.
+    fn release_expired(&self, now: SystemTime) -> bool {
+        let Self { locked, expires_at } = self;
+        let mut expires_at = expires_at.lock().unwrap_or_else(PoisonError::into_inner);
+        match *expires_at {
+            Some(lock_expires_at) if now >= lock_expires_at => {
+                *expires_at = None;
+                locked.store(false, Ordering::SeqCst);
+                true
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Locks of the file handles of a cache, kept without keeping the handles alive.
+#[derive(Debug, Default)]
+pub(crate) struct LockRegistry {
+    /// Path of each file along with the lock of a handle of the file
+    locks: Mutex<Vec<(PathBuf, Weak<FileLock>)>>,
+    /// Paths of the files being created, kept from being evicted until they are
+    creating: Mutex<Vec<PathBuf>>,
+}
+
+/// Guard marking a file as being created until it is dropped.
+#[derive(Debug)]
+pub(crate) struct CreationGuard<'a> {
+    /// Registry the file is marked in
+    registry: &'a LockRegistry,
+    /// Path of the file
+    path: PathBuf,
+}
+
+impl LockRegistry {
+    /// Registers the lock of a handle of the file, unless it is already registered.
+    pub(crate) fn register(&self, path: &Path, lock: &Arc<FileLock>) {
+        let Self { locks, .. } = self;
+        let mut locks = locks.lock().unwrap_or_else(PoisonError::into_inner);
+        // Locks of dropped handles are no longer held
+        locks.retain(|(_, registered_lock)| registered_lock.strong_count() > 0);
+        let lock = Arc::downgrade(lock);
+        if !locks.iter().any(|(_, registered_lock)| registered_lock.ptr_eq(&lock)) {
+            locks.push((path.to_path_buf(), lock));
+        }
+    }
+
+    /// Returns whether the lock of a handle of any file within the directory is held.
+    pub(crate) fn is_held_within(&self, dir: &Path, now: SystemTime) -> bool {
+        let Self { locks, .. } = self;
+        let locks = locks.lock().unwrap_or_else(PoisonError::into_inner);
+        locks
+            .iter()
+            .filter(|(path, _)| path.starts_with(dir))
+            .any(|(_, lock)| lock.upgrade().is_some_and(|lock| lock.is_held(now)))
+    }
+
+    /// Marks the file as being created until the returned guard is dropped.
+    pub(crate) fn begin_creation(&self, path: &Path) -> CreationGuard<'_> {
+        let Self { creating, .. } = self;
+        let path = path.to_path_buf();
+        creating
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(path.clone());
+        CreationGuard { registry: self, path }
+    }
+
+    /// Returns whether the file is being created.
+    pub(crate) fn is_creating(&self, path: &Path) -> bool {
+        let Self { creating, .. } = self;
+        let creating = creating.lock().unwrap_or_else(PoisonError::into_inner);
+        creating.iter().any(|creating_path| creating_path == path)
+    }
+}
+
+impl Drop for CreationGuard<'_> {
+    fn drop(&mut self) {
+        let Self { registry, path } = self;
+        let mut creating = registry.creating.lock().unwrap_or_else(PoisonError::into_inner);
+        // Files may be created by several handles at once, so only one mark is removed
+        if let Some(index) = creating.iter().position(|creating_path| creating_path == path) {
+            creating.swap_remove(index);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+
+use crate::result::Result;
+
+/// Default maximum size of a file kept in memory, in bytes
+pub(crate) const DEFAULT_MAX_ENTRY_SIZE: usize = 64 * 1024;
+
+/// In-memory copies of small files, shared between the handles of the same files and evicted in least recently used order.
+#[derive(Debug)]
+pub(crate) struct MemoryCache {
+    /// Maximum total size of the kept content in bytes, where `0` disables the layer
+    max_bytes: usize,
+    /// Maximum size of a single file kept in memory in bytes
+    max_entry_size: usize,
+    /// Kept content of each file
+    entries: Mutex<MemoryEntries>,
+}
+
+/// Kept content of files, along with the bookkeeping of their use.
+#[derive(Debug, Default)]
+struct MemoryEntries {
+    /// Content of each file, with the modification time and size it was read at, and when it was last used
+    entries: HashMap<PathBuf, MemoryEntry>,
+    /// Total size of the kept content in bytes
+    bytes: usize,
+    /// Counter ordering the uses of the entries
+    tick: u64,
+}
+
+/// Content of a file kept in memory.
+#[derive(Debug)]
+struct MemoryEntry {
+    /// Content of the file
+    content: Arc<[u8]>,
+    /// Modification time of the file when it was read
+    modified: SystemTime,
+    /// Size of the file when it was read
+    len: u64,
+    /// Tick of the last use of the entry
+    used_at: u64,
+}
+
+impl MemoryCache {
+    /// Creates an empty layer keeping at most `max_bytes` bytes of files of at most `max_entry_size` bytes each.
+    pub(crate) fn new(max_bytes: usize, max_entry_size: usize) -> Self {
+        let entries = Mutex::new(MemoryEntries::default());
+        Self {
+            max_bytes,
+            max_entry_size,
+            entries,
+        }
+    }
+
+    /// Returns the maximum total size of the kept content in bytes.
+    pub(crate) fn max_bytes(&self) -> usize {
+        let Self { max_bytes, .. } = self;
+        *max_bytes
+    }
+
+    /// Returns the maximum size of a single file kept in memory in bytes.
+    pub(crate) fn max_entry_size(&self) -> usize {
+        let Self { max_entry_size, .. } = self;
+        *max_entry_size
+    }
+
+    /// Returns the kept content of the file, reading it with `read` unless it is kept and unchanged on disk according to the given metadata.
+    ///
+    /// Files larger than the maximum entry size are read every time without being kept.
+    pub(crate) fn get(
+        &self,
+        path: &Path,
+        metadata: &Metadata,
+        read: impl FnOnce() -> Result<Vec<u8>>,
+    ) -> Result<Arc<[u8]>> {
+        let Self {
+            max_bytes,
+            max_entry_size,
+            entries,
+        } = self;
+        let modified = metadata.modified()?;
+        let len = metadata.len();
+        if *max_bytes == 0 || len > *max_entry_size as u64 {
+            return read().map(Arc::from);
+        }
+
+        let mut entries = entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.tick += 1;
+        let tick = entries.tick;
+        if let Some(entry) = entries.entries.get_mut(path)
+            && entry.modified == modified
+            && entry.len == len
+        {
+            entry.used_at = tick;
+            return Ok(entry.content.clone());
+        }
+
+        // Files may have grown since their metadata was read
+        let content: Arc<[u8]> = Arc::from(read()?);
+        entries.remove(path);
+        if content.len() <= *max_entry_size {
+            entries.bytes += content.len();
+            let entry = MemoryEntry {
+                content: content.clone(),
+                modified,
+                len,
+                used_at: tick,
+            };
+            entries.entries.insert(path.to_path_buf(), entry);
+            entries.evict(*max_bytes);
+        }
+        Ok(content)
+    }
+
+    /// Drops the kept content of the file.
+    pub(crate) fn invalidate(&self, path: &Path) {
+        let Self { entries, .. } = self;
+        entries.lock().unwrap_or_else(PoisonError::into_inner).remove(path);
+    }
+}
+
+impl MemoryEntries {
+    /// Drops the content of the file, if kept.
+    fn remove(&mut self, path: &Path) {
+        let Self { entries, bytes, .. } = self;
+        if let Some(entry) = entries.remove(path) {
+            *bytes -= entry.content.len();
+        }
+    }
+
+    /// Drops the least recently used content until the total size fits within the limit.
+    fn evict(&mut self, max_bytes: usize) {
+        while self.bytes > max_bytes {
+            let Self { entries, .. } = self;
+            let Some(path) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used_at)
+                .map(|(path, _)| path.clone())
+            else {
+                break;
+            };
+            self.remove(&path);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::result::Result;
+
+/// Number of bytes inspected when sniffing the content type
+const SNIFF_LEN: u64 = 512;
+
+/// Content types by file extension
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("webp", "image/webp"),
+    ("xml", "application/xml"),
+    ("zip", "application/zip"),
+];
+
+/// Content types by leading magic bytes
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x1f\x8b", "application/gzip"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"%PDF-", "application/pdf"),
+];
+
+/// Detects the content type of a file from its leading bytes.
+///
+/// Known magic numbers (gzip, zip, PNG, JPEG, PDF) are recognized first, then content that is valid UTF-8 is reported as `text/plain`.
+/// Returns `None` for empty files and unrecognized binary content, leaving the fallback (e.g. `application/octet-stream`) to the caller.
+///
+/// # Example
+///
+/// ```rust
+/// # fn wrapper() -> fcache::Result<()> {
+/// # let temp_dir = tempfile::TempDir::new()?;
+/// # let path = temp_dir.path().join("image");
+/// # std::fs::write(&path, b"\x89PNG\r\n\x1a\n")?;
+/// // Detect the content type of a file without an extension
+/// assert_eq!(fcache::sniff(&path)?, Some("image/png"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the file cannot be opened or read.
+pub fn sniff(path: impl AsRef<Path>) -> Result<Option<&'static str>> {
+    let mut head = Vec::new();
+    File::open(path)?.take(SNIFF_LEN).read_to_end(&mut head)?;
+
+    let content_type = MAGIC_NUMBERS
+        .iter()
+        .find(|(magic_number, _)| head.starts_with(magic_number))
+        .map(|(_, content_type)| *content_type);
+    if content_type.is_some() || head.is_empty() {
+        return Ok(content_type);
+    }
+
+    // The head may end in the middle of a multibyte character
+    match std::str::from_utf8(&head) {
+        Ok(_) => Ok(Some("text/plain")),
+        Err(error) if error.error_len().is_none() => Ok(Some("text/plain")),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Detects the content type of a file from its extension.
+pub(crate) fn from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?;
+    EXTENSIONS
+        .iter()
+        .find(|(known_extension, _)| known_extension.eq_ignore_ascii_case(extension))
+        .map(|(_, content_type)| *content_type)
+}
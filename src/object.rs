@@ -0,0 +1,39 @@
+use std::io::{self, Write};
+
+use sha2::{Digest, Sha256};
+
+/// Writer computing the SHA-256 digest of all data written through it.
+pub(crate) struct DigestWriter<W> {
+    /// Underlying writer
+    inner: W,
+    /// Digest of the data written so far
+    hasher: Sha256,
+}
+
+impl<W: Write> DigestWriter<W> {
+    /// Creates a new digest writer on top of the given writer.
+    pub(crate) fn new(inner: W) -> Self {
+        let hasher = Sha256::new();
+        Self { inner, hasher }
+    }
+
+    /// Returns the hex-encoded digest of the data written so far.
+    pub(crate) fn hex_digest(self) -> String {
+        let Self { hasher, .. } = self;
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+impl<W: Write> Write for DigestWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Self { inner, hasher } = self;
+        let length = inner.write(buf)?;
+        hasher.update(&buf[..length]);
+        Ok(length)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let Self { inner, .. } = self;
+        inner.flush()
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Seek;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use crate::result::Result;
+
+/// Default maximum number of pooled descriptors per file
+pub(crate) const DEFAULT_FD_POOL_SIZE: usize = 1;
+
+/// Pool of open file descriptors, shared between the handles of the same files.
+#[derive(Debug)]
+pub(crate) struct FileDescriptorPool {
+    /// Maximum number of descriptors pooled per file
+    size: usize,
+    /// Pooled descriptors of each file, and the index of the descriptor to hand out next
+    entries: Mutex<HashMap<PathBuf, (Vec<File>, usize)>>,
+}
+
+impl FileDescriptorPool {
+    /// Creates an empty pool keeping at most `size` descriptors per file.
+    pub(crate) fn new(size: usize) -> Self {
+        let entries = Mutex::new(HashMap::new());
+        Self { size, entries }
+    }
+
+    /// Returns the maximum number of descriptors pooled per file.
+    pub(crate) fn size(&self) -> usize {
+        let Self { size, .. } = self;
+        *size
+    }
+
+    /// Returns a descriptor of the file, opening a new one with `open` until the pool of the file is full.
+    ///
+    /// Pooled descriptors are duplicated and rewound before they are handed out.
+    pub(crate) fn get(&self, path: &Path, open: impl FnOnce() -> Result<File>) -> Result<File> {
+        let Self { size, entries } = self;
+        if *size == 0 {
+            return open();
+        }
+
+        let mut entries = entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let (files, next) = entries.entry(path.to_path_buf()).or_default();
+        if files.len() < *size {
+            let file = open()?;
+            files.push(file.try_clone()?);
+            return Ok(file);
+        }
+
+        // Hand out the pooled descriptors in turns
+        let mut file = files[*next].try_clone()?;
+        *next = (*next + 1) % files.len();
+        file.rewind()?;
+        Ok(file)
+    }
+
+    /// Drops the pooled descriptors of the file.
+    pub(crate) fn invalidate(&self, path: &Path) {
+        let Self { entries, .. } = self;
+        let _ = entries.lock().unwrap_or_else(PoisonError::into_inner).remove(path);
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(doc)]
+use crate::Cache;
+
+/// Storage budget of a cache, enforced by evicting files.
+///
+/// Check the [`Cache::with_storage_quota`] method for more details on how the quota is enforced.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::{EvictionPolicy, StorageQuota};
+///
+/// // Keep at most 1000 files of at most 100 MiB in total, evicting the largest files first
+/// let quota = StorageQuota {
+///     max_bytes: 100 * 1024 * 1024,
+///     max_files: 1000,
+///     eviction: EvictionPolicy::LargestFirst,
+/// };
+/// # let _ = quota;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageQuota {
+    /// Maximum total size of files in bytes
+    pub max_bytes: u64,
+    /// Maximum number of files
+    pub max_files: usize,
+    /// Order in which files are evicted when the quota is exceeded
+    pub eviction: EvictionPolicy,
+}
+
+/// Describes which files are evicted first when a [`StorageQuota`] is exceeded.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::EvictionPolicy;
+///
+/// // Evict the least recently modified files first
+/// let eviction = EvictionPolicy::default();
+/// assert_eq!(eviction, EvictionPolicy::LeastRecentlyModified);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// The least recently modified files are evicted first.
+    #[default]
+    LeastRecentlyModified,
+    /// The largest files are evicted first.
+    LargestFirst,
+}
@@ -0,0 +1,76 @@
+use std::fmt::{self, Debug};
+
+use crate::callback::CallbackFn;
+#[cfg(doc)]
+use crate::{Cache, Error};
+
+/// Describes how failures of the callback are handled when a file is created or refreshed.
+///
+/// Check the [`Cache::with_error_recovery`] method for more details on how to use this enum.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::ErrorRecovery;
+///
+/// // Retry the callback up to three times before giving up
+/// let strategy = ErrorRecovery::DeleteAndRetry { max_retries: 3 };
+/// # let _ = strategy;
+/// ```
+#[derive(Default)]
+pub enum ErrorRecovery {
+    /// The error is returned as [`Error::Callback`].
+    ///
+    /// A file being created is removed, while a file being refreshed keeps the partially written content.
+    #[default]
+    Propagate,
+    /// The partially written file is discarded and the callback retried up to `max_retries` times,
+    /// after which the file is removed and the last error returned.
+    DeleteAndRetry {
+        /// Maximum number of retries after the first failure
+        max_retries: u32,
+    },
+    /// The partially written file is removed and the error returned, so the file is created again on the next access.
+    DeleteAndSkip,
+    /// The partially written file is discarded and the alternate callback run instead,
+    /// after which the file is removed and the error returned if the alternate callback fails too.
+    Fallback(Box<dyn CallbackFn>),
+}
+
+impl ErrorRecovery {
+    /// Creates a strategy running the given alternate callback when the callback fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    ///
+    /// use fcache::ErrorRecovery;
+    ///
+    /// // Write a placeholder when the content cannot be generated
+    /// let strategy = ErrorRecovery::fallback(|mut file| {
+    ///     file.write_all(b"placeholder")?;
+    ///     Ok(())
+    /// });
+    /// # let _ = strategy;
+    /// ```
+    #[must_use]
+    pub fn fallback(callback: impl CallbackFn + 'static) -> Self {
+        Self::Fallback(Box::new(callback))
+    }
+}
+
+impl Debug for ErrorRecovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Propagate => f.write_str("Propagate"),
+            Self::DeleteAndRetry { max_retries } => {
+                f.debug_struct("DeleteAndRetry")
+                    .field("max_retries", max_retries)
+                    .finish()
+            },
+            Self::DeleteAndSkip => f.write_str("DeleteAndSkip"),
+            Self::Fallback(_) => f.debug_tuple("Fallback").field(&"...").finish(),
+        }
+    }
+}
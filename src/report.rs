@@ -0,0 +1,268 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::result::Error;
+#[cfg(doc)]
+use crate::{Cache, CacheFile, CacheLazyFile};
+
+/// Predicted outcome of a [`Cache::get`] call, as returned by [`Cache::dry_run_get`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+///
+/// // Check what would happen without running the callback
+/// let report = cache.dry_run_get("data.txt", |_| Ok(()))?;
+/// assert!(report.would_create);
+/// assert!(report.would_error.is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DryRunReport {
+    /// Whether the file would be created and the callback invoked.
+    pub would_create: bool,
+    /// Whether the file already exists and is expired, so it would be refreshed on the next access.
+    pub would_refresh: bool,
+    /// Error that the call would return, e.g. [`Error::PathTraversal`] or [`Error::FileAlreadyExists`].
+    pub would_error: Option<Error>,
+    /// Resolved path of the file, or the requested path if it could not be resolved.
+    pub path: PathBuf,
+    /// Estimated duration of the call, if known.
+    ///
+    /// The duration of the callback cannot be predicted without running it, so this is currently always `None`.
+    pub estimated_duration: Option<Duration>,
+}
+
+/// Decision taken on the next access of a file, as reported by [`CacheExplanation::decision`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheDecision {
+    /// The file doesn't exist, so it would be created and the callback invoked.
+    WouldCreate,
+    /// The file is valid, so it would be used as it is without invoking the callback.
+    WouldSkip,
+    /// The file is invalid, so it would be refreshed and the callback invoked.
+    WouldRefresh,
+    /// The file is locked, so it would not be refreshed.
+    Locked,
+}
+
+/// Trace of the checks deciding whether a file is created, refreshed, or used as it is.
+///
+/// Check the [`Cache::explain`] and [`CacheLazyFile::explain`] methods for more details on how to obtain it.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::CacheDecision;
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+///
+/// // Explain why the callback would be invoked
+/// let explanation = cache.explain("data.txt")?;
+/// assert!(!explanation.exists);
+/// assert_eq!(explanation.decision, CacheDecision::WouldCreate);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CacheExplanation {
+    /// Resolved path of the file.
+    pub path: PathBuf,
+    /// Whether the file exists.
+    pub exists: bool,
+    /// Size of the file in bytes, if it exists.
+    pub file_size: Option<u64>,
+    /// Modification time of the file, if it exists.
+    pub mtime: Option<SystemTime>,
+    /// Time elapsed since the modification of the file, if it exists.
+    pub age: Option<Duration>,
+    /// Refresh interval applied to the file.
+    pub refresh_interval: Duration,
+    /// Whether the file is locked.
+    pub is_locked: bool,
+    /// Whether the file is valid, if it exists.
+    pub is_valid: Option<bool>,
+    /// Decision taken on the next access of the file.
+    pub decision: CacheDecision,
+}
+
+/// State of a file in the cache, as returned by [`Cache::status`] and [`CacheFile::status`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::EntryStatus;
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+///
+/// // Diagnose the file in one call
+/// match cache.status("data.txt")? {
+///     EntryStatus::Missing => println!("missing"),
+///     EntryStatus::Present { valid, size, .. } => println!("{size} bytes, valid: {valid}"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EntryStatus {
+    /// The file doesn't exist.
+    Missing,
+    /// The file exists.
+    Present {
+        /// Whether the file is valid.
+        valid: bool,
+        /// Whether the file is locked.
+        locked: bool,
+        /// Size of the file in bytes.
+        size: u64,
+        /// Modification time of the file.
+        modified: SystemTime,
+        /// Time until the file is valid, or `None` if it never expires.
+        expires: Option<SystemTime>,
+    },
+}
+
+/// Summary of a backup, as returned by [`Cache::backup`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+/// let _ = cache.get("data.txt", |mut file| {
+///     file.write_all(b"data")?;
+///     Ok(())
+/// })?;
+///
+/// // Back up the cache
+/// let backup_dir = tempfile::tempdir()?;
+/// let report = cache.backup(backup_dir.path())?;
+/// assert_eq!(report.files_copied, 1);
+/// assert_eq!(report.bytes_written, 4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct BackupReport {
+    /// Number of copied files.
+    pub files_copied: usize,
+    /// Number of written bytes.
+    pub bytes_written: u64,
+    /// Canonicalized path of the backup directory.
+    pub dest: PathBuf,
+}
+
+/// Summary of closing a cache, as returned by [`Cache::close`].
+///
+/// # Example
+///
+/// ```rust
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+///
+/// // Close the cache, cleaning up after interrupted writes
+/// let report = cache.close()?;
+/// println!("removed {} temporary files", report.temp_files_removed);
+/// println!(
+///     "{} operations completed, {} abandoned",
+///     report.operations_completed, report.operations_abandoned
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CloseReport {
+    /// Number of removed temporary files left behind by interrupted writes.
+    pub temp_files_removed: usize,
+    /// Number of operations of file handles which completed while the cache was closed.
+    pub operations_completed: usize,
+    /// Number of operations of file handles still in progress when the timeout elapsed.
+    pub operations_abandoned: usize,
+}
+
+/// Description of a file in the cache, as returned by [`Cache::entries`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+/// let _ = cache.get("data.txt", |mut file| {
+///     file.write_all(b"data")?;
+///     Ok(())
+/// })?;
+///
+/// // Print the files of the cache
+/// for entry in cache.entries()? {
+///     println!("{}: {} bytes", entry.key.display(), entry.size);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EntryInfo {
+    /// Path of the file relative to the cache directory.
+    pub key: PathBuf,
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Time the file was last modified.
+    pub modified: SystemTime,
+}
+
+/// Statistics of the files in the cache, as returned by [`Cache::stats`].
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = fcache::new()?;
+/// let _ = cache.get("reports/report.json", |mut file| {
+///     file.write_all(b"{}")?;
+///     Ok(())
+/// })?;
+///
+/// // Check how much space the cache takes
+/// let stats = cache.stats()?;
+/// println!(
+///     "{} files in {} directories take {} bytes",
+///     stats.file_count, stats.dir_count, stats.total_bytes
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheStats {
+    /// Total size of the files in bytes.
+    pub total_bytes: u64,
+    /// Number of files.
+    pub file_count: usize,
+    /// Number of directories within the cache directory, not counting the cache directory itself.
+    pub dir_count: usize,
+    /// Modification time of the least recently modified file, or `None` if there are no files.
+    pub oldest_modified: Option<SystemTime>,
+    /// Modification time of the most recently modified file, or `None` if there are no files.
+    pub newest_modified: Option<SystemTime>,
+}
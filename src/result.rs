@@ -1,13 +1,19 @@
 use std::fmt::Debug;
 use std::path::PathBuf;
-use std::time::SystemTimeError;
+use std::string::FromUtf8Error;
+use std::time::{Duration, SystemTimeError};
 use std::{error, io, result};
 
 use thiserror::Error;
 
+use crate::Cache;
+
 /// Custom error types for the cache operations.
+///
+/// The type parameter `E` is the type of errors returned by callback functions, which is
+/// boxed by default. Typed callbacks (see [`Cache::get_typed`](crate::Cache::get_typed)) keep their errors unboxed.
 #[derive(Error, Debug)]
-pub enum Error {
+pub enum Error<E = Box<dyn error::Error + Send + Sync>> {
     /// The specified path exists but is not a directory.
     ///
     /// This error occurs when trying to create a cache in a location
@@ -15,6 +21,48 @@ pub enum Error {
     #[error("Path is not a directory: {path}")]
     NotADirectory { path: PathBuf },
 
+    /// The cache directory does not exist.
+    ///
+    /// This error occurs when opening a cache in a directory which is
+    /// expected to be provisioned beforehand, but is missing.
+    #[error("Cache directory does not exist: {path}")]
+    CacheDirMissing { path: PathBuf },
+
+    /// The cache directory does not exist anymore.
+    ///
+    /// This error occurs when the cache directory has been removed while
+    /// the cache is in use, and automatic recreation is disabled.
+    #[error("Cache directory is missing: {path}")]
+    CacheRootMissing { path: PathBuf },
+
+    /// The cache directory is not writable.
+    ///
+    /// This error occurs when the probe file cannot be created or deleted
+    /// in the cache directory, e.g. due to a wrong ownership of the directory.
+    #[error("Cache directory is not writable: {path}")]
+    CacheNotWritable { path: PathBuf, source: io::Error },
+
+    /// The cache directory has insecure permissions.
+    ///
+    /// This error occurs when the cache uses restrictive permissions, but
+    /// its directory is writable by the group or others.
+    #[error("Cache directory has insecure permissions: {path} (mode {mode:o})")]
+    InsecurePermissions { path: PathBuf, mode: u32 },
+
+    /// The cache directory is not empty.
+    ///
+    /// This error occurs when opening a cache in a directory which must be
+    /// empty, but already contains files or directories.
+    #[error("Directory is not empty: {path}")]
+    DirectoryNotEmpty { path: PathBuf },
+
+    /// The cache directory lacks the marker file.
+    ///
+    /// This error occurs when opening a cache in a directory which must
+    /// contain the marker file, but was not created as a cache directory.
+    #[error("Directory is not marked as a cache directory: {path}")]
+    MissingMarker { path: PathBuf },
+
     /// Path traversal attempt detected outside the cache directory.
     ///
     /// This error occurs when a file path would escape the cache directory
@@ -26,8 +74,8 @@ pub enum Error {
     ///
     /// This error occurs when a file path is not valid, such as when it contains
     /// invalid characters or is otherwise malformed.
-    #[error("Invalid path: {path}")]
-    InvalidPath { path: PathBuf },
+    #[error("Invalid path: {path} ({reason})")]
+    InvalidPath { path: PathBuf, reason: String },
 
     /// The specified path has no parent directory.
     ///
@@ -43,9 +91,96 @@ pub enum Error {
     #[error("File already exists: {path}")]
     FileAlreadyExists { path: PathBuf },
 
+    /// The file does not exist.
+    ///
+    /// This error occurs when trying to access a file that
+    /// is expected to exist in the cache, but is missing.
+    #[error("File not found: {path}")]
+    FileNotFound { path: PathBuf },
+
+    /// The file cannot be removed.
+    ///
+    /// This error occurs when clearing the cache and a file cannot be
+    /// removed, e.g. because it is open by another process on Windows.
+    #[error("File cannot be removed: {path}")]
+    RemoveFailed { path: PathBuf, source: io::Error },
+
+    /// The file cannot be refreshed.
+    ///
+    /// This error occurs when trying to refresh a file whose content
+    /// cannot be regenerated, such as a content-addressed object.
+    #[error("File is immutable: {path}")]
+    ImmutableFile { path: PathBuf },
+
+    /// The file exceeds the maximum file size.
+    ///
+    /// This error occurs when a callback writes more data than allowed
+    /// by the size limit. The oversized file is removed.
+    #[error("File too large: {path} has {size} bytes, which exceeds the limit of {limit} bytes")]
+    FileTooLarge { path: PathBuf, size: u64, limit: u64 },
+
+    /// The content of the file is too stale to be served.
+    ///
+    /// This error occurs when the file cannot be refreshed, and its content
+    /// expired longer than the maximum staleness ago, given as `age`.
+    #[error("File is too stale: {path} expired {age:?} ago")]
+    TooStale { path: PathBuf, age: Duration },
+
+    /// The cache is full.
+    ///
+    /// This error occurs when creating a file in a cache which already holds
+    /// the maximum number of files, and all of them are locked.
+    #[error("Cache is full: all {max_entries} files are locked")]
+    CacheFull { max_entries: usize },
+
+    /// The cache is frozen.
+    ///
+    /// This error occurs when trying to write to a cache which has been
+    /// frozen, until the cache is thawed.
+    #[error("Cache is frozen: {path}")]
+    Frozen { path: PathBuf },
+
+    /// The operation was cancelled.
+    ///
+    /// This error occurs when a file is created or refreshed after the
+    /// cache has been cancelled, or while a callback observes the cancellation.
+    #[error("Operation cancelled: {path}")]
+    Cancelled { path: PathBuf },
+
+    /// The callback panicked.
+    ///
+    /// This error occurs when a callback function panics while a file is
+    /// created or refreshed. The partially written file is removed, and the
+    /// file is marked poisoned until it is successfully refreshed.
+    #[error("Callback panicked for {path}: {message}")]
+    CallbackPanicked { path: PathBuf, message: String },
+
+    /// The interval cannot be parsed.
+    ///
+    /// This error occurs when a refresh interval given as a string is not
+    /// a valid human-readable duration like `90s` or `1h30m`.
+    #[error("Invalid interval: {input:?}")]
+    InvalidInterval { input: String },
+
+    /// The options of the cache conflict.
+    ///
+    /// This error occurs when building a cache with a
+    /// [`CacheBuilder`](crate::CacheBuilder) given conflicting options,
+    /// e.g. both a directory and a prefix of a temporary directory.
+    #[error("Invalid configuration: {reason}")]
+    InvalidConfiguration { reason: String },
+
+    /// The content of the file is not valid UTF-8.
+    ///
+    /// This error occurs when reading the content of a file as a string,
+    /// e.g. with [`Cache::read_string`](crate::Cache::read_string).
+    #[error("File is not valid UTF-8: {path}")]
+    Utf8 { path: PathBuf, source: FromUtf8Error },
+
     /// The file is already in a locked state.
     ///
-    /// This error occurs when trying to lock a file that is already locked.
+    /// This error occurs when trying to lock a file that is already locked,
+    /// to modify the content of or refresh a locked file, or to clear a cache holding a locked file.
     #[error("File already locked")]
     FileAlreadyLocked,
 
@@ -55,12 +190,20 @@ pub enum Error {
     #[error("File already unlocked")]
     FileAlreadyUnlocked,
 
+    /// ZIP archive error.
+    ///
+    /// This error occurs when a ZIP archive cannot be read or written,
+    /// e.g. when importing a malformed archive into the cache.
+    #[cfg(feature = "zip")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+
     /// Error from a user-provided callback function.
     ///
     /// This error wraps any error returned by callback functions
     /// used for file initialization or processing.
-    #[error(transparent)]
-    Callback(Box<dyn error::Error + Send + Sync>),
+    #[error("{0}")]
+    Callback(E),
 
     /// System time calculation error.
     ///
@@ -77,8 +220,96 @@ pub enum Error {
     IO(#[from] io::Error),
 }
 
+impl Error {
+    /// Recovers the typed error of a callback from the boxed one.
+    ///
+    /// Callback errors of a different type are wrapped into [`Error::IO`].
+    pub(crate) fn downcast_callback<E: error::Error + Send + Sync + 'static>(self) -> Error<E> {
+        match self {
+            Self::NotADirectory { path } => Error::NotADirectory { path },
+            Self::CacheDirMissing { path } => Error::CacheDirMissing { path },
+            Self::CacheRootMissing { path } => Error::CacheRootMissing { path },
+            Self::CacheNotWritable { path, source } => Error::CacheNotWritable { path, source },
+            Self::InsecurePermissions { path, mode } => Error::InsecurePermissions { path, mode },
+            Self::DirectoryNotEmpty { path } => Error::DirectoryNotEmpty { path },
+            Self::MissingMarker { path } => Error::MissingMarker { path },
+            Self::PathTraversal { path, cache_dir } => Error::PathTraversal { path, cache_dir },
+            Self::InvalidPath { path, reason } => Error::InvalidPath { path, reason },
+            Self::NoParentDirectory { path } => Error::NoParentDirectory { path },
+            Self::FileAlreadyExists { path } => Error::FileAlreadyExists { path },
+            Self::FileNotFound { path } => Error::FileNotFound { path },
+            Self::RemoveFailed { path, source } => Error::RemoveFailed { path, source },
+            Self::ImmutableFile { path } => Error::ImmutableFile { path },
+            Self::FileTooLarge { path, size, limit } => Error::FileTooLarge { path, size, limit },
+            Self::TooStale { path, age } => Error::TooStale { path, age },
+            Self::CacheFull { max_entries } => Error::CacheFull { max_entries },
+            Self::Frozen { path } => Error::Frozen { path },
+            Self::Cancelled { path } => Error::Cancelled { path },
+            Self::CallbackPanicked { path, message } => Error::CallbackPanicked { path, message },
+            Self::InvalidInterval { input } => Error::InvalidInterval { input },
+            Self::InvalidConfiguration { reason } => Error::InvalidConfiguration { reason },
+            Self::Utf8 { path, source } => Error::Utf8 { path, source },
+            Self::FileAlreadyLocked => Error::FileAlreadyLocked,
+            Self::FileAlreadyUnlocked => Error::FileAlreadyUnlocked,
+            #[cfg(feature = "zip")]
+            Self::Zip(error) => Error::Zip(error),
+            Self::Callback(error) => {
+                match error.downcast::<E>() {
+                    result::Result::Ok(error) => Error::Callback(*error),
+                    result::Result::Err(error) => Error::IO(io::Error::other(error)),
+                }
+            },
+            Self::SystemTime(error) => Error::SystemTime(error),
+            Self::IO(error) => Error::IO(error),
+        }
+    }
+}
+
+/// Error of moving a cache directory with [`Cache::persist`], giving the cache back.
+///
+/// The files of the cache are left in place, so the cache can still be used, or moved to another directory.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// # let target = tempfile::tempdir()?;
+/// let cache = Cache::new()?;
+/// std::fs::write(target.path().join("other.txt"), b"other")?;
+///
+/// // Keep using the cache if the target directory is not empty
+/// let cache = match cache.persist(target.path()) {
+///     Ok(cache) => cache,
+///     Err(error) => {
+///         eprintln!("cannot keep the cache: {}", error.error);
+///         *error.cache
+///     },
+/// };
+/// # let _ = cache;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Error, Debug)]
+#[error("{error}")]
+pub struct PersistError {
+    /// Error which occurred while moving the cache directory.
+    #[source]
+    pub error: Error,
+    /// Cache which could not be moved, still in its previous directory.
+    pub cache: Box<Cache>,
+}
+
+impl From<PersistError> for Error {
+    fn from(error: PersistError) -> Self {
+        let PersistError { error, .. } = error;
+        error
+    }
+}
+
 /// Type alias for [`Result`](std::result::Result) with custom [`enum@Error`] type.
-pub type Result<T> = result::Result<T, Error>;
+pub type Result<T, E = Error> = result::Result<T, E>;
 
 /// Creates an [`Ok`](std::result::Result::Ok) result with the custom [`enum@Error`] type.
 ///
@@ -0,0 +1,163 @@
+use sha2::{Digest, Sha256};
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Options of the [`sanitize_key`] function.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::SanitizeOptions;
+///
+/// // Keep names within 64 bytes, replacing forbidden characters with dashes
+/// let options = SanitizeOptions::new().max_len(64).replacement('-');
+/// # let _ = options;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SanitizeOptions {
+    /// Maximum length of names in bytes
+    max_len: usize,
+    /// Character replacing forbidden characters
+    replacement: char,
+}
+
+impl SanitizeOptions {
+    /// Default maximum length of names in bytes, leaving room for the prefix and extensions of sidecar files within common file system limits
+    const DEFAULT_MAX_LEN: usize = 200;
+    /// Default character replacing forbidden characters
+    const DEFAULT_REPLACEMENT: char = '_';
+
+    /// Creates new options with a maximum length of 200 bytes and forbidden characters replaced with underscores.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::SanitizeOptions;
+    ///
+    /// let options = SanitizeOptions::new();
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length of names in bytes.
+    ///
+    /// Longer names are truncated and suffixed with a short hash of the input, so distinct inputs keep distinct names.
+    /// The length is never lower than the length of the hash suffix plus one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::SanitizeOptions;
+    ///
+    /// let options = SanitizeOptions::new().max_len(64);
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn max_len(self, max_len: usize) -> Self {
+        let max_len = max_len.max(HASH_SUFFIX_LEN + 1);
+        Self { max_len, ..self }
+    }
+
+    /// Sets the character replacing forbidden characters.
+    ///
+    /// Forbidden characters, whitespace, and dots can't be used, in which case underscores are used instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::SanitizeOptions;
+    ///
+    /// let options = SanitizeOptions::new().replacement('-');
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn replacement(self, replacement: char) -> Self {
+        let replacement = if is_forbidden(replacement) || replacement.is_whitespace() || replacement == '.' {
+            Self::DEFAULT_REPLACEMENT
+        } else {
+            replacement
+        };
+        Self { replacement, ..self }
+    }
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        let max_len = Self::DEFAULT_MAX_LEN;
+        let replacement = Self::DEFAULT_REPLACEMENT;
+        Self { max_len, replacement }
+    }
+}
+
+/// Length of the suffix appended to truncated names, a dash followed by 8 hex digits
+const HASH_SUFFIX_LEN: usize = 9;
+
+/// Turns an arbitrary string, e.g. a title or a URL, into a safe file name.
+///
+/// Path separators and characters forbidden in file names are replaced, runs of whitespace are collapsed into single spaces,
+/// and leading dots are stripped so the name is neither hidden nor a parent directory reference.
+/// Names longer than the maximum length are truncated and suffixed with a short hash of the input, so distinct long inputs sharing a prefix keep distinct names.
+///
+/// The mapping is deterministic: the same input and options always produce the same name.
+/// Inputs differing only in replaced characters or whitespace, e.g. `a/b` and `a:b`, produce the same name.
+/// Check the [`Cache::get_sanitized`] method for creating files with sanitized keys.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::SanitizeOptions;
+///
+/// let options = SanitizeOptions::new();
+/// assert_eq!(
+///     fcache::sanitize_key("https://example.com/a?b", &options),
+///     "https___example.com_a_b"
+/// );
+/// assert_eq!(fcache::sanitize_key("..My   Title ", &options), "My Title");
+/// ```
+#[must_use]
+pub fn sanitize_key(input: &str, options: &SanitizeOptions) -> String {
+    let SanitizeOptions { max_len, replacement } = options;
+    let mut key = String::with_capacity(input.len());
+    let mut whitespace = false;
+    for c in input.chars() {
+        if c.is_whitespace() {
+            whitespace = true;
+            continue;
+        }
+        // Leading whitespace and dots are stripped
+        if key.is_empty() && c == '.' {
+            whitespace = false;
+            continue;
+        }
+        if whitespace && !key.is_empty() {
+            key.push(' ');
+        }
+        whitespace = false;
+        key.push(if is_forbidden(c) { *replacement } else { c });
+    }
+    if key.is_empty() {
+        key.push(*replacement);
+    }
+
+    if key.len() > *max_len {
+        let mut len = max_len - HASH_SUFFIX_LEN;
+        while !key.is_char_boundary(len) {
+            len -= 1;
+        }
+        key.truncate(len);
+        key.truncate(key.trim_end().len());
+        let digest = format!("{:x}", Sha256::digest(input.as_bytes()));
+        key.push('-');
+        key.push_str(&digest[..HASH_SUFFIX_LEN - 1]);
+    }
+    key
+}
+
+/// Returns whether the character is not allowed in file names on common platforms.
+fn is_forbidden(c: char) -> bool {
+    c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+}
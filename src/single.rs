@@ -0,0 +1,179 @@
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::Cache;
+use crate::callback::{self, SendCallbackFn};
+use crate::file::{CacheFile, CacheLazyFile};
+use crate::result::Result;
+
+/// A cache accepting callbacks which are not shareable across threads.
+///
+/// The callbacks are only required to be [`Send`] (see [`SendCallbackFn`](crate::SendCallbackFn)), so they can capture types like [`Cell`] or [`RefCell`](std::cell::RefCell).
+/// In exchange the cache itself is not [`Sync`], so it can't be shared across threads.
+/// Check the [`Cache::with_single_threaded_mode`] method for more details on how to create this cache.
+///
+/// # Example
+///
+/// ```rust
+/// use std::cell::Cell;
+///
+/// use fcache::prelude::*;
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// // Create a new single-threaded cache instance
+/// let cache = Cache::new()?.with_single_threaded_mode();
+///
+/// // Count the callback calls without atomics
+/// let calls = Cell::new(0);
+/// let cache_file = cache.get("example.txt", move |mut file| {
+///     calls.set(calls.get() + 1);
+///     writeln!(file, "Generated {} time(s)", calls.get())?;
+///     Ok(())
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The cache can't be shared across threads:
+///
+/// ```compile_fail
+/// use fcache::prelude::*;
+///
+/// fn assert_sync<T: Sync>(_: &T) {}
+///
+/// # fn wrapper() -> fcache::Result<()> {
+/// let cache = Cache::new()?.with_single_threaded_mode();
+/// assert_sync(&cache);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SingleThreadedCache {
+    /// Underlying thread-safe cache
+    cache: Cache,
+    /// Marker opting out of `Sync`
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl SingleThreadedCache {
+    /// Creates a single-threaded cache from a cache.
+    pub(crate) fn new(cache: Cache) -> Self {
+        let _not_sync = PhantomData;
+        Self { cache, _not_sync }
+    }
+
+    /// Creates a file in the cache using a callback for initialization.
+    ///
+    /// This is the counterpart of [`Cache::get`] accepting callbacks which are only [`Send`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new single-threaded cache instance
+    /// let cache = Cache::new()?.with_single_threaded_mode();
+    ///
+    /// // Create a new file in the cache
+    /// let content = RefCell::new(b"Hello, Cache!".to_vec());
+    /// let cache_file = cache.get("example.txt", move |mut file| {
+    ///     file.write_all(&content.borrow())?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`Cache::get`].
+    pub fn get<'a>(&'a self, path: impl AsRef<Path>, callback: impl SendCallbackFn + 'static) -> Result<CacheFile<'a>> {
+        let Self { cache, .. } = self;
+        cache.get(path, callback::synchronized(callback))
+    }
+
+    /// Creates a file in the cache that is lazily created when accessed.
+    ///
+    /// This is the counterpart of [`Cache::get_lazy`] accepting callbacks which are only [`Send`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    ///
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new single-threaded cache instance
+    /// let cache = Cache::new()?.with_single_threaded_mode();
+    ///
+    /// // Create a new lazy file in the cache
+    /// let content = RefCell::new(b"Hello, Cache!".to_vec());
+    /// let cache_file = cache.get_lazy("example.txt", move |mut file| {
+    ///     file.write_all(&content.borrow())?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error in the same cases as [`Cache::get_lazy`].
+    pub fn get_lazy<'a>(
+        &'a self,
+        path: impl AsRef<Path>,
+        callback: impl SendCallbackFn + 'static,
+    ) -> Result<CacheLazyFile<'a>> {
+        let Self { cache, .. } = self;
+        cache.get_lazy(path, callback::synchronized(callback))
+    }
+
+    /// Returns the underlying cache, e.g. for inspecting its options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new single-threaded cache instance
+    /// let cache = Cache::new()?.with_single_threaded_mode();
+    ///
+    /// // Print the cache directory
+    /// println!("Cache directory: {}", cache.cache().path().display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn cache(&self) -> &Cache {
+        let Self { cache, .. } = self;
+        cache
+    }
+
+    /// Converts the single-threaded cache back into a thread-safe cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::prelude::*;
+    ///
+    /// # fn wrapper() -> fcache::Result<()> {
+    /// // Create a new single-threaded cache instance
+    /// let cache = Cache::new()?.with_single_threaded_mode();
+    ///
+    /// // Share the cache across threads again
+    /// let cache = cache.into_inner();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn into_inner(self) -> Cache {
+        let Self { cache, .. } = self;
+        cache
+    }
+}
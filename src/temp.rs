@@ -0,0 +1,168 @@
+use std::io;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+#[cfg(doc)]
+use crate::Cache;
+
+/// Options of the temporary directory of a cache.
+///
+/// The directory is named `<prefix><random characters><suffix>` and created within the parent directory, which defaults to the system temporary directory.
+/// Check the [`Cache::temp_with`] method for more details on how to use these options.
+///
+/// # Example
+///
+/// ```rust
+/// use fcache::TempDirOptions;
+///
+/// // Use short directory names, e.g. to keep socket paths within limits
+/// let options = TempDirOptions::default()
+///     .with_prefix("c")
+///     .with_random_len(4);
+/// # let _ = options;
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TempDirOptions {
+    /// Prefix of the directory name
+    prefix: String,
+    /// Suffix of the directory name
+    suffix: String,
+    /// Number of random characters in the directory name
+    random_len: usize,
+    /// Directory the temporary directory is created within
+    parent: Option<PathBuf>,
+    /// Permission bits of the directory
+    #[cfg(unix)]
+    mode: Option<u32>,
+}
+
+impl TempDirOptions {
+    /// Default prefix of the directory name
+    const DEFAULT_PREFIX: &str = "fcache";
+    /// Default number of random characters in the directory name
+    const DEFAULT_RANDOM_LEN: usize = 6;
+
+    /// Sets the prefix of the directory name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    ///
+    /// let options = TempDirOptions::default().with_prefix("my_cache");
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn with_prefix(self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        Self { prefix, ..self }
+    }
+
+    /// Sets the suffix of the directory name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    ///
+    /// let options = TempDirOptions::default().with_suffix(".cache");
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn with_suffix(self, suffix: impl Into<String>) -> Self {
+        let suffix = suffix.into();
+        Self { suffix, ..self }
+    }
+
+    /// Sets the number of random characters in the directory name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    ///
+    /// let options = TempDirOptions::default().with_random_len(4);
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn with_random_len(self, random_len: usize) -> Self {
+        Self { random_len, ..self }
+    }
+
+    /// Sets the directory the temporary directory is created within.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    ///
+    /// let options = TempDirOptions::default().with_parent("/var/tmp");
+    /// # let _ = options;
+    /// ```
+    #[must_use]
+    pub fn with_parent(self, parent: impl Into<PathBuf>) -> Self {
+        let parent = Some(parent.into());
+        Self { parent, ..self }
+    }
+
+    /// Sets the permission bits of the directory, e.g. `0o700`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use fcache::TempDirOptions;
+    ///
+    /// // Restrict the directory to the owner
+    /// let options = TempDirOptions::default().with_mode(0o700);
+    /// # let _ = options;
+    /// ```
+    #[cfg(unix)]
+    #[must_use]
+    pub fn with_mode(self, mode: u32) -> Self {
+        let mode = Some(mode);
+        Self { mode, ..self }
+    }
+
+    /// Creates the temporary directory.
+    pub(crate) fn tempdir(&self) -> io::Result<TempDir> {
+        let Self {
+            prefix,
+            suffix,
+            random_len,
+            parent,
+            #[cfg(unix)]
+            mode,
+        } = self;
+        let mut builder = tempfile::Builder::new();
+        builder.prefix(prefix).suffix(suffix).rand_bytes(*random_len);
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::fs::Permissions;
+            use std::os::unix::fs::PermissionsExt;
+
+            builder.permissions(Permissions::from_mode(*mode));
+        }
+        match parent {
+            Some(parent) => builder.tempdir_in(parent),
+            None => builder.tempdir(),
+        }
+    }
+}
+
+impl Default for TempDirOptions {
+    fn default() -> Self {
+        let prefix = Self::DEFAULT_PREFIX.to_string();
+        let suffix = String::new();
+        let random_len = Self::DEFAULT_RANDOM_LEN;
+        let parent = None;
+        Self {
+            prefix,
+            suffix,
+            random_len,
+            parent,
+            #[cfg(unix)]
+            mode: None,
+        }
+    }
+}
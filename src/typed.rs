@@ -0,0 +1,185 @@
+use std::error;
+use std::fmt::{self, Debug};
+use std::fs::File;
+use std::marker::PhantomData;
+use std::path::Path;
+
+#[cfg(doc)]
+use crate::Cache;
+use crate::file::CacheFile;
+use crate::result::{Error, Result};
+
+/// A file in the cache whose callback returns errors of type `E`.
+///
+/// Operations running the callback return [`Error<E>`](enum@Error), so the callback errors can be matched without downcasting.
+/// Check the [`Cache::get_typed`] method for more details on how to create typed files.
+pub struct TypedCacheFile<'a, E>(CacheFile<'a>, PhantomData<fn() -> E>);
+
+impl<'a, E: error::Error + Send + Sync + 'static> TypedCacheFile<'a, E> {
+    /// Creates a typed file from a file whose callback returns errors of type `E`.
+    pub(crate) fn new(cache_file: CacheFile<'a>) -> Self {
+        Self(cache_file, PhantomData)
+    }
+
+    /// Returns the path of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// assert!(typed_file.path().ends_with("data.txt"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn path(&self) -> &Path {
+        let Self(cache_file, _) = self;
+        cache_file.path()
+    }
+
+    /// Opens the file for reading.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Read, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// let mut content = String::new();
+    /// typed_file.open()?.read_to_string(&mut content)?;
+    /// assert_eq!(content, "content");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be opened (see [`CacheFile::open`]).
+    pub fn open(&self) -> Result<File, Error<E>> {
+        let Self(cache_file, _) = self;
+        cache_file.open().map_err(Error::downcast_callback)
+    }
+
+    /// Refreshes the file if it is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// // Refresh only if the file is invalid
+    /// typed_file.refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the refresh fails (see [`CacheFile::refresh`]), with the callback errors returned as [`Error::Callback`].
+    pub fn refresh(&self) -> Result<(), Error<E>> {
+        let Self(cache_file, _) = self;
+        cache_file.refresh().map_err(Error::downcast_callback)
+    }
+
+    /// Forces a refresh of the file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"fresh data")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// // Force refresh regardless of validity
+    /// typed_file.force_refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the refresh fails (see [`CacheFile::force_refresh`]), with the callback errors returned as [`Error::Callback`].
+    pub fn force_refresh(&self) -> Result<(), Error<E>> {
+        let Self(cache_file, _) = self;
+        cache_file.force_refresh().map_err(Error::downcast_callback)
+    }
+
+    /// Returns the underlying file, whose operations return boxed callback errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// assert!(typed_file.as_cache_file().is_valid()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_cache_file(&self) -> &CacheFile<'a> {
+        let Self(cache_file, _) = self;
+        cache_file
+    }
+
+    /// Converts the typed file into the underlying file, whose operations return boxed callback errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    ///
+    /// # fn wrapper() -> Result<(), Box<dyn std::error::Error>> {
+    /// let cache = fcache::new()?;
+    /// let typed_file = cache.get_typed("data.txt", |mut file| {
+    ///     file.write_all(b"content")?;
+    ///     Ok::<(), io::Error>(())
+    /// })?;
+    ///
+    /// let cache_file = typed_file.into_cache_file();
+    /// cache_file.force_refresh()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_cache_file(self) -> CacheFile<'a> {
+        let Self(cache_file, _) = self;
+        cache_file
+    }
+}
+
+impl<E> Debug for TypedCacheFile<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(cache_file, _) = self;
+        f.debug_tuple("TypedFile").field(cache_file).finish()
+    }
+}
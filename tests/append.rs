@@ -0,0 +1,168 @@
+mod common;
+
+use std::time::SystemTime;
+
+use common::*;
+
+const HEADER: &[u8] = b"# events\n";
+
+#[test]
+fn test_append_file() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create an appendable file with a header
+    let append_file = cache.get_appendable("events.log", |mut file| {
+        file.write_all(HEADER)?;
+        Ok(())
+    })?;
+    assert!(append_file.path().exists());
+
+    // Append to the file
+    append_file.append(b"first\n")?;
+    append_file.append(b"second\n")?;
+
+    // Verify the header is written once
+    {
+        let mut content = Vec::new();
+        append_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, b"# events\nfirst\nsecond\n");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_append_file_rotation() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create an appendable file rotated after 16 bytes, keeping 2 rotated files
+    let append_file = cache
+        .get_appendable("events.log", |mut file| {
+            file.write_all(HEADER)?;
+            Ok(())
+        })?
+        .with_rotation(16, 2);
+
+    // Append past the limit multiple times
+    for index in 1..=4 {
+        append_file.append(format!("event {index}\n").as_bytes())?;
+    }
+
+    // Verify the rotated files
+    assert_eq!(std::fs::read(append_file.rotated_path(1))?, b"# events\nevent 4\n");
+    assert_eq!(std::fs::read(append_file.rotated_path(2))?, b"# events\nevent 3\n");
+    assert!(
+        !append_file.rotated_path(3).exists(),
+        "Rotated files beyond the limit should be removed"
+    );
+
+    // Verify the active file was started with the header
+    {
+        let mut content = Vec::new();
+        append_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, HEADER);
+    }
+
+    // Verify the number of files
+    let files = std::fs::read_dir(cache.path())?.count();
+    assert_eq!(files, 3, "Should keep the active file and 2 rotated files");
+
+    Ok(())
+}
+
+#[test]
+fn test_append_file_rotation_interval() -> anyhow::Result<()> {
+    // Create a new cache instance with a manual clock starting at 2024-06-01T00:00:00Z
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?.with_clock(clock.clone());
+
+    // Create an appendable file rotated daily
+    let append_file = cache
+        .get_appendable("events.log", |mut file| {
+            file.write_all(HEADER)?;
+            Ok(())
+        })?
+        .with_rotation_interval(Duration::from_secs(24 * 60 * 60));
+
+    // Append within the first day
+    append_file.append(b"event 1\n")?;
+    clock.advance(Duration::from_secs(23 * 60 * 60));
+    append_file.append(b"event 2\n")?;
+    assert!(
+        append_file.rotated_files()?.is_empty(),
+        "Should not rotate within the interval"
+    );
+
+    // Append after crossing the day boundary
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    append_file.append(b"event 3\n")?;
+    append_file.append(b"event 4\n")?;
+
+    // Verify exactly one rotation happened
+    let rotated_files = append_file.rotated_files()?;
+    assert_eq!(rotated_files.len(), 1, "Should rotate exactly once");
+    assert!(rotated_files[0].ends_with("events.log.2024-06-01"));
+    assert_eq!(std::fs::read(&rotated_files[0])?, b"# events\nevent 1\nevent 2\n");
+
+    // Verify the active file was started with the header
+    {
+        let mut content = Vec::new();
+        append_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, b"# events\nevent 3\nevent 4\n");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_open_append_and_rotate() -> anyhow::Result<()> {
+    // Create a new cache instance which always refreshes files
+    let cache = fcache::new()?.with_refresh_interval(Duration::ZERO);
+
+    // Create a file in the cache with a header
+    let cache_file = cache.get("events.log", |mut file| {
+        file.write_all(HEADER)?;
+        Ok(())
+    })?;
+
+    // Append records without refreshing the file
+    cache_file.open_append()?.write_all(b"started\n")?;
+    cache_file.open_append()?.write_all(b"stopped\n")?;
+    assert_eq!(std::fs::read(cache_file.path())?, b"# events\nstarted\nstopped\n");
+
+    // Verify the file isn't rotated below the size limit
+    cache_file.rotate(1024, |_| Ok(()))?;
+    assert!(
+        !cache_file.path().with_extension("log.1").exists(),
+        "File shouldn't be rotated"
+    );
+
+    // Rotate the file twice, starting fresh files with a header
+    for _ in 0..2 {
+        cache_file.open_append()?.write_all(b"started\n")?;
+        cache_file.rotate(HEADER.len() as u64, |mut file| {
+            file.write_all(HEADER)?;
+            Ok(())
+        })?;
+    }
+    assert_eq!(std::fs::read(cache_file.path())?, HEADER);
+    assert_eq!(
+        std::fs::read(cache_file.path().with_extension("log.1"))?,
+        b"# events\nstarted\n"
+    );
+    assert_eq!(
+        std::fs::read(cache_file.path().with_extension("log.2"))?,
+        b"# events\nstarted\nstopped\nstarted\n"
+    );
+
+    // Verify locked files aren't rotated
+    cache_file.lock()?;
+    assert!(
+        matches!(cache_file.rotate(0, |_| Ok(())), Err(fcache::Error::FileAlreadyLocked)),
+        "Should fail to rotate a locked file"
+    );
+
+    Ok(())
+}
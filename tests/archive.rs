@@ -0,0 +1,71 @@
+#![cfg(feature = "zip")]
+
+mod common;
+
+use std::time::SystemTime;
+
+use common::*;
+
+#[test]
+fn test_cache_export_and_import_zip() -> anyhow::Result<()> {
+    let archive_dir = TempDir::new()?;
+
+    // Create a new cache instance with files in nested directories
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60 * 60));
+    let cache_file = cache.get("dir/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    cache_file.set_refresh_interval_persist(Duration::from_secs(2 * 60 * 60))?;
+    let expired_file = cache.get("expired.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Expire one of the files
+    File::options()
+        .write(true)
+        .open(expired_file.path())?
+        .set_modified(SystemTime::now() - Duration::from_secs(3 * 60 * 60))?;
+
+    // Export the valid files only, along with their sidecar files
+    let valid_archive = archive_dir.path().join("valid.zip");
+    assert_eq!(cache.export_zip(&valid_archive, false)?, 1);
+
+    // Export all files
+    let all_archive = archive_dir.path().join("all.zip");
+    assert_eq!(cache.export_zip(&all_archive, true)?, 2);
+
+    // Verify the archive cannot be written into the cache directory
+    assert!(
+        matches!(
+            cache.export_zip(cache.path().join("cache.zip"), true),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should fail to export into the cache directory"
+    );
+
+    // Import the valid files into another cache, preserving relative paths and sidecar files
+    let seeded_cache = fcache::new()?;
+    assert_eq!(seeded_cache.import_zip(&valid_archive)?, 1);
+    let imported_path = seeded_cache.path().join("dir").join("file.txt");
+    assert_eq!(std::fs::read(&imported_path)?, TEST_CONTENT);
+    assert!(seeded_cache.path().join("dir/.fcache-meta-file.txt.ttl").exists());
+    assert!(!seeded_cache.path().join("expired.txt").exists());
+
+    // Verify existing files are skipped
+    std::fs::write(&imported_path, b"local")?;
+    assert_eq!(seeded_cache.import_zip(&all_archive)?, 1);
+    assert_eq!(std::fs::read(&imported_path)?, b"local");
+    assert!(seeded_cache.path().join("expired.txt").exists());
+
+    // Verify malformed archives are rejected
+    let malformed_archive = archive_dir.path().join("malformed.zip");
+    std::fs::write(&malformed_archive, TEST_CONTENT)?;
+    assert!(
+        matches!(seeded_cache.import_zip(&malformed_archive), Err(fcache::Error::Zip(_))),
+        "Should fail to import a malformed archive"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use std::time::SystemTime;
+
+use common::*;
+
+#[test]
+fn test_cache_backup() -> anyhow::Result<()> {
+    let backup_dir = TempDir::new()?;
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    let valid_file = cache.get("a/valid.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    valid_file.set_refresh_interval_persist(Duration::from_secs(3600))?;
+    let expired_file = cache.get("expired.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let mtime = SystemTime::now() - Duration::from_secs(120);
+    File::options()
+        .write(true)
+        .open(expired_file.path())?
+        .set_modified(mtime)?;
+
+    // Create transient files which are excluded
+    File::create(cache.path().join(".fcache-tmp1234"))?;
+    File::create(cache.path().join("a/state.journal"))?;
+
+    // Back up the cache
+    let report = cache.backup(backup_dir.path())?;
+    assert_eq!(report.files_copied, 3);
+    assert_eq!(
+        report.bytes_written,
+        2 * TEST_CONTENT.len() as u64 + "3600000".len() as u64
+    );
+    assert_eq!(report.dest, backup_dir.path().canonicalize()?);
+
+    // Verify the structure, sidecar files, and modification times are preserved
+    assert_eq!(std::fs::read(backup_dir.path().join("a/valid.txt"))?, TEST_CONTENT);
+    assert!(backup_dir.path().join("a/.fcache-meta-valid.txt.ttl").exists());
+    assert_eq!(
+        std::fs::metadata(backup_dir.path().join("expired.txt"))?.modified()?,
+        mtime
+    );
+    assert!(!backup_dir.path().join(".fcache-tmp1234").exists());
+    assert!(!backup_dir.path().join("a/state.journal").exists());
+
+    // Restore the cache and verify the validity of the files is retained
+    let cache = fcache::Cache::restore_from_backup(backup_dir.path())?.with_refresh_interval(Duration::from_secs(60));
+    let (valid_paths, invalid_paths) = cache.group_by_validity()?;
+    assert_eq!(valid_paths.len(), 1);
+    assert_eq!(invalid_paths, vec![cache.path().join("expired.txt")]);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_backup_within_cache() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Verify backups within the cache directory are rejected
+    assert!(
+        matches!(
+            cache.backup(cache.path().join("backup")),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when the destination is within the cache directory"
+    );
+
+    // Verify restoring a missing backup fails
+    assert!(
+        matches!(
+            fcache::Cache::restore_from_backup(cache.path().join("missing")),
+            Err(fcache::Error::CacheDirMissing { .. })
+        ),
+        "Should return an error when the backup does not exist"
+    );
+
+    Ok(())
+}
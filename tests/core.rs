@@ -56,6 +56,62 @@ fn test_cache_with_dir() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cache_with_dir_checked() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let empty_dir = temp_dir.path().join("empty");
+    std::fs::create_dir(&empty_dir)?;
+    let populated_dir = temp_dir.path().join("populated");
+    std::fs::create_dir(&populated_dir)?;
+    File::create(populated_dir.join("document.txt"))?;
+
+    // Verify existing directories are allowed
+    let _ = fcache::with_dir_checked(&populated_dir, fcache::DirPolicy::AllowExisting)?;
+
+    // Verify only empty directories are allowed
+    let _ = fcache::with_dir_checked(&empty_dir, fcache::DirPolicy::RequireEmpty)?;
+    assert!(
+        matches!(
+            fcache::with_dir_checked(&populated_dir, fcache::DirPolicy::RequireEmpty),
+            Err(fcache::Error::DirectoryNotEmpty { .. })
+        ),
+        "Should return an error when the directory is not empty"
+    );
+
+    // Verify the marker is written on first use
+    let cache = fcache::with_dir_checked(&empty_dir, fcache::DirPolicy::RequireMarker)?;
+    assert!(empty_dir.join(".fcache").exists());
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    assert_eq!(cache.most_recent(10)?.len(), 1, "Marker file should be skipped");
+
+    // Verify marker-bearing directories are reused
+    let _ = fcache::with_dir_checked(&empty_dir, fcache::DirPolicy::RequireMarker)?;
+    assert!(
+        matches!(
+            fcache::with_dir_checked(&empty_dir, fcache::DirPolicy::RequireEmpty),
+            Err(fcache::Error::DirectoryNotEmpty { .. })
+        ),
+        "Should return an error when the directory is not empty"
+    );
+
+    // Verify directories lacking the marker are refused
+    assert!(
+        matches!(
+            fcache::with_dir_checked(&populated_dir, fcache::DirPolicy::RequireMarker),
+            Err(fcache::Error::MissingMarker { .. })
+        ),
+        "Should return an error when the directory lacks the marker"
+    );
+    assert!(!populated_dir.join(".fcache").exists());
+
+    // Verify missing directories are created
+    let missing_dir = temp_dir.path().join("missing");
+    let _ = fcache::with_dir_checked(&missing_dir, fcache::DirPolicy::RequireMarker)?;
+    assert!(missing_dir.join(".fcache").exists());
+
+    Ok(())
+}
+
 #[test]
 fn test_cache_with_file() -> anyhow::Result<()> {
     let temp_dir = TempDir::new()?;
@@ -73,6 +129,175 @@ fn test_cache_with_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cache_open_dir() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    // Verify missing directories are not created
+    let missing_dir = temp_dir.path().join("missing");
+    assert!(
+        matches!(
+            fcache::open_dir(&missing_dir),
+            Err(fcache::Error::CacheDirMissing { .. })
+        ),
+        "Should return an error when the directory does not exist"
+    );
+    assert!(!missing_dir.exists(), "Directory should not be created");
+
+    // Verify files are not accepted
+    let file_path = temp_dir.path().join("file");
+    let _ = File::create(&file_path)?;
+    assert!(
+        matches!(fcache::open_dir(&file_path), Err(fcache::Error::NotADirectory { .. })),
+        "Should return an error when providing a file path instead of directory"
+    );
+
+    // Verify existing directories are opened
+    let cache = fcache::open_dir(temp_dir.path())?;
+    assert_eq!(cache.path(), temp_dir.path().canonicalize()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_with_dir_ephemeral() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let cache_dir = temp_dir.path().join("scratch");
+
+    // Create and populate a new cache instance
+    let cache = fcache::with_dir_ephemeral(&cache_dir)?;
+    assert!(cache_dir.is_dir());
+    let _ = cache.get("a/b/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert!(cache_dir.join("a/b/file.txt").exists());
+
+    // Verify the directory is removed along with the cache
+    drop(cache);
+    assert!(!cache_dir.exists(), "Cache directory should be removed");
+
+    // Verify dropping the cache tolerates an externally removed directory
+    let cache = fcache::with_dir_ephemeral(&cache_dir)?;
+    std::fs::remove_dir_all(&cache_dir)?;
+    drop(cache);
+
+    // Verify pre-existing non-empty directories are refused and left untouched
+    std::fs::create_dir(&cache_dir)?;
+    File::create(cache_dir.join("document.txt"))?;
+    assert!(
+        matches!(
+            fcache::with_dir_ephemeral(&cache_dir),
+            Err(fcache::Error::DirectoryNotEmpty { .. })
+        ),
+        "Should return an error when the directory is not empty"
+    );
+    assert!(cache_dir.join("document.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_cleanup_on_drop() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let cache_dir = temp_dir.path().join("cache");
+
+    // Create and populate a new cache instance cleaned up on drop
+    let cache = fcache::with_dir(&cache_dir)?.with_cleanup_on_drop(true);
+    let _ = cache.get("a/b/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+
+    // Verify the files are removed along with the cache, but not the directory
+    drop(cache);
+    assert!(cache_dir.is_dir(), "Cache directory should be kept");
+    assert!(
+        std::fs::read_dir(&cache_dir)?.next().is_none(),
+        "Cache directory should be empty"
+    );
+
+    // Verify files which were in the directory before are kept, along with their directories
+    std::fs::create_dir_all(cache_dir.join("a/docs"))?;
+    std::fs::write(cache_dir.join("notes.md"), b"notes")?;
+    std::fs::write(cache_dir.join("a/docs/readme.md"), b"readme")?;
+    let cache = fcache::with_dir(&cache_dir)?.with_cleanup_on_drop(true);
+    let _ = cache.get("a/b/file.txt", |_| Ok(()))?;
+    let _ = cache.get("a/docs/file.txt", |_| Ok(()))?;
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    drop(cache);
+    assert_eq!(std::fs::read(cache_dir.join("notes.md"))?, b"notes");
+    assert_eq!(std::fs::read(cache_dir.join("a/docs/readme.md"))?, b"readme");
+    assert!(!cache_dir.join("a/b").exists(), "Added directories should be removed");
+    assert!(
+        !cache_dir.join("a/docs/file.txt").exists(),
+        "Added files should be removed"
+    );
+    assert!(!cache_dir.join("file.txt").exists(), "Added files should be removed");
+
+    // Verify the whole content is removed only when requested
+    let cache = fcache::with_dir(&cache_dir)?.with_cleanup_all_on_drop(true);
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    drop(cache);
+    assert!(cache_dir.is_dir(), "Cache directory should be kept");
+    assert!(
+        std::fs::read_dir(&cache_dir)?.next().is_none(),
+        "Cache directory should be empty"
+    );
+
+    // Verify disabling the cleanup keeps the files
+    let cache = fcache::with_dir(&cache_dir)?
+        .with_cleanup_on_drop(true)
+        .with_cleanup_on_drop(false);
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    drop(cache);
+    assert!(cache_dir.join("file.txt").exists(), "Files should be kept");
+
+    // Verify disabling the cleanup keeps the directory of an ephemeral cache
+    let scratch_dir = temp_dir.path().join("scratch");
+    let cache = fcache::with_dir_ephemeral(&scratch_dir)?.with_cleanup_on_drop(false);
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    drop(cache);
+    assert!(scratch_dir.join("file.txt").exists(), "Files should be kept");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_builder() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let cache_dir = temp_dir.path().join("cache");
+
+    // Build a cache within a directory
+    let cache = fcache::CacheBuilder::new()
+        .dir(&cache_dir)
+        .refresh_interval(Duration::from_secs(60))
+        .build()?;
+    assert_eq!(cache.path(), cache_dir);
+    assert_eq!(cache.refresh_interval(), Duration::from_secs(60));
+    drop(cache);
+    assert!(cache_dir.exists(), "Cache directory should be kept");
+
+    // Build a cache within a temporary directory
+    let cache = fcache::CacheBuilder::new().temp().prefix("builder").build()?;
+    let path = cache.path().to_path_buf();
+    assert!(
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("builder"))
+    );
+    drop(cache);
+    assert!(!path.exists(), "Temporary directory should be removed");
+
+    // Verify conflicting options are rejected
+    let result = fcache::CacheBuilder::new().dir(&cache_dir).prefix("builder").build();
+    assert!(matches!(result, Err(fcache::Error::InvalidConfiguration { .. })));
+    let result = fcache::CacheBuilder::new().dir(&cache_dir).temp().build();
+    assert!(matches!(result, Err(fcache::Error::InvalidConfiguration { .. })));
+
+    Ok(())
+}
+
 #[test]
 fn test_cache_with_refresh_interval() -> anyhow::Result<()> {
     let refresh_interval = Duration::from_secs(10);
@@ -86,6 +311,58 @@ fn test_cache_with_refresh_interval() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_cache_with_refresh_interval_str() -> anyhow::Result<()> {
+    // Verify valid intervals are parsed
+    for (input, refresh_interval) in [
+        ("90s", Duration::from_secs(90)),
+        ("15m", Duration::from_secs(15 * 60)),
+        ("6h", Duration::from_secs(6 * 60 * 60)),
+        ("2d", Duration::from_secs(2 * 24 * 60 * 60)),
+        ("500ms", Duration::from_millis(500)),
+        ("1h30m", Duration::from_secs(90 * 60)),
+        (" 1h 30m 15s ", Duration::from_secs(90 * 60 + 15)),
+        ("0", Duration::ZERO),
+        ("never", Duration::MAX),
+    ] {
+        let cache = fcache::new()?.with_refresh_interval_str(input)?;
+        assert_eq!(
+            cache.refresh_interval(),
+            refresh_interval,
+            "Interval {input:?} does not match"
+        );
+    }
+
+    // Verify invalid intervals are rejected
+    for input in [
+        "",
+        "  ",
+        "15",
+        "h",
+        "1x",
+        "1.5h",
+        "-1s",
+        "1h30",
+        "forever",
+        "99999999999999999999d",
+    ] {
+        assert!(
+            matches!(
+                fcache::new()?.with_refresh_interval_str(input),
+                Err(fcache::Error::InvalidInterval { .. })
+            ),
+            "Should return an error when the interval {input:?} is invalid"
+        );
+    }
+
+    // Verify file intervals are parsed
+    let cache = fcache::new()?;
+    let cache_file = cache.get("file.txt", |_| Ok(()))?.with_refresh_interval_str("1h")?;
+    assert_eq!(cache_file.refresh_interval(), Duration::from_secs(60 * 60));
+
+    Ok(())
+}
+
 #[test]
 fn test_cache_with_default_refresh_interval() -> anyhow::Result<()> {
     let refresh_interval = Duration::from_secs(30);
@@ -100,3 +377,343 @@ fn test_cache_with_default_refresh_interval() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_cache_root_missing() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    let cache_file = cache.get_lazy("a/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Remove the cache directory
+    std::fs::remove_dir_all(cache.path())?;
+
+    // Verify operations report the missing cache directory
+    assert!(
+        matches!(
+            cache.get("file.txt", |_| Ok(())),
+            Err(fcache::Error::CacheRootMissing { .. })
+        ),
+        "Should return an error when the cache directory is missing"
+    );
+    assert!(
+        matches!(cache_file.open(), Err(fcache::Error::CacheRootMissing { .. })),
+        "Should return an error when the cache directory is missing"
+    );
+    assert!(!cache.path().exists(), "Cache directory should not be recreated");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_root_recreated() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let cache_dir = temp_dir.path().join("cache");
+
+    // Create a new cache instance
+    let cache = fcache::with_dir(&cache_dir)?.with_auto_recreate_root(true);
+    assert!(cache.auto_recreate_root());
+    let cache_file = cache.get("a/b/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Remove the cache directory
+    std::fs::remove_dir_all(&cache_dir)?;
+
+    // Verify the file is recreated along with its parents
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT, "File content does not match");
+
+    // Remove the cache directory again
+    std::fs::remove_dir_all(&cache_dir)?;
+
+    // Verify new files can be created
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    assert!(cache_file.path().exists());
+
+    // Verify temporary caches are recreated at the original path
+    let cache = fcache::new()?.with_auto_recreate_root(true);
+    let path = cache.path().to_path_buf();
+    std::fs::remove_dir_all(&path)?;
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    assert!(path.join("file.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_new_sibling() -> anyhow::Result<()> {
+    let parent_dir = TempDir::new()?;
+
+    // Create a new cache instance within the parent directory
+    let cache = fcache::with_dir(parent_dir.path().join("images"))?.with_refresh_interval(Duration::from_secs(42));
+
+    // Create a sibling cache inheriting the refresh interval
+    let sibling = cache.new_sibling("thumbnails")?;
+    assert_eq!(sibling.path(), parent_dir.path().canonicalize()?.join("thumbnails"));
+    assert!(sibling.path().is_dir(), "Sibling directory should be created");
+    assert_eq!(sibling.refresh_interval(), Duration::from_secs(42));
+
+    // Verify names other than plain directory names are rejected
+    for name in ["", "..", "a/b", "/absolute"] {
+        assert!(
+            matches!(cache.new_sibling(name), Err(fcache::Error::InvalidPath { .. })),
+            "Should reject the sibling name {name:?}"
+        );
+    }
+
+    // Create a sibling of a temporary cache, which outlives the cache
+    let temp_cache = fcache::new()?;
+    let sibling = temp_cache.new_sibling(&format!("fcache-sibling-{}", std::process::id()))?;
+    drop(temp_cache);
+    assert!(
+        sibling.path().is_dir(),
+        "Sibling directory should outlive the temporary cache"
+    );
+    std::fs::remove_dir_all(sibling.path())?;
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_temp_with() -> anyhow::Result<()> {
+    let parent_dir = TempDir::new()?;
+
+    // Create a new cache instance with a custom directory name
+    let options = fcache::TempDirOptions::default()
+        .with_prefix("pre-")
+        .with_suffix("-suf")
+        .with_random_len(3)
+        .with_parent(parent_dir.path());
+    let cache = fcache::temp_with(options)?;
+
+    // Verify the directory name shape
+    assert_eq!(cache.path().parent(), Some(parent_dir.path().canonicalize()?.as_path()));
+    let name = cache
+        .path()
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    assert!(
+        name.starts_with("pre-") && name.ends_with("-suf"),
+        "Unexpected name {name}"
+    );
+    assert_eq!(name.len(), "pre-".len() + 3 + "-suf".len());
+
+    // Verify the directory is removed with the cache
+    let path = cache.path().to_path_buf();
+    drop(cache);
+    assert!(!path.exists(), "Temporary directory should be removed");
+
+    // Verify the permissions of the directory
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cache = fcache::Cache::temp_with(fcache::TempDirOptions::default().with_mode(0o700))?;
+        let mode = std::fs::metadata(cache.path())?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cache_write_check() -> anyhow::Result<()> {
+    use std::fs::{self, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    // Create a read-only directory
+    let temp_dir = TempDir::new()?;
+    fs::set_permissions(temp_dir.path(), Permissions::from_mode(0o555))?;
+    if File::create(temp_dir.path().join("probe.txt")).is_ok() {
+        // Permissions are not enforced, e.g. when running as root
+        fs::set_permissions(temp_dir.path(), Permissions::from_mode(0o755))?;
+        return Ok(());
+    }
+
+    // Verify the cache cannot be created in the directory
+    let result = fcache::with_dir(temp_dir.path());
+    assert!(
+        matches!(result, Err(fcache::Error::CacheNotWritable { .. })),
+        "Should return the cache not writable error"
+    );
+
+    // Verify the directory can be opened as a read-only cache
+    let cache = fcache::Cache::open_dir_read_only(temp_dir.path())?;
+    assert!(!cache.write_check());
+    assert!(cache.is_frozen());
+    let result = cache.get("test.txt", |_| Ok(()));
+    assert!(
+        matches!(result, Err(fcache::Error::Frozen { .. })),
+        "Should return the frozen error"
+    );
+
+    fs::set_permissions(temp_dir.path(), Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[test]
+fn test_cache_write_check_cleans_up() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    // Create a new cache instance probing the directory twice
+    let cache = fcache::with_dir(temp_dir.path())?.with_write_check(true)?;
+    assert!(cache.write_check());
+
+    // Verify no probe file is left behind
+    assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 0);
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cache_secure_permissions() -> anyhow::Result<()> {
+    use std::fs::{self, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = |path: &std::path::Path| -> anyhow::Result<u32> { Ok(fs::metadata(path)?.permissions().mode() & 0o777) };
+
+    // Create a new secure cache instance
+    let cache = fcache::Cache::secure()?;
+    assert!(cache.secure_permissions());
+    assert_eq!(mode(cache.path())?, 0o700);
+
+    // Verify nested directories and files are restricted to the owner
+    let cache_file = cache.get("a/b/token.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(mode(&cache.path().join("a"))?, 0o700);
+    assert_eq!(mode(&cache.path().join("a/b"))?, 0o700);
+    assert_eq!(mode(cache_file.path())?, 0o600);
+    cache_file.force_refresh()?;
+    assert_eq!(mode(cache_file.path())?, 0o600);
+
+    // Verify existing directories are restricted to the owner
+    let temp_dir = TempDir::new()?;
+    fs::set_permissions(temp_dir.path(), Permissions::from_mode(0o755))?;
+    let cache = fcache::with_dir(temp_dir.path())?.with_secure_permissions(true)?;
+    assert_eq!(mode(cache.path())?, 0o700);
+
+    // Verify directories writable by others are rejected
+    fs::set_permissions(temp_dir.path(), Permissions::from_mode(0o777))?;
+    let result = fcache::with_dir(temp_dir.path())?.with_secure_permissions(true);
+    assert!(
+        matches!(result, Err(fcache::Error::InsecurePermissions { mode: 0o777, .. })),
+        "Should return the insecure permissions error"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_close() -> anyhow::Result<()> {
+    // Create a new cache instance in a directory
+    let temp_dir = TempDir::new()?;
+    let cache = fcache::Cache::with_dir(temp_dir.path())?;
+
+    // Create a file slowly on another thread through an owned handle
+    let (started_sender, started_receiver) = std::sync::mpsc::channel();
+    let cache_file = cache
+        .get_lazy("nested/file.txt", move |mut file| {
+            let _ = started_sender.send(());
+            std::thread::sleep(Duration::from_millis(200));
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?
+        .into_owned();
+    let handle = std::thread::spawn(move || cache_file.create().map(|_| ()));
+    started_receiver.recv()?;
+
+    // Leave a staging file behind as an interrupted write would, and another as a write in progress in another process
+    let stale_path = temp_dir.path().join(".fcache-tmpAbC123");
+    std::fs::write(&stale_path, TEST_CONTENT)?;
+    File::options()
+        .write(true)
+        .open(&stale_path)?
+        .set_modified(std::time::SystemTime::now() - Duration::from_secs(2 * 60 * 60))?;
+    let fresh_path = temp_dir.path().join("nested/.fcache-tmpXyZ789");
+    std::fs::write(&fresh_path, TEST_CONTENT)?;
+    let _ = cache.get(".tmprc", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Close the cache while the file is being created
+    let report = cache.close_with_timeout(Duration::from_secs(10))?;
+    assert_eq!(report.operations_completed, 1);
+    assert_eq!(report.operations_abandoned, 0);
+    assert_eq!(report.temp_files_removed, 1);
+    handle.join().expect("Thread should not panic")?;
+
+    // Verify only the stale staging file was removed
+    assert!(!stale_path.exists(), "Stale staging file should be removed");
+    assert!(fresh_path.exists(), "Recent staging file should be kept");
+    assert_eq!(std::fs::read(temp_dir.path().join(".tmprc"))?, TEST_CONTENT);
+    assert_eq!(std::fs::read(temp_dir.path().join("nested/file.txt"))?, TEST_CONTENT);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_close_clock() -> anyhow::Result<()> {
+    // Leave a staging file behind in a cache directory, recent by the wall clock
+    let temp_dir = TempDir::new()?;
+    let staging_path = temp_dir.path().join(".fcache-tmpAbC123");
+    std::fs::write(&staging_path, TEST_CONTENT)?;
+
+    // Verify the age of the staging file is judged by the clock of the cache
+    let clock = fcache::ManualClock::new(std::time::SystemTime::now());
+    let report = fcache::Cache::with_dir(temp_dir.path())?
+        .with_clock(clock.clone())
+        .close()?;
+    assert_eq!(report.temp_files_removed, 0);
+    assert!(staging_path.exists(), "Recent staging file should be kept");
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    let report = fcache::Cache::with_dir(temp_dir.path())?.with_clock(clock).close()?;
+    assert_eq!(report.temp_files_removed, 1);
+    assert!(!staging_path.exists(), "Stale staging file should be removed");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_close_timeout() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file on another thread until the cache is cancelled
+    let cancel_token = cache.cancellation_token();
+    let (started_sender, started_receiver) = std::sync::mpsc::channel();
+    let cache_file = cache
+        .get_lazy("file.txt", move |_| {
+            let _ = started_sender.send(());
+            let started_at = std::time::Instant::now();
+            while !cancel_token.is_cancelled() && started_at.elapsed() < Duration::from_secs(10) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err("cancelled".into())
+        })?
+        .into_owned();
+    let handle = std::thread::spawn(move || cache_file.create().map(|_| ()));
+    started_receiver.recv()?;
+
+    // Verify the creation is abandoned and cancelled once the timeout elapses
+    let report = cache.close_with_timeout(Duration::from_millis(50))?;
+    assert_eq!(report.operations_completed, 0);
+    assert_eq!(report.operations_abandoned, 1);
+    let result = handle.join().expect("Thread should not panic");
+    assert!(
+        matches!(result, Err(fcache::Error::Cancelled { .. })),
+        "Should return the cancelled error"
+    );
+
+    Ok(())
+}
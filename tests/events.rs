@@ -0,0 +1,66 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::*;
+use fcache::CacheEvent;
+
+#[test]
+fn test_cache_observer() -> anyhow::Result<()> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    // Create a new cache instance recording the events
+    let recorded_events = Arc::clone(&events);
+    let cache = fcache::new()?
+        .with_entry_limit_policy(fcache::LimitPolicy::new().max_entries(1))
+        .with_observer(move |event| recorded_events.lock().unwrap().push(event));
+
+    // Create, lock, refresh, and remove a file
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let path = cache_file.path().to_path_buf();
+    cache_file.lock()?;
+    cache_file.unlock()?;
+    cache_file.force_refresh()?;
+    cache_file.remove()?;
+    assert_eq!(
+        events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![
+            CacheEvent::Created { path: path.clone() },
+            CacheEvent::LockAcquired { path: path.clone() },
+            CacheEvent::LockReleased { path: path.clone() },
+            CacheEvent::Refreshed { path: path.clone() },
+            CacheEvent::Removed { path: path.clone() },
+        ]
+    );
+
+    // Evict a file by exceeding the limit policy
+    let evicted_file = cache.get("evicted.txt", |_| Ok(()))?;
+    let evicted_path = evicted_file.path().to_path_buf();
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    assert_eq!(
+        events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        vec![
+            CacheEvent::Created {
+                path: evicted_path.clone()
+            },
+            CacheEvent::Created { path: path.clone() },
+            CacheEvent::Evicted { path: evicted_path },
+        ]
+    );
+
+    // Fail to create a file
+    let failing_file = cache.get_lazy("failing.txt", |_| Err("generation failed".into()))?;
+    assert!(failing_file.open().is_err(), "Should fail to create the file");
+    assert!(
+        matches!(
+            events.lock().unwrap().last(),
+            Some(CacheEvent::Error { path: Some(error_path), error }) if error_path.ends_with("failing.txt") && error == "generation failed"
+        ),
+        "Should emit the error"
+    );
+
+    Ok(())
+}
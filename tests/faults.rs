@@ -0,0 +1,240 @@
+#![cfg(feature = "testing")]
+
+mod common;
+
+use std::io::{self, ErrorKind};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use common::*;
+use fcache::FaultPoint;
+
+#[test]
+fn test_fault_storage_full_on_refresh() -> anyhow::Result<()> {
+    // Create a new cache instance running out of space on demand
+    let disk_full = Arc::new(AtomicBool::new(false));
+    let cache = fcache::new()?.with_fault_injector({
+        let disk_full = disk_full.clone();
+        move |fault_point| {
+            match fault_point {
+                FaultPoint::RefreshOpen { .. } if disk_full.load(Ordering::SeqCst) => {
+                    Some(io::Error::from(ErrorKind::StorageFull))
+                },
+                _ => None,
+            }
+        }
+    });
+
+    // Create a file in the cache
+    let cache_file = cache.get("test.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the refresh fails with the injected error
+    disk_full.store(true, Ordering::SeqCst);
+    let result = cache_file.force_refresh();
+    assert!(
+        matches!(result, Err(fcache::Error::IO(ref error)) if error.kind() == ErrorKind::StorageFull),
+        "Should return the injected error"
+    );
+
+    // Verify the content is left untouched
+    let mut content = Vec::new();
+    File::open(cache_file.path())?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+
+    // Verify the refresh succeeds once there is space again
+    disk_full.store(false, Ordering::SeqCst);
+    cache_file.force_refresh()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_permission_denied_on_create() -> anyhow::Result<()> {
+    // Create a new cache instance denied to create files
+    let cache = fcache::new()?.with_fault_injector(|fault_point| {
+        match fault_point {
+            FaultPoint::CreateOpen { path } if path.ends_with("denied.txt") => {
+                Some(io::Error::from(ErrorKind::PermissionDenied))
+            },
+            _ => None,
+        }
+    });
+
+    // Verify the creation fails with the injected error
+    let result = cache.get("denied.txt", |_| Ok(()));
+    assert!(
+        matches!(result, Err(fcache::Error::IO(ref error)) if error.kind() == ErrorKind::PermissionDenied),
+        "Should return the injected error"
+    );
+    assert!(!cache.path().join("denied.txt").exists());
+
+    // Verify other files are created
+    let cache_file = cache.get("allowed.txt", |_| Ok(()))?;
+    assert!(cache_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_points() -> anyhow::Result<()> {
+    // Create a new cache instance failing every operation on demand
+    let failing = Arc::new(AtomicBool::new(false));
+    let cache = fcache::new()?.with_fault_injector({
+        let failing = failing.clone();
+        move |_| failing.load(Ordering::SeqCst).then(|| io::Error::other("injected"))
+    });
+    let cache_file = cache.get("test.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the callback, metadata, and removal faults
+    failing.store(true, Ordering::SeqCst);
+    assert!(
+        matches!(cache.get("other.txt", |_| Ok(())), Err(fcache::Error::IO(_))),
+        "Should fail to create the file"
+    );
+    assert!(
+        matches!(cache_file.is_valid(), Err(fcache::Error::IO(_))),
+        "Should fail to read the metadata"
+    );
+    assert!(
+        matches!(cache_file.remove(), Err(fcache::Error::IO(_))),
+        "Should fail to remove the file"
+    );
+    assert!(cache_file.path().exists());
+
+    // Verify the callback faults are returned as callback errors
+    let callback_cache = fcache::new()?.with_fault_injector(|fault_point| {
+        match fault_point {
+            FaultPoint::CallbackWrite { .. } => Some(io::Error::from(ErrorKind::StorageFull)),
+            _ => None,
+        }
+    });
+    assert!(
+        matches!(
+            callback_cache.get("test.txt", |_| Ok(())),
+            Err(fcache::Error::Callback(_))
+        ),
+        "Should fail to write the file"
+    );
+
+    // Verify the faults are inherited by subdirectories
+    let subdirectory = cache.subdirectory("nested")?;
+    assert!(
+        matches!(subdirectory.get("test.txt", |_| Ok(())), Err(fcache::Error::IO(_))),
+        "Should fail to create the file"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_remove_on_clear() -> anyhow::Result<()> {
+    // Create a new cache instance denied to remove a file
+    let cache = fcache::new()?.with_fault_injector(|fault_point| {
+        match fault_point {
+            FaultPoint::Remove { path } if path.ends_with("busy.txt") => {
+                Some(io::Error::from(ErrorKind::PermissionDenied))
+            },
+            _ => None,
+        }
+    });
+    let _ = cache.get("a/busy.txt", |_| Ok(()))?;
+
+    // Verify the clear fails with the path of the file
+    let result = cache.clear();
+    assert!(
+        matches!(
+            result,
+            Err(fcache::Error::RemoveFailed { ref path, ref source })
+                if path.ends_with("a/busy.txt") && source.kind() == ErrorKind::PermissionDenied
+        ),
+        "Should return the path of the file"
+    );
+    assert!(cache.exists("a/busy.txt")?);
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_rename_on_persist() -> anyhow::Result<()> {
+    // Create a new cache instance which cannot be moved
+    let cache = fcache::new()?.with_fault_injector(|fault_point| {
+        match fault_point {
+            FaultPoint::Rename { .. } => Some(io::Error::from(ErrorKind::PermissionDenied)),
+            _ => None,
+        }
+    });
+    let temp_path = cache.path().to_path_buf();
+    let _ = cache.get("a/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the failed move gives the cache back with its files
+    let parent_dir = TempDir::new()?;
+    let target = parent_dir.path().join("persisted");
+    let error = match cache.persist(&target) {
+        Ok(_) => anyhow::bail!("Should return the injected error"),
+        Err(error) => error,
+    };
+    assert!(matches!(&error.error, fcache::Error::IO(error) if error.kind() == ErrorKind::PermissionDenied));
+    let cache = *error.cache;
+    assert_eq!(cache.path(), temp_path);
+    assert_eq!(std::fs::read(temp_path.join("a/file.txt"))?, TEST_CONTENT);
+    assert!(!target.exists(), "Target should not be created");
+
+    // Verify the temporary directory is still removed once the cache is dropped
+    drop(cache);
+    assert!(!temp_path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_cross_device_on_persist() -> anyhow::Result<()> {
+    // Create a new cache instance which cannot be renamed
+    let cache = fcache::new()?
+        .with_refresh_interval(Duration::from_secs(60))
+        .with_fault_injector(|fault_point| {
+            match fault_point {
+                FaultPoint::Rename { .. } => Some(io::Error::from(ErrorKind::CrossesDevices)),
+                _ => None,
+            }
+        });
+    let temp_path = cache.path().to_path_buf();
+
+    // Create files in the cache
+    let cache_file = cache.get("a/b/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let modified = std::fs::metadata(cache_file.path())?.modified()?;
+    drop(cache_file);
+
+    // Persist the cache into another directory
+    let parent_dir = TempDir::new()?;
+    let target = parent_dir.path().join("persisted");
+    let cache = cache.persist(&target)?;
+
+    // Verify the directory was copied along with the modification times
+    assert_eq!(cache.path(), target.canonicalize()?);
+    assert!(!temp_path.exists(), "Temporary directory should be removed");
+    let path = target.join("a/b/file.txt");
+    assert_eq!(std::fs::read(&path)?, TEST_CONTENT);
+    assert_eq!(std::fs::metadata(&path)?.modified()?, modified);
+    assert!(matches!(
+        cache.status("a/b/file.txt")?,
+        fcache::EntryStatus::Present { valid: true, .. }
+    ));
+
+    // Verify the persisted cache is kept when dropped
+    drop(cache);
+    assert!(path.exists(), "Persisted cache should be kept");
+
+    Ok(())
+}
@@ -1,5 +1,10 @@
 mod common;
 
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
+
 use common::*;
 
 #[test]
@@ -64,6 +69,98 @@ fn test_get_lazy_file() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_put() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Store bytes and text in the cache
+    let cache_file = cache.put("file.bin", TEST_CONTENT)?;
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+    let text_file = cache.put_string("file.txt", String::from("text"))?;
+    assert_eq!(std::fs::read_to_string(text_file.path())?, "text");
+
+    // Verify the content is written again on refresh
+    std::fs::write(cache_file.path(), b"changed")?;
+    cache_file.force_refresh()?;
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    // Verify the same checks as for created files apply
+    assert!(
+        matches!(cache.put("file.bin", b""), Err(fcache::Error::FileAlreadyExists { .. })),
+        "Should return an error when trying to create the same file twice"
+    );
+    assert!(
+        cache.put_string("../file.txt", "text").is_err(),
+        "Paths outside the cache should be rejected"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_bytes_and_string() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Read the content of a new file
+    let content = cache.read_bytes("file.bin", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(content, TEST_CONTENT);
+
+    // Read the content of the existing file without running the callback
+    let content = cache.read_string("file.bin", |_| Err("unused".into()))?;
+    assert_eq!(content.as_bytes(), TEST_CONTENT);
+
+    // Verify invalid UTF-8 is rejected
+    assert!(
+        matches!(
+            cache.read_string("invalid.txt", |mut file| {
+                file.write_all(&[0xFF, 0xFE])?;
+                Ok(())
+            }),
+            Err(fcache::Error::Utf8 { .. })
+        ),
+        "Should return an error when the content is not valid UTF-8"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_read_bytes_and_string() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_millis(50));
+
+    // Read a lazy file, creating it
+    let lazy_file = cache.get_lazy("lazy.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(lazy_file.read_bytes()?, TEST_CONTENT);
+    assert_eq!(lazy_file.read_string()?.as_bytes(), TEST_CONTENT);
+
+    // Read an expired file, refreshing it
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    std::fs::write(cache_file.path(), b"stale")?;
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(cache_file.read_bytes()?, TEST_CONTENT);
+
+    // Verify invalid UTF-8 is rejected
+    std::fs::write(cache_file.path(), [0xFF, 0xFE])?;
+    assert!(
+        matches!(cache_file.read_string(), Err(fcache::Error::Utf8 { .. })),
+        "Should return an error when the content is not valid UTF-8"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_double_file_get() -> anyhow::Result<()> {
     // Create a new cache instance
@@ -165,6 +262,169 @@ fn test_file_out_of_cache() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_file_absolute_path() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file using an absolute path within the cache
+    let cache_file = cache.get(cache.path().join("a/file.txt"), |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(cache_file.path(), cache.path().join("a/file.txt"));
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    // Verify absolute and relative paths refer to the same file
+    let cache_file = cache.get_lazy(cache.path().join("b/file.txt"), |_| Ok(()))?;
+    assert_eq!(cache_file.path(), cache.path().join("b/file.txt"));
+    assert!(cache.explain(cache.path().join("a/file.txt"))?.exists);
+
+    // Create a file using an absolute path out of the cache
+    let temp_dir = TempDir::new()?;
+    assert!(
+        matches!(
+            cache.get(temp_dir.path().join("file.txt"), |_| Ok(())),
+            Err(fcache::Error::PathTraversal { .. })
+        ),
+        "Should return an error when trying to create a file outside the cache"
+    );
+    assert!(
+        matches!(
+            cache.get(cache.path().join("../file.txt"), |_| Ok(())),
+            Err(fcache::Error::PathTraversal { .. })
+        ),
+        "Should return an error when trying to create a file outside the cache"
+    );
+
+    // Create a file using the cache directory itself
+    assert!(
+        matches!(
+            cache.get(cache.path(), |_| Ok(())),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when the path is the cache directory"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_current_dir_prefix() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file using a key with a leading current directory
+    let cache_file = cache.get("./a/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(cache_file.path(), cache.path().join("a/file.txt"));
+
+    // Verify both keys refer to the same file
+    assert!(cache.explain("a/file.txt")?.exists);
+    assert!(cache.explain("./a/file.txt")?.exists);
+
+    // Create a second reference to the same file
+    assert!(
+        matches!(
+            cache.get("a/file.txt", |_| Ok(())),
+            Err(fcache::Error::FileAlreadyExists { .. })
+        ),
+        "Should return an error when trying to create the same file twice"
+    );
+    assert!(
+        matches!(
+            cache.get("./a/file.txt", |_| Ok(())),
+            Err(fcache::Error::FileAlreadyExists { .. })
+        ),
+        "Should return an error when trying to create the same file twice"
+    );
+
+    // Create a file using the current directory only
+    assert!(
+        matches!(cache.get(".", |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when the key is the current directory"
+    );
+    assert!(
+        matches!(cache.get("./", |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when the key is the current directory"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_path_canonicalization() -> anyhow::Result<()> {
+    for mode in [
+        fcache::CanonicalizationMode::Strict,
+        fcache::CanonicalizationMode::Lenient,
+        fcache::CanonicalizationMode::None,
+    ] {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join("cache");
+
+        // Create a new cache instance
+        let cache = fcache::with_dir(&cache_dir)?.with_path_canonicalization(mode);
+        assert_eq!(cache.path_canonicalization(), mode);
+
+        // Create a file in nested directories
+        let cache_file = cache.get("a/b/file.txt", |_| Ok(()))?;
+        assert!(cache_file.path().exists());
+
+        // Create a file out of the cache
+        assert!(
+            matches!(
+                cache.get("a/../../outside/file.txt", |_| Ok(())),
+                Err(fcache::Error::PathTraversal { .. })
+            ),
+            "Should return an error when trying to create a file outside the cache"
+        );
+        if mode != fcache::CanonicalizationMode::Strict {
+            assert!(
+                !temp_dir.path().join("outside").exists(),
+                "Directories should be checked before they are created"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_path_canonicalization_symlink() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let outside_dir = TempDir::new()?;
+
+    // Link a directory within the cache to a directory outside of it
+    let cache_dir = temp_dir.path().join("cache");
+    std::fs::create_dir(&cache_dir)?;
+    std::os::unix::fs::symlink(outside_dir.path(), cache_dir.join("link"))?;
+
+    // Verify resolving modes detect the link
+    for mode in [
+        fcache::CanonicalizationMode::Strict,
+        fcache::CanonicalizationMode::Lenient,
+    ] {
+        let cache = fcache::with_dir(&cache_dir)?.with_path_canonicalization(mode);
+        assert!(
+            matches!(
+                cache.get("link/file.txt", |_| Ok(())),
+                Err(fcache::Error::PathTraversal { .. })
+            ),
+            "Should return an error when the link points outside the cache"
+        );
+    }
+
+    // Verify lexical resolution doesn't follow the link
+    let cache = fcache::with_dir(&cache_dir)?.with_path_canonicalization(fcache::CanonicalizationMode::None);
+    let cache_file = cache.get_lazy("link/file.txt", |_| Ok(()))?;
+    assert_eq!(cache_file.path(), cache.path().join("link/file.txt"));
+
+    Ok(())
+}
+
 #[test]
 fn test_file_callback_error() -> anyhow::Result<()> {
     // Create a new cache instance
@@ -185,6 +445,47 @@ fn test_file_callback_error() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_file_cancelled() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    let token = cache.cancellation_token();
+
+    // Cancel the cache while the callback is running
+    let canceller = token.clone();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        canceller.cancel();
+    });
+    let result = cache.get("file.txt", move |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        while !token.is_cancelled() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        Err("cancelled".into())
+    });
+    handle.join().expect("Canceller thread should not panic");
+
+    // Verify the cancellation is reported and the partial file is removed
+    assert!(
+        matches!(result, Err(fcache::Error::Cancelled { .. })),
+        "Should return an error when the callback is cancelled"
+    );
+    assert!(
+        !cache.path().join("file.txt").exists(),
+        "Partial file should be removed"
+    );
+
+    // Verify new files are not created after cancellation
+    assert!(
+        matches!(cache.get("other.txt", |_| Ok(())), Err(fcache::Error::Cancelled { .. })),
+        "Should return an error when the cache is cancelled"
+    );
+    assert!(!cache.path().join("other.txt").exists());
+
+    Ok(())
+}
+
 #[test]
 fn test_file_removal() -> anyhow::Result<()> {
     // Create a new cache instance
@@ -205,6 +506,74 @@ fn test_file_removal() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_file_removal_on_drop() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create files removed when their handles are dropped
+    let cache_file = cache.get("a/file.txt", |_| Ok(()))?.on_drop_remove(true);
+    let path = cache_file.path().to_path_buf();
+    let lazy_file = cache.get_lazy("b/file.txt", |_| Ok(()))?.forget_on_drop();
+    lazy_file.open()?;
+    let lazy_path = lazy_file.path().to_path_buf();
+    assert!(path.exists());
+    assert!(lazy_path.exists());
+
+    // Verify files are gone along with their handles
+    drop(cache_file);
+    drop(lazy_file);
+    assert!(!path.exists());
+    assert!(!lazy_path.exists());
+    assert!(!cache.path().join("a").exists(), "Parent directory should not exist");
+
+    // Create a file which is kept after all
+    let cache_file = cache
+        .get("file.txt", |_| Ok(()))?
+        .on_drop_remove(true)
+        .on_drop_remove(false);
+    let path = cache_file.path().to_path_buf();
+
+    // Verify file is kept
+    drop(cache_file);
+    assert!(path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_file_remove_on_drop() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Verify files are kept by default
+    let cache_file = cache.get("kept.txt", |_| Ok(()))?;
+    let path = cache_file.path().to_path_buf();
+    drop(cache_file);
+    assert!(path.exists(), "File should be kept by default");
+
+    // Verify files are removed along with their handles
+    let mut cache_file = cache.get("a/scratch.txt", |_| Ok(()))?;
+    cache_file.remove_on_drop(true);
+    let path = cache_file.path().to_path_buf();
+    drop(cache_file);
+    assert!(!path.exists(), "File should be removed on drop");
+    assert!(!cache.path().join("a").exists(), "Parent directory should not exist");
+
+    // Verify explicitly removed files are not removed again on drop
+    let mut cache_file = cache.get("removed.txt", |_| Ok(()))?;
+    cache_file.remove_on_drop(true);
+    cache_file.remove()?;
+    let recreated_file = cache.get("removed.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    drop(cache_file);
+    assert!(recreated_file.path().exists(), "File created again should be kept");
+
+    Ok(())
+}
+
 #[test]
 fn test_nested_file_removal() -> anyhow::Result<()> {
     // Create a new cache instance
@@ -249,70 +618,1304 @@ fn test_nested_file_removal() -> anyhow::Result<()> {
 }
 
 #[test]
-fn test_large_file_content() -> anyhow::Result<()> {
+fn test_file_refresh_after_sibling_removal() -> anyhow::Result<()> {
     // Create a new cache instance
     let cache = fcache::new()?;
 
+    // Create a lazy file which is not yet initialized
+    let cache_file = cache.get_lazy("a/b/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Create and remove a sibling file, collapsing the shared parent directories
+    let sibling_file = cache.get("a/b/c/file.txt", |_| Ok(()))?;
+    sibling_file.remove()?;
+    assert_eq!(
+        cache_file.path().parent().map(|parent| parent.exists()),
+        Some(false),
+        "Parent directory should not exist"
+    );
+
+    // Force refresh the surviving file
+    cache_file.force_refresh()?;
+
+    // Read the content
+    {
+        let mut content = Vec::new();
+        cache_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_CONTENT);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_path_transformer() -> anyhow::Result<()> {
+    // Create a new cache instance storing files in a partition directory
+    let cache = fcache::new()?;
+    let root = cache.path().to_path_buf();
+    let partition = root.join("partition");
+    let cache = cache.with_path_transformer(move |path| {
+        let relative_path = path.strip_prefix(&root).unwrap_or(&path);
+        Ok(partition.join(relative_path))
+    });
+
     // Create a file in the cache
-    let cache_file = cache.get("file.txt", |mut file| {
-        file.write_all(TEST_LARGE_CONTENT)?;
+    let cache_file = cache.get("a/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
         Ok(())
     })?;
 
-    // Verify file exists on disk
-    assert!(cache_file.path().exists());
+    // Verify the file is stored in the partition directory
+    assert!(cache_file.path().ends_with("partition/a/file.txt"));
+    assert_eq!(cache_file.name(), "file.txt");
 
-    // Verify content matches
-    let mut content = Vec::new();
-    cache_file.open()?.read_to_end(&mut content)?;
-    assert_eq!(content, TEST_LARGE_CONTENT, "File content does not match");
+    // Read the content
+    {
+        let mut content = Vec::new();
+        cache_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_CONTENT);
+    }
 
     Ok(())
 }
 
 #[test]
-fn test_file_with_refresh_interval() -> anyhow::Result<()> {
-    let refresh_interval = Duration::from_secs(10);
+fn test_file_path_transformer_out_of_cache() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let outside_path = temp_dir.path().join("file.txt");
+
+    // Create a new cache instance moving files out of the cache directory
+    let cache = fcache::new()?.with_path_transformer(move |_| Ok(outside_path.clone()));
 
+    // Try to create a file in the cache
+    let result = cache.get("file.txt", |_| Ok(()));
+    assert!(
+        matches!(result, Err(fcache::Error::PathTraversal { .. })),
+        "Should fail with path traversal error"
+    );
+
+    // Verify the file was not created
+    assert!(!temp_dir.path().join("file.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_file_open_and_parse() -> anyhow::Result<()> {
     // Create a new cache instance
     let cache = fcache::new()?;
 
     // Create a file in the cache
-    let cache_file = cache
-        .get("file.txt", |_| Ok(()))?
-        .with_refresh_interval(refresh_interval);
+    let cache_file = cache.get("number.txt", |mut file| {
+        file.write_all(b"42")?;
+        Ok(())
+    })?;
 
-    // Verify refresh interval
-    assert_eq!(
-        cache_file.refresh_interval(),
-        refresh_interval,
-        "Refresh interval was not updated"
+    // Parse the content
+    let number = cache_file.open_and_parse(|file| {
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        Ok(content.parse::<u32>()?)
+    })?;
+    assert_eq!(number, 42);
+
+    // Verify parser errors are reported
+    assert!(
+        matches!(
+            cache_file.open_and_parse(|_| Ok("fail".parse::<u32>()?)),
+            Err(fcache::Error::Callback { .. })
+        ),
+        "Should return an error when parser fails"
     );
 
     Ok(())
 }
 
 #[test]
-fn test_file_with_default_refresh_interval() -> anyhow::Result<()> {
-    let refresh_interval = Duration::from_secs(10);
+fn test_file_truncate() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_LARGE_CONTENT)?;
+        Ok(())
+    })?;
+    let modified = SystemTime::now() - Duration::from_secs(60);
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(modified)?;
+
+    // Truncate the file keeping the modification time
+    cache_file.truncate(10)?;
+    assert_eq!(std::fs::metadata(cache_file.path())?.len(), 10);
+    assert_eq!(std::fs::read(cache_file.path())?, &TEST_LARGE_CONTENT[..10]);
+    assert_eq!(std::fs::metadata(cache_file.path())?.modified()?, modified);
+
+    // Truncate the file updating the modification time
+    cache_file.truncate_and_touch(5)?;
+    assert_eq!(cache_file.read_range(0, 100)?, &TEST_LARGE_CONTENT[..5]);
+    assert!(std::fs::metadata(cache_file.path())?.modified()? > modified);
+
+    // Verify locked files are not truncated
+    cache_file.lock()?;
+    assert!(
+        matches!(cache_file.truncate(1), Err(fcache::Error::FileAlreadyLocked)),
+        "Should return an error when the file is locked"
+    );
+    assert_eq!(std::fs::metadata(cache_file.path())?.len(), 5);
 
+    Ok(())
+}
+
+#[test]
+fn test_file_split_into_chunks() -> anyhow::Result<()> {
     // Create a new cache instance
     let cache = fcache::new()?;
 
     // Create a file in the cache
-    let cache_file = cache
-        .get("file.txt", |_| Ok(()))?
-        .with_refresh_interval(refresh_interval);
+    let cache_file = cache.get("a/file.txt", |mut file| {
+        file.write_all(TEST_LARGE_CONTENT)?;
+        Ok(())
+    })?;
 
-    // Update the cache file to use the default refresh interval
-    let cache_file = cache_file.with_default_refresh_interval();
+    // Split the file into small chunks
+    let chunk_size = TEST_LARGE_CONTENT.len().div_ceil(5);
+    let chunks = cache_file.split_into_chunks(chunk_size)?;
+    assert_eq!(chunks.len(), 5);
+    for (index, (chunk, content)) in chunks.iter().zip(TEST_LARGE_CONTENT.chunks(chunk_size)).enumerate() {
+        assert_eq!(chunk.name(), format!("file.txt.part{index}"));
+        assert_eq!(std::fs::read(chunk.path())?, content);
+    }
+    assert!(cache_file.path().exists(), "Original file should be kept");
+
+    // Split the file into fewer chunks
+    let chunks = cache_file.split_into_chunks(TEST_LARGE_CONTENT.len().div_ceil(2))?;
+    assert_eq!(chunks.len(), 2);
+    assert!(
+        !cache.path().join("a/file.txt.part2").exists(),
+        "Stale chunks should be removed"
+    );
 
-    // Verify the refresh interval is set to the default
-    assert_eq!(
-        cache_file.refresh_interval(),
-        cache.refresh_interval(),
-        "Refresh interval was not set to default"
+    // Reassemble the removed file from its chunks
+    cache_file.remove()?;
+    let cache_file = cache.reassemble_chunks("a/file.txt")?;
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_LARGE_CONTENT);
+
+    // Reassemble a file without chunks
+    assert!(
+        matches!(
+            cache.reassemble_chunks("missing.txt"),
+            Err(fcache::Error::FileNotFound { .. })
+        ),
+        "Should return an error when there are no chunks"
     );
 
+    // Split the file into empty chunks
+    assert!(
+        matches!(cache_file.split_into_chunks(0), Err(fcache::Error::IO(_))),
+        "Should return an error when the chunk size is zero"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_read_range() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::ZERO); // Zero refresh interval to always refresh
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        i.fetch_add(1, Ordering::SeqCst);
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Read parts of the file
+    assert_eq!(cache_file.read_range(2, 3)?, &TEST_CONTENT[2..5]);
+    assert_eq!(cache_file.read_range(1, TEST_CONTENT.len())?, &TEST_CONTENT[1..]);
+    assert!(cache_file.read_range(TEST_CONTENT.len() as u64, 1)?.is_empty());
+
+    // Verify the file was not refreshed
+    assert!(cache_file.is_invalid()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_content_type() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Detect the content type of an extensionless gzip file
+    let gzip_file = cache.get("archive", |mut file| {
+        file.write_all(b"\x1f\x8b\x08\x00")?;
+        Ok(())
+    })?;
+    assert_eq!(gzip_file.content_type()?, Some("application/gzip"));
+
+    // Detect the content type of a JSON file by its extension
+    let json_file = cache.get("data.json", |mut file| {
+        file.write_all(b"{}")?;
+        Ok(())
+    })?;
+    assert_eq!(json_file.content_type()?, Some("application/json"));
+
+    // Verify unrecognized binary content has no content type
+    let blob_file = cache.get("blob", |mut file| {
+        file.write_all(&[0x00, 0xFF, 0xFE, 0x80])?;
+        Ok(())
+    })?;
+    assert_eq!(blob_file.content_type()?, None);
+    assert_eq!(fcache::sniff(blob_file.path())?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_open_or_insert_with() -> anyhow::Result<()> {
+    let calls = AtomicUsize::new(0);
+    let generate = || {
+        calls.fetch_add(1, Ordering::SeqCst);
+        TEST_CONTENT.to_vec()
+    };
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+
+    // Insert the generated content
+    assert_eq!(cache.open_or_insert_with("a/file.txt", generate)?, TEST_CONTENT);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Return the cached content without generating it
+    assert_eq!(cache.open_or_insert_with("a/file.txt", generate)?, TEST_CONTENT);
+    assert_eq!(calls.load(Ordering::SeqCst), 1, "Valid content should not be generated");
+
+    // Expire the file
+    File::options()
+        .write(true)
+        .open(cache.path().join("a/file.txt"))?
+        .set_modified(SystemTime::now() - Duration::from_secs(120))?;
+
+    // Generate the content again
+    assert_eq!(cache.open_or_insert_with("a/file.txt", generate)?, TEST_CONTENT);
+    assert_eq!(calls.load(Ordering::SeqCst), 2, "Expired content should be generated");
+
+    Ok(())
+}
+
+#[test]
+fn test_creation_mode() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let counter = Arc::new(AtomicUsize::new(0));
+    let callback = |counter: Arc<AtomicUsize>| {
+        move |mut file: File| {
+            let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            write!(file, "{count}")?;
+            Ok(())
+        }
+    };
+
+    // Verify existing files are rejected by default
+    let cache = fcache::with_dir(temp_dir.path())?;
+    assert_eq!(cache.creation_mode(), fcache::CreationMode::CreateNew);
+    let _ = cache.get("file.txt", callback(counter.clone()))?;
+    assert!(
+        matches!(
+            cache.get("file.txt", callback(counter.clone())),
+            Err(fcache::Error::FileAlreadyExists { .. })
+        ),
+        "Should return an error when the file already exists"
+    );
+
+    // Verify existing files are reused without running the callback
+    let cache = fcache::with_dir(temp_dir.path())?.with_creation_mode(fcache::CreationMode::UseExisting);
+    let cache_file = cache.get("file.txt", callback(counter.clone()))?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "1");
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+    // Verify existing files are regenerated
+    let cache = fcache::with_dir(temp_dir.path())?.with_creation_mode(fcache::CreationMode::CreateOrTruncate);
+    let cache_file = cache.get("file.txt", callback(counter.clone()))?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "2");
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    // Verify missing files are created in every mode
+    let cache_file = cache.get("other.txt", callback(counter.clone()))?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "3");
+
+    Ok(())
+}
+
+#[test]
+fn test_single_threaded_cache() -> anyhow::Result<()> {
+    // Create a new single-threaded cache instance
+    let cache = fcache::new()?
+        .with_refresh_interval(Duration::from_secs(60))
+        .with_single_threaded_mode();
+    assert_eq!(cache.cache().refresh_interval(), Duration::from_secs(60));
+
+    // Create a file with a callback whose state is not thread-safe
+    let calls = Cell::new(0);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        calls.set(calls.get() + 1);
+        write!(file, "{}", calls.get())?;
+        Ok(())
+    })?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "1");
+
+    // Verify the callback keeps its state between refreshes
+    cache_file.force_refresh()?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "2");
+
+    // Create a lazy file with a callback whose state is not thread-safe
+    let content = RefCell::new(TEST_CONTENT.to_vec());
+    let lazy_file = cache.get_lazy("lazy.txt", move |mut file| {
+        file.write_all(&content.borrow())?;
+        Ok(())
+    })?;
+    let mut content = Vec::new();
+    lazy_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+
+    // Verify the cache can be converted back
+    drop((cache_file, lazy_file));
+    let cache = cache.into_inner();
+    assert_eq!(cache.refresh_interval(), Duration::from_secs(60));
+
+    Ok(())
+}
+
+#[test]
+fn test_large_file_content() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_LARGE_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify file exists on disk
+    assert!(cache_file.path().exists());
+
+    // Verify content matches
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_LARGE_CONTENT, "File content does not match");
+
+    Ok(())
+}
+
+#[test]
+fn test_file_with_refresh_interval() -> anyhow::Result<()> {
+    let refresh_interval = Duration::from_secs(10);
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache
+        .get("file.txt", |_| Ok(()))?
+        .with_refresh_interval(refresh_interval);
+
+    // Verify refresh interval
+    assert_eq!(
+        cache_file.refresh_interval(),
+        refresh_interval,
+        "Refresh interval was not updated"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_with_default_refresh_interval() -> anyhow::Result<()> {
+    let refresh_interval = Duration::from_secs(10);
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache
+        .get("file.txt", |_| Ok(()))?
+        .with_refresh_interval(refresh_interval);
+
+    // Update the cache file to use the default refresh interval
+    let cache_file = cache_file.with_default_refresh_interval();
+
+    // Verify the refresh interval is set to the default
+    assert_eq!(
+        cache_file.refresh_interval(),
+        cache.refresh_interval(),
+        "Refresh interval was not set to default"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_dry_run_get() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::MAX); // Max refresh interval to avoid expiry
+
+    // Predict the creation of a nested file
+    let report = cache.dry_run_get("a/b/file.txt", |_| panic!("Callback should never be invoked"))?;
+    assert!(report.would_create, "File should be created");
+    assert!(!report.would_refresh, "Missing file should not be refreshed");
+    assert!(report.would_error.is_none(), "No error should be predicted");
+    assert_eq!(report.path, cache.path().join("a/b/file.txt"));
+    assert_eq!(report.estimated_duration, None);
+
+    // Verify nothing was created
+    assert!(!cache.path().join("a").exists(), "Directories should not be created");
+
+    // Predict the outcome for an existing file
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    let report = cache.dry_run_get("file.txt", |_| panic!("Callback should never be invoked"))?;
+    assert!(!report.would_create, "Existing file should not be created");
+    assert!(!report.would_refresh, "Valid file should not be refreshed");
+    assert!(matches!(
+        report.would_error,
+        Some(fcache::Error::FileAlreadyExists { .. })
+    ));
+
+    // Predict the outcome for invalid paths
+    let report = cache.dry_run_get("../file.txt", |_| Ok(()))?;
+    assert!(!report.would_create, "File out of the cache should not be created");
+    assert!(matches!(report.would_error, Some(fcache::Error::PathTraversal { .. })));
+    let report = cache.dry_run_get("a/missing/../../../file.txt", |_| Ok(()))?;
+    assert!(matches!(report.would_error, Some(fcache::Error::PathTraversal { .. })));
+    let report = cache.dry_run_get("dir/", |_| Ok(()))?;
+    assert!(matches!(report.would_error, Some(fcache::Error::InvalidPath { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn test_file_too_large() -> anyhow::Result<()> {
+    let limit = TEST_CONTENT.len() as u64;
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_max_file_size(limit);
+    assert_eq!(cache.max_file_size(), Some(limit));
+
+    // Create a file within the limit
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert!(cache_file.path().exists());
+
+    // Create a file exceeding the limit
+    let result = cache.get("large.txt", |mut file| {
+        file.write_all(TEST_LARGE_CONTENT)?;
+        Ok(())
+    });
+    assert!(
+        matches!(result, Err(fcache::Error::FileTooLarge { size, limit: error_limit, .. }) if size == TEST_LARGE_CONTENT.len() as u64 && error_limit == limit),
+        "Should return an error when the file exceeds the limit"
+    );
+
+    // Verify the oversized file is removed
+    assert!(!cache.path().join("large.txt").exists());
+
+    // Override the limit for a single file
+    let cache_file = cache.get_with_max_size(
+        "large.txt",
+        |mut file| {
+            file.write_all(TEST_LARGE_CONTENT)?;
+            Ok(())
+        },
+        TEST_LARGE_CONTENT.len() as u64,
+    )?;
+    assert!(cache_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_file_too_large_on_refresh() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_max_file_size(TEST_CONTENT.len() as u64);
+
+    // Create a file that grows on refresh
+    let cache_file = cache.get("file.txt", move |mut file| {
+        if i.fetch_add(1, Ordering::SeqCst) == 0 {
+            file.write_all(TEST_CONTENT)?;
+        } else {
+            file.write_all(TEST_LARGE_CONTENT)?;
+        }
+        Ok(())
+    })?;
+
+    // Verify the oversized refresh is rejected and the file removed
+    assert!(
+        matches!(cache_file.force_refresh(), Err(fcache::Error::FileTooLarge { .. })),
+        "Should return an error when the refreshed file exceeds the limit"
+    );
+    assert!(!cache_file.path().exists());
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+enum FetchError {
+    #[error("upstream unavailable")]
+    Unavailable,
+    #[error("upstream returned status {0}")]
+    Status(u16),
+}
+
+#[test]
+fn test_get_typed_file() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Verify the typed error of a failing creation
+    let result = cache.get_typed("failing.txt", |_| Err(FetchError::Unavailable));
+    assert!(
+        matches!(result, Err(fcache::Error::Callback(FetchError::Unavailable))),
+        "Should return the typed callback error"
+    );
+
+    // Create a typed file failing on the second call
+    let calls = Arc::new(AtomicUsize::new(0));
+    let typed_file = cache.get_typed("test.txt", {
+        let calls = calls.clone();
+        move |mut file| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => file.write_all(TEST_CONTENT).map_err(|_| FetchError::Unavailable),
+                _ => Err(FetchError::Status(503)),
+            }
+        }
+    })?;
+
+    // Verify the typed error of a failing refresh
+    let result = typed_file.force_refresh();
+    assert!(
+        matches!(result, Err(fcache::Error::Callback(FetchError::Status(503)))),
+        "Should return the typed callback error"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Verify the untyped errors are preserved
+    cache.freeze();
+    let result = typed_file.force_refresh();
+    assert!(
+        matches!(result, Err(fcache::Error::Frozen { .. })),
+        "Should return the frozen error"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_entry() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a mix of eager and lazy files
+    let calls = Arc::new(AtomicUsize::new(0));
+    let callback = {
+        let calls = calls.clone();
+        move |mut file: File| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        }
+    };
+    let entries: Vec<fcache::CacheEntry> = vec![
+        cache.get("eager.txt", callback.clone())?.into(),
+        cache.get_lazy("lazy.txt", callback.clone())?.into(),
+        cache.get_lazy("other.txt", callback)?.into(),
+    ];
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(matches!(entries[0], fcache::CacheEntry::Eager(_)));
+    assert!(matches!(entries[1], fcache::CacheEntry::Lazy(_)));
+
+    // Refresh all the files
+    for entry in &entries {
+        entry.force_refresh()?;
+        assert!(entry.is_valid()?);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+    // Verify the content of all the files
+    for entry in &entries {
+        let mut content = Vec::new();
+        entry.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_CONTENT);
+    }
+
+    // Verify the locking is delegated
+    entries[1].lock()?;
+    assert!(entries[1].is_locked());
+    entries[1].unlock()?;
+    assert!(!entries[1].is_locked());
+
+    // Convert the entries into eager files
+    let mut entries = entries.into_iter();
+    let lazy_entry = entries.nth(1).expect("Should contain the lazy entry");
+    let cache_file = lazy_entry.into_eager()?;
+    assert!(cache_file.path().ends_with("lazy.txt"));
+
+    // Remove the remaining file
+    let other_entry = entries.next().expect("Should contain the other entry");
+    other_entry.remove()?;
+    assert!(!other_entry.path().exists());
+
+    Ok(())
+}
+
+/// Reader failing after yielding the given number of bytes.
+struct FailingReader {
+    remaining: usize,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(std::io::Error::other("connection reset"));
+        }
+        let len = buf.len().min(self.remaining);
+        buf[..len].fill(b'x');
+        self.remaining -= len;
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_file_put_from_reader() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Stream a multi-megabyte content into the cache
+    let data: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+    let cache_file = cache.put_from_reader("file.bin", std::io::Cursor::new(data.clone()))?;
+    assert_eq!(std::fs::read(cache_file.path())?, data);
+    assert!(cache_file.is_valid()?);
+
+    // Verify the file can't be refreshed through its callback
+    assert!(matches!(cache_file.force_refresh(), Err(fcache::Error::Callback(_))));
+
+    // Replace the content
+    let written = cache_file.replace_from_reader(TEST_CONTENT)?;
+    assert_eq!(written, TEST_CONTENT.len() as u64);
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+
+    // Verify locked files are not replaced
+    cache_file.lock()?;
+    assert!(matches!(
+        cache_file.replace_from_reader(&b"locked"[..]),
+        Err(fcache::Error::FileAlreadyLocked)
+    ));
+    cache_file.unlock()?;
+
+    // Verify a failed replacement keeps the previous content
+    assert!(
+        cache_file
+            .replace_from_reader(FailingReader { remaining: 100_000 })
+            .is_err()
+    );
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    // Verify a failed creation leaves no file behind
+    assert!(
+        cache
+            .put_from_reader("failed.bin", FailingReader { remaining: 100_000 })
+            .is_err()
+    );
+    assert!(!cache.path().join("failed.bin").exists());
+    for entry in std::fs::read_dir(cache.path())? {
+        let file_name = entry?.file_name();
+        assert!(
+            !file_name.to_string_lossy().starts_with(".fcache-tmp"),
+            "No temporary files should be left behind"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_exists() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a nested file
+    let _ = cache.get("a/b/file.txt", |_| Ok(()))?;
+
+    // Check the file and a missing sibling
+    assert!(cache.exists("a/b/file.txt")?, "Existing file should be reported");
+    assert!(cache.contains("a/b/file.txt")?, "Existing file should be reported");
+    assert!(!cache.exists("a/b/missing.txt")?, "Missing file should not be reported");
+    assert!(
+        !cache.contains("a/b/missing.txt")?,
+        "Missing file should not be reported"
+    );
+
+    // Check missing files in missing directories
+    assert!(!cache.exists("c/d/missing.txt")?, "Missing file should not be reported");
+    assert!(!cache.path().join("c").exists(), "No directories should be created");
+
+    // Verify invalid paths are rejected
+    assert!(matches!(
+        cache.exists("../file.txt"),
+        Err(fcache::Error::PathTraversal { .. })
+    ));
+    assert!(matches!(cache.exists(""), Err(fcache::Error::InvalidPath { .. })));
+    assert!(matches!(
+        cache.contains("a/../../file.txt"),
+        Err(fcache::Error::PathTraversal { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_file_attach() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Populate a cache directory in a previous run
+    let temp_dir = TempDir::new()?;
+    {
+        let cache = fcache::Cache::with_dir(temp_dir.path())?;
+        let _ = cache.get("data/file.txt", |mut file| {
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?;
+    }
+
+    // Attach to the file in a new run
+    let cache = fcache::Cache::with_dir(temp_dir.path())?.with_refresh_interval(Duration::from_secs(60));
+    let counter = Arc::clone(&calls);
+    let cache_file = cache.attach("data/file.txt", move |mut file| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(cache_file.refresh_interval(), Duration::from_secs(60));
+    assert!(cache_file.is_valid()?);
+
+    // Verify the callback is only used for refreshes
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    cache_file.force_refresh()?;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Verify missing files and invalid paths are rejected
+    assert!(matches!(
+        cache.attach("data/missing.txt", |_| Ok(())),
+        Err(fcache::Error::FileNotFound { .. })
+    ));
+    assert!(matches!(
+        cache.attach("../file.txt", |_| Ok(())),
+        Err(fcache::Error::PathTraversal { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_file_get_or_attach() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Get the file twice
+    for _ in 0..2 {
+        let counter = Arc::clone(&calls);
+        let cache_file = cache.get_or_attach("file.txt", move |mut file| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?;
+        assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+    }
+
+    // Verify the callback ran only once
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Verify the attached handle still refreshes with its callback
+    let counter = Arc::clone(&calls);
+    let cache_file = cache.get_or_attach("file.txt", move |mut file| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    cache_file.force_refresh()?;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Get another file from many threads at once
+    let calls = Arc::new(AtomicUsize::new(0));
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let counter = Arc::clone(&calls);
+                let cache = &cache;
+                scope.spawn(move || {
+                    cache
+                        .get_or_attach("shared.txt", move |mut file| {
+                            counter.fetch_add(1, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(50));
+                            file.write_all(TEST_CONTENT)?;
+                            Ok(())
+                        })
+                        .map(|_| ())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .try_for_each(|handle| handle.join().expect("Thread should not panic"))
+    })?;
+
+    // Verify only a single thread ran the callback
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_remove() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create nested files
+    let _ = cache.get("a/b/file.txt", |_| Ok(()))?;
+    let _ = cache.get("a/other.txt", |_| Ok(()))?;
+
+    // Remove a file by its key
+    cache.remove("a/b/file.txt")?;
+    assert!(!cache.exists("a/b/file.txt")?, "File should be removed");
+    assert!(
+        !cache.path().join("a/b").exists(),
+        "Empty parent directory should be removed"
+    );
+    assert!(cache.exists("a/other.txt")?, "Sibling file should be kept");
+
+    // Verify removing a missing file succeeds
+    cache.remove("a/b/file.txt")?;
+    cache.remove("missing/file.txt")?;
+    assert!(
+        !cache.path().join("missing").exists(),
+        "No directories should be created"
+    );
+
+    // Verify invalid paths are rejected
+    assert!(matches!(
+        cache.remove("../file.txt"),
+        Err(fcache::Error::PathTraversal { .. })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_clear() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create files in nested subdirectories
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    let _ = cache.get("a/b/c/file.txt", |_| Ok(()))?;
+    let other_file = cache.get("a/other.txt", |_| Ok(()))?;
+    other_file.set_refresh_interval_persist(Duration::from_secs(60))?;
+
+    // Verify a locked file blocks the clear
+    let locked_file = cache.get("locked.txt", |_| Ok(()))?;
+    locked_file.lock()?;
+    assert!(matches!(cache.clear(), Err(fcache::Error::FileAlreadyLocked)));
+    assert!(cache.exists("file.txt")?, "Files should be kept");
+
+    // Verify unlocked and dropped handles don't block the clear
+    locked_file.unlock()?;
+    let dropped_file = cache.get("dropped.txt", |_| Ok(()))?;
+    dropped_file.lock()?;
+    drop(dropped_file);
+    assert_eq!(cache.clear()?, 5);
+
+    // Verify every file and subdirectory is removed, but not the cache directory
+    assert!(cache.path().exists(), "Cache directory should be kept");
+    for path in ["file.txt", "a/b/c/file.txt", "a/other.txt", "locked.txt", "dropped.txt"] {
+        assert!(!cache.exists(path)?, "File should be removed: {path}");
+    }
+    assert!(!cache.path().join("a").exists(), "Subdirectories should be removed");
+
+    // Verify the cache is still usable
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert!(cache_file.is_valid()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_subdirectory_clear() -> anyhow::Result<()> {
+    // Create a new cache instance with nested subdirectories
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    let project = cache.subdirectory("project")?;
+    let nested = project.subdirectory("nested")?;
+    let sibling = cache.subdirectory("sibling")?;
+    assert_eq!(nested.path(), cache.path().join("project/nested"));
+    assert_eq!(nested.refresh_interval(), Duration::from_secs(60));
+
+    // Create files in each of them
+    let nested_file = nested.get("file.txt", |_| Ok(()))?;
+    assert_eq!(nested_file.path(), cache.path().join("project/nested/file.txt"));
+    let _ = project.get("file.txt", |_| Ok(()))?;
+    let _ = sibling.get("file.txt", |_| Ok(()))?;
+
+    // Verify paths are validated relative to the subdirectory
+    assert!(
+        nested.get("../file.txt", |_| Ok(())).is_err(),
+        "Paths outside the subdirectory should be rejected"
+    );
+
+    // Verify clearing a subdirectory doesn't touch its siblings
+    assert_eq!(project.clear()?, 2);
+    assert!(!nested_file.path().exists(), "Nested files should be removed");
+    assert!(sibling.exists("file.txt")?, "Sibling files should be kept");
+    assert!(project.path().exists(), "Subdirectory should be kept");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_scope() -> anyhow::Result<()> {
+    // Create a new cache instance with nested scopes
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    let project = cache.scope("project")?;
+    let build = project.scope("build")?;
+    assert_eq!(build.path(), cache.path().join("project/build"));
+    assert_eq!(build.refresh_interval(), Duration::from_secs(60));
+
+    // Verify keys are confined to the scope
+    let artifact = build.get("artifact.bin", |_| Ok(()))?;
+    assert_eq!(artifact.path(), cache.path().join("project/build/artifact.bin"));
+    assert!(project.exists("build/artifact.bin")?);
+
+    // Verify keys escaping the scope are rejected, even if they stay within the cache directory
+    for key in ["../artifact.bin", "a/../../artifact.bin", "../../other/artifact.bin"] {
+        assert!(
+            matches!(build.get(key, |_| Ok(())), Err(fcache::Error::PathTraversal { .. })),
+            "Keys escaping the scope should be rejected"
+        );
+    }
+    let absolute_key = cache.path().join("project/artifact.bin");
+    assert!(
+        matches!(
+            build.get(&absolute_key, |_| Ok(())),
+            Err(fcache::Error::PathTraversal { .. })
+        ),
+        "Absolute keys outside the scope should be rejected"
+    );
+    assert!(!absolute_key.exists());
+
+    // Verify the prefix of a scope is validated like the path of a subdirectory
+    assert!(
+        cache.scope("../outside").is_err(),
+        "Scopes outside the cache should be rejected"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_persist() -> anyhow::Result<()> {
+    // Create a new temporary cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    let temp_path = cache.path().to_path_buf();
+    let _ = cache.get("a/file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify a non-empty target is rejected, giving the cache back with its files
+    let parent_dir = TempDir::new()?;
+    let occupied = parent_dir.path().join("occupied");
+    std::fs::create_dir(&occupied)?;
+    std::fs::write(occupied.join("other.txt"), b"other")?;
+    let error = match cache.persist(&occupied) {
+        Ok(_) => anyhow::bail!("Should return an error when the target is not empty"),
+        Err(error) => error,
+    };
+    assert!(matches!(error.error, fcache::Error::DirectoryNotEmpty { .. }));
+    let cache = *error.cache;
+    assert_eq!(cache.path(), temp_path);
+    assert_eq!(std::fs::read(temp_path.join("a/file.txt"))?, TEST_CONTENT);
+    assert_eq!(std::fs::read(occupied.join("other.txt"))?, b"other");
+
+    // Persist the cache by renaming its directory
+    let target = parent_dir.path().join("nested/persisted");
+    let cache = cache.persist(&target)?;
+    assert!(!temp_path.exists(), "Temporary directory should be moved");
+    assert_eq!(cache.path(), target.canonicalize()?);
+    assert_eq!(cache.refresh_interval(), Duration::from_secs(60));
+    assert!(matches!(
+        cache.status("a/file.txt")?,
+        fcache::EntryStatus::Present { valid: true, .. }
+    ));
+
+    // Verify the persisted cache is kept when dropped
+    drop(cache);
+    assert_eq!(std::fs::read(target.join("a/file.txt"))?, TEST_CONTENT);
+
+    // Keep a temporary cache in place
+    let cache = fcache::new()?;
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    let path = cache.into_path();
+    assert!(path.join("file.txt").exists(), "Temporary directory should be kept");
+    std::fs::remove_dir_all(path)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_file_into_owned() -> anyhow::Result<()> {
+    // Keep the cache along with a file of it
+    struct App {
+        cache: fcache::Cache,
+        file: fcache::OwnedCacheFile,
+    }
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    let file = cache
+        .get("data.txt", |mut file| {
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?
+        .into_owned();
+    let app = App { cache, file };
+    assert!(app.file.is_valid()?);
+
+    // Verify the owned handle can be used from another thread
+    let app = std::thread::spawn(move || -> fcache::Result<App> {
+        app.file.force_refresh()?;
+        Ok(app)
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("thread panicked"))??;
+    let mut content = Vec::new();
+    app.file.open()?.read_to_end(&mut content)?;
+    assert!(app.cache.exists("data.txt")?);
+    assert_eq!(content, TEST_CONTENT);
+
+    // Create a new cache instance within a directory
+    let temp_dir = TempDir::new()?;
+    let cache = fcache::with_dir(temp_dir.path())?;
+
+    // Verify locks of owned handles are shared with the cache
+    let locked_file = cache.get("locked.txt", |_| Ok(()))?.into_owned();
+    locked_file.lock()?;
+    assert!(matches!(cache.clear(), Err(fcache::Error::FileAlreadyLocked)));
+    locked_file.unlock()?;
+
+    // Verify the file is still removed along with the owned handle
+    let scratch_file = cache.get("scratch.txt", |_| Ok(()))?.forget_on_drop().into_owned();
+    let path = scratch_file.path().to_path_buf();
+    drop(cache);
+    assert!(path.exists(), "File should be kept while the handle is alive");
+    drop(scratch_file);
+    assert!(!path.exists(), "File should be removed along with the handle");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_list() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    assert!(cache.list()?.is_empty(), "New cache should be empty");
+
+    // Create files in nested subdirectories, along with sidecar and temporary files
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+    let _ = cache.get("a/b/file.txt", |_| Ok(()))?;
+    let lazy_file = cache.get_lazy("a/lazy.txt", |_| Ok(()))?;
+    let versioned_file = cache.get("a/versioned.txt", |_| Ok(()))?.with_version("v1");
+    versioned_file.force_refresh()?;
+    versioned_file.set_refresh_interval_persist(Duration::from_secs(60))?;
+    File::create(cache.path().join("a/.fcache-tmp1234"))?;
+
+    // Verify only the files are listed, relative to the cache directory
+    let files = cache.list()?;
+    assert_eq!(
+        files,
+        ["a/b/file.txt", "a/versioned.txt", "file.txt"]
+            .map(std::path::PathBuf::from)
+            .to_vec()
+    );
+
+    // Verify the listed paths are keys of the files
+    for path in &files {
+        assert!(cache.exists(path)?, "Listed file should exist: {}", path.display());
+    }
+
+    // Verify lazy files are listed once created
+    lazy_file.open()?;
+    assert!(cache.list()?.contains(&"a/lazy.txt".into()));
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_file_count_and_total_size() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    assert_eq!(cache.file_count()?, 0);
+    assert_eq!(cache.total_size()?, 0);
+
+    // Create files in nested subdirectories, along with sidecar and temporary files
+    let _ = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let nested_file = cache.get("a/b/file.txt", |mut file| {
+        file.write_all(b"data")?;
+        Ok(())
+    })?;
+    nested_file.set_refresh_interval_persist(Duration::from_secs(60))?;
+    let _ = cache.get_lazy("a/lazy.txt", |_| Ok(()))?;
+    File::create(cache.path().join("a/.fcache-tmp1234"))?.write_all(b"partial")?;
+
+    // Verify only the files are counted
+    assert_eq!(cache.file_count()?, 2);
+    assert_eq!(cache.total_size()?, TEST_CONTENT.len() as u64 + 4);
+    assert_eq!(cache.file_count()?, cache.list()?.len());
+
+    // Verify removed files are no longer counted
+    nested_file.remove()?;
+    assert_eq!(cache.file_count()?, 1);
+    assert_eq!(cache.total_size()?, TEST_CONTENT.len() as u64);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_entries() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create files in nested subdirectories
+    for (path, content) in [("a/one.txt", "1"), ("a/b/two.txt", "22"), ("three.txt", "333")] {
+        let _ = cache.get(path, move |mut file| {
+            file.write_all(content.as_bytes())?;
+            Ok(())
+        })?;
+    }
+
+    // Verify every file is described, without the directories
+    let entries: Vec<_> = cache.entries()?.collect();
+    let keys: Vec<_> = entries.iter().map(|entry| entry.key.to_str()).collect();
+    assert_eq!(keys, [Some("a/b/two.txt"), Some("a/one.txt"), Some("three.txt")]);
+    for entry in &entries {
+        assert_eq!(entry.path, cache.path().join(&entry.key));
+        assert_eq!(entry.size, std::fs::metadata(&entry.path)?.len());
+        assert_eq!(entry.modified, std::fs::metadata(&entry.path)?.modified()?);
+    }
+    assert_eq!(entries.iter().map(|entry| entry.size).sum::<u64>(), 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_is_empty() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    assert!(cache.is_empty()?, "New cache should be empty");
+
+    // Verify empty subdirectories and temporary files don't count
+    std::fs::create_dir_all(cache.path().join("a/b/c"))?;
+    File::create(cache.path().join("a/.fcache-tmp1234"))?;
+    assert!(cache.is_empty()?, "Cache without files should be empty");
+
+    // Verify lazy files count once created
+    let lazy_file = cache.get_lazy("a/b/c/file.txt", |_| Ok(()))?;
+    assert!(cache.is_empty()?);
+    lazy_file.open()?;
+    assert!(!cache.is_empty()?, "Cache with a file should not be empty");
+
+    // Verify the cache is empty again once cleared
+    cache.clear()?;
+    assert!(cache.is_empty()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_stats() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    let stats = cache.stats()?;
+    assert_eq!((stats.total_bytes, stats.file_count, stats.dir_count), (0, 0, 0));
+    assert_eq!((stats.oldest_modified, stats.newest_modified), (None, None));
+
+    // Create files in nested subdirectories, along with an empty directory
+    let contents = [("a/one.txt", "1"), ("a/b/two.txt", "22"), ("three.txt", "333")];
+    for (path, content) in contents {
+        let _ = cache.get(path, move |mut file| {
+            file.write_all(content.as_bytes())?;
+            Ok(())
+        })?;
+    }
+    std::fs::create_dir(cache.path().join("empty"))?;
+    let oldest = SystemTime::now() - Duration::from_secs(60);
+    File::options()
+        .write(true)
+        .open(cache.path().join("a/one.txt"))?
+        .set_modified(oldest)?;
+
+    // Verify the statistics of the files
+    let stats = cache.stats()?;
+    let total_bytes: usize = contents.iter().map(|(_, content)| content.len()).sum();
+    assert_eq!(stats.total_bytes, total_bytes as u64);
+    assert_eq!(stats.file_count, 3);
+    assert_eq!(stats.dir_count, 3);
+    assert_eq!(stats.oldest_modified, Some(oldest));
+    assert!(stats.newest_modified.is_some_and(|newest| newest > oldest));
+
+    Ok(())
+}
+
+#[test]
+fn test_lazy_file_validity_before_creation() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    let lazy_file = cache.get_lazy("nested/data.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the file which was not created yet is invalid
+    assert!(!lazy_file.is_valid()?);
+    assert!(lazy_file.is_invalid()?);
+    assert!(matches!(
+        lazy_file.valid_until(),
+        Err(fcache::Error::FileNotFound { ref path }) if path == lazy_file.path()
+    ));
+
+    // Verify the file is valid once created
+    lazy_file.open()?;
+    assert!(lazy_file.is_valid()?);
+    assert!(lazy_file.valid_until()?.is_some());
+
     Ok(())
 }
@@ -0,0 +1,364 @@
+use std::io::Write;
+use std::path::Path;
+
+use fcache::{KeyPolicy, SanitizeOptions};
+
+#[test]
+fn test_default_key_policy() -> anyhow::Result<()> {
+    // Create a new cache instance with the default key policy
+    let cache = fcache::new()?.with_key_policy(KeyPolicy::default());
+
+    // Verify deep, long, and hidden paths are allowed
+    let _ = cache.get("a/b/c/d/e/f/file.txt", |_| Ok(()))?;
+    let _ = cache.get("x".repeat(200), |_| Ok(()))?;
+    let _ = cache.get(".hidden/.file", |_| Ok(()))?;
+    let _ = cache.get("ünïcödé file.txt", |_| Ok(()))?;
+
+    // Verify built-in checks still apply
+    assert!(
+        matches!(cache.get("dir/", |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when path ends with slash"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_key_policy_max_depth() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_key_policy(KeyPolicy::new().max_depth(1));
+
+    // Verify the depth is limited
+    let _ = cache.get("a/file.txt", |_| Ok(()))?;
+    assert!(
+        matches!(
+            cache.get("a/b/file.txt", |_| Ok(())),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when path is too deep"
+    );
+    assert!(!cache.path().join("a/b").exists(), "No directories should be created");
+
+    Ok(())
+}
+
+#[test]
+fn test_key_policy_max_component_len() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_key_policy(KeyPolicy::new().max_component_len(8));
+
+    // Verify the component length is limited
+    let _ = cache.get("abcdefgh/file.txt", |_| Ok(()))?;
+    assert!(
+        matches!(
+            cache.get("abcdefghi/file.txt", |_| Ok(())),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when component is too long"
+    );
+    assert!(
+        matches!(
+            cache.get("long_file.txt", |_| Ok(())),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when file name is too long"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_key_policy_allowed_chars() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_key_policy(KeyPolicy::new().allowed_chars(|c| c.is_ascii_lowercase() || c == '.'));
+
+    // Verify the characters are restricted
+    let _ = cache.get("dir/file.txt", |_| Ok(()))?;
+    let result = cache.get("dir/File.txt", |_| Ok(()));
+    assert!(
+        matches!(&result, Err(fcache::Error::InvalidPath { reason, .. }) if reason.contains("'F'")),
+        "Should return an error naming the disallowed character"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_key_policy_hidden() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_key_policy(KeyPolicy::new().allow_hidden(false));
+
+    // Verify hidden components are rejected
+    let _ = cache.get("dir/file.txt", |_| Ok(()))?;
+    assert!(
+        matches!(cache.get(".env", |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when file is hidden"
+    );
+    assert!(
+        matches!(
+            cache.get(".git/config", |_| Ok(())),
+            Err(fcache::Error::InvalidPath { .. })
+        ),
+        "Should return an error when directory is hidden"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sidecar_extensions() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Verify keys with the extensions of sidecar files are accepted and listed
+    let keys = [
+        "a/b.generation",
+        "app.version",
+        "b.revision",
+        "c.stale",
+        "d.rawkey",
+        "e.accessed",
+        "ontology.ttl",
+    ];
+    for key in keys {
+        let _ = cache.get(key, |mut file| {
+            file.write_all(b"content")?;
+            Ok(())
+        })?;
+        assert!(cache.contains(key)?);
+    }
+    assert_eq!(cache.file_count()?, keys.len());
+    let mut paths = cache.list()?;
+    paths.sort();
+    assert_eq!(paths, keys.map(std::path::PathBuf::from));
+
+    // Verify a key with the extension of a sidecar file does not collide with the sidecar file of another file
+    let cache = fcache::new()?.with_version("v1");
+    let _ = cache.get("app", |_| Ok(()))?;
+    let _ = cache.get("app.version", |mut file| {
+        file.write_all(b"content")?;
+        Ok(())
+    })?;
+    assert_eq!(std::fs::read(cache.path().join("app.version"))?, b"content");
+    assert_eq!(
+        std::fs::read_to_string(cache.path().join(".fcache-meta-app.version"))?,
+        "v1"
+    );
+    assert!(cache.attach("app", |_| Ok(()))?.is_valid()?);
+
+    // Verify keys with the reserved prefix of the files of the cache are rejected
+    for key in [
+        ".fcache",
+        ".fcache-meta-app.version",
+        ".fcache-tmp1234",
+        "a/.fcache-probe",
+    ] {
+        assert!(
+            matches!(cache.get(key, |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+            "Should return an error when the key has the reserved prefix"
+        );
+        assert!(
+            matches!(cache.contains(key), Err(fcache::Error::InvalidPath { .. })),
+            "Should return an error when the key has the reserved prefix"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_flat_cache() -> anyhow::Result<()> {
+    // Create a new flat cache instance
+    let cache = fcache::new()?.with_flat(true);
+    assert!(cache.is_flat());
+
+    // Verify nested keys are rejected
+    for key in ["a/file.txt", "a/b/c/file.txt", "./a/file.txt", "a/../file.txt"] {
+        assert!(
+            matches!(cache.get(key, |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+            "Should return an error when the key is nested"
+        );
+        assert!(
+            matches!(cache.get_lazy(key, |_| Ok(())), Err(fcache::Error::InvalidPath { .. })),
+            "Should return an error when the key is nested"
+        );
+    }
+
+    // Verify flat keys work
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    assert_eq!(cache_file.path(), cache.path().join("file.txt"));
+    let _ = cache.get("./other.txt", |_| Ok(()))?;
+    cache_file.remove()?;
+    assert!(!cache_file.path().exists());
+
+    // Verify no subdirectories appeared
+    for entry in std::fs::read_dir(cache.path())? {
+        assert!(
+            !entry?.file_type()?.is_dir(),
+            "Flat cache should not contain directories"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_relative_path() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create nested files with keys given in different forms
+    let absolute_key = cache.path().join("reports/2025/summary.json");
+    let keys = [
+        ("reports/2024/summary.json", "reports/2024/summary.json"),
+        ("./reports/2024/details.json", "reports/2024/details.json"),
+        (absolute_key.to_str().unwrap_or_default(), "reports/2025/summary.json"),
+    ];
+    for (key, relative_path) in keys {
+        let cache_file = cache.get(key, |_| Ok(()))?;
+
+        // Verify the normalized key round-trips through the cache
+        assert_eq!(cache_file.relative_path(), Path::new(relative_path));
+        assert_eq!(cache.path_for(cache_file.relative_path())?, cache_file.path());
+    }
+
+    // Verify keys outside the cache are rejected
+    assert!(
+        matches!(
+            cache.path_for("../escape.txt"),
+            Err(fcache::Error::PathTraversal { .. })
+        ),
+        "Should return the path traversal error"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_sanitize_key_separators() {
+    let options = SanitizeOptions::new();
+
+    // Verify separators and forbidden characters are replaced
+    assert_eq!(fcache::sanitize_key("a/b\\c", &options), "a_b_c");
+    assert_eq!(
+        fcache::sanitize_key("what? <now>: \"yes\" | *", &options),
+        "what_ _now__ _yes_ _ _"
+    );
+    assert_eq!(fcache::sanitize_key("tab\there\0", &options), "tab here_");
+
+    // Verify whitespace is collapsed and leading dots are stripped
+    assert_eq!(
+        fcache::sanitize_key("  many    spaces\n\nhere  ", &options),
+        "many spaces here"
+    );
+    assert_eq!(fcache::sanitize_key("../../etc/passwd", &options), "_.._etc_passwd");
+    assert_eq!(fcache::sanitize_key(".hidden", &options), "hidden");
+    assert_eq!(fcache::sanitize_key("..", &options), "_");
+    assert_eq!(fcache::sanitize_key("", &options), "_");
+
+    // Verify the replacement character is configurable
+    let options = SanitizeOptions::new().replacement('-');
+    assert_eq!(fcache::sanitize_key("a/b", &options), "a-b");
+    let options = SanitizeOptions::new().replacement('/');
+    assert_eq!(fcache::sanitize_key("a/b", &options), "a_b");
+}
+
+#[test]
+fn test_sanitize_key_unicode() {
+    let options = SanitizeOptions::new();
+
+    // Verify unicode characters are preserved
+    assert_eq!(fcache::sanitize_key("Zażółć gęślą jaźń", &options), "Zażółć gęślą jaźń");
+    assert_eq!(fcache::sanitize_key("日本語/テキスト", &options), "日本語_テキスト");
+
+    // Verify truncation never splits a character
+    let options = SanitizeOptions::new().max_len(20);
+    let key = fcache::sanitize_key(&"ż".repeat(50), &options);
+    assert!(key.len() <= 20);
+    assert!(key.starts_with("żżż"));
+}
+
+#[test]
+fn test_sanitize_key_long_inputs() {
+    let options = SanitizeOptions::new().max_len(32);
+
+    // Verify short inputs are not truncated
+    assert_eq!(fcache::sanitize_key(&"x".repeat(32), &options), "x".repeat(32));
+
+    // Verify long inputs are truncated with a hash suffix
+    let key = fcache::sanitize_key(&"x".repeat(100), &options);
+    assert_eq!(key.len(), 32);
+    assert!(key.starts_with(&"x".repeat(23)));
+    assert!(key[23..].starts_with('-'));
+    assert!(key[24..].chars().all(|c| c.is_ascii_hexdigit()));
+
+    // Verify the mapping is deterministic
+    assert_eq!(fcache::sanitize_key(&"x".repeat(100), &options), key);
+
+    // Verify the default maximum length
+    let key = fcache::sanitize_key(&"y".repeat(1000), &SanitizeOptions::new());
+    assert_eq!(key.len(), 200);
+}
+
+#[test]
+fn test_sanitize_key_collisions() {
+    let options = SanitizeOptions::new().max_len(32);
+
+    // Verify long inputs sharing a prefix keep distinct names
+    let prefix = "p".repeat(100);
+    let first = fcache::sanitize_key(&format!("{prefix}-first"), &options);
+    let second = fcache::sanitize_key(&format!("{prefix}-second"), &options);
+    assert_ne!(first, second);
+    assert_eq!(first[..23], second[..23]);
+
+    // Verify inputs differing only in replaced characters collide
+    assert_eq!(
+        fcache::sanitize_key("a/b", &options),
+        fcache::sanitize_key("a:b", &options)
+    );
+}
+
+#[test]
+fn test_get_sanitized() -> anyhow::Result<()> {
+    // Create a new cache instance reusing existing files
+    let cache = fcache::new()?.with_creation_mode(fcache::CreationMode::UseExisting);
+
+    // Create a file with a raw key
+    let raw_key = "https://example.com/articles?id=42";
+    let cache_file = cache.get_sanitized(raw_key, |mut file| {
+        file.write_all(b"article")?;
+        Ok(())
+    })?;
+    assert_eq!(
+        cache_file.relative_path(),
+        Path::new("https___example.com_articles_id=42")
+    );
+    assert_eq!(cache_file.original_key()?.as_deref(), Some(raw_key));
+
+    // Verify the original key survives reattaching
+    let reattached_file = cache.get(cache_file.relative_path(), |_| Ok(()))?;
+    assert_eq!(reattached_file.original_key()?.as_deref(), Some(raw_key));
+
+    // Verify the same raw key maps to the same file
+    let same_file = cache.get_sanitized(raw_key, |_| Ok(()))?;
+    assert_eq!(same_file.path(), cache_file.path());
+
+    // Verify the sidecar file is not listed as a cache file
+    assert_eq!(cache.quota_usage()?.1, 1);
+
+    // Verify files created with plain keys have no original key
+    let plain_file = cache.get("plain.txt", |_| Ok(()))?;
+    assert_eq!(plain_file.original_key()?, None);
+
+    // Verify removing the file removes the original key along with it
+    let rawkey_path = cache
+        .path()
+        .join(".fcache-meta-https___example.com_articles_id=42.rawkey");
+    assert!(rawkey_path.exists());
+    cache_file.remove()?;
+    assert!(!rawkey_path.exists(), "Original key should be removed");
+    let recreated_file = cache.get(cache_file.relative_path(), |_| Ok(()))?;
+    assert_eq!(recreated_file.original_key()?, None);
+
+    Ok(())
+}
@@ -0,0 +1,314 @@
+mod common;
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use common::*;
+use fcache::{EvictionPolicy, LimitPolicy, StorageQuota};
+
+/// Sets the modification time of the file to the given age.
+fn set_age(path: &Path, age: Duration) -> anyhow::Result<()> {
+    File::options()
+        .write(true)
+        .open(path)?
+        .set_modified(SystemTime::now() - age)?;
+    Ok(())
+}
+
+#[test]
+fn test_enforce_limit_policy() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create files of different ages
+    for (name, hours) in [("old.txt", 3), ("a.txt", 2), ("b/b.txt", 1), ("c.txt", 0)] {
+        let cache_file = cache.get(name, |mut file| {
+            file.write_all(&[0; 10])?;
+            Ok(())
+        })?;
+        set_age(cache_file.path(), Duration::from_secs(hours * 60 * 60))?;
+    }
+
+    // Limit the age, the size, and the number of files
+    let policy = LimitPolicy::new()
+        .max_age(Duration::from_secs(150 * 60))
+        .max_size_bytes(25)
+        .max_entries(1);
+    let cache = cache.with_entry_limit_policy(policy);
+    assert_eq!(cache.entry_limit_policy(), Some(policy));
+
+    // Enforce the policy
+    let evicted = cache.enforce_limit_policy()?;
+    assert_eq!(evicted, 3);
+
+    // Verify only the newest file remains
+    assert!(!cache.path().join("old.txt").exists(), "Old file should be evicted");
+    assert!(!cache.path().join("a.txt").exists(), "File should be evicted by size");
+    assert!(!cache.path().join("b").exists(), "File should be evicted by count");
+    assert!(cache.path().join("c.txt").exists(), "Newest file should be kept");
+
+    Ok(())
+}
+
+#[test]
+fn test_limit_policy_on_get() -> anyhow::Result<()> {
+    // Create a new cache instance keeping at most 2 files
+    let cache = fcache::new()?.with_entry_limit_policy(LimitPolicy::new().max_entries(2));
+
+    // Create files of different ages
+    let first_file = cache.get("first.txt", |_| Ok(()))?;
+    set_age(first_file.path(), Duration::from_secs(120))?;
+    let second_file = cache.get("second.txt", |_| Ok(()))?;
+    set_age(second_file.path(), Duration::from_secs(60))?;
+
+    // Create a file exceeding the limit
+    let third_file = cache.get("third.txt", |_| Ok(()))?;
+
+    // Verify the oldest file was evicted
+    assert!(!first_file.path().exists(), "Oldest file should be evicted");
+    assert!(second_file.path().exists());
+    assert!(third_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_storage_quota_on_get() -> anyhow::Result<()> {
+    // Create a new cache instance with a budget of 25 bytes
+    let quota = StorageQuota {
+        max_bytes: 25,
+        max_files: 10,
+        eviction: EvictionPolicy::LeastRecentlyModified,
+    };
+    let cache = fcache::new()?.with_storage_quota(quota);
+    assert_eq!(cache.storage_quota(), Some(quota));
+
+    // Create files of different ages
+    let first_file = cache.get("first.txt", |mut file| {
+        file.write_all(&[0; 10])?;
+        Ok(())
+    })?;
+    set_age(first_file.path(), Duration::from_secs(120))?;
+    let second_file = cache.get("second.txt", |mut file| {
+        file.write_all(&[0; 10])?;
+        Ok(())
+    })?;
+    set_age(second_file.path(), Duration::from_secs(60))?;
+    assert_eq!(cache.quota_usage()?, (20, 2));
+
+    // Create a file exceeding the quota
+    let third_file = cache.get("third.txt", |mut file| {
+        file.write_all(&[0; 10])?;
+        Ok(())
+    })?;
+
+    // Verify the oldest file was evicted
+    assert!(!first_file.path().exists(), "Oldest file should be evicted");
+    assert!(second_file.path().exists());
+    assert!(third_file.path().exists());
+    assert_eq!(cache.quota_usage()?, (20, 2));
+
+    Ok(())
+}
+
+#[test]
+fn test_storage_quota_largest_first() -> anyhow::Result<()> {
+    // Create a new cache instance keeping at most 2 files
+    let quota = StorageQuota {
+        max_bytes: u64::MAX,
+        max_files: 2,
+        eviction: EvictionPolicy::LargestFirst,
+    };
+    let cache = fcache::new()?.with_storage_quota(quota);
+
+    // Create files of different sizes
+    let large_file = cache.get("large.txt", |mut file| {
+        file.write_all(&[0; 100])?;
+        Ok(())
+    })?;
+    let small_file = cache.get("small.txt", |mut file| {
+        file.write_all(&[0; 10])?;
+        Ok(())
+    })?;
+
+    // Create a file exceeding the quota
+    let new_file = cache.get("new.txt", |mut file| {
+        file.write_all(&[0; 50])?;
+        Ok(())
+    })?;
+
+    // Verify the largest file was evicted
+    assert!(!large_file.path().exists(), "Largest file should be evicted");
+    assert!(small_file.path().exists());
+    assert!(new_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_subdirectory_storage_quotas() -> anyhow::Result<()> {
+    // Create a new cache instance shared by two subsystems
+    let cache = fcache::new()?;
+    let quota = StorageQuota {
+        max_bytes: u64::MAX,
+        max_files: 1,
+        eviction: EvictionPolicy::default(),
+    };
+    let first_cache = cache.subdirectory("first")?.with_storage_quota(quota);
+    let second_cache = cache
+        .subdirectory("second")?
+        .with_storage_quota(StorageQuota { max_files: 2, ..quota });
+    assert_eq!(first_cache.path(), cache.path().join("first"));
+
+    // Create files in both subsystems
+    let first_file = first_cache.get("a.txt", |_| Ok(()))?;
+    set_age(first_file.path(), Duration::from_secs(60))?;
+    let _ = first_cache.get("b.txt", |_| Ok(()))?;
+    let _ = second_cache.get("a.txt", |_| Ok(()))?;
+    let _ = second_cache.get("b.txt", |_| Ok(()))?;
+
+    // Verify the quotas are enforced independently
+    assert!(!first_file.path().exists(), "File should be evicted by its own quota");
+    assert_eq!(first_cache.quota_usage()?.1, 1);
+    assert_eq!(second_cache.quota_usage()?.1, 2);
+    assert_eq!(cache.quota_usage()?.1, 3);
+
+    // Create a subdirectory out of the cache
+    assert!(
+        matches!(cache.subdirectory("../other"), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when the subdirectory is out of the cache"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_max_size() -> anyhow::Result<()> {
+    // Create a new cache instance keeping at most 100 bytes
+    let cache = fcache::new()?.with_max_size(100);
+    let old_file = cache.get("old.txt", |mut file| {
+        file.write_all(&[0; 40])?;
+        Ok(())
+    })?;
+    set_age(old_file.path(), Duration::from_secs(120))?;
+    let locked_file = cache.get("locked.txt", |mut file| {
+        file.write_all(&[0; 40])?;
+        Ok(())
+    })?;
+    set_age(locked_file.path(), Duration::from_secs(180))?;
+    locked_file.lock()?;
+    let new_file = cache.get("new.txt", |mut file| {
+        file.write_all(&[0; 10])?;
+        Ok(())
+    })?;
+    assert!(old_file.path().exists());
+
+    // Refresh a file past the limit
+    set_age(new_file.path(), Duration::from_secs(60))?;
+    new_file.replace_from_reader(&[0; 30][..])?;
+
+    // Verify the least recently modified unlocked file was evicted
+    assert!(!old_file.path().exists(), "Oldest file should be evicted on refresh");
+    assert!(locked_file.path().exists(), "Locked file should never be evicted");
+    assert!(new_file.path().exists());
+
+    // Create a file larger than the limit
+    locked_file.unlock()?;
+    let large_file = cache.get("large.txt", |mut file| {
+        file.write_all(&[0; 200])?;
+        Ok(())
+    })?;
+
+    // Verify everything else was evicted
+    assert!(large_file.path().exists(), "File larger than the limit should be kept");
+    assert!(!locked_file.path().exists());
+    assert!(!new_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_max_entries() -> anyhow::Result<()> {
+    // Create a new cache instance keeping at most 3 files
+    let cache = fcache::new()?.with_max_entries(3);
+    assert_eq!(cache.max_entries(), Some(3));
+
+    // Create more files than the limit, from the oldest
+    let mut files = Vec::new();
+    for i in 0..6 {
+        let cache_file = cache.get(format!("file{i}.txt"), |_| Ok(()))?;
+        set_age(cache_file.path(), Duration::from_secs(60 * (10 - i)))?;
+        files.push(cache_file);
+    }
+
+    // Verify only the newest files remain
+    assert_eq!(cache.file_count()?, 3);
+    for (i, cache_file) in files.iter().enumerate() {
+        assert_eq!(
+            cache_file.path().exists(),
+            i >= 3,
+            "Only the newest files should remain"
+        );
+    }
+
+    // Lock all the files
+    for cache_file in &files[3..] {
+        cache_file.lock()?;
+    }
+
+    // Verify no file can be created while all the files are locked
+    assert!(
+        matches!(
+            cache.get("new.txt", |_| Ok(())),
+            Err(fcache::Error::CacheFull { max_entries: 3 })
+        ),
+        "Should return an error when all the files are locked"
+    );
+
+    // Verify the oldest unlocked file is removed instead of the locked ones
+    files[4].unlock()?;
+    let new_file = cache.get("new.txt", |_| Ok(()))?;
+    assert!(new_file.path().exists());
+    assert!(files[3].path().exists(), "Locked file should not be removed");
+    assert!(!files[4].path().exists(), "Unlocked file should be removed");
+    assert!(files[5].path().exists(), "Locked file should not be removed");
+
+    Ok(())
+}
+
+#[test]
+fn test_max_entries_concurrent() -> anyhow::Result<()> {
+    const MAX_ENTRIES: usize = 4;
+    const THREADS: usize = 8;
+
+    // Create a new cache instance which is already full
+    let cache = fcache::new()?.with_max_entries(MAX_ENTRIES);
+    for i in 0..MAX_ENTRIES {
+        let _ = cache.get(format!("old{i}.txt"), |_| Ok(()))?;
+    }
+
+    // Create files from several threads at once
+    let created = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let cache = &cache;
+                scope.spawn(move || cache.get(format!("new{i}.txt"), |_| Ok(())).is_ok())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok())
+            .filter(|created| *created)
+            .count()
+    });
+
+    // Verify the racing threads exceed the limit by at most their number
+    assert_eq!(created, THREADS, "All the threads should create their files");
+    assert!(cache.file_count()? < MAX_ENTRIES + THREADS);
+
+    // Verify the limit is restored by the next file
+    let _ = cache.get("last.txt", |_| Ok(()))?;
+    assert_eq!(cache.file_count()?, MAX_ENTRIES);
+
+    Ok(())
+}
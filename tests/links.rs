@@ -0,0 +1,114 @@
+mod common;
+
+use std::time::SystemTime;
+
+use common::*;
+use fcache::LinkMode;
+
+#[test]
+fn test_linked_file_copy() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source.txt");
+    std::fs::write(&source, TEST_CONTENT)?;
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Mirror the source file in the cache
+    let cache_file = cache.get_linked("a/file.txt", &source, LinkMode::Copy)?;
+    assert!(cache_file.path().starts_with(cache.path()));
+    assert!(!cache_file.path().is_symlink(), "Copy should not be a symlink");
+    assert!(cache_file.is_valid()?);
+
+    // Read the content
+    {
+        let mut content = Vec::new();
+        cache_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_CONTENT);
+    }
+
+    // Update the source file
+    std::fs::write(&source, TEST_LARGE_CONTENT)?;
+    File::options()
+        .write(true)
+        .open(&source)?
+        .set_modified(SystemTime::now() + Duration::from_secs(60))?;
+    assert!(
+        cache_file.is_invalid()?,
+        "Copy should be invalid when the source is newer"
+    );
+
+    // Read the refreshed content
+    {
+        let mut content = Vec::new();
+        cache_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_LARGE_CONTENT);
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_linked_file_symlink() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source.txt");
+    std::fs::write(&source, TEST_CONTENT)?;
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Link the source file in the cache
+    let cache_file = cache.get_linked("a/file.txt", &source, LinkMode::Symlink)?;
+    assert!(cache_file.path().starts_with(cache.path()));
+    assert!(cache_file.path().is_symlink(), "File should be a symlink");
+    assert!(cache_file.is_valid()?);
+
+    // Update the source file
+    std::fs::write(&source, TEST_LARGE_CONTENT)?;
+    File::options()
+        .write(true)
+        .open(&source)?
+        .set_modified(SystemTime::now() + Duration::from_secs(60))?;
+    assert!(
+        cache_file.is_invalid()?,
+        "Link should be invalid when the source is newer"
+    );
+
+    // Read the content through the link
+    {
+        let mut content = Vec::new();
+        cache_file.open()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_LARGE_CONTENT);
+    }
+
+    // Verify the source was not modified by the refresh
+    assert!(cache_file.path().is_symlink(), "File should still be a symlink");
+    assert_eq!(std::fs::read(&source)?, TEST_LARGE_CONTENT);
+
+    // Remove the link, keeping the source
+    cache_file.remove()?;
+    assert!(!cache_file.path().exists());
+    assert!(source.exists(), "Source should not be removed");
+
+    Ok(())
+}
+
+#[test]
+fn test_linked_file_out_of_cache() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let source = temp_dir.path().join("source.txt");
+    std::fs::write(&source, TEST_CONTENT)?;
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Try to link a file outside of the cache directory
+    let result = cache.get_linked("../file.txt", &source, LinkMode::Symlink);
+    assert!(
+        matches!(result, Err(fcache::Error::PathTraversal { .. })),
+        "Should fail with path traversal error"
+    );
+
+    Ok(())
+}
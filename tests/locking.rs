@@ -1,3 +1,9 @@
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
 #[test]
 fn test_new_file_unlocked_by_default() -> anyhow::Result<()> {
     // Create a new cache instance
@@ -18,7 +24,7 @@ fn test_file_locking() -> anyhow::Result<()> {
     let cache = fcache::new()?;
 
     // Create a file in the cache
-    let mut cache_file = cache.get("file.txt", |_| Ok(()))?;
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
 
     // Lock the file
     cache_file.lock()?;
@@ -36,3 +42,179 @@ fn test_file_locking() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_shared_file_locking() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache shared between threads
+    let cache_file = Arc::new(cache.get("file.txt", |_| Ok(()))?);
+
+    thread::scope(|scope| -> anyhow::Result<()> {
+        // Lock the file from one thread
+        let shared_file = Arc::clone(&cache_file);
+        scope
+            .spawn(move || shared_file.lock())
+            .join()
+            .expect("Thread should not panic")?;
+
+        // Verify the lock is observed from another thread
+        let shared_file = Arc::clone(&cache_file);
+        scope
+            .spawn(move || -> anyhow::Result<()> {
+                assert!(shared_file.is_locked(), "File should be locked");
+                assert!(
+                    !shared_file.refresh_if(|_| true)?,
+                    "Locked file should not be refreshed"
+                );
+                assert!(
+                    matches!(shared_file.truncate(0), Err(fcache::Error::FileAlreadyLocked)),
+                    "Should return an error when the file is locked"
+                );
+                Ok(())
+            })
+            .join()
+            .expect("Thread should not panic")?;
+
+        Ok(())
+    })?;
+
+    // Unlock the file
+    cache_file.unlock()?;
+
+    // Lock the file from many threads at once
+    let locked = thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let shared_file = Arc::clone(&cache_file);
+                scope.spawn(move || shared_file.lock().is_ok())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Thread should not panic"))
+            .filter(|&locked| locked)
+            .count()
+    });
+
+    // Verify only a single thread acquired the lock
+    assert_eq!(locked, 1, "Only one thread should lock the file");
+    assert!(cache_file.is_locked(), "File should be locked");
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_expires() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+
+    // Lock the file for a short time
+    cache_file.lock_for(Duration::from_millis(50))?;
+    assert!(cache_file.is_locked(), "File should be locked");
+    assert!(
+        !cache_file.refresh_if_older_than(Duration::ZERO)?,
+        "Locked file should not be refreshed"
+    );
+    assert!(cache_file.lock().is_err(), "Locked file should not be locked again");
+
+    // Wait for the lock to expire
+    thread::sleep(Duration::from_millis(100));
+
+    // Verify the file is unlocked and refreshed
+    assert!(!cache_file.is_locked(), "Expired lock should be released");
+    assert!(
+        cache_file.refresh_if_older_than(Duration::ZERO)?,
+        "File with an expired lock should be refreshed"
+    );
+
+    // Verify unlocking an expired lock succeeds
+    cache_file.lock_for(Duration::from_millis(50))?;
+    thread::sleep(Duration::from_millis(100));
+    cache_file.unlock()?;
+    assert!(
+        cache_file.unlock().is_err(),
+        "Unlocked file should not be unlocked again"
+    );
+
+    // Verify locking indefinitely clears the previous expiry
+    cache_file.lock()?;
+    thread::sleep(Duration::from_millis(100));
+    assert!(cache_file.is_locked(), "File locked indefinitely should stay locked");
+
+    Ok(())
+}
+
+#[test]
+fn test_lock_guard() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+
+    // Lock the file with a guard
+    {
+        let _guard = cache_file.lock_guard()?;
+        assert!(cache_file.is_locked(), "File should be locked");
+        assert!(
+            cache_file.lock_guard().is_err(),
+            "Locked file should not be locked again"
+        );
+    }
+
+    // Verify dropping the guard unlocks the file
+    assert!(!cache_file.is_locked(), "File should be unlocked");
+
+    // Verify dropping the guard after an explicit unlock is fine
+    let guard = cache_file.lock_guard()?;
+    cache_file.unlock()?;
+    drop(guard);
+    assert!(!cache_file.is_locked(), "File should stay unlocked");
+
+    Ok(())
+}
+
+#[test]
+fn test_locked_file_not_refreshed() -> anyhow::Result<()> {
+    // Create a new cache instance with a short refresh interval
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_millis(50));
+
+    // Create a file in the cache, writing a different content on each refresh
+    let counter = AtomicUsize::new(0);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        let count = counter.fetch_add(1, Ordering::SeqCst);
+        write!(file, "{count}")?;
+        Ok(())
+    })?;
+
+    // Lock the file and wait for it to expire
+    cache_file.lock()?;
+    thread::sleep(Duration::from_millis(100));
+
+    // Verify refreshing the locked file fails
+    assert!(
+        matches!(cache_file.force_refresh(), Err(fcache::Error::FileAlreadyLocked)),
+        "Locked file should not be force refreshed"
+    );
+    assert!(
+        matches!(cache_file.refresh(), Err(fcache::Error::FileAlreadyLocked)),
+        "Locked file should not be refreshed"
+    );
+
+    // Verify the locked file is opened without being refreshed
+    let mut content = String::new();
+    cache_file.open()?.read_to_string(&mut content)?;
+    assert_eq!(content, "0");
+
+    // Verify the unlocked file is refreshed again
+    cache_file.unlock()?;
+    cache_file.force_refresh()?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "1");
+
+    Ok(())
+}
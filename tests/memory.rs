@@ -0,0 +1,147 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::*;
+
+/// Overwrites the file with content of the same size, keeping its modification time.
+fn overwrite_unnoticed(path: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let modified = fs::metadata(path)?.modified()?;
+    fs::write(path, content)?;
+    File::options().write(true).open(path)?.set_modified(modified)?;
+    Ok(())
+}
+
+#[test]
+fn test_read_cached() -> anyhow::Result<()> {
+    // Create a new cache instance with an in-memory layer
+    let cache = fcache::new()?.with_memory_cache(1024);
+    assert_eq!(cache.memory_cache_max_bytes(), 1024);
+
+    // Create a file in the cache
+    let calls = Arc::new(AtomicUsize::new(0));
+    let cache_file = cache.get("test.txt", {
+        let calls = calls.clone();
+        move |mut file| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            file.write_all(format!("content {call}").as_bytes())?;
+            Ok(())
+        }
+    })?;
+    assert_eq!(&*cache_file.read_cached()?, b"content 0");
+
+    // Verify the kept content is served while the file looks unchanged
+    overwrite_unnoticed(cache_file.path(), b"changed 0")?;
+    assert_eq!(&*cache_file.read_cached()?, b"content 0");
+
+    // Verify the kept content is dropped when the file is refreshed
+    cache_file.force_refresh()?;
+    assert_eq!(&*cache_file.read_cached()?, b"content 1");
+
+    // Verify the kept content is dropped when the file changes on disk
+    fs::write(cache_file.path(), b"changed on disk")?;
+    assert_eq!(&*cache_file.read_cached()?, b"changed on disk");
+
+    // Verify the kept content is dropped when the file is removed
+    cache_file.remove()?;
+    assert_eq!(&*cache_file.read_cached()?, b"content 2");
+
+    Ok(())
+}
+
+#[test]
+fn test_read_cached_bypass() -> anyhow::Result<()> {
+    // Create a new cache instance keeping only small files in memory
+    let cache = fcache::new()?
+        .with_memory_cache(1024)
+        .with_memory_cache_max_entry_size(16);
+    assert_eq!(cache.memory_cache_max_entry_size(), 16);
+
+    // Verify large files are read from disk every time
+    let large_file = cache.get("large.txt", |mut file| {
+        file.write_all(&[b'a'; 32])?;
+        Ok(())
+    })?;
+    assert_eq!(&*large_file.read_cached()?, &[b'a'; 32]);
+    overwrite_unnoticed(large_file.path(), &[b'b'; 32])?;
+    assert_eq!(&*large_file.read_cached()?, &[b'b'; 32]);
+
+    // Verify files are read from disk every time if the layer is disabled
+    let cache = fcache::new()?;
+    let cache_file = cache.get("test.txt", |mut file| {
+        file.write_all(b"small")?;
+        Ok(())
+    })?;
+    assert_eq!(&*cache_file.read_cached()?, b"small");
+    overwrite_unnoticed(cache_file.path(), b"other")?;
+    assert_eq!(&*cache_file.read_cached()?, b"other");
+
+    Ok(())
+}
+
+#[test]
+fn test_read_cached_eviction() -> anyhow::Result<()> {
+    // Create a new cache instance keeping at most two files in memory
+    let cache = fcache::new()?.with_memory_cache(20);
+    let files = ["first.txt", "second.txt", "third.txt"]
+        .into_iter()
+        .map(|name| {
+            cache.get(name, |mut file| {
+                file.write_all(b"0123456789")?;
+                Ok(())
+            })
+        })
+        .collect::<fcache::Result<Vec<_>>>()?;
+
+    // Read the files in turns, so the first one is the least recently used
+    for cache_file in &files {
+        cache_file.read_cached()?;
+    }
+    for cache_file in &files {
+        overwrite_unnoticed(cache_file.path(), b"abcdefghij")?;
+    }
+
+    // Verify only the least recently used file was evicted
+    assert_eq!(&*files[0].read_cached()?, b"abcdefghij");
+    assert_eq!(&*files[2].read_cached()?, b"0123456789");
+
+    Ok(())
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_read_cached_disk_reads() -> anyhow::Result<()> {
+    // Create a new cache instance counting the reads from disk
+    let reads = Arc::new(AtomicUsize::new(0));
+    let cache = fcache::new()?.with_memory_cache(1024).with_fault_injector({
+        let reads = reads.clone();
+        move |fault_point| {
+            if let fcache::FaultPoint::Read { .. } = fault_point {
+                reads.fetch_add(1, Ordering::SeqCst);
+            }
+            None
+        }
+    });
+    let cache_file = cache.get("test.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the file is read from disk once
+    for _ in 0..100 {
+        assert_eq!(&*cache_file.read_cached()?, TEST_CONTENT);
+    }
+    assert_eq!(reads.load(Ordering::SeqCst), 1);
+
+    // Verify the file is read from disk again after a forced refresh
+    cache_file.force_refresh()?;
+    for _ in 0..100 {
+        assert_eq!(&*cache_file.read_cached()?, TEST_CONTENT);
+    }
+    assert_eq!(reads.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
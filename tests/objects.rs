@@ -0,0 +1,93 @@
+mod common;
+
+use common::*;
+
+#[test]
+fn test_put_content_addressed() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Store the content
+    let (digest, cache_file) = cache.put_content_addressed(TEST_CONTENT)?;
+
+    // Verify the digest addresses the object
+    assert_eq!(digest.len(), 64);
+    assert!(
+        cache_file
+            .path()
+            .ends_with(format!("objects/{}/{}", &digest[..2], &digest[2..]))
+    );
+
+    // Verify content matches
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT, "Object content does not match");
+
+    // Verify the digest of known content
+    let (digest, _) = cache.put_content_addressed(&b"abc"[..])?;
+    assert_eq!(
+        digest,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_put_content_addressed_dedupe() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Store identical content twice
+    let (first_digest, first_file) = cache.put_content_addressed(TEST_LARGE_CONTENT)?;
+    let (second_digest, second_file) = cache.put_content_addressed(TEST_LARGE_CONTENT)?;
+
+    // Verify a single object is stored
+    assert_eq!(first_digest, second_digest);
+    assert_eq!(first_file.path(), second_file.path());
+    let objects = std::fs::read_dir(cache.path().join("objects").join(&first_digest[..2]))?.count();
+    assert_eq!(objects, 1, "Identical content should be stored once");
+    let temp_files = std::fs::read_dir(cache.path().join("objects"))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .count();
+    assert_eq!(temp_files, 0, "No temporary files should be left behind");
+
+    Ok(())
+}
+
+#[test]
+fn test_open_object() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Store the content and open it by digest
+    let (digest, _) = cache.put_content_addressed(TEST_CONTENT)?;
+    let cache_file = cache.open_object(&digest)?;
+
+    // Verify content matches
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT, "Object content does not match");
+
+    // Verify objects cannot be refreshed
+    assert!(
+        matches!(cache_file.force_refresh(), Err(fcache::Error::ImmutableFile { .. })),
+        "Should return an error when refreshing an object"
+    );
+
+    // Verify missing and malformed digests
+    assert!(
+        matches!(
+            cache.open_object(&"0".repeat(64)),
+            Err(fcache::Error::FileNotFound { .. })
+        ),
+        "Should return an error when opening a missing object"
+    );
+    assert!(
+        matches!(cache.open_object("../file.txt"), Err(fcache::Error::InvalidPath { .. })),
+        "Should return an error when opening a malformed digest"
+    );
+
+    Ok(())
+}
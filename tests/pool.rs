@@ -0,0 +1,81 @@
+#![cfg(feature = "fd-pool")]
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::*;
+
+#[test]
+fn test_open_pooled() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_fd_pool_size(2);
+    assert_eq!(cache.fd_pool_size(), 2);
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Read the file repeatedly using pooled descriptors
+    for _ in 0..5 {
+        let mut content = Vec::new();
+        cache_file.open_pooled()?.read_to_end(&mut content)?;
+        assert_eq!(content, TEST_CONTENT);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_open_pooled_invalidated_on_force_refresh() -> anyhow::Result<()> {
+    let i = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_fmt(format_args!("{}", i.fetch_add(1, Ordering::SeqCst)))?;
+        Ok(())
+    })?;
+
+    // Read the initial content
+    {
+        let mut content = String::new();
+        cache_file.open_pooled()?.read_to_string(&mut content)?;
+        assert_eq!(content, "0");
+    }
+
+    // Force a refresh of the file
+    cache_file.force_refresh()?;
+
+    // Pooled descriptors should see the new content
+    {
+        let mut content = String::new();
+        cache_file.open_pooled()?.read_to_string(&mut content)?;
+        assert_eq!(content, "1");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_open_pooled_disabled() -> anyhow::Result<()> {
+    // Create a new cache instance without pooling
+    let cache = fcache::new()?.with_fd_pool_size(0);
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Read the file
+    let mut content = Vec::new();
+    cache_file.open_pooled()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use common::*;
+use fcache::ErrorRecovery;
+
+#[test]
+fn test_error_recovery_propagate() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+    assert!(matches!(cache.error_recovery(), ErrorRecovery::Propagate));
+
+    // Create a file with a failing callback
+    assert!(
+        matches!(
+            cache.get("file.txt", |mut file| {
+                file.write_all(TEST_CONTENT)?;
+                Err("failure".into())
+            }),
+            Err(fcache::Error::Callback(_))
+        ),
+        "Should return an error when the callback fails"
+    );
+
+    // Verify the partial file is removed
+    assert!(
+        !cache.path().join("file.txt").exists(),
+        "Failed creation should leave no file behind"
+    );
+
+    // Verify the file is created on the next access
+    let i = AtomicUsize::new(0);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        if i.fetch_add(1, Ordering::SeqCst) > 0 {
+            return Err("failure".into());
+        }
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    // Refresh the file with a failing callback
+    assert!(cache_file.force_refresh().is_err());
+
+    // Verify the partially refreshed file is kept
+    assert!(cache_file.path().exists(), "Failed refresh should keep the file");
+
+    Ok(())
+}
+
+#[test]
+fn test_error_recovery_delete_and_retry() -> anyhow::Result<()> {
+    let i = AtomicUsize::new(0);
+
+    // Create a new cache instance retrying failed callbacks
+    let cache = fcache::new()?.with_error_recovery(ErrorRecovery::DeleteAndRetry { max_retries: 3 });
+
+    // Create a file with a callback failing twice
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        if i.fetch_add(1, Ordering::SeqCst) < 2 {
+            return Err("failure".into());
+        }
+        Ok(())
+    })?;
+
+    // Verify the partial content was discarded
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    // Create a file with a callback always failing
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    assert!(
+        matches!(
+            cache.get("failing.txt", move |mut file| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                file.write_all(TEST_CONTENT)?;
+                Err("failure".into())
+            }),
+            Err(fcache::Error::Callback(_))
+        ),
+        "Should return an error when all retries fail"
+    );
+
+    // Verify the callback was retried and the partial file removed
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+    assert!(!cache.path().join("failing.txt").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_error_recovery_delete_and_skip() -> anyhow::Result<()> {
+    // Create a new cache instance removing partial files
+    let cache = fcache::new()?.with_error_recovery(ErrorRecovery::DeleteAndSkip);
+
+    // Create a file with a failing callback
+    assert!(
+        matches!(
+            cache.get("file.txt", |mut file| {
+                file.write_all(TEST_CONTENT)?;
+                Err("failure".into())
+            }),
+            Err(fcache::Error::Callback(_))
+        ),
+        "Should return an error when the callback fails"
+    );
+
+    // Verify the partial file is removed, so the file can be created again
+    assert!(!cache.path().join("file.txt").exists());
+    let _ = cache.get("file.txt", |_| Ok(()))?;
+
+    Ok(())
+}
+
+#[test]
+fn test_error_recovery_fallback() -> anyhow::Result<()> {
+    // Create a new cache instance writing a placeholder on failure
+    let cache = fcache::new()?.with_error_recovery(ErrorRecovery::fallback(|mut file| {
+        file.write_all(b"placeholder")?;
+        Ok(())
+    }));
+
+    // Create a file with a failing callback
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Err("failure".into())
+    })?;
+
+    // Verify the fallback content replaced the partial content
+    assert_eq!(std::fs::read(cache_file.path())?, b"placeholder");
+
+    // Refresh the file with a failing callback
+    cache_file.force_refresh()?;
+    assert_eq!(std::fs::read(cache_file.path())?, b"placeholder");
+
+    Ok(())
+}
+
+#[test]
+fn test_callback_panic_on_create() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create a file with a callback panicking after a partial write
+    let cache_file = cache.get_lazy("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        panic!("upstream unavailable");
+    })?;
+    match cache_file.open() {
+        Err(fcache::Error::CallbackPanicked { path, message }) => {
+            assert_eq!(path, cache_file.path());
+            assert_eq!(message, "upstream unavailable");
+        },
+        other => panic!("Should return a panic error, got {other:?}"),
+    }
+
+    // Verify the partial file was removed and the file is poisoned
+    assert!(!cache_file.path().exists(), "Partial file should be removed");
+    assert!(cache_file.is_poisoned(), "File should be poisoned");
+
+    // Verify the poison can be cleared
+    cache_file.clear_poison();
+    assert!(!cache_file.is_poisoned(), "Poison should be cleared");
+
+    Ok(())
+}
+
+#[test]
+fn test_callback_panic_on_refresh() -> anyhow::Result<()> {
+    let fail = Arc::new(AtomicBool::new(false));
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::MAX);
+
+    // Create a file with a callback panicking on demand
+    let should_fail = Arc::clone(&fail);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        assert!(!should_fail.load(Ordering::SeqCst), "upstream unavailable");
+        Ok(())
+    })?;
+    assert!(!cache_file.is_poisoned(), "File should not be poisoned");
+
+    // Refresh the file with a panicking callback
+    fail.store(true, Ordering::SeqCst);
+    assert!(
+        matches!(
+            cache_file.force_refresh(),
+            Err(fcache::Error::CallbackPanicked { ref message, .. }) if message == "upstream unavailable"
+        ),
+        "Should return a panic error"
+    );
+
+    // Verify the truncated file was removed and the file is poisoned
+    assert!(!cache_file.path().exists(), "Truncated file should be removed");
+    assert!(cache_file.is_poisoned(), "File should be poisoned");
+
+    // Recover with a successful refresh
+    fail.store(false, Ordering::SeqCst);
+    cache_file.force_refresh()?;
+    assert!(!cache_file.is_poisoned(), "Successful refresh should clear the poison");
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+    assert!(cache_file.is_valid()?);
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
 mod common;
 
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::SystemTime;
 
 use common::*;
 
@@ -108,3 +111,1036 @@ fn test_file_force_refresh() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_file_expires_at() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::ZERO); // Zero refresh interval to always refresh
+
+    // Create a file in the cache that expires in the future
+    let cache_file = cache
+        .get("file.txt", move |mut file| {
+            file.write_fmt(format_args!("{}", i.load(Ordering::SeqCst)))?;
+            i.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        })?
+        .with_expires_at(SystemTime::now() + Duration::from_secs(60 * 60));
+
+    // Verify the absolute expiry time overrides the refresh interval
+    assert!(cache_file.is_valid()?, "File should be valid until the expiry time");
+    assert!(cache_file.refresh_interval() > Duration::ZERO);
+
+    // Read the content without refreshing
+    {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "0");
+    }
+
+    // Move the expiry time into the past
+    let expires_at = SystemTime::now() - Duration::from_secs(1);
+    let cache_file = cache_file.with_expires_at(expires_at);
+
+    // Verify the file is expired
+    assert!(cache_file.is_invalid()?, "File should be invalid after the expiry time");
+    assert_eq!(cache_file.valid_until()?, Some(expires_at));
+    assert_eq!(cache_file.expiry(), fcache::ExpiryMode::Absolute(expires_at));
+
+    // Refresh the file during the next access
+    {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "1");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_without_refresh() -> anyhow::Result<()> {
+    // Create a new cache instance which never refreshes files
+    let cache = fcache::new()?.without_refresh();
+    assert_eq!(cache.refresh_interval_opt(), None);
+    assert_eq!(cache.refresh_interval(), Duration::MAX);
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the file never expires
+    assert!(cache_file.is_valid()?, "File should be valid");
+    assert_eq!(cache_file.refresh_interval_opt(), None);
+    assert_eq!(cache_file.valid_until()?, None);
+    assert_eq!(cache_file.expiry(), fcache::ExpiryMode::Never);
+
+    // Verify the maximum refresh interval is treated as never refreshing
+    let cache_file = cache_file
+        .with_refresh_interval(Duration::from_secs(60))
+        .with_refresh_interval(Duration::MAX);
+    assert_eq!(cache_file.refresh_interval_opt(), None);
+    assert_eq!(cache_file.valid_until()?, None);
+
+    // Verify a file can opt out of refreshing on its own
+    let cache = fcache::new()?.with_refresh_interval(Duration::ZERO);
+    let cache_file = cache
+        .get("file.txt", |mut file| {
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?
+        .without_refresh();
+    assert!(
+        cache_file.is_valid()?,
+        "File should be valid despite the zero cache refresh interval"
+    );
+    assert_eq!(cache_file.valid_until()?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_file_is_invalid() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?
+        .with_refresh_interval(Duration::MAX) // Max refresh interval to avoid auto-refresh
+        .with_empty_is_invalid(true);
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_fmt(format_args!("{}", i.load(Ordering::SeqCst)))?;
+        i.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    })?;
+    assert!(cache_file.is_valid()?, "File should be valid after creation");
+
+    // Truncate the file out-of-band
+    File::options().write(true).truncate(true).open(cache_file.path())?;
+    assert!(cache_file.is_invalid()?, "Empty file should be invalid");
+
+    // Regenerate the file during the next access
+    {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "1");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_file_is_valid_by_default() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::MAX); // Max refresh interval to avoid auto-refresh
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_fmt(format_args!("{}", i.load(Ordering::SeqCst)))?;
+        i.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    })?;
+
+    // Truncate the file out-of-band
+    File::options().write(true).truncate(true).open(cache_file.path())?;
+    assert!(cache_file.is_valid()?, "Empty file should be valid by default");
+
+    // Read the empty content without regenerating
+    {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "");
+    }
+
+    // Opt out per file on a cache treating empty files as invalid
+    let cache = fcache::new()?
+        .with_refresh_interval(Duration::MAX)
+        .with_empty_is_invalid(true);
+    let cache_file = cache.get("file.txt", |_| Ok(()))?.with_empty_is_invalid(false);
+    assert!(cache_file.is_valid()?, "Empty file should be valid when opted out");
+
+    Ok(())
+}
+
+#[test]
+fn test_file_min_size() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+
+    // Create a file in the cache
+    let cache_file = cache
+        .get("file.txt", move |mut file| {
+            file.write_fmt(format_args!("{}", i.fetch_add(1, Ordering::SeqCst)))?;
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?
+        .with_min_size(TEST_CONTENT.len() as u64);
+    assert_eq!(cache_file.min_size(), Some(TEST_CONTENT.len() as u64));
+    assert!(cache_file.is_valid()?, "Complete file should be valid");
+
+    // Cut the file short out-of-band
+    cache_file.truncate(4)?;
+    assert!(cache_file.is_invalid()?, "Incomplete file should be invalid");
+
+    // Regenerate the file during the next refresh
+    cache_file.refresh()?;
+    assert!(std::fs::read(cache_file.path())?.starts_with(b"1"));
+    assert!(cache_file.is_valid()?, "Complete file should be valid");
+
+    // Verify complete files still expire
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(SystemTime::now() - Duration::from_secs(120))?;
+    assert!(cache_file.is_invalid()?, "Expired file should be invalid");
+
+    Ok(())
+}
+
+#[test]
+fn test_file_conditional_refresh() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::MAX); // Max refresh interval to avoid auto-refresh
+
+    // Create a file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_fmt(format_args!("v{}", i.fetch_add(1, Ordering::SeqCst) + 1))?;
+        Ok(())
+    })?;
+
+    // Skip the refresh when the condition is not satisfied
+    assert!(!cache_file.conditional_refresh(|content| content != b"v1")?);
+
+    // Refresh the file when the condition is satisfied
+    assert!(cache_file.conditional_refresh(|content| !content.starts_with(b"v2"))?);
+
+    // Read the content after the refresh
+    {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "v2");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_file_refresh_interval_persist() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Persist the refresh interval of a lazy file
+    {
+        let cache_file = cache.get_lazy("file.txt", |_| Ok(()))?;
+        cache_file.set_refresh_interval_persist(Duration::from_millis(1500))?;
+        assert!(cache_file.path().with_file_name(".fcache-meta-file.txt.ttl").exists());
+    }
+
+    // Verify the persisted refresh interval is applied to new handles
+    {
+        let cache_file = cache.get_lazy("file.txt", |_| Ok(()))?;
+        assert_eq!(cache_file.refresh_interval(), Duration::from_millis(1500));
+
+        // Clear the persisted refresh interval
+        cache_file.clear_refresh_interval_persist()?;
+    }
+
+    // Verify the cache refresh interval is applied again
+    let cache_file = cache.get_lazy("file.txt", |_| Ok(()))?;
+    assert_eq!(cache_file.refresh_interval(), cache.refresh_interval());
+
+    Ok(())
+}
+
+#[test]
+fn test_group_by_validity() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+
+    // Create files in the cache
+    let valid_file = cache.get("valid.txt", |_| Ok(()))?;
+    let invalid_file = cache.get("a/invalid.txt", |_| Ok(()))?;
+
+    // Expire one of the files
+    File::options()
+        .write(true)
+        .open(invalid_file.path())?
+        .set_modified(SystemTime::now() - Duration::from_secs(120))?;
+
+    // Group the files by validity
+    let (valid_paths, invalid_paths) = cache.group_by_validity()?;
+    assert_eq!(valid_paths, vec![valid_file.path().to_path_buf()]);
+    assert_eq!(invalid_paths, vec![invalid_file.path().to_path_buf()]);
+
+    // Verify nothing was removed
+    assert!(invalid_file.path().exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_most_recent_and_oldest() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?;
+
+    // Create files with controlled modification times
+    let now = SystemTime::now();
+    for (path, age) in [("a.txt", 30), ("b/b.txt", 10), ("c.txt", 40), ("b/d.txt", 20)] {
+        let cache_file = cache.get(path, |_| Ok(()))?;
+        File::options()
+            .write(true)
+            .open(cache_file.path())?
+            .set_modified(now - Duration::from_secs(age))?;
+    }
+
+    // Verify the newest files come first
+    let most_recent = cache.most_recent(3)?;
+    assert_eq!(
+        most_recent.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>(),
+        vec![
+            PathBuf::from("b/b.txt"),
+            PathBuf::from("b/d.txt"),
+            PathBuf::from("a.txt")
+        ]
+    );
+    assert_eq!(most_recent[0].1, now - Duration::from_secs(10));
+
+    // Verify the oldest files come first
+    let oldest = cache.oldest(2)?;
+    assert_eq!(
+        oldest.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>(),
+        vec![PathBuf::from("c.txt"), PathBuf::from("a.txt")]
+    );
+
+    // Verify the number of files is truncated
+    assert_eq!(cache.most_recent(10)?.len(), 4);
+    assert!(cache.oldest(0)?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_freeze() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+    let counter = Arc::new(AtomicUsize::new(0));
+    let cache_file = cache.get("file.txt", {
+        let counter = counter.clone();
+        move |mut file| {
+            let count = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            write!(file, "{count}")?;
+            Ok(())
+        }
+    })?;
+
+    // Freeze the cache
+    cache.freeze();
+    assert!(cache.is_frozen());
+
+    // Verify writes are rejected and the content is unchanged
+    assert!(
+        matches!(cache_file.force_refresh(), Err(fcache::Error::Frozen { .. })),
+        "Should return an error when the cache is frozen"
+    );
+    assert!(
+        matches!(cache_file.remove(), Err(fcache::Error::Frozen { .. })),
+        "Should return an error when the cache is frozen"
+    );
+    assert!(
+        matches!(cache.get("other.txt", |_| Ok(())), Err(fcache::Error::Frozen { .. })),
+        "Should return an error when the cache is frozen"
+    );
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "1");
+
+    // Verify expired files are still served without refreshing
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(SystemTime::now() - Duration::from_secs(120))?;
+    let mut content = String::new();
+    cache_file.open()?.read_to_string(&mut content)?;
+    assert_eq!(content, "1");
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+    // Thaw the cache and verify the file is refreshed
+    cache.thaw();
+    assert!(!cache.is_frozen());
+    cache_file.force_refresh()?;
+    assert_eq!(std::fs::read_to_string(cache_file.path())?, "2");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_explain() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+
+    // Explain a missing file
+    let explanation = cache.explain("file.txt")?;
+    assert!(!explanation.exists);
+    assert_eq!(explanation.file_size, None);
+    assert_eq!(explanation.is_valid, None);
+    assert_eq!(explanation.refresh_interval, Duration::from_secs(60));
+    assert_eq!(explanation.decision, fcache::CacheDecision::WouldCreate);
+    assert!(!explanation.path.exists(), "Explaining should not create the file");
+
+    // Explain a fresh file
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let explanation = cache.explain("file.txt")?;
+    assert!(explanation.exists);
+    assert_eq!(explanation.path, cache_file.path());
+    assert_eq!(explanation.file_size, Some(TEST_CONTENT.len() as u64));
+    assert!(explanation.age.is_some_and(|age| age < Duration::from_secs(60)));
+    assert_eq!(explanation.is_valid, Some(true));
+    assert_eq!(explanation.decision, fcache::CacheDecision::WouldSkip);
+
+    // Explain an expired file
+    let mtime = SystemTime::now() - Duration::from_secs(120);
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(mtime)?;
+    let explanation = cache.explain("file.txt")?;
+    assert_eq!(explanation.mtime, Some(mtime));
+    assert_eq!(explanation.is_valid, Some(false));
+    assert_eq!(explanation.decision, fcache::CacheDecision::WouldRefresh);
+
+    // Explain a locked file
+    cache_file.lock()?;
+    let explanation = cache_file.explain()?;
+    assert!(explanation.is_locked);
+    assert_eq!(explanation.decision, fcache::CacheDecision::Locked);
+
+    // Verify nothing was modified
+    assert_eq!(std::fs::metadata(cache_file.path())?.modified()?, mtime);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_status() -> anyhow::Result<()> {
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60));
+
+    // Check a missing file
+    assert_eq!(cache.status("file.txt")?, fcache::EntryStatus::Missing);
+    assert!(
+        !cache.path().join("file.txt").exists(),
+        "Checking the status should not create the file"
+    );
+
+    // Check a fresh file
+    let cache_file = cache.get("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    let mtime = SystemTime::now() - Duration::from_secs(10);
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(mtime)?;
+    let expected = fcache::EntryStatus::Present {
+        valid: true,
+        locked: false,
+        size: TEST_CONTENT.len() as u64,
+        modified: mtime,
+        expires: Some(mtime + Duration::from_secs(60)),
+    };
+    assert_eq!(cache.status("file.txt")?, expected);
+
+    // Check an expired file
+    let mtime = SystemTime::now() - Duration::from_secs(120);
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(mtime)?;
+    let expected = fcache::EntryStatus::Present {
+        valid: false,
+        locked: false,
+        size: TEST_CONTENT.len() as u64,
+        modified: mtime,
+        expires: Some(mtime + Duration::from_secs(60)),
+    };
+    assert_eq!(cache.status("file.txt")?, expected);
+
+    // Check a locked file
+    cache_file.lock()?;
+    let expected = fcache::EntryStatus::Present {
+        valid: false,
+        locked: true,
+        size: TEST_CONTENT.len() as u64,
+        modified: mtime,
+        expires: Some(mtime + Duration::from_secs(60)),
+    };
+    assert_eq!(cache_file.status()?, expected);
+
+    // Verify nothing was modified
+    assert_eq!(std::fs::metadata(cache_file.path())?.modified()?, mtime);
+
+    // Verify the path is validated
+    assert!(
+        cache.status("../file.txt").is_err(),
+        "Paths outside the cache should be rejected"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_refresh_if() -> anyhow::Result<()> {
+    let i: AtomicUsize = AtomicUsize::new(0);
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::MAX); // Max refresh interval to avoid auto-refresh
+
+    // Create an empty file in the cache
+    let cache_file = cache.get("file.txt", move |mut file| {
+        if i.fetch_add(1, Ordering::SeqCst) > 0 {
+            file.write_all(TEST_CONTENT)?;
+        }
+        Ok(())
+    })?;
+
+    // Skip the refresh when the predicate is not satisfied
+    assert!(!cache_file.refresh_if(|metadata| metadata.len() > 0)?);
+
+    // Skip the refresh of a locked file
+    cache_file.lock()?;
+    assert!(!cache_file.refresh_if(|metadata| metadata.len() == 0)?);
+    cache_file.unlock()?;
+
+    // Refresh the file when the predicate is satisfied
+    assert!(cache_file.refresh_if(|metadata| metadata.len() == 0)?);
+    assert_eq!(std::fs::read(cache_file.path())?, TEST_CONTENT);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_version() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let temp_dir = TempDir::new()?;
+
+    // Create a new cache instance with a version token and a long refresh interval
+    let cache = fcache::with_dir(temp_dir.path())?
+        .with_refresh_interval(Duration::from_secs(60 * 60))
+        .with_version("v1");
+    assert_eq!(cache.version(), Some("v1"));
+
+    // Create a file in the cache, recording the version token
+    let counter = Arc::clone(&calls);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        let i = counter.fetch_add(1, Ordering::SeqCst);
+        file.write_fmt(format_args!("{i}"))?;
+        Ok(())
+    })?;
+    assert_eq!(cache_file.version(), Some("v1"));
+    assert!(cache_file.is_valid()?, "File should be valid with the same version");
+    assert!(
+        !cache
+            .most_recent(10)?
+            .iter()
+            .any(|(path, _)| path.ends_with(".fcache-meta-file.txt.version")),
+        "Version sidecar should be skipped"
+    );
+
+    // Reconfigure the file with another version token
+    let cache_file = cache_file.with_version("v2");
+    assert!(cache_file.is_invalid()?, "File should be invalid with another version");
+
+    // Verify the callback reruns exactly once
+    for _ in 0..3 {
+        let mut content = String::new();
+        cache_file.open()?.read_to_string(&mut content)?;
+        assert_eq!(content, "1");
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Re-attach the file with the recorded version token
+    let cache = fcache::with_dir(temp_dir.path())?
+        .with_refresh_interval(Duration::from_secs(60 * 60))
+        .with_creation_mode(fcache::CreationMode::UseExisting)
+        .with_version("v2");
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    assert!(cache_file.is_valid()?, "File should be valid with the recorded version");
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_bump_generation() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let temp_dir = TempDir::new()?;
+
+    // Create new cache instances with a long refresh interval
+    let cache = fcache::with_dir(temp_dir.path())?.with_refresh_interval(Duration::from_secs(60 * 60));
+    let other_cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60 * 60));
+    assert_eq!(cache.generation(), 0);
+
+    // Create files in both caches, counting the callback calls
+    let counted_callback = |calls: &Arc<AtomicUsize>| {
+        let calls = Arc::clone(calls);
+        move |mut file: File| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        }
+    };
+    let cache_files = [
+        cache.get("first.txt", counted_callback(&calls))?,
+        cache.get("second.txt", counted_callback(&calls))?,
+    ];
+    let other_calls = Arc::new(AtomicUsize::new(0));
+    let other_file = other_cache.get("file.txt", counted_callback(&other_calls))?;
+
+    // Bump the generation of one cache
+    cache.bump_generation()?;
+    assert_eq!(cache.generation(), 1);
+    assert!(
+        cache_files[0].is_invalid()?,
+        "File should be invalid after bumping the generation"
+    );
+
+    // Verify each file refreshes exactly once on its next access
+    for _ in 0..3 {
+        for cache_file in &cache_files {
+            cache_file.open()?;
+        }
+        other_file.open()?;
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+    assert_eq!(other_calls.load(Ordering::SeqCst), 1);
+
+    // Verify the generation is persisted, and the generation file isn't listed as a cache file
+    let reopened_cache = fcache::with_dir(temp_dir.path())?;
+    assert_eq!(reopened_cache.generation(), 1);
+    assert_eq!(reopened_cache.most_recent(10)?.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_revision() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+
+    // Create a new cache instance with a long refresh interval
+    let cache = fcache::with_dir(temp_dir.path())?.with_refresh_interval(Duration::from_secs(60 * 60));
+
+    // Create a lazy file in the cache
+    let cache_file = cache.get_lazy("file.txt", |mut file| {
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+    assert_eq!(cache_file.revision(), 0);
+
+    // Verify creating the file increments the revision
+    assert!(cache_file.open_if_changed(0)?.is_some(), "Content should be new");
+    assert_eq!(cache_file.revision(), 1);
+
+    // Verify the revision doesn't change while the file is valid
+    let cache_file = cache_file.init()?;
+    cache_file.refresh()?;
+    assert!(!cache_file.conditional_refresh(|content| content != TEST_CONTENT)?);
+    assert_eq!(cache_file.revision(), 1);
+    assert!(cache_file.open_if_changed(1)?.is_none(), "Content should be unchanged");
+
+    // Verify forcing a refresh increments the revision
+    cache_file.force_refresh()?;
+    assert_eq!(cache_file.revision(), 2);
+    assert!(cache_file.open_if_changed(1)?.is_some(), "Content should be changed");
+
+    // Verify the revision survives re-attaching the file after a restart
+    drop(cache_file);
+    let cache = fcache::with_dir(temp_dir.path())?
+        .with_refresh_interval(Duration::from_secs(60 * 60))
+        .with_creation_mode(fcache::CreationMode::UseExisting);
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    assert_eq!(cache_file.revision(), 2);
+
+    // Verify removing the file resets the revision
+    cache_file.remove()?;
+    let cache_file = cache.get("file.txt", |_| Ok(()))?;
+    assert_eq!(cache_file.revision(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_sub_second_refresh_interval() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Create a new cache instance with a manual clock, which doesn't match the file system timestamps
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?
+        .with_clock(clock.clone())
+        .with_refresh_interval(Duration::from_millis(100));
+
+    // Create a file in the cache
+    let counter = Arc::clone(&calls);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        file.write_all(TEST_CONTENT)?;
+        Ok(())
+    })?;
+
+    // Verify the validity is based on the precise time of the write
+    clock.advance(Duration::from_millis(60));
+    assert!(cache_file.is_valid()?, "File should be valid within the interval");
+    assert_eq!(
+        cache_file.valid_until()?,
+        Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_717_200_000_100))
+    );
+    clock.advance(Duration::from_millis(60));
+    assert!(cache_file.is_invalid()?, "File should be invalid after the interval");
+
+    // Verify the refresh records the time of the write again
+    cache_file.open()?;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    clock.advance(Duration::from_millis(90));
+    assert!(cache_file.is_valid()?, "File should be valid after the refresh");
+
+    // Verify files modified since the write fall back to their modification time
+    File::options()
+        .write(true)
+        .open(cache_file.path())?
+        .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000))?;
+    assert!(
+        cache_file.is_invalid()?,
+        "File modified by others should use its modification time"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_open_stale() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Create a new cache instance
+    let cache = fcache::new()?.with_refresh_interval(Duration::ZERO); // Zero refresh interval to always refresh
+
+    // Verify there is nothing to serve before the file is created
+    let cache_file = cache.get_lazy("file.txt", {
+        let calls = calls.clone();
+        move |mut file| {
+            file.write_fmt(format_args!("{}", calls.fetch_add(1, Ordering::SeqCst)))?;
+            Ok(())
+        }
+    })?;
+    assert!(cache_file.open_stale()?.is_none());
+    let cache_file = cache_file.init()?;
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Read the expired content without refreshing it
+    for _ in 0..3 {
+        let mut content = String::new();
+        cache_file.open_stale()?.read_to_string(&mut content)?;
+        assert_eq!(content, "0");
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(cache_file.staleness()?.is_some(), "Should be past its expiry");
+
+    // Read the content refreshing it
+    let mut content = String::new();
+    cache_file.open()?.read_to_string(&mut content)?;
+    assert_eq!(content, "1");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Verify files with a longer refresh interval are not stale
+    let cache_file = cache_file.with_refresh_interval(Duration::from_secs(60));
+    assert_eq!(cache_file.staleness()?, None);
+
+    // Verify removed files cannot be opened
+    cache_file.remove()?;
+    assert!(
+        matches!(cache_file.open_stale(), Err(fcache::Error::FileNotFound { .. })),
+        "Should return the file not found error"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_refresh_if_older_than() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Create a new cache instance with a manual clock
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?
+        .with_clock(clock.clone())
+        .with_refresh_interval(Duration::from_secs(600));
+
+    // Create a file in the cache
+    let counter = Arc::clone(&calls);
+    let cache_file = cache.get("file.txt", move |mut file| {
+        file.write_fmt(format_args!("{}", counter.fetch_add(1, Ordering::SeqCst)))?;
+        Ok(())
+    })?;
+
+    // Verify fresh files are not refreshed
+    clock.advance(Duration::from_secs(20));
+    assert!(!cache_file.refresh_if_older_than(Duration::from_secs(30))?);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Verify older files are refreshed, although they are still valid
+    clock.advance(Duration::from_secs(20));
+    assert!(cache_file.is_valid()?, "File should be valid within the interval");
+    assert!(cache_file.refresh_if_older_than(Duration::from_secs(30))?);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Verify opening refreshes only files older than the given age
+    clock.advance(Duration::from_secs(20));
+    let mut content = String::new();
+    cache_file
+        .open_fresher_than(Duration::from_secs(30))?
+        .read_to_string(&mut content)?;
+    assert_eq!(content, "1");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    clock.advance(Duration::from_secs(20));
+    let mut content = String::new();
+    cache_file
+        .open_fresher_than(Duration::from_secs(30))?
+        .read_to_string(&mut content)?;
+    assert_eq!(content, "2");
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    // Verify the refresh interval of the file is unchanged
+    assert_eq!(cache_file.refresh_interval(), Duration::from_secs(600));
+
+    Ok(())
+}
+
+#[test]
+fn test_file_max_stale() -> anyhow::Result<()> {
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    // Create a new cache instance with a manual clock
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?
+        .with_clock(clock.clone())
+        .with_refresh_interval(Duration::from_secs(10));
+
+    // Create a file whose callback fails after the creation
+    let counter = Arc::clone(&calls);
+    let cache_file = cache
+        .get("file.txt", move |mut file| {
+            if counter.fetch_add(1, Ordering::SeqCst) > 0 {
+                return Err("upstream unavailable".into());
+            }
+            file.write_all(TEST_CONTENT)?;
+            Ok(())
+        })?
+        .with_stale_if_error(true)
+        .with_max_stale(Duration::from_secs(5));
+
+    // Verify stale content is served within the bound
+    clock.advance(Duration::from_secs(12));
+    assert_eq!(cache_file.staleness()?, Some(Duration::from_secs(2)));
+    let mut content = Vec::new();
+    cache_file.open()?.read_to_end(&mut content)?;
+    assert_eq!(content, TEST_CONTENT);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Verify stale content is not served beyond the bound
+    clock.advance(Duration::from_secs(5));
+    let result = cache_file.open();
+    assert!(
+        matches!(result, Err(fcache::Error::TooStale { age, .. }) if age == Duration::from_secs(7)),
+        "Should return the too stale error"
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    // Verify the bound applies to locked files and frozen caches as well
+    cache_file.lock()?;
+    assert!(
+        matches!(cache_file.open(), Err(fcache::Error::TooStale { .. })),
+        "Should return the too stale error for locked files"
+    );
+    cache_file.unlock()?;
+    cache.freeze();
+    assert!(
+        matches!(cache_file.open(), Err(fcache::Error::TooStale { .. })),
+        "Should return the too stale error in frozen caches"
+    );
+    cache.thaw();
+
+    // Verify the refresh error is returned without serving stale content
+    let cache_file = cache_file
+        .with_stale_if_error(false)
+        .with_max_stale(Duration::from_secs(60));
+    assert!(
+        matches!(cache_file.open(), Err(fcache::Error::Callback(_))),
+        "Should return the callback error"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_file_last_refresh() -> anyhow::Result<()> {
+    // Create a new cache instance with a manual clock
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000);
+    let clock = fcache::ManualClock::new(start);
+    let cache = fcache::new()?.with_clock(clock.clone());
+
+    // Create a lazy file failing on the second call
+    let calls = Arc::new(AtomicUsize::new(0));
+    let cache_file = cache.get_lazy("test.txt", {
+        let calls = calls.clone();
+        move |mut file| {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => {
+                    std::thread::sleep(Duration::from_millis(10));
+                    file.write_all(TEST_CONTENT)?;
+                    Ok(())
+                },
+                _ => Err("upstream unavailable".into()),
+            }
+        }
+    })?;
+    assert_eq!(cache_file.last_refresh_at(), None);
+    assert_eq!(cache_file.last_refresh_duration(), None);
+    assert_eq!(cache_file.last_refresh_error(), None);
+
+    // Verify a successful creation is recorded
+    cache_file.open()?;
+    assert_eq!(cache_file.last_refresh_at(), Some(start));
+    assert!(cache_file.last_refresh_duration() >= Some(Duration::from_millis(10)));
+    assert_eq!(cache_file.last_refresh_error(), None);
+
+    // Verify a failed refresh keeps the last success and records the error
+    clock.advance(Duration::from_secs(60));
+    assert!(cache_file.force_refresh().is_err(), "Should fail to refresh the file");
+    assert_eq!(cache_file.last_refresh_at(), Some(start));
+    assert!(cache_file.last_refresh_duration() >= Some(Duration::from_millis(10)));
+    assert_eq!(cache_file.last_refresh_error().as_deref(), Some("upstream unavailable"));
+
+    Ok(())
+}
+
+#[test]
+fn test_file_idle_timeout() -> anyhow::Result<()> {
+    // Create a new cache instance with a manual clock
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?
+        .with_clock(clock.clone())
+        .with_refresh_interval(Duration::from_secs(10 * 60));
+
+    // Create a lazy file expiring after a minute without use
+    let calls = Arc::new(AtomicUsize::new(0));
+    let cache_file = cache
+        .get_lazy("test.txt", {
+            let calls = calls.clone();
+            move |mut file| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                file.write_all(TEST_CONTENT)?;
+                Ok(())
+            }
+        })?
+        .with_idle_timeout(Duration::from_secs(60));
+    assert_eq!(cache_file.idle_timeout(), Some(Duration::from_secs(60)));
+    cache_file.open()?;
+
+    // Verify frequent access keeps the file valid past its idle window
+    for _ in 0..8 {
+        clock.advance(Duration::from_secs(30));
+        assert!(cache_file.is_valid()?);
+        cache_file.open()?;
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Verify the file expires after a quiet period
+    clock.advance(Duration::from_secs(90));
+    assert!(!cache_file.is_valid()?);
+    cache_file.open()?;
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+    // Verify the refresh interval still applies to frequently accessed files
+    for _ in 0..20 {
+        clock.advance(Duration::from_secs(30));
+        cache_file.open()?;
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_prune_idle() -> anyhow::Result<()> {
+    // Create a new cache instance with a manual clock
+    let clock = fcache::ManualClock::new(SystemTime::UNIX_EPOCH + Duration::from_secs(1_717_200_000));
+    let cache = fcache::new()?.with_clock(clock.clone());
+
+    // Create files tracking their accesses
+    let active_file = cache
+        .get_lazy("active.txt", |_| Ok(()))?
+        .with_idle_timeout(Duration::from_secs(60 * 60));
+    let idle_file = cache
+        .get_lazy("idle.txt", |_| Ok(()))?
+        .with_idle_timeout(Duration::from_secs(60 * 60));
+    active_file.open()?;
+    idle_file.open()?;
+
+    // Keep accessing only one of the files
+    for _ in 0..3 {
+        clock.advance(Duration::from_secs(10 * 60));
+        active_file.open()?;
+    }
+
+    // Verify only the idle file is pruned
+    assert_eq!(cache.prune_idle(Duration::from_secs(20 * 60))?, 1);
+    assert!(active_file.path().exists());
+    assert!(!idle_file.path().exists());
+    assert!(
+        !idle_file
+            .path()
+            .with_file_name(".fcache-meta-idle.txt.accessed")
+            .exists()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_cache_purge_expired() -> anyhow::Result<()> {
+    // Create a new cache instance refreshing files every hour
+    let cache = fcache::new()?.with_refresh_interval(Duration::from_secs(60 * 60));
+
+    // Create files, and make some of them expire
+    let _ = cache.get("fresh.txt", |_| Ok(()))?;
+    let locked_file = cache.get("locked.txt", |_| Ok(()))?;
+    locked_file.lock()?;
+    for path in ["expired.txt", "a/b/expired.txt", "locked.txt"] {
+        let _ = cache.get_or_attach(path, |_| Ok(()))?;
+        let modified = SystemTime::now() - Duration::from_secs(2 * 60 * 60);
+        File::options()
+            .write(true)
+            .open(cache.path().join(path))?
+            .set_modified(modified)?;
+    }
+
+    // Verify only the expired files which aren't locked are purged
+    assert_eq!(cache.purge_expired()?, 2);
+    assert_eq!(cache.list()?, [PathBuf::from("fresh.txt"), PathBuf::from("locked.txt")]);
+    assert!(!cache.path().join("a").exists(), "Empty directories should be removed");
+
+    // Verify unlocked files are purged
+    locked_file.unlock()?;
+    assert_eq!(cache.purge_expired()?, 1);
+    assert_eq!(cache.purge_expired()?, 0);
+
+    Ok(())
+}